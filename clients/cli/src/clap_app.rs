@@ -127,6 +127,7 @@ pub enum CommandName {
     SetInterestRate,
     Transfer,
     Burn,
+    RecoveryTransfer,
     Mint,
     Freeze,
     Thaw,
@@ -145,12 +146,17 @@ pub enum CommandName {
     SyncNative,
     EnableRequiredTransferMemos,
     DisableRequiredTransferMemos,
+    SetMemoTransferPolicy,
     EnableCpiGuard,
     DisableCpiGuard,
+    SetCpiGuardPolicy,
     UpdateDefaultAccountState,
+    SetDefaultAccountStateAttestor,
     UpdateMetadataAddress,
     WithdrawWithheldTokens,
     SetTransferFee,
+    EnableTransferFeeExemption,
+    DisableTransferFeeExemption,
     WithdrawExcessLamports,
     SetTransferHook,
     InitializeMetadata,
@@ -158,8 +164,15 @@ pub enum CommandName {
     InitializeGroup,
     UpdateGroupMaxSize,
     InitializeMember,
+    RemoveMember,
+    MoveMember,
+    FreezeGroupMembership,
+    ThawGroupMembership,
     UpdateConfidentialTransferSettings,
     ConfigureConfidentialTransferAccount,
+    CreateElgamalRegistry,
+    UpdateElgamalRegistry,
+    ShowElgamalRegistry,
     EnableConfidentialCredits,
     DisableConfidentialCredits,
     EnableNonConfidentialCredits,
@@ -168,9 +181,18 @@ pub enum CommandName {
     WithdrawConfidentialTokens,
     ApplyPendingBalance,
     ApplyPendingBurn,
+    ApproveConfidentialTransferAccount,
+    EmptyConfidentialTransferAccount,
+    WithdrawConfidentialWithheldTokens,
+    HarvestConfidentialWithheldTokens,
+    EnableConfidentialHarvestToMint,
+    DisableConfidentialHarvestToMint,
+    RotateConfidentialSupplyElgamalPubkey,
+    UpdateConfidentialDecryptableSupply,
     UpdateGroupAddress,
     UpdateMemberAddress,
     UpdateUiAmountMultiplier,
+    CancelUiAmountMultiplier,
     Pause,
     Resume,
     UnwrapSol,
@@ -211,6 +233,65 @@ where
         _ => Err("Transfer hook account must be present as <ADDRESS>:<ROLE>".to_string()),
     }
 }
+pub fn parse_transfer_fee_tier(string: &str) -> Result<(Amount, u16), String> {
+    match string.split(':').collect::<Vec<_>>().as_slice() {
+        [minimum_amount, transfer_fee_basis_points] => {
+            let minimum_amount = Amount::parse(minimum_amount).map_err(|e| format!("{e}"))?;
+            let transfer_fee_basis_points =
+                u16::from_str(transfer_fee_basis_points).map_err(|e| format!("{e}"))?;
+            Ok((minimum_amount, transfer_fee_basis_points))
+        }
+        _ => Err(
+            "Transfer fee tier must be present as <MINIMUM_AMOUNT>:<FEE_IN_BASIS_POINTS>"
+                .to_string(),
+        ),
+    }
+}
+pub fn parse_rational_multiplier(string: &str) -> Result<(u64, u64), String> {
+    let (numerator, denominator) = match string.split('/').collect::<Vec<_>>().as_slice() {
+        [numerator, denominator] => (
+            u64::from_str(numerator).map_err(|e| format!("{e}"))?,
+            u64::from_str(denominator).map_err(|e| format!("{e}"))?,
+        ),
+        [decimal] => {
+            // an exact decimal such as 1.25 is read as 125/100
+            let (integer, fraction) = decimal.split_once('.').unwrap_or((decimal, ""));
+            let numerator =
+                u64::from_str(&format!("{integer}{fraction}")).map_err(|e| format!("{e}"))?;
+            let denominator = 10_u64
+                .checked_pow(fraction.len() as u32)
+                .ok_or_else(|| "Multiplier has too many decimal places".to_string())?;
+            (numerator, denominator)
+        }
+        _ => {
+            return Err(
+                "Exact multiplier must be present as <NUMERATOR>/<DENOMINATOR> or a decimal"
+                    .to_string(),
+            )
+        }
+    };
+    if numerator == 0 || denominator == 0 {
+        return Err("Multiplier numerator and denominator must be positive".to_string());
+    }
+    Ok((numerator, denominator))
+}
+pub fn parse_metadata_field_update(string: &str) -> Result<(&str, &str), String> {
+    match string.split_once('=') {
+        Some((field, value)) if !field.is_empty() => Ok((field, value)),
+        _ => {
+            Err("Metadata field update must be present as <FIELD_NAME>=<VALUE_STRING>".to_string())
+        }
+    }
+}
+fn validate_ui_amount_multiplier(string: &str) -> Result<(), String> {
+    if string.contains('/') {
+        parse_rational_multiplier(string).map(|_| ())
+    } else {
+        f64::from_str(string)
+            .map(|_| ())
+            .map_err(|e| format!("{e}"))
+    }
+}
 fn validate_transfer_hook_account<T>(string: T) -> Result<(), String>
 where
     T: AsRef<str> + fmt::Display,
@@ -248,6 +329,7 @@ pub enum CliAuthorityType {
     ScaledUiAmount,
     Pause,
     PermissionedBurn,
+    NonTransferableRecovery,
 }
 impl TryFrom<CliAuthorityType> for AuthorityType {
     type Error = Error;
@@ -281,6 +363,7 @@ impl TryFrom<CliAuthorityType> for AuthorityType {
             CliAuthorityType::ScaledUiAmount => Ok(AuthorityType::ScaledUiAmount),
             CliAuthorityType::Pause => Ok(AuthorityType::Pause),
             CliAuthorityType::PermissionedBurn => Ok(AuthorityType::PermissionedBurn),
+            CliAuthorityType::NonTransferableRecovery => Ok(AuthorityType::NonTransferableRecovery),
         }
     }
 }
@@ -756,6 +839,38 @@ pub fn app<'a>(
                             Rate authority defaults to the mint authority."
                         ),
                 )
+                .arg(
+                    Arg::with_name("interest_compounding")
+                        .long("interest-compounding")
+                        .value_name("PERIOD")
+                        .takes_value(true)
+                        .possible_values(["continuous", "daily", "monthly"])
+                        .requires("interest_rate")
+                        .help(
+                            "Specify how often interest compounds. \
+                            Defaults to continuously."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("min_interest_rate")
+                        .long("min-interest-rate")
+                        .value_name("RATE_BPS")
+                        .takes_value(true)
+                        .allow_hyphen_values(true)
+                        .validator(|s| i16::from_str(s).map(|_| ()).map_err(|e| e.to_string()))
+                        .requires("interest_rate")
+                        .help("Specify the lowest interest rate that can be set, in basis points"),
+                )
+                .arg(
+                    Arg::with_name("max_interest_rate")
+                        .long("max-interest-rate")
+                        .value_name("RATE_BPS")
+                        .takes_value(true)
+                        .allow_hyphen_values(true)
+                        .validator(|s| i16::from_str(s).map(|_| ()).map_err(|e| e.to_string()))
+                        .requires("interest_rate")
+                        .help("Specify the highest interest rate that can be set, in basis points"),
+                )
                 .arg(
                     Arg::with_name("metadata_address")
                         .long("metadata-address")
@@ -798,6 +913,18 @@ pub fn app<'a>(
                             "Permanently force tokens to be non-transferable. They may still be burned."
                         ),
                 )
+                .arg(
+                    Arg::with_name("non_transferable_recovery_authority")
+                        .long("non-transferable-recovery-authority")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("AUTHORITY")
+                        .takes_value(true)
+                        .requires("enable_non_transferable")
+                        .help(
+                            "Specify an authority that can move non-transferable tokens between \
+                             accounts, e.g. to recover them from a lost wallet."
+                        ),
+                )
                 .arg(
                     Arg::with_name("default_account_state")
                         .long("default-account-state")
@@ -896,6 +1023,17 @@ pub fn app<'a>(
                         .takes_value(false)
                         .help("Enables metadata in the mint. The mint authority must initialize the metadata."),
                 )
+                .arg(
+                    Arg::with_name("metadata_size_limit")
+                        .long("metadata-size-limit")
+                        .value_name("BYTES")
+                        .value_parser(clap::value_parser!(u32))
+                        .takes_value(true)
+                        .help(
+                            "Limit the size of the metadata stored in the mint, in bytes. \
+                            Requires --enable-metadata or --metadata-address."
+                        ),
+                )
                 .arg(
                     Arg::with_name("enable_group")
                         .long("enable-group")
@@ -914,10 +1052,12 @@ pub fn app<'a>(
                     Arg::with_name("ui_amount_multiplier")
                         .long("ui-amount-multiplier")
                         .value_name("MULTIPLIER")
+                        .validator(|s| validate_ui_amount_multiplier(s))
                         .takes_value(true)
                         .conflicts_with("interest_rate")
                         .help(
                             "Specify the UI multiplier. \
+                            Pass the multiplier as NUMERATOR/DENOMINATOR to make UI amounts exact. \
                             Multiplier authority defaults to the mint authority."
                         ),
                 )
@@ -976,6 +1116,41 @@ pub fn app<'a>(
                         .required(true)
                         .help("The new interest rate in basis points"),
                 )
+                .arg(
+                    Arg::with_name("compounding")
+                        .long("compounding")
+                        .value_name("PERIOD")
+                        .takes_value(true)
+                        .possible_values(["continuous", "daily", "monthly"])
+                        .help(
+                            "Change how often interest compounds, updating the rate bounds too. \
+                            Defaults to the current compounding period."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("min_rate")
+                        .long("min-rate")
+                        .value_name("RATE_BPS")
+                        .takes_value(true)
+                        .allow_hyphen_values(true)
+                        .validator(|s| i16::from_str(s).map(|_| ()).map_err(|e| e.to_string()))
+                        .help(
+                            "Change the lowest interest rate that can be set, in basis points. \
+                            Bounds that are not given are removed when updating the terms."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("max_rate")
+                        .long("max-rate")
+                        .value_name("RATE_BPS")
+                        .takes_value(true)
+                        .allow_hyphen_values(true)
+                        .validator(|s| i16::from_str(s).map(|_| ()).map_err(|e| e.to_string()))
+                        .help(
+                            "Change the highest interest rate that can be set, in basis points. \
+                            Bounds that are not given are removed when updating the terms."
+                        ),
+                )
                 .arg(
                     Arg::with_name("rate_authority")
                     .long("rate-authority")
@@ -1103,7 +1278,7 @@ pub fn app<'a>(
                     Arg::with_name("field")
                         .value_name("FIELD_NAME")
                         .takes_value(true)
-                        .required(true)
+                        .required_unless("fields")
                         .index(2)
                         .help("The name of the field to update. Can be a base field (\"name\", \"symbol\", or \"uri\") or any new field to add."),
                 )
@@ -1112,9 +1287,21 @@ pub fn app<'a>(
                         .value_name("VALUE_STRING")
                         .takes_value(true)
                         .index(3)
-                        .required_unless("remove")
+                        .required_unless_present_any(&["remove", "fields"])
                         .help("The value for the field"),
                 )
+                .arg(
+                    Arg::with_name("fields")
+                        .long("field")
+                        .value_name("FIELD_NAME=VALUE_STRING")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .validator(|s| parse_metadata_field_update(s).map(|_| ()))
+                        .conflicts_with_all(&["field", "value", "remove"])
+                        .help("Set a field to a value, for example `--field name=MyToken`. \
+                            May be given several times to update all of the fields in a single instruction."),
+                )
                 .arg(
                     Arg::with_name("remove")
                         .long("remove")
@@ -1260,6 +1447,144 @@ pub fn app<'a>(
                         ),
                 )
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::RemoveMember.into())
+                .about("Remove a token mint from its group")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("The token address of the member account."),
+                )
+                .arg(
+                    Arg::with_name("group_token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("GROUP_TOKEN_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("The token address of the group account that the token will leave."),
+                )
+                .arg(
+                    Arg::with_name("authority")
+                        .long("authority")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the group update authority or member mint authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::MoveMember.into())
+                .about("Move a token mint from its group to another group")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("The token address of the member account."),
+                )
+                .arg(
+                    Arg::with_name("group_token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("GROUP_TOKEN_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("The token address of the group account that the token will leave."),
+                )
+                .arg(
+                    Arg::with_name("new_group_token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("NEW_GROUP_TOKEN_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(3)
+                        .help("The token address of the group account that the token will join."),
+                )
+                .arg(
+                    Arg::with_name("mint_authority")
+                        .long("mint-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the mint authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("group_update_authority")
+                        .long("group-update-authority")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the update authority keypair of the new group. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair address."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::FreezeGroupMembership.into())
+                .about("Prevent members from joining, leaving or moving out of a group")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("The token address of the group account."),
+                )
+                .arg(
+                    Arg::with_name("update_authority")
+                        .long("update-authority")
+                        .value_name("SIGNER")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the update authority address. \
+                             Defaults to the client keypair address."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::ThawGroupMembership.into())
+                .about("Allow members to join, leave or move out of a group again")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("The token address of the group account."),
+                )
+                .arg(
+                    Arg::with_name("update_authority")
+                        .long("update-authority")
+                        .value_name("SIGNER")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the update authority address. \
+                             Defaults to the client keypair address."
+                        ),
+                )
+        )
         .subcommand(
             SubCommand::with_name(CommandName::CreateAccount.into())
                 .about("Create a new token account")
@@ -1292,6 +1617,19 @@ pub fn app<'a>(
                             "Lock the owner of this token account from ever being changed"
                         ),
                 )
+                .arg(
+                    Arg::with_name("attestor")
+                        .long("attestor")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .requires("account_keypair")
+                        .help(
+                            "Specify the account state attestor registered on the mint, \
+                             so that the account skips the mint's default account state. \
+                             This may be a keypair file or the ASK keyword."
+                        ),
+                )
                 .arg(owner_address_arg())
                 .nonce_args(true)
         )
@@ -1576,6 +1914,53 @@ pub fn app<'a>(
                 .arg(memo_arg())
                 .offline_args_config(&SignOnlyNeedsFullMintSpec{}),
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::RecoveryTransfer.into())
+                .about("Move non-transferable tokens to another account as the mint's recovery authority")
+                .arg(
+                    Arg::with_name("source")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("SOURCE_TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token account address to recover tokens from"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("DESTINATION_TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("The token account address to move the tokens to"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .value_parser(Amount::parse)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Amount to move, in tokens; accepts keyword ALL"),
+                )
+                .arg(
+                    Arg::with_name("recovery_authority")
+                        .long("recovery-authority")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the recovery authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(multisig_signer_arg())
+                .mint_args()
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsFullMintSpec{}),
+        )
         .subcommand(
             SubCommand::with_name(CommandName::Mint.into())
                 .about("Mint new tokens")
@@ -2041,6 +2426,17 @@ pub fn app<'a>(
                             "Print token account addresses only"
                         ),
                 )
+                .arg(
+                    Arg::with_name("timestamp")
+                        .long("timestamp")
+                        .value_name("UNIX_TIMESTAMP")
+                        .takes_value(true)
+                        .validator(|s| i64::from_str(s).map(|_| ()).map_err(|e| e.to_string()))
+                        .help(
+                            "Show balances as of the given UNIX timestamp, for tokens \
+                            whose UI amounts change over time. [Default: current cluster time]"
+                        ),
+                )
                 .arg(owner_address_arg())
         )
         .subcommand(
@@ -2171,6 +2567,12 @@ pub fn app<'a>(
                         .required(true)
                         .help("The address of the token account to require transfer memos for")
                 )
+                .arg(
+                    Arg::with_name("outgoing")
+                        .long("outgoing")
+                        .takes_value(false)
+                        .help("Require memos for transfers out of the account, instead of into it"),
+                )
                 .arg(
                     owner_address_arg()
                 )
@@ -2189,6 +2591,12 @@ pub fn app<'a>(
                         .required(true)
                         .help("The address of the token account to stop requiring transfer memos for"),
                 )
+                .arg(
+                    Arg::with_name("outgoing")
+                        .long("outgoing")
+                        .takes_value(false)
+                        .help("Stop requiring memos for transfers out of the account, instead of into it"),
+                )
                 .arg(
                     owner_address_arg()
                 )
@@ -2196,8 +2604,8 @@ pub fn app<'a>(
                 .nonce_args(true)
         )
         .subcommand(
-            SubCommand::with_name(CommandName::EnableCpiGuard.into())
-                .about("Enable CPI Guard for token account")
+            SubCommand::with_name(CommandName::SetMemoTransferPolicy.into())
+                .about("Set the policy on the content of required transfer memos for token account")
                 .arg(
                     Arg::with_name("account")
                         .validator(|s| is_valid_pubkey(s))
@@ -2205,21 +2613,68 @@ pub fn app<'a>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account to enable CPI Guard for")
+                        .help("The address of the token account to set the memo policy for"),
                 )
                 .arg(
-                    owner_address_arg()
+                    Arg::with_name("min_len")
+                        .long("min-len")
+                        .value_name("BYTES")
+                        .value_parser(clap::value_parser!(u16))
+                        .takes_value(true)
+                        .help("Minimum length of incoming transfer memos, in bytes"),
                 )
-                .arg(multisig_signer_arg())
-                .nonce_args(true)
-        )
-        .subcommand(
-            SubCommand::with_name(CommandName::DisableCpiGuard.into())
-                .about("Disable CPI Guard for token account")
                 .arg(
-                    Arg::with_name("account")
-                        .validator(|s| is_valid_pubkey(s))
-                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                    Arg::with_name("prefix")
+                        .long("prefix")
+                        .value_name("PREFIX")
+                        .takes_value(true)
+                        .help(
+                            "Prefix that incoming transfer memos must start with, \
+                             up to 32 bytes"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("numeric_tag_len")
+                        .long("numeric-tag-len")
+                        .value_name("DIGITS")
+                        .value_parser(clap::value_parser!(u8))
+                        .takes_value(true)
+                        .help(
+                            "Require incoming transfer memos to consist of the prefix \
+                             followed by exactly this many digits"
+                        ),
+                )
+                .arg(
+                    owner_address_arg()
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::EnableCpiGuard.into())
+                .about("Enable CPI Guard for token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to enable CPI Guard for")
+                )
+                .arg(
+                    owner_address_arg()
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::DisableCpiGuard.into())
+                .about("Disable CPI Guard for token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
@@ -2231,6 +2686,56 @@ pub fn app<'a>(
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::SetCpiGuardPolicy.into())
+                .about("Set the operations allowed through CPI while CPI Guard is enabled")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to set the CPI Guard policy for"),
+                )
+                .arg(
+                    Arg::with_name("allow")
+                    .long("allow")
+                    .value_name("OPERATION")
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .possible_values([
+                        "transfer",
+                        "burn",
+                        "approve",
+                        "close-account",
+                        "set-authority",
+                    ])
+                    .help(
+                        "Allow the given operations through CPI. \
+                        Defaults to allowing nothing."
+                    )
+                )
+                .arg(
+                    Arg::with_name("allowed_program")
+                    .long("allowed-program")
+                    .validator(|s| is_valid_pubkey(s))
+                    .value_name("PROGRAM_ID")
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .help(
+                        "Only allow the operations when invoked directly by the given programs, \
+                        which must pass the instructions sysvar. Defaults to allowing any program."
+                    )
+                )
+                .arg(
+                    owner_address_arg()
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
         .subcommand(
             SubCommand::with_name(CommandName::UpdateDefaultAccountState.into())
                 .about("Updates default account state for the mint. Requires the default account state extension.")
@@ -2269,6 +2774,49 @@ pub fn app<'a>(
                 .nonce_args(true)
                 .offline_args(),
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::SetDefaultAccountStateAttestor.into())
+                .about("Register the signer that can approve new accounts skipping the default account state. Requires the default account state extension.")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token mint to register the attestor for"),
+                )
+                .arg(
+                    Arg::with_name("attestor")
+                        .index(2)
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("ATTESTOR_ADDRESS")
+                        .takes_value(true)
+                        .required_unless("disable")
+                        .help("Specify the signer that can approve new accounts"),
+                )
+                .arg(
+                    Arg::with_name("disable")
+                        .long("disable")
+                        .takes_value(false)
+                        .conflicts_with("attestor")
+                        .help("Unset the attestor.")
+                )
+                .arg(
+                    Arg::with_name("freeze_authority")
+                        .long("freeze-authority")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the token's freeze authority. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
+                        ),
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
         .subcommand(
             SubCommand::with_name(CommandName::UpdateMetadataAddress.into())
                 .about("Updates metadata pointer address for the mint. Requires the metadata pointer extension.")
@@ -2480,6 +3028,31 @@ pub fn app<'a>(
                         .required(true)
                         .help("The new maximum transfer fee in UI amount"),
                 )
+                .arg(
+                    Arg::with_name("fee_tier")
+                        .long("fee-tier")
+                        .value_parser(parse_transfer_fee_tier)
+                        .value_name("MINIMUM_AMOUNT:FEE_IN_BASIS_POINTS")
+                        .takes_value(true)
+                        .multiple(true)
+                        .min_values(1_usize)
+                        .help(
+                            "Additional tier of a tiered transfer fee, charging the given rate on \
+                            transfers of at least the given UI amount. FEE_IN_BASIS_POINTS applies \
+                            to smaller transfers. Requires a mint with tiered transfer fees."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("minimum_fee")
+                        .long("minimum-fee")
+                        .value_parser(Amount::parse)
+                        .value_name("MINIMUM_FEE")
+                        .takes_value(true)
+                        .help(
+                            "The new minimum transfer fee in UI amount. \
+                            Requires a mint with tiered transfer fees."
+                        ),
+                )
                 .arg(
                     Arg::with_name("transfer_fee_authority")
                     .long("transfer-fee-authority")
@@ -2494,6 +3067,58 @@ pub fn app<'a>(
                 .arg(mint_decimals_arg())
                 .offline_args_config(&SignOnlyNeedsMintDecimals{})
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::EnableTransferFeeExemption.into())
+                .about("Exempt a token account from transfer fees")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to exempt from transfer fees"),
+                )
+                .arg(
+                    Arg::with_name("transfer_fee_authority")
+                    .long("transfer-fee-authority")
+                    .validator(|s| is_valid_signer(s))
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the transfer fee authority keypair. \
+                        Defaults to the client keypair address."
+                    )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::DisableTransferFeeExemption.into())
+                .about("Remove the transfer fee exemption of a token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to charge transfer fees again"),
+                )
+                .arg(
+                    Arg::with_name("transfer_fee_authority")
+                    .long("transfer-fee-authority")
+                    .validator(|s| is_valid_signer(s))
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the transfer fee authority keypair. \
+                        Defaults to the client keypair address."
+                    )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
         .subcommand(
             SubCommand::with_name(CommandName::WithdrawExcessLamports.into())
                 .about("Withdraw lamports from a Token Program owned account")
@@ -2613,9 +3238,42 @@ pub fn app<'a>(
                             Defaults to 65536 (2^16)"
                         )
                 )
+                .arg(
+                    Arg::with_name("elgamal_registry")
+                        .long("elgamal-registry")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("ELGAMAL_REGISTRY_ADDRESS")
+                        .takes_value(true)
+                        .conflicts_with("maximum_pending_balance_credit_counter")
+                        .help(
+                            "Configure the account using the ElGamal public key stored in an \
+                            ElGamal registry account instead of deriving one from the owner. \
+                            The registry must be owned by the token account owner."
+                        )
+                )
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::CreateElgamalRegistry.into())
+                .about("Create an ElGamal registry holding the ElGamal public key derived from \
+                    the owner, which can then configure confidential transfers for any token account \
+                    of the owner with `configure-confidential-transfer-account --elgamal-registry`")
+                .arg(owner_keypair_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::UpdateElgamalRegistry.into())
+                .about("Update the ElGamal public key of an ElGamal registry to the key derived \
+                    from the owner")
+                .arg(owner_keypair_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::ShowElgamalRegistry.into())
+                .about("Display the ElGamal registry of an owner")
+                .arg(owner_address_arg())
+        )
         .subcommand(
             SubCommand::with_name(CommandName::EnableConfidentialCredits.into())
                 .about("Enable confidential transfers for token account. To enable confidential transfers \
@@ -2853,6 +3511,269 @@ pub fn app<'a>(
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
+        .subcommand(
+            SubCommand::with_name(CommandName::ApproveConfidentialTransferAccount.into())
+                .about("Approve a token account for confidential transfers")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with confidential transfers enabled"),
+                )
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("The address of the token account to approve"),
+                )
+                .arg(
+                    Arg::with_name("confidential_transfer_authority")
+                        .long("confidential-transfer-authority")
+                        .validator(|s| is_valid_signer(s))
+                        .value_name("SIGNER")
+                        .takes_value(true)
+                        .help(
+                            "Specify the confidential transfer authority keypair. \
+                            Defaults to the client keypair address."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::EmptyConfidentialTransferAccount.into())
+                .about("Prepare a confidential token account for closing by verifying that \
+                    its available balance is zero")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required_unless("address")
+                        .help("The token address with confidential transfers enabled"),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .long("address")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .conflicts_with("token")
+                        .help("The address of the token account to empty \
+                            [default: owner's associated token account]")
+                )
+                .arg(
+                    owner_address_arg()
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::WithdrawConfidentialWithheldTokens.into())
+                .about("Withdraw withheld confidential transfer fees from mint and / or account(s)")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("FEE_RECIPIENT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token account to send withdrawn fees to. The account must be \
+                            configured for confidential transfers."),
+                )
+                .arg(
+                    Arg::with_name("source")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("SOURCE_ADDRESS")
+                        .takes_value(true)
+                        .multiple(true)
+                        .min_values(0_usize)
+                        .index(2)
+                        .help("The token account(s) to withdraw fees from.")
+                )
+                .arg(
+                    Arg::with_name("include_mint")
+                        .long("include-mint")
+                        .takes_value(false)
+                        .help("Also withdraw withheld tokens from the mint"),
+                )
+                .arg(
+                    Arg::with_name("withdraw_withheld_authority")
+                        .long("withdraw-withheld-authority")
+                        .value_name("KEYPAIR")
+                        .validator(|s| is_valid_signer(s))
+                        .takes_value(true)
+                        .help(
+                            "Specify the withdraw withheld authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    owner_keypair_arg_with_value_name("FEE_RECIPIENT_OWNER_KEYPAIR")
+                        .help(
+                            "Specify the owner keypair of the fee recipient account, used to \
+                            derive the encryption keys of the account. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .group(
+                    ArgGroup::with_name("account_group")
+                        .arg("account")
+                        .required(true)
+                )
+                .group(
+                    ArgGroup::with_name("source_or_mint")
+                        .arg("source")
+                        .arg("include_mint")
+                        .multiple(true)
+                        .required(true)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::HarvestConfidentialWithheldTokens.into())
+                .about("Harvest withheld confidential transfer fees from account(s) to the mint")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with confidential transfer fees enabled"),
+                )
+                .arg(
+                    Arg::with_name("source")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("SOURCE_ADDRESS")
+                        .takes_value(true)
+                        .multiple(true)
+                        .min_values(1_usize)
+                        .index(2)
+                        .required(true)
+                        .help("The token account(s) to harvest fees from.")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::EnableConfidentialHarvestToMint.into())
+                .about("Enable harvesting of confidential transfer fees to the mint")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with confidential transfer fees enabled"),
+                )
+                .arg(
+                    Arg::with_name("confidential_transfer_fee_authority")
+                        .long("confidential-transfer-fee-authority")
+                        .validator(|s| is_valid_signer(s))
+                        .value_name("SIGNER")
+                        .takes_value(true)
+                        .help(
+                            "Specify the confidential transfer fee authority keypair. \
+                            Defaults to the client keypair address."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::DisableConfidentialHarvestToMint.into())
+                .about("Disable harvesting of confidential transfer fees to the mint")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with confidential transfer fees enabled"),
+                )
+                .arg(
+                    Arg::with_name("confidential_transfer_fee_authority")
+                        .long("confidential-transfer-fee-authority")
+                        .validator(|s| is_valid_signer(s))
+                        .value_name("SIGNER")
+                        .takes_value(true)
+                        .help(
+                            "Specify the confidential transfer fee authority keypair. \
+                            Defaults to the client keypair address."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::RotateConfidentialSupplyElgamalPubkey.into())
+                .about("Rotate the ElGamal public key that encrypts the confidential supply of a mint")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with confidential mint and burn enabled"),
+                )
+                .arg(
+                    Arg::with_name("new_supply_elgamal_pubkey")
+                        .value_name("NEW_SUPPLY_ELGAMAL_PUBKEY")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help(
+                            "The new supply encryption public key, given as a direct *base64* \
+                            encoding of an ElGamal public key or a path to an ElGamal keypair file."
+                        ),
+                )
+                .arg(
+                    owner_keypair_arg_with_value_name("MINT_AUTHORITY_KEYPAIR")
+                        .help(
+                            "Specify the mint authority keypair. The current supply encryption \
+                            keys are derived from this keypair. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::UpdateConfidentialDecryptableSupply.into())
+                .about("Recompute the decryptable supply of a confidential mint from the encrypted supply")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with confidential mint and burn enabled"),
+                )
+                .arg(
+                    owner_keypair_arg_with_value_name("MINT_AUTHORITY_KEYPAIR")
+                        .help(
+                            "Specify the mint authority keypair. The supply encryption \
+                            keys are derived from this keypair. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
         .subcommand(
             SubCommand::with_name(CommandName::UpdateUiAmountMultiplier.into())
                 .about("Update UI multiplier")
@@ -2868,10 +3789,12 @@ pub fn app<'a>(
                 .arg(
                     Arg::with_name("multiplier")
                         .value_name("MULTIPLIER")
+                        .validator(|s| validate_ui_amount_multiplier(s))
                         .takes_value(true)
                         .index(2)
                         .required(true)
-                        .help("The new multiplier"),
+                        .help("The new multiplier. Mints with an exact multiplier also accept \
+                            NUMERATOR/DENOMINATOR, and decimals are converted exactly."),
                 )
                 .arg(
                     Arg::with_name("timestamp")
@@ -2879,7 +3802,53 @@ pub fn app<'a>(
                         .takes_value(true)
                         .index(3)
                         .help("The effective time for the new multiplier, given as a UNIX timestamp \
-                            [default: current time]",)
+                            [default: current cluster time]",)
+                )
+                .arg(
+                    Arg::with_name("ui_multiplier_authority")
+                    .long("ui-multiplier-authority")
+                    .alias("owner")
+                    .validator(|s| is_valid_signer(s))
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the multiplier authority keypair. \
+                        Defaults to the client keypair address."
+                    )
+                )
+                .arg(
+                    Arg::with_name("schedule")
+                        .long("schedule")
+                        .takes_value(false)
+                        .help(
+                            "Queue the change in the mint's multiplier schedule, which keeps \
+                            earlier queued changes and the multiplier history. \
+                            Implied once the mint has a schedule."
+                        ),
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::CancelUiAmountMultiplier.into())
+                .about("Cancel a scheduled UI multiplier change")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(|s| is_valid_pubkey(s))
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token address with a UI multiplier schedule"),
+                )
+                .arg(
+                    Arg::with_name("timestamp")
+                        .value_name("TIMESTAMP")
+                        .validator(|s| i64::from_str(s).map(|_| ()).map_err(|e| e.to_string()))
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("The UNIX timestamp of the scheduled change"),
                 )
                 .arg(
                     Arg::with_name("ui_multiplier_authority")
@@ -2920,6 +3889,19 @@ pub fn app<'a>(
                         Defaults to the client keypair address."
                     )
                 )
+                .arg(
+                    Arg::with_name("operations")
+                    .long("operations")
+                    .value_name("OPERATION")
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .possible_values(["mint", "burn", "transfer"])
+                    .help(
+                        "Only pause the given operations, leaving the others active. \
+                        Defaults to pausing mint, burn, and transfer together."
+                    )
+                )
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
@@ -2947,6 +3929,19 @@ pub fn app<'a>(
                         Defaults to the client keypair address."
                     )
                 )
+                .arg(
+                    Arg::with_name("operations")
+                    .long("operations")
+                    .value_name("OPERATION")
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .possible_values(["mint", "burn", "transfer"])
+                    .help(
+                        "Only resume the given operations, if they were paused \
+                        individually. Defaults to resuming everything."
+                    )
+                )
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
@@ -27,10 +27,13 @@ use {
     solana_client::rpc_request::TokenAccountsFilter,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_sdk::{
+        account::from_account,
+        clock::Clock,
         instruction::AccountMeta,
         program_option::COption,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
+        sysvar,
     },
     solana_system_interface::program as system_program,
     solana_zk_elgamal_proof_interface::proof_data::{
@@ -48,24 +51,39 @@ use {
         extension::{
             confidential_mint_burn::ConfidentialMintBurn,
             confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
-            confidential_transfer_fee::ConfidentialTransferFeeConfig,
-            cpi_guard::CpiGuard,
+            confidential_transfer_fee::{
+                ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
+            },
+            cpi_guard::{CpiGuard, CpiGuardPolicy, MAX_CPI_GUARD_ALLOWED_PROGRAMS},
             default_account_state::DefaultAccountState,
             group_member_pointer::GroupMemberPointer,
             group_pointer::GroupPointer,
-            interest_bearing_mint::InterestBearingConfig,
-            memo_transfer::MemoTransfer,
+            interest_bearing_mint::{
+                CompoundingPeriod, InterestBearingConfig, InterestBearingTerms,
+            },
+            memo_transfer::{
+                MemoTransfer, MemoTransferPolicy, OutgoingMemoTransfer, MAX_MEMO_PREFIX_LEN,
+            },
             metadata_pointer::MetadataPointer,
             mint_close_authority::MintCloseAuthority,
-            pausable::PausableConfig,
+            non_transferable::NonTransferableRecovery,
+            pausable::{PausableConfig, PausedOperations},
             permanent_delegate::PermanentDelegate,
             permissioned_burn::PermissionedBurnConfig,
-            scaled_ui_amount::ScaledUiAmountConfig,
-            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            scaled_ui_amount::{
+                ScaledUiAmountConfig, ScaledUiAmountRationalConfig, ScaledUiAmountSchedule,
+            },
+            token_group::is_group_member,
+            token_metadata::{instruction::FieldUpdate, TokenMetadataSizeLimit},
+            transfer_fee::{
+                TieredTransferFeeConfig, TransferFeeAmount, TransferFeeConfig,
+                TransferFeeExemption, TransferFeeTier,
+            },
             transfer_hook::TransferHook,
             BaseStateWithExtensions, ExtensionType, StateWithExtensionsOwned,
         },
         state::{Account, AccountState, Mint},
+        ui_amount::UiAmountConverter,
     },
     spl_token_client::{
         client::{ProgramRpcClientSendTransaction, RpcClientResponse},
@@ -75,15 +93,17 @@ use {
         zk_proofs::{
             confidential_mint_burn::{BurnAccountInfo, SupplyAccountInfo},
             confidential_transfer::{
-                ApplyPendingBalanceAccountInfo, TransferAccountInfo, WithdrawAccountInfo,
+                ApplyPendingBalanceAccountInfo, EmptyAccountAccountInfo, TransferAccountInfo,
+                WithdrawAccountInfo,
             },
+            confidential_transfer_fee::WithheldTokensInfo,
         },
     },
     spl_token_confidential_transfer_proof_generation::{
         burn::BurnProofData, mint::MintProofData, transfer::TransferProofData,
         transfer_with_fee::TransferWithFeeProofData, withdraw::WithdrawProofData,
     },
-    spl_token_group_interface::state::TokenGroup,
+    spl_token_group_interface::state::{TokenGroup, TokenGroupMember},
     spl_token_metadata_interface::state::{Field, TokenMetadata},
     std::{
         collections::HashMap,
@@ -144,6 +164,20 @@ fn get_signer(
     })
 }
 
+/// Current UNIX timestamp of the cluster, falling back to the local clock when
+/// signing offline
+async fn cluster_unix_timestamp(config: &Config<'_>) -> Result<i64, Error> {
+    if config.sign_only {
+        return Ok(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64);
+    }
+    let clock_account = config.rpc_client.get_account(&sysvar::clock::id()).await?;
+    let clock: Clock = from_account(&clock_account).ok_or("Unable to deserialize clock sysvar")?;
+    Ok(clock.unix_timestamp)
+}
+
 async fn check_wallet_balance(
     config: &Config<'_>,
     wallet: &Pubkey,
@@ -262,22 +296,26 @@ async fn command_create_token(
     enable_freeze: bool,
     enable_close: bool,
     enable_non_transferable: bool,
+    non_transferable_recovery_authority: Option<Pubkey>,
     enable_permanent_delegate: bool,
     memo: Option<String>,
     metadata_address: Option<Pubkey>,
     group_address: Option<Pubkey>,
     member_address: Option<Pubkey>,
     rate_bps: Option<i16>,
+    interest_terms: Option<(CompoundingPeriod, Option<i16>, Option<i16>)>,
     default_account_state: Option<AccountState>,
     transfer_fee: Option<(u16, u64)>,
     confidential_transfer_auto_approve: Option<bool>,
     enable_confidential_mint_burn: bool,
     transfer_hook_program_id: Option<Pubkey>,
     enable_metadata: bool,
+    metadata_size_limit: Option<u32>,
     enable_group: bool,
     enable_member: bool,
     enable_transfer_hook: bool,
     ui_multiplier: Option<f64>,
+    exact_ui_multiplier: Option<(u64, u64)>,
     pausable: bool,
     enable_permissioned_burn: bool,
     permissioned_burn_authority: Option<Pubkey>,
@@ -316,10 +354,22 @@ async fn command_create_token(
         })
     }
 
+    if let Some((compounding_period, minimum_rate, maximum_rate)) = interest_terms {
+        extensions.push(ExtensionInitializationParams::InterestBearingTerms {
+            compounding_period,
+            minimum_rate,
+            maximum_rate,
+        })
+    }
+
     if enable_non_transferable {
         extensions.push(ExtensionInitializationParams::NonTransferable);
     }
 
+    if let Some(authority) = non_transferable_recovery_authority {
+        extensions.push(ExtensionInitializationParams::NonTransferableRecovery { authority });
+    }
+
     if let Some(state) = default_account_state {
         assert!(
             enable_freeze,
@@ -386,6 +436,13 @@ async fn command_create_token(
         });
     }
 
+    if let Some((numerator, denominator)) = exact_ui_multiplier {
+        extensions.push(ExtensionInitializationParams::ScaledUiAmountRational {
+            numerator,
+            denominator,
+        });
+    }
+
     if let Some(text) = memo {
         token.with_memo(text, bulk_signers.iter().map(|s| s.pubkey()).collect());
     }
@@ -403,6 +460,15 @@ async fn command_create_token(
         });
     }
 
+    if let Some(max_len) = metadata_size_limit {
+        if metadata_address.is_none() && !enable_metadata {
+            return Err(
+                "The metadata size limit requires --enable-metadata or --metadata-address".into(),
+            );
+        }
+        extensions.push(ExtensionInitializationParams::TokenMetadataSizeLimit { max_len });
+    }
+
     if group_address.is_some() || enable_group {
         let group_address = if enable_group {
             Some(token_pubkey)
@@ -498,6 +564,7 @@ async fn command_set_interest_rate(
     token_pubkey: Pubkey,
     rate_authority: Pubkey,
     rate_bps: i16,
+    terms: Option<(Option<CompoundingPeriod>, Option<i16>, Option<i16>)>,
     bulk_signers: Vec<Arc<dyn Signer>>,
 ) -> CommandResult {
     let mut token = token_client_from_config(config, &token_pubkey, None)?;
@@ -508,6 +575,7 @@ async fn command_set_interest_rate(
         token = token.with_compute_unit_limit(ComputeUnitLimit::Static(2_500));
     }
 
+    let mut current_terms = None;
     if !config.sign_only {
         let mint_account = config.get_account_checked(&token_pubkey).await?;
 
@@ -532,6 +600,78 @@ async fn command_set_interest_rate(
         } else {
             return Err(format!("Mint {} is not interest-bearing", token_pubkey).into());
         }
+
+        current_terms = mint_state
+            .get_extension::<InterestBearingTerms>()
+            .ok()
+            .copied();
+        if terms.is_some() && current_terms.is_none() {
+            return Err(format!(
+                "Mint {} has no interest-bearing terms to update",
+                token_pubkey
+            )
+            .into());
+        }
+    }
+
+    if let Some((compounding_period, minimum_rate, maximum_rate)) = terms {
+        let compounding_period = match (compounding_period, current_terms) {
+            (Some(compounding_period), _) => compounding_period,
+            (None, Some(current_terms)) => current_terms.compounding_period()?,
+            (None, None) => {
+                return Err("The compounding period must be specified when signing offline".into())
+            }
+        };
+        let new_terms = InterestBearingTerms::new(compounding_period, minimum_rate, maximum_rate);
+        if !new_terms.is_valid() {
+            return Err("The minimum rate must not exceed the maximum rate".into());
+        }
+        if !new_terms.contains_rate(rate_bps) {
+            return Err(format!(
+                "Interest rate {} bps is outside of the new bounds",
+                rate_bps
+            )
+            .into());
+        }
+
+        println_display(
+            config,
+            format!(
+                "Setting Interest Rate for {} to {} bps, compounding {:?}",
+                token_pubkey, rate_bps, compounding_period
+            ),
+        );
+
+        let res = token
+            .update_interest_terms(
+                &rate_authority,
+                compounding_period,
+                minimum_rate,
+                maximum_rate,
+                rate_bps,
+                &bulk_signers,
+            )
+            .await?;
+
+        let tx_return = finish_tx(config, &res, false).await?;
+        return Ok(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
+    if let Some(current_terms) = current_terms {
+        if !current_terms.contains_rate(rate_bps) {
+            return Err(format!(
+                "Interest rate {} bps is outside of the bounds of mint {}",
+                rate_bps, token_pubkey
+            )
+            .into());
+        }
     }
 
     println_display(
@@ -705,6 +845,37 @@ async fn command_update_metadata(
     })
 }
 
+async fn command_update_metadata_fields(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    authority: Pubkey,
+    updates: Vec<FieldUpdate>,
+    transfer_lamports: Option<u64>,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    let res = token
+        .token_metadata_update_fields_with_rent_transfer(
+            &config.fee_payer()?.pubkey(),
+            &authority,
+            updates,
+            transfer_lamports,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn command_initialize_group(
     config: &Config<'_>,
@@ -793,15 +964,116 @@ async fn command_initialize_member(
     })
 }
 
+async fn command_remove_member(
+    config: &Config<'_>,
+    member_token_pubkey: Pubkey,
+    group_token_pubkey: Pubkey,
+    authority: Pubkey,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &member_token_pubkey, None)?;
+
+    let res = token
+        .token_group_remove_member_with_rent_transfer(
+            &config.fee_payer()?.pubkey(),
+            &group_token_pubkey,
+            &authority,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_move_member(
+    config: &Config<'_>,
+    member_token_pubkey: Pubkey,
+    mint_authority: Pubkey,
+    group_token_pubkey: Pubkey,
+    new_group_token_pubkey: Pubkey,
+    group_update_authority: Pubkey,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &member_token_pubkey, None)?;
+
+    let res = token
+        .token_group_move_member_with_rent_transfer(
+            &config.fee_payer()?.pubkey(),
+            &mint_authority,
+            &group_token_pubkey,
+            &new_group_token_pubkey,
+            &group_update_authority,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_set_group_membership_frozen(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    update_authority: Pubkey,
+    frozen: bool,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    let res = if frozen {
+        token
+            .token_group_freeze_membership_with_rent_transfer(
+                &config.fee_payer()?.pubkey(),
+                &update_authority,
+                &bulk_signers,
+            )
+            .await?
+    } else {
+        token
+            .token_group_thaw_membership(&update_authority, &bulk_signers)
+            .await?
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn command_set_transfer_fee(
     config: &Config<'_>,
     token_pubkey: Pubkey,
     transfer_fee_authority: Pubkey,
     transfer_fee_basis_points: u16,
     maximum_fee: Amount,
+    fee_tiers: Option<Vec<(Amount, u16)>>,
+    minimum_fee: Option<Amount>,
     mint_decimals: Option<u8>,
     bulk_signers: Vec<Arc<dyn Signer>>,
 ) -> CommandResult {
+    // Offline, only an explicitly tiered fee can be set on a tiered mint
+    let mut tiered = fee_tiers.is_some() || minimum_fee.is_some();
     let decimals = if !config.sign_only {
         let mint_account = config.get_account_checked(&token_pubkey).await?;
 
@@ -836,6 +1108,14 @@ async fn command_set_transfer_fee(
         } else {
             return Err(format!("Mint {} does not have a transfer fee", token_pubkey).into());
         }
+
+        let has_tiers = mint_state
+            .get_extension::<TieredTransferFeeConfig>()
+            .is_ok();
+        if tiered && !has_tiers {
+            return Err(format!("Mint {} does not have tiered transfer fees", token_pubkey).into());
+        }
+        tiered = has_tiers;
         mint_state.base.decimals
     } else {
         mint_decimals.unwrap()
@@ -844,24 +1124,71 @@ async fn command_set_transfer_fee(
     let token = token_client_from_config(config, &token_pubkey, Some(decimals))?;
     let maximum_fee = amount_to_raw_amount(maximum_fee, decimals, None, "MAXIMUM_FEE");
 
-    println_display(
-        config,
-        format!(
-            "Setting transfer fee for {} to {} bps, {} maximum",
-            token_pubkey,
-            transfer_fee_basis_points,
-            spl_token_2022::amount_to_ui_amount(maximum_fee, decimals)
-        ),
-    );
+    let res = if tiered {
+        let minimum_fee = minimum_fee
+            .map(|amount| amount_to_raw_amount(amount, decimals, None, "MINIMUM_FEE"))
+            .unwrap_or_default();
+        let mut tiers = vec![TransferFeeTier {
+            minimum_amount: 0.into(),
+            transfer_fee_basis_points: transfer_fee_basis_points.into(),
+        }];
+        for (minimum_amount, transfer_fee_basis_points) in fee_tiers.unwrap_or_default() {
+            let minimum_amount =
+                amount_to_raw_amount(minimum_amount, decimals, None, "MINIMUM_AMOUNT");
+            tiers.push(TransferFeeTier {
+                minimum_amount: minimum_amount.into(),
+                transfer_fee_basis_points: transfer_fee_basis_points.into(),
+            });
+        }
 
-    let res = token
-        .set_transfer_fee(
-            &transfer_fee_authority,
-            transfer_fee_basis_points,
-            maximum_fee,
-            &bulk_signers,
-        )
-        .await?;
+        println_display(
+            config,
+            format!(
+                "Setting tiered transfer fee for {} to {}, {} minimum, {} maximum",
+                token_pubkey,
+                tiers
+                    .iter()
+                    .map(|tier| format!(
+                        "{} bps from {}",
+                        u16::from(tier.transfer_fee_basis_points),
+                        spl_token_2022::amount_to_ui_amount(tier.minimum_amount.into(), decimals)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                spl_token_2022::amount_to_ui_amount(minimum_fee, decimals),
+                spl_token_2022::amount_to_ui_amount(maximum_fee, decimals)
+            ),
+        );
+
+        token
+            .set_tiered_transfer_fee(
+                &transfer_fee_authority,
+                maximum_fee,
+                minimum_fee,
+                &tiers,
+                &bulk_signers,
+            )
+            .await?
+    } else {
+        println_display(
+            config,
+            format!(
+                "Setting transfer fee for {} to {} bps, {} maximum",
+                token_pubkey,
+                transfer_fee_basis_points,
+                spl_token_2022::amount_to_ui_amount(maximum_fee, decimals)
+            ),
+        );
+
+        token
+            .set_transfer_fee(
+                &transfer_fee_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+                &bulk_signers,
+            )
+            .await?
+    };
 
     let tx_return = finish_tx(config, &res, false).await?;
     Ok(match tx_return {
@@ -874,27 +1201,103 @@ async fn command_set_transfer_fee(
     })
 }
 
-async fn command_create_account(
+// both exempts accounts from transfer fees and removes exemptions, via exempt bool
+async fn command_transfer_fee_exemption(
     config: &Config<'_>,
-    token_pubkey: Pubkey,
-    owner: Pubkey,
-    maybe_account: Option<Pubkey>,
-    immutable_owner: bool,
-    bulk_signers: Vec<Arc<dyn Signer>>,
+    token_account_address: Pubkey,
+    transfer_fee_authority: Pubkey,
+    bulk_signers: BulkSigners,
+    exempt: bool,
 ) -> CommandResult {
-    let token = token_client_from_config(config, &token_pubkey, None)?;
-    let mut extensions = vec![];
+    if config.sign_only {
+        panic!("Config can not be sign-only for changing transfer fee exemptions.");
+    }
 
-    let (account, is_associated) = if let Some(account) = maybe_account {
-        (
-            account,
-            token.get_associated_token_address(&owner) == account,
-        )
+    let account = config.get_account_checked(&token_account_address).await?;
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+    let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
+
+    let mint_state = token.get_mint_info().await?;
+    if let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() {
+        let mint_fee_authority_pubkey =
+            Option::<Pubkey>::from(transfer_fee_config.transfer_fee_config_authority);
+        if mint_fee_authority_pubkey != Some(transfer_fee_authority) {
+            return Err(format!(
+                "Mint {} has transfer fee authority {}, but {} was provided",
+                state_with_extension.base.mint,
+                mint_fee_authority_pubkey
+                    .map(|pubkey| pubkey.to_string())
+                    .unwrap_or_else(|| "disabled".to_string()),
+                transfer_fee_authority
+            )
+            .into());
+        }
     } else {
-        (token.get_associated_token_address(&owner), true)
-    };
+        return Err(format!(
+            "Mint {} does not have a transfer fee",
+            state_with_extension.base.mint
+        )
+        .into());
+    }
 
-    println_display(config, format!("Creating account {}", account));
+    let current_exempt = state_with_extension
+        .get_extension::<TransferFeeExemption>()
+        .map(|extension| bool::from(extension.exempt))
+        .unwrap_or(false);
+    if current_exempt == exempt {
+        return Ok(format!(
+            "Account {} is already {}",
+            token_account_address,
+            if exempt {
+                "exempt from transfer fees"
+            } else {
+                "charged transfer fees"
+            }
+        ));
+    }
+
+    let res = token
+        .set_transfer_fee_exemption(
+            &token_account_address,
+            &transfer_fee_authority,
+            exempt,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_create_account(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    owner: Pubkey,
+    maybe_account: Option<Pubkey>,
+    immutable_owner: bool,
+    attestor: Option<Pubkey>,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let mut extensions = vec![];
+
+    let (account, is_associated) = if let Some(account) = maybe_account {
+        (
+            account,
+            token.get_associated_token_address(&owner) == account,
+        )
+    } else {
+        (token.get_associated_token_address(&owner), true)
+    };
+
+    println_display(config, format!("Creating account {}", account));
 
     if !config.sign_only {
         if let Some(account_data) = config.program_client.get_account(account).await? {
@@ -930,9 +1333,24 @@ async fn command_create_account(
             .find(|signer| signer.pubkey() == account)
             .unwrap_or_else(|| panic!("No signer provided for account {}", account));
 
-        token
-            .create_auxiliary_token_account_with_extension_space(&**signer, &owner, extensions)
-            .await
+        if let Some(attestor) = attestor {
+            let attestor_signer = bulk_signers
+                .iter()
+                .find(|signer| signer.pubkey() == attestor)
+                .unwrap_or_else(|| panic!("No signer provided for attestor {}", attestor));
+            token
+                .create_attested_auxiliary_token_account(
+                    &**signer,
+                    &owner,
+                    extensions,
+                    &**attestor_signer,
+                )
+                .await
+        } else {
+            token
+                .create_auxiliary_token_account_with_extension_space(&**signer, &owner, extensions)
+                .await
+        }
     }?;
 
     let tx_return = finish_tx(config, &res, false).await?;
@@ -1163,6 +1581,16 @@ async fn command_authorize(
                         ))
                     }
                 }
+                CliAuthorityType::NonTransferableRecovery => {
+                    if let Ok(extension) = mint.get_extension::<NonTransferableRecovery>() {
+                        Ok(Option::<Pubkey>::from(extension.authority))
+                    } else {
+                        Err(format!(
+                            "Mint `{}` does not support non-transferable recovery",
+                            account
+                        ))
+                    }
+                }
             }?;
 
             Ok((account, previous_authority))
@@ -1207,7 +1635,8 @@ async fn command_authorize(
                 | CliAuthorityType::GroupMemberPointer
                 | CliAuthorityType::ScaledUiAmount
                 | CliAuthorityType::Pause
-                | CliAuthorityType::PermissionedBurn => Err(format!(
+                | CliAuthorityType::PermissionedBurn
+                | CliAuthorityType::NonTransferableRecovery => Err(format!(
                     "Authority type `{auth_str}` not supported for SPL Token accounts",
                 )),
                 CliAuthorityType::Owner => {
@@ -2597,6 +3026,71 @@ async fn command_freeze(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn command_recovery_transfer(
+    config: &Config<'_>,
+    source: Pubkey,
+    destination: Pubkey,
+    recovery_authority: Pubkey,
+    ui_amount: Amount,
+    mint_address: Option<Pubkey>,
+    mint_decimals: Option<u8>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mint_address = config.check_account(&source, mint_address).await?;
+    let mint_info = config
+        .get_mint_info(&mint_address, mint_decimals, None)
+        .await?;
+    let token = token_client_from_config(config, &mint_info.address, Some(mint_info.decimals))?;
+
+    let amount = match ui_amount {
+        Amount::Raw(ui_amount) => ui_amount,
+        Amount::Decimal(ui_amount) => {
+            spl_token_2022::ui_amount_to_amount(ui_amount, mint_info.decimals)
+        }
+        Amount::All => {
+            if config.sign_only {
+                return Err(
+                    "Use of ALL keyword to recover tokens requires online signing"
+                        .to_string()
+                        .into(),
+                );
+            }
+            token.get_account_info(&source).await?.base.amount
+        }
+    };
+
+    println_display(
+        config,
+        format!(
+            "Recover {} tokens\n  Source: {}\n  Recipient: {}",
+            spl_token_2022::amount_to_ui_amount(amount, mint_info.decimals),
+            source,
+            destination
+        ),
+    );
+
+    let res = token
+        .recovery_transfer(
+            &source,
+            &destination,
+            &recovery_authority,
+            amount,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 async fn command_thaw(
     config: &Config<'_>,
     account: Pubkey,
@@ -3028,6 +3522,8 @@ async fn command_close_mint(
     recipient: Pubkey,
     bulk_signers: BulkSigners,
 ) -> CommandResult {
+    // a member mint leaves its group when closed
+    let mut group_mint = None;
     if !config.sign_only {
         let mint_account = config.get_account_checked(&token_pubkey).await?;
 
@@ -3061,12 +3557,30 @@ async fn command_close_mint(
         } else {
             return Err(format!("Mint {} does not support close authority", token_pubkey).into());
         }
+
+        group_mint = mint_state
+            .get_extension::<TokenGroupMember>()
+            .ok()
+            .filter(|member| is_group_member(member))
+            .map(|member| member.group);
     }
 
     let token = token_client_from_config(config, &token_pubkey, None)?;
-    let res = token
-        .close_account(&token_pubkey, &recipient, &close_authority, &bulk_signers)
-        .await?;
+    let res = if let Some(group_mint) = group_mint {
+        token
+            .close_group_member_mint_with_rent_transfer(
+                &config.fee_payer()?.pubkey(),
+                &group_mint,
+                &recipient,
+                &close_authority,
+                &bulk_signers,
+            )
+            .await?
+    } else {
+        token
+            .close_account(&token_pubkey, &recipient, &close_authority, &bulk_signers)
+            .await?
+    };
 
     let tx_return = finish_tx(config, &res, false).await?;
     Ok(match tx_return {
@@ -3101,6 +3615,7 @@ async fn command_accounts(
     owner: Pubkey,
     account_filter: AccountFilter,
     print_addresses_only: bool,
+    unix_timestamp: i64,
 ) -> CommandResult {
     let filters = if let Some(token_pubkey) = maybe_token {
         let _ = config.get_mint_info(&token_pubkey, None, None).await?;
@@ -3125,7 +3640,7 @@ async fn command_accounts(
     }
     let accounts = accounts.into_iter().flatten().collect();
 
-    let cli_token_accounts =
+    let mut cli_token_accounts =
         sort_and_parse_token_accounts(&owner, accounts, maybe_token.is_some(), account_filter)?;
 
     if print_addresses_only {
@@ -3137,10 +3652,73 @@ async fn command_accounts(
             .collect::<Vec<_>>()
             .join("\n"))
     } else {
+        convert_ui_amounts(config, &mut cli_token_accounts, unix_timestamp).await?;
         Ok(config.output_format.formatted_string(&cli_token_accounts))
     }
 }
 
+/// The RPC node only knows some of the Token-2022 extensions that change UI
+/// amounts, and only converts at the current time, so recompute the UI amounts
+/// from the mints instead
+async fn convert_ui_amounts(
+    config: &Config<'_>,
+    cli_token_accounts: &mut CliTokenAccounts,
+    unix_timestamp: i64,
+) -> Result<(), Error> {
+    let token_2022_program_id = spl_token_2022_interface::id().to_string();
+    let mut groups = cli_token_accounts
+        .accounts
+        .iter_mut()
+        .filter(|accounts| {
+            accounts
+                .first()
+                .is_some_and(|account| account.program_id == token_2022_program_id)
+        })
+        .collect::<Vec<_>>();
+
+    for chunk in groups.chunks_mut(100) {
+        let mints = chunk
+            .iter()
+            .map(|accounts| Pubkey::from_str(&accounts[0].account.mint))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mint_accounts = config.rpc_client.get_multiple_accounts(&mints).await?;
+        for (accounts, mint_account) in chunk.iter_mut().zip(mint_accounts) {
+            let Some(mint_account) = mint_account else {
+                continue;
+            };
+            let mint = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)?;
+            let converter = UiAmountConverter::new(mint.base.decimals, &mint)?;
+            if !converter.is_time_dependent() {
+                continue;
+            }
+            for account in accounts.iter_mut() {
+                let token_amount = &mut account.account.token_amount;
+                let amount = token_amount.amount.parse::<u64>()?;
+                let ui_amount = converter
+                    .amount_to_ui_amount(amount, unix_timestamp)
+                    .ok_or_else(|| format!("Could not convert amount {}", amount))?;
+                token_amount.ui_amount = ui_amount.parse::<f64>().ok();
+                token_amount.ui_amount_string = ui_amount;
+            }
+        }
+    }
+
+    cli_token_accounts.max_len_balance = cli_token_accounts
+        .accounts
+        .iter()
+        .flatten()
+        .map(|account| {
+            account
+                .account
+                .token_amount
+                .real_number_string_trimmed()
+                .len()
+        })
+        .max()
+        .unwrap_or_default();
+    Ok(())
+}
+
 async fn command_address(
     config: &Config<'_>,
     token: Option<Pubkey>,
@@ -3205,11 +3783,68 @@ async fn command_display(config: &Config<'_>, address: Pubkey) -> CommandResult
         }
         Ok(TokenAccountType::Mint(mint)) => {
             let epoch_info = config.rpc_client.get_epoch_info().await?;
+            // the exact multiplier, the multiplier schedule, interest-bearing
+            // terms and the metadata size limit are not parsed into `UiMint`
+            let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account_data.data)
+                .map_err(|_| format!("Could not deserialize token mint {}", address))?;
+            let exact_ui_amount_multiplier = mint_state
+                .get_extension::<ScaledUiAmountRationalConfig>()
+                .ok()
+                .map(|extension| CliExactUiAmountMultiplier {
+                    multiplier: format!(
+                        "{}/{}",
+                        u64::from(extension.multiplier.numerator),
+                        u64::from(extension.multiplier.denominator)
+                    ),
+                    new_multiplier_effective_timestamp: extension
+                        .new_multiplier_effective_timestamp
+                        .into(),
+                    new_multiplier: format!(
+                        "{}/{}",
+                        u64::from(extension.new_multiplier.numerator),
+                        u64::from(extension.new_multiplier.denominator)
+                    ),
+                });
+            let ui_amount_multiplier_schedule = mint_state
+                .get_variable_len_extension::<ScaledUiAmountSchedule>()
+                .ok()
+                .map(|schedule| {
+                    schedule
+                        .entries
+                        .iter()
+                        .map(|entry| CliScheduledMultiplier {
+                            effective_timestamp: entry.effective_timestamp.into(),
+                            multiplier: entry.multiplier.into(),
+                        })
+                        .collect()
+                });
+            let interest_bearing_terms = mint_state
+                .get_extension::<InterestBearingTerms>()
+                .ok()
+                .map(|terms| CliInterestBearingTerms {
+                    compounding_period: match terms.compounding_period() {
+                        Ok(CompoundingPeriod::Continuous) => "continuous",
+                        Ok(CompoundingPeriod::Daily) => "daily",
+                        Ok(CompoundingPeriod::Monthly) => "monthly",
+                        Err(_) => "unknown",
+                    }
+                    .to_string(),
+                    minimum_rate: Some(i16::from(terms.minimum_rate)).filter(|r| *r != i16::MIN),
+                    maximum_rate: Some(i16::from(terms.maximum_rate)).filter(|r| *r != i16::MAX),
+                });
+            let metadata_size_limit = mint_state
+                .get_extension::<TokenMetadataSizeLimit>()
+                .ok()
+                .map(|size_limit| u32::from(size_limit.max_len));
             let cli_output = CliMint {
                 address: address.to_string(),
                 epoch: epoch_info.epoch,
                 program_id: config.program_id.to_string(),
                 mint,
+                exact_ui_amount_multiplier,
+                ui_amount_multiplier_schedule,
+                interest_bearing_terms,
+                metadata_size_limit,
             };
 
             Ok(config.output_format.formatted_string(&cli_output))
@@ -3456,6 +4091,7 @@ async fn command_required_transfer_memos(
     owner: Pubkey,
     bulk_signers: BulkSigners,
     enable_memos: bool,
+    outgoing: bool,
 ) -> CommandResult {
     if config.sign_only {
         panic!("Config can not be sign-only for enabling/disabling required transfer memos.");
@@ -3467,17 +4103,31 @@ async fn command_required_transfer_memos(
     let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
     let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
 
+    let extension_type = if outgoing {
+        ExtensionType::OutgoingMemoTransfer
+    } else {
+        ExtensionType::MemoTransfer
+    };
+
     // Reallocation (if needed)
     let mut existing_extensions: Vec<ExtensionType> = state_with_extension.get_extension_types()?;
-    if existing_extensions.contains(&ExtensionType::MemoTransfer) {
-        let extension_state = state_with_extension
-            .get_extension::<MemoTransfer>()?
-            .require_incoming_transfer_memos
-            .into();
+    if existing_extensions.contains(&extension_type) {
+        let extension_state = if outgoing {
+            state_with_extension
+                .get_extension::<OutgoingMemoTransfer>()?
+                .require_outgoing_transfer_memos
+                .into()
+        } else {
+            state_with_extension
+                .get_extension::<MemoTransfer>()?
+                .require_incoming_transfer_memos
+                .into()
+        };
 
         if extension_state == enable_memos {
             return Ok(format!(
-                "Required transfer memos were already {}",
+                "Required {}transfer memos were already {}",
+                if outgoing { "outgoing " } else { "" },
                 if extension_state {
                     "enabled"
                 } else {
@@ -3486,7 +4136,7 @@ async fn command_required_transfer_memos(
             ));
         }
     } else {
-        existing_extensions.push(ExtensionType::MemoTransfer);
+        existing_extensions.push(extension_type);
         let needed_account_len =
             ExtensionType::try_calculate_account_len::<Account>(&existing_extensions)?;
         if needed_account_len > current_account_len {
@@ -3494,21 +4144,42 @@ async fn command_required_transfer_memos(
                 .reallocate(
                     &token_account_address,
                     &owner,
-                    &[ExtensionType::MemoTransfer],
+                    &[extension_type],
                     &bulk_signers,
                 )
                 .await?;
         }
     }
 
-    let res = if enable_memos {
-        token
-            .enable_required_transfer_memos(&token_account_address, &owner, &bulk_signers)
-            .await
-    } else {
-        token
-            .disable_required_transfer_memos(&token_account_address, &owner, &bulk_signers)
-            .await
+    let res = match (enable_memos, outgoing) {
+        (true, false) => {
+            token
+                .enable_required_transfer_memos(&token_account_address, &owner, &bulk_signers)
+                .await
+        }
+        (false, false) => {
+            token
+                .disable_required_transfer_memos(&token_account_address, &owner, &bulk_signers)
+                .await
+        }
+        (true, true) => {
+            token
+                .enable_required_outgoing_transfer_memos(
+                    &token_account_address,
+                    &owner,
+                    &bulk_signers,
+                )
+                .await
+        }
+        (false, true) => {
+            token
+                .disable_required_outgoing_transfer_memos(
+                    &token_account_address,
+                    &owner,
+                    &bulk_signers,
+                )
+                .await
+        }
     }?;
 
     let tx_return = finish_tx(config, &res, false).await?;
@@ -3522,6 +4193,56 @@ async fn command_required_transfer_memos(
     })
 }
 
+async fn command_set_memo_transfer_policy(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+    policy: MemoTransferPolicy,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Config can not be sign-only for setting a memo transfer policy.");
+    }
+
+    let account = config.get_account_checked(&token_account_address).await?;
+    let current_account_len = account.data.len();
+
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+    let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
+
+    // Reallocation (if needed)
+    let mut existing_extensions: Vec<ExtensionType> = state_with_extension.get_extension_types()?;
+    if !existing_extensions.contains(&ExtensionType::MemoTransferPolicy) {
+        existing_extensions.push(ExtensionType::MemoTransferPolicy);
+        let needed_account_len =
+            ExtensionType::try_calculate_account_len::<Account>(&existing_extensions)?;
+        if needed_account_len > current_account_len {
+            token
+                .reallocate(
+                    &token_account_address,
+                    &owner,
+                    &[ExtensionType::MemoTransferPolicy],
+                    &bulk_signers,
+                )
+                .await?;
+        }
+    }
+
+    let res = token
+        .set_memo_transfer_policy(&token_account_address, &owner, &policy, &bulk_signers)
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 // both enables and disables cpi guard, via enable_guard bool
 async fn command_cpi_guard(
     config: &Config<'_>,
@@ -3595,30 +4316,80 @@ async fn command_cpi_guard(
     })
 }
 
-async fn command_update_pointer_address(
+async fn command_set_cpi_guard_policy(
     config: &Config<'_>,
-    token_pubkey: Pubkey,
-    authority: Pubkey,
-    new_address: Option<Pubkey>,
+    token_account_address: Pubkey,
+    owner: Pubkey,
     bulk_signers: BulkSigners,
-    pointer: Pointer,
+    policy: CpiGuardPolicy,
 ) -> CommandResult {
     if config.sign_only {
-        panic!(
-            "Config can not be sign-only for updating {} pointer address.",
-            pointer
-        );
+        panic!("Config can not be sign-only for setting a CPI Guard policy.");
     }
 
-    let token = token_client_from_config(config, &token_pubkey, None)?;
-    let res = match pointer {
-        Pointer::Metadata => {
+    let account = config.get_account_checked(&token_account_address).await?;
+    let current_account_len = account.data.len();
+
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+    let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
+
+    // reallocation (if needed)
+    let mut existing_extensions: Vec<ExtensionType> = state_with_extension.get_extension_types()?;
+    if !existing_extensions.contains(&ExtensionType::CpiGuardPolicy) {
+        existing_extensions.push(ExtensionType::CpiGuardPolicy);
+        let required_account_len =
+            ExtensionType::try_calculate_account_len::<Account>(&existing_extensions)?;
+        if required_account_len > current_account_len {
             token
-                .update_metadata_address(&authority, new_address, &bulk_signers)
-                .await
+                .reallocate(
+                    &token_account_address,
+                    &owner,
+                    &[ExtensionType::CpiGuardPolicy],
+                    &bulk_signers,
+                )
+                .await?;
         }
-        Pointer::Group => {
-            token
+    }
+
+    let res = token
+        .set_cpi_guard_policy(&token_account_address, &owner, &policy, &bulk_signers)
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_update_pointer_address(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    authority: Pubkey,
+    new_address: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+    pointer: Pointer,
+) -> CommandResult {
+    if config.sign_only {
+        panic!(
+            "Config can not be sign-only for updating {} pointer address.",
+            pointer
+        );
+    }
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = match pointer {
+        Pointer::Metadata => {
+            token
+                .update_metadata_address(&authority, new_address, &bulk_signers)
+                .await
+        }
+        Pointer::Group => {
+            token
                 .update_group_address(&authority, new_address, &bulk_signers)
                 .await
         }
@@ -3705,6 +4476,63 @@ async fn command_update_default_account_state(
     })
 }
 
+async fn command_set_default_account_state_attestor(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    freeze_authority: Pubkey,
+    attestor: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Config can not be sign-only for setting a default account state attestor.");
+    }
+
+    let mint_account = config.get_account_checked(&token_pubkey).await?;
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+        .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+    match mint_state.base.freeze_authority {
+        COption::None => {
+            return Err(format!("Mint {} has no freeze authority.", token_pubkey).into())
+        }
+        COption::Some(mint_freeze_authority) => {
+            if mint_freeze_authority != freeze_authority {
+                return Err(format!(
+                    "Mint {} has a freeze authority {}, {} provided",
+                    token_pubkey, mint_freeze_authority, freeze_authority
+                )
+                .into());
+            }
+        }
+    }
+    if mint_state.get_extension::<DefaultAccountState>().is_err() {
+        return Err(format!(
+            "Mint {} does not support default account states",
+            token_pubkey
+        )
+        .into());
+    }
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = token
+        .set_default_account_state_attestor_with_rent_transfer(
+            &config.fee_payer()?.pubkey(),
+            &freeze_authority,
+            attestor.as_ref(),
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 async fn command_withdraw_withheld_tokens(
     config: &Config<'_>,
     destination_token_account: Pubkey,
@@ -3896,6 +4724,7 @@ async fn command_configure_confidential_transfer_account(
     owner: Pubkey,
     maybe_account: Option<Pubkey>,
     maximum_credit_counter: Option<u64>,
+    elgamal_registry: Option<Pubkey>,
     elgamal_keypair: &ElGamalKeypair,
     aes_key: &AeKey,
     bulk_signers: BulkSigners,
@@ -3919,6 +4748,29 @@ async fn command_configure_confidential_transfer_account(
     let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
     let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
 
+    // The registry instruction reallocates the account itself when given a
+    // payer, so no owner signature is needed on this path
+    if let Some(elgamal_registry) = elgamal_registry {
+        let payer = config.fee_payer()?.pubkey();
+        let res = token
+            .confidential_transfer_configure_token_account_with_registry(
+                &token_account_address,
+                &elgamal_registry,
+                Some(&payer),
+            )
+            .await?;
+
+        let tx_return = finish_tx(config, &res, false).await?;
+        return Ok(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
     // Reallocation (if needed)
     let mut existing_extensions: Vec<ExtensionType> = state_with_extension.get_extension_types()?;
     if !existing_extensions.contains(&ExtensionType::ConfidentialTransferAccount) {
@@ -3964,6 +4816,79 @@ async fn command_configure_confidential_transfer_account(
     })
 }
 
+async fn command_create_update_elgamal_registry(
+    config: &Config<'_>,
+    owner: Pubkey,
+    elgamal_keypair: &ElGamalKeypair,
+    update: bool,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Sign-only is not yet supported.");
+    }
+
+    // default is safe here because the registry is not tied to a mint
+    let token = token_client_from_config(config, &Pubkey::default(), None)?;
+    let elgamal_registry = token.get_elgamal_registry_address(&owner);
+
+    println_display(
+        config,
+        format!(
+            "{} ElGamal registry {} for {}",
+            if update { "Updating" } else { "Creating" },
+            elgamal_registry,
+            owner,
+        ),
+    );
+
+    let res = if update {
+        token
+            .update_elgamal_registry(&owner, elgamal_keypair, &bulk_signers)
+            .await?
+    } else {
+        token
+            .create_elgamal_registry(&owner, elgamal_keypair, &bulk_signers)
+            .await?
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_show_elgamal_registry(config: &Config<'_>, owner: Pubkey) -> CommandResult {
+    // default is safe here because the registry is not tied to a mint
+    let token = token_client_from_config(config, &Pubkey::default(), None)?;
+    let address = token.get_elgamal_registry_address(&owner);
+    let (registry, labelled_keys) = token
+        .get_elgamal_registry_info(&owner)
+        .await
+        .map_err(|_| format!("Could not find ElGamal registry {} for {}", address, owner))?;
+
+    let cli_output = CliElGamalRegistry {
+        address: address.to_string(),
+        owner: registry.owner.to_string(),
+        elgamal_pubkey: registry.elgamal_pubkey.to_string(),
+        labelled_keys: labelled_keys
+            .iter()
+            .map(|key| CliLabelledElGamalPubkey {
+                label: String::from_utf8_lossy(&key.label)
+                    .trim_end_matches('\0')
+                    .to_string(),
+                elgamal_pubkey: key.elgamal_pubkey.to_string(),
+            })
+            .collect(),
+    };
+
+    Ok(config.output_format.formatted_string(&cli_output))
+}
+
 async fn command_enable_disable_confidential_transfers(
     config: &Config<'_>,
     maybe_token: Option<Pubkey>,
@@ -4402,57 +5327,55 @@ async fn command_apply_pending_burn(
     })
 }
 
-async fn command_update_multiplier(
+async fn command_approve_confidential_transfer_account(
     config: &Config<'_>,
     token_pubkey: Pubkey,
-    ui_multiplier_authority: Pubkey,
-    new_multiplier: f64,
-    new_multiplier_effective_timestamp: i64,
-    bulk_signers: Vec<Arc<dyn Signer>>,
+    account: Pubkey,
+    authority: Pubkey,
+    bulk_signers: BulkSigners,
 ) -> CommandResult {
-    let token = token_client_from_config(config, &token_pubkey, None)?;
-
     if !config.sign_only {
         let mint_account = config.get_account_checked(&token_pubkey).await?;
 
         let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
             .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
 
-        if let Ok(scaled_ui_amount_config) = mint_state.get_extension::<ScaledUiAmountConfig>() {
-            let scaled_ui_amount_authority_pubkey =
-                Option::<Pubkey>::from(scaled_ui_amount_config.authority);
+        if let Ok(confidential_transfer_mint) =
+            mint_state.get_extension::<ConfidentialTransferMint>()
+        {
+            let expected_authority = Option::<Pubkey>::from(confidential_transfer_mint.authority);
 
-            if scaled_ui_amount_authority_pubkey != Some(ui_multiplier_authority) {
+            if expected_authority != Some(authority) {
                 return Err(format!(
-                    "Mint {} has multiplier authority {}, but {} was provided",
+                    "Mint {} has confidential transfer authority {}, but {} was provided",
                     token_pubkey,
-                    scaled_ui_amount_authority_pubkey
+                    expected_authority
                         .map(|pubkey| pubkey.to_string())
                         .unwrap_or_else(|| "disabled".to_string()),
-                    ui_multiplier_authority
+                    authority
                 )
                 .into());
             }
         } else {
-            return Err(format!("Mint {} does not have a UI multiplier", token_pubkey).into());
+            return Err(format!(
+                "Mint {} does not support confidential transfers",
+                token_pubkey
+            )
+            .into());
         }
     }
 
     println_display(
         config,
         format!(
-            "Setting UI Multiplier for {} to {} at UNIX timestamp {}",
-            token_pubkey, new_multiplier, new_multiplier_effective_timestamp
+            "Approving {} for confidential transfers of {}",
+            account, token_pubkey
         ),
     );
 
+    let token = token_client_from_config(config, &token_pubkey, None)?;
     let res = token
-        .update_multiplier(
-            &ui_multiplier_authority,
-            new_multiplier,
-            new_multiplier_effective_timestamp,
-            &bulk_signers,
-        )
+        .confidential_transfer_approve_account(&account, &authority, &bulk_signers)
         .await?;
 
     let tx_return = finish_tx(config, &res, false).await?;
@@ -4466,55 +5389,65 @@ async fn command_update_multiplier(
     })
 }
 
-async fn command_pause_resume(
+async fn command_empty_confidential_transfer_account(
     config: &Config<'_>,
-    token_pubkey: Pubkey,
-    pause_authority: Pubkey,
-    bulk_signers: Vec<Arc<dyn Signer>>,
-    allow_mint_burn_transfer: bool,
+    maybe_token: Option<Pubkey>,
+    owner: Pubkey,
+    maybe_account: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+    elgamal_keypair: &ElGamalKeypair,
 ) -> CommandResult {
-    if !config.sign_only {
-        let mint_account = config.get_account_checked(&token_pubkey).await?;
+    if config.sign_only {
+        panic!("Sign-only is not yet supported.");
+    }
 
-        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
-            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+    let token_account_address = if let Some(account) = maybe_account {
+        account
+    } else {
+        let token_pubkey =
+            maybe_token.expect("Either a valid token or account address must be provided");
+        let token = token_client_from_config(config, &token_pubkey, None)?;
+        token.get_associated_token_address(&owner)
+    };
 
-        if let Ok(pausable_config) = mint_state.get_extension::<PausableConfig>() {
-            let pause_authority_pubkey = Option::<Pubkey>::from(pausable_config.authority);
+    let account = config.get_account_checked(&token_account_address).await?;
 
-            if pause_authority_pubkey != Some(pause_authority) {
-                return Err(format!(
-                    "Mint {} has pause authority {}, but {} was provided",
-                    token_pubkey,
-                    pause_authority_pubkey
-                        .map(|pubkey| pubkey.to_string())
-                        .unwrap_or_else(|| "disabled".to_string()),
-                    pause_authority
-                )
-                .into());
-            }
-        } else {
-            return Err(format!("Mint {} is not pausable", token_pubkey).into());
-        }
-    }
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+    let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
 
-    let res = if allow_mint_burn_transfer {
-        println_display(
-            config,
-            format!("Resuming mint, burn, and transfer for {}", token_pubkey,),
-        );
+    let extension_state = state_with_extension
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| {
+            format!(
+                "Confidential transfer is not configured for account {}",
+                token_account_address
+            )
+        })?;
+    if u64::from(extension_state.pending_balance_credit_counter) > 0 {
+        return Err(format!(
+            "Account {} has a pending balance. Use `apply-pending-balance` and withdraw \
+            the available balance first.",
+            token_account_address
+        )
+        .into());
+    }
+    let account_info = EmptyAccountAccountInfo::new(extension_state);
 
-        let token = token_client_from_config(config, &token_pubkey, None)?;
-        token.resume(&pause_authority, &bulk_signers).await?
-    } else {
-        println_display(
-            config,
-            format!("Pausing mint, burn, and transfer for {}", token_pubkey,),
-        );
+    println_display(
+        config,
+        format!("Emptying confidential balance of {}", token_account_address),
+    );
 
-        let token = token_client_from_config(config, &token_pubkey, None)?;
-        token.pause(&pause_authority, &bulk_signers).await?
-    };
+    let res = token
+        .confidential_transfer_empty_account(
+            &token_account_address,
+            &owner,
+            None,
+            Some(account_info),
+            elgamal_keypair,
+            &bulk_signers,
+        )
+        .await?;
 
     let tx_return = finish_tx(config, &res, false).await?;
     Ok(match tx_return {
@@ -4527,19 +5460,686 @@ async fn command_pause_resume(
     })
 }
 
-struct ConfidentialTransferArgs {
-    sender_elgamal_keypair: ElGamalKeypair,
-    sender_aes_key: AeKey,
-    recipient_elgamal_pubkey: Option<PodElGamalPubkey>,
-    auditor_elgamal_pubkey: Option<PodElGamalPubkey>,
-}
-
-pub async fn process_command(
-    sub_command: &CommandName,
-    sub_matches: &ArgMatches,
+#[allow(clippy::too_many_arguments)]
+async fn command_withdraw_confidential_withheld_tokens(
     config: &Config<'_>,
-    mut wallet_manager: Option<Rc<RemoteWalletManager>>,
-    mut bulk_signers: Vec<Arc<dyn Signer>>,
+    destination_token_account: Pubkey,
+    source_token_accounts: Vec<Pubkey>,
+    authority: Pubkey,
+    include_mint: bool,
+    withdraw_withheld_authority_elgamal_keypair: &ElGamalKeypair,
+    destination_aes_key: &AeKey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Config can not be sign-only for withdrawing withheld tokens.");
+    }
+    let destination_account = config
+        .get_account_checked(&destination_token_account)
+        .await?;
+    let destination_state = StateWithExtensionsOwned::<Account>::unpack(destination_account.data)
+        .map_err(|_| {
+        format!(
+            "Could not deserialize token account {}",
+            destination_token_account
+        )
+    })?;
+    let token_pubkey = destination_state.base.mint;
+    let destination_extension = destination_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| {
+            format!(
+                "Confidential transfer is not configured for account {}",
+                destination_token_account
+            )
+        })?;
+    let destination_elgamal_pubkey: elgamal::ElGamalPubkey = destination_extension
+        .elgamal_pubkey
+        .try_into()
+        .map_err(|_| "Invalid destination ElGamal pubkey".to_string())?;
+    let mut destination_available_balance =
+        ApplyPendingBalanceAccountInfo::new(destination_extension)
+            .get_available_balance(destination_aes_key)?;
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let mut results = vec![];
+    if include_mint {
+        let mint_state = token.get_mint_info().await?;
+        let withheld_tokens_info = WithheldTokensInfo::new(
+            &mint_state
+                .get_extension::<ConfidentialTransferFeeConfig>()
+                .map_err(|_| {
+                    format!(
+                        "Token mint {} has no confidential transfer fee configured",
+                        token_pubkey
+                    )
+                })?
+                .withheld_amount,
+        );
+        destination_available_balance = destination_available_balance
+            .checked_add(
+                withheld_tokens_info
+                    .decrypted_withheld_amount(withdraw_withheld_authority_elgamal_keypair)?,
+            )
+            .ok_or("Destination available balance overflow")?;
+        let new_decryptable_available_balance =
+            destination_aes_key.encrypt(destination_available_balance);
+
+        let res = token
+            .confidential_transfer_withdraw_withheld_tokens_from_mint(
+                &destination_token_account,
+                &authority,
+                None,
+                Some(withheld_tokens_info),
+                withdraw_withheld_authority_elgamal_keypair,
+                &destination_elgamal_pubkey,
+                &new_decryptable_available_balance.into(),
+                &bulk_signers,
+            )
+            .await;
+        let tx_return = finish_tx(config, &res?, false).await?;
+        results.push(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
+    let source_refs = source_token_accounts.iter().collect::<Vec<_>>();
+    // each withdrawal carries a proof, so keep the account list small
+    const MAX_WITHDRAWAL_ACCOUNTS: usize = 15;
+    for sources in source_refs.chunks(MAX_WITHDRAWAL_ACCOUNTS) {
+        let mut aggregate_withheld_amount = elgamal::ElGamalCiphertext::default();
+        for source in sources {
+            let source_account = config.get_account_checked(source).await?;
+            let source_state = StateWithExtensionsOwned::<Account>::unpack(source_account.data)
+                .map_err(|_| format!("Could not deserialize token account {}", source))?;
+            let withheld_amount: elgamal::ElGamalCiphertext = source_state
+                .get_extension::<ConfidentialTransferFeeAmount>()
+                .map_err(|_| {
+                    format!(
+                        "Token account {} has no confidential transfer fee amount",
+                        source
+                    )
+                })?
+                .withheld_amount
+                .try_into()
+                .map_err(|_| format!("Invalid withheld amount in {}", source))?;
+            aggregate_withheld_amount = aggregate_withheld_amount + withheld_amount;
+        }
+        let withheld_tokens_info = WithheldTokensInfo::new(&aggregate_withheld_amount.into());
+        destination_available_balance = destination_available_balance
+            .checked_add(
+                withheld_tokens_info
+                    .decrypted_withheld_amount(withdraw_withheld_authority_elgamal_keypair)?,
+            )
+            .ok_or("Destination available balance overflow")?;
+        let new_decryptable_available_balance =
+            destination_aes_key.encrypt(destination_available_balance);
+
+        let res = token
+            .confidential_transfer_withdraw_withheld_tokens_from_accounts(
+                &destination_token_account,
+                &authority,
+                None,
+                Some(withheld_tokens_info),
+                withdraw_withheld_authority_elgamal_keypair,
+                &destination_elgamal_pubkey,
+                &new_decryptable_available_balance.into(),
+                sources,
+                &bulk_signers,
+            )
+            .await;
+        let tx_return = finish_tx(config, &res?, false).await?;
+        results.push(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
+    Ok(results.join(""))
+}
+
+async fn command_harvest_confidential_withheld_tokens(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    source_token_accounts: Vec<Pubkey>,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Config can not be sign-only for harvesting withheld tokens.");
+    }
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let source_refs = source_token_accounts.iter().collect::<Vec<_>>();
+    // this can be tweaked better, but keep it simple for now
+    const MAX_HARVEST_ACCOUNTS: usize = 25;
+    let mut results = vec![];
+    for sources in source_refs.chunks(MAX_HARVEST_ACCOUNTS) {
+        let res = token
+            .confidential_transfer_harvest_withheld_tokens_to_mint(sources)
+            .await;
+        let tx_return = finish_tx(config, &res?, false).await?;
+        results.push(match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+
+    Ok(results.join(""))
+}
+
+async fn command_enable_disable_confidential_harvest_to_mint(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    authority: Pubkey,
+    bulk_signers: BulkSigners,
+    enable_harvest_to_mint: bool,
+) -> CommandResult {
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(confidential_transfer_fee_config) =
+            mint_state.get_extension::<ConfidentialTransferFeeConfig>()
+        {
+            let expected_authority =
+                Option::<Pubkey>::from(confidential_transfer_fee_config.authority);
+
+            if expected_authority != Some(authority) {
+                return Err(format!(
+                    "Mint {} has confidential transfer fee authority {}, but {} was provided",
+                    token_pubkey,
+                    expected_authority
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    authority
+                )
+                .into());
+            }
+
+            if bool::from(confidential_transfer_fee_config.harvest_to_mint_enabled)
+                == enable_harvest_to_mint
+            {
+                return Ok(format!(
+                    "Harvest to mint is already {}",
+                    if enable_harvest_to_mint {
+                        "enabled"
+                    } else {
+                        "disabled"
+                    }
+                ));
+            }
+        } else {
+            return Err(format!(
+                "Mint {} does not support confidential transfer fees",
+                token_pubkey
+            )
+            .into());
+        }
+    }
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = if enable_harvest_to_mint {
+        println_display(
+            config,
+            format!("Enabling confidential harvest to mint for {}", token_pubkey),
+        );
+        token
+            .confidential_transfer_enable_harvest_to_mint(&authority, &bulk_signers)
+            .await?
+    } else {
+        println_display(
+            config,
+            format!(
+                "Disabling confidential harvest to mint for {}",
+                token_pubkey
+            ),
+        );
+        token
+            .confidential_transfer_disable_harvest_to_mint(&authority, &bulk_signers)
+            .await?
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn command_rotate_confidential_supply_elgamal_pubkey(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    mint_authority: Pubkey,
+    new_supply_elgamal_pubkey: PodElGamalPubkey,
+    supply_elgamal_keypair: &ElGamalKeypair,
+    supply_aes_key: &AeKey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Sign-only is not yet supported.");
+    }
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let mint_state = token.get_mint_info().await?;
+    let supply_account_info =
+        SupplyAccountInfo::new(mint_state.get_extension::<ConfidentialMintBurn>().map_err(
+            |_| {
+                format!(
+                    "Mint {} does not support confidential mint and burn",
+                    token_pubkey
+                )
+            },
+        )?);
+    println_display(
+        config,
+        format!(
+            "Rotating supply encryption pubkey for {} to {}",
+            token_pubkey, new_supply_elgamal_pubkey
+        ),
+    );
+
+    let new_supply_elgamal_pubkey: elgamal::ElGamalPubkey = new_supply_elgamal_pubkey
+        .try_into()
+        .map_err(|_| "Invalid new supply ElGamal pubkey".to_string())?;
+
+    let res = token
+        .confidential_transfer_rotate_supply_elgamal_pubkey(
+            &mint_authority,
+            supply_elgamal_keypair,
+            &new_supply_elgamal_pubkey,
+            supply_aes_key,
+            None,
+            Some(supply_account_info),
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_update_confidential_decryptable_supply(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    mint_authority: Pubkey,
+    supply_elgamal_keypair: &ElGamalKeypair,
+    supply_aes_key: &AeKey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Sign-only is not yet supported.");
+    }
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let mint_state = token.get_mint_info().await?;
+    let supply_account_info =
+        SupplyAccountInfo::new(mint_state.get_extension::<ConfidentialMintBurn>().map_err(
+            |_| {
+                format!(
+                    "Mint {} does not support confidential mint and burn",
+                    token_pubkey
+                )
+            },
+        )?);
+    let current_supply =
+        supply_account_info.decrypted_current_supply(supply_aes_key, supply_elgamal_keypair)?;
+    let new_decryptable_supply = supply_aes_key.encrypt(current_supply);
+
+    println_display(
+        config,
+        format!(
+            "Updating decryptable supply for {} to {}",
+            token_pubkey,
+            spl_token_2022::amount_to_ui_amount(current_supply, mint_state.base.decimals)
+        ),
+    );
+
+    let res = token
+        .confidential_transfer_update_decrypt_supply(
+            &mint_authority,
+            &new_decryptable_supply.into(),
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_update_multiplier(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    ui_multiplier_authority: Pubkey,
+    new_multiplier: &str,
+    new_multiplier_effective_timestamp: i64,
+    mut schedule: bool,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    // Offline, only an explicitly rational multiplier is set exactly
+    let mut exact = new_multiplier.contains('/');
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(scaled_ui_amount_config) = mint_state.get_extension::<ScaledUiAmountConfig>() {
+            let scaled_ui_amount_authority_pubkey =
+                Option::<Pubkey>::from(scaled_ui_amount_config.authority);
+
+            if scaled_ui_amount_authority_pubkey != Some(ui_multiplier_authority) {
+                return Err(format!(
+                    "Mint {} has multiplier authority {}, but {} was provided",
+                    token_pubkey,
+                    scaled_ui_amount_authority_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    ui_multiplier_authority
+                )
+                .into());
+            }
+        } else {
+            return Err(format!("Mint {} does not have a UI multiplier", token_pubkey).into());
+        }
+
+        let has_exact_multiplier = mint_state
+            .get_extension::<ScaledUiAmountRationalConfig>()
+            .is_ok();
+        if exact && !has_exact_multiplier {
+            return Err(
+                format!("Mint {} does not have an exact UI multiplier", token_pubkey).into(),
+            );
+        }
+        exact = has_exact_multiplier;
+
+        // once a mint has a schedule, all changes go through it
+        schedule |= mint_state
+            .get_extension_bytes::<ScaledUiAmountSchedule>()
+            .is_ok();
+    }
+
+    if exact && schedule {
+        return Err("Exact multiplier changes can't be scheduled".into());
+    }
+
+    let res = if exact {
+        let (numerator, denominator) = parse_rational_multiplier(new_multiplier)?;
+        println_display(
+            config,
+            format!(
+                "Setting UI Multiplier for {} to {}/{} at UNIX timestamp {}",
+                token_pubkey, numerator, denominator, new_multiplier_effective_timestamp
+            ),
+        );
+
+        token
+            .update_rational_multiplier(
+                &ui_multiplier_authority,
+                numerator,
+                denominator,
+                new_multiplier_effective_timestamp,
+                &bulk_signers,
+            )
+            .await?
+    } else {
+        let new_multiplier = f64::from_str(new_multiplier)?;
+        println_display(
+            config,
+            format!(
+                "{} UI Multiplier for {} to {} at UNIX timestamp {}",
+                if schedule { "Scheduling" } else { "Setting" },
+                token_pubkey,
+                new_multiplier,
+                new_multiplier_effective_timestamp
+            ),
+        );
+
+        if schedule {
+            token
+                .schedule_multiplier_with_rent_transfer(
+                    &config.fee_payer()?.pubkey(),
+                    &ui_multiplier_authority,
+                    new_multiplier,
+                    new_multiplier_effective_timestamp,
+                    &bulk_signers,
+                )
+                .await?
+        } else {
+            token
+                .update_multiplier(
+                    &ui_multiplier_authority,
+                    new_multiplier,
+                    new_multiplier_effective_timestamp,
+                    &bulk_signers,
+                )
+                .await?
+        }
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_cancel_multiplier(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    ui_multiplier_authority: Pubkey,
+    effective_timestamp: i64,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        let schedule = mint_state
+            .get_variable_len_extension::<ScaledUiAmountSchedule>()
+            .map_err(|_| format!("Mint {} does not have a multiplier schedule", token_pubkey))?;
+        if !schedule
+            .entries
+            .iter()
+            .any(|entry| i64::from(entry.effective_timestamp) == effective_timestamp)
+        {
+            return Err(format!(
+                "Mint {} has no multiplier change scheduled at UNIX timestamp {}",
+                token_pubkey, effective_timestamp
+            )
+            .into());
+        }
+    }
+
+    println_display(
+        config,
+        format!(
+            "Cancelling UI Multiplier change for {} at UNIX timestamp {}",
+            token_pubkey, effective_timestamp
+        ),
+    );
+
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = token
+        .cancel_scheduled_multiplier(&ui_multiplier_authority, effective_timestamp, &bulk_signers)
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_pause_resume(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    pause_authority: Pubkey,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+    allow_mint_burn_transfer: bool,
+    operations: Option<u8>,
+) -> CommandResult {
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(pausable_config) = mint_state.get_extension::<PausableConfig>() {
+            let pause_authority_pubkey = Option::<Pubkey>::from(pausable_config.authority);
+
+            if pause_authority_pubkey != Some(pause_authority) {
+                return Err(format!(
+                    "Mint {} has pause authority {}, but {} was provided",
+                    token_pubkey,
+                    pause_authority_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    pause_authority
+                )
+                .into());
+            }
+        } else {
+            return Err(format!("Mint {} is not pausable", token_pubkey).into());
+        }
+    }
+
+    let description = operations
+        .map(paused_operations_description)
+        .unwrap_or_else(|| "mint, burn, and transfer".to_string());
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+    let res = if allow_mint_burn_transfer {
+        println_display(
+            config,
+            format!("Resuming {} for {}", description, token_pubkey,),
+        );
+
+        if let Some(operations) = operations {
+            token
+                .resume_operations(&pause_authority, operations, &bulk_signers)
+                .await?
+        } else {
+            token.resume(&pause_authority, &bulk_signers).await?
+        }
+    } else {
+        println_display(
+            config,
+            format!("Pausing {} for {}", description, token_pubkey,),
+        );
+
+        if let Some(operations) = operations {
+            token
+                .pause_operations_with_rent_transfer(
+                    &config.fee_payer()?.pubkey(),
+                    &pause_authority,
+                    operations,
+                    &bulk_signers,
+                )
+                .await?
+        } else {
+            token.pause(&pause_authority, &bulk_signers).await?
+        }
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+fn paused_operations_description(operations: u8) -> String {
+    [
+        (PausedOperations::MINT, "mint"),
+        (PausedOperations::BURN, "burn"),
+        (PausedOperations::TRANSFER, "transfer"),
+    ]
+    .iter()
+    .filter(|(flag, _)| operations & flag != 0)
+    .map(|(_, name)| *name)
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+fn compounding_period_of(matches: &ArgMatches, name: &str) -> Option<CompoundingPeriod> {
+    matches.value_of(name).map(|s| match s {
+        "continuous" => CompoundingPeriod::Continuous,
+        "daily" => CompoundingPeriod::Daily,
+        "monthly" => CompoundingPeriod::Monthly,
+        _ => unreachable!(),
+    })
+}
+
+fn metadata_field(name: &str) -> Field {
+    match name.to_lowercase().as_str() {
+        "name" => Field::Name,
+        "symbol" => Field::Symbol,
+        "uri" => Field::Uri,
+        _ => Field::Key(name.to_string()),
+    }
+}
+
+struct ConfidentialTransferArgs {
+    sender_elgamal_keypair: ElGamalKeypair,
+    sender_aes_key: AeKey,
+    recipient_elgamal_pubkey: Option<PodElGamalPubkey>,
+    auditor_elgamal_pubkey: Option<PodElGamalPubkey>,
+}
+
+pub async fn process_command(
+    sub_command: &CommandName,
+    sub_matches: &ArgMatches,
+    config: &Config<'_>,
+    mut wallet_manager: Option<Rc<RemoteWalletManager>>,
+    mut bulk_signers: Vec<Arc<dyn Signer>>,
 ) -> CommandResult {
     match (sub_command, sub_matches) {
         (CommandName::Bench, arg_matches) => {
@@ -4557,10 +6157,36 @@ pub async fn process_command(
                 config.pubkey_or_default(arg_matches, "mint_authority", &mut wallet_manager)?;
             let memo = value_t!(arg_matches, "memo", String).ok();
             let rate_bps = value_t!(arg_matches, "interest_rate", i16).ok();
+            let interest_terms = if arg_matches.is_present("interest_compounding")
+                || arg_matches.is_present("min_interest_rate")
+                || arg_matches.is_present("max_interest_rate")
+            {
+                Some((
+                    compounding_period_of(arg_matches, "interest_compounding").unwrap_or_default(),
+                    value_t!(arg_matches, "min_interest_rate", i16).ok(),
+                    value_t!(arg_matches, "max_interest_rate", i16).ok(),
+                ))
+            } else {
+                None
+            };
             let metadata_address = value_t!(arg_matches, "metadata_address", Pubkey).ok();
             let group_address = value_t!(arg_matches, "group_address", Pubkey).ok();
             let member_address = value_t!(arg_matches, "member_address", Pubkey).ok();
-            let ui_multiplier = value_t!(arg_matches, "ui_amount_multiplier", f64).ok();
+            let (ui_multiplier, exact_ui_multiplier) =
+                match arg_matches.value_of("ui_amount_multiplier") {
+                    Some(multiplier) if multiplier.contains('/') => {
+                        let (numerator, denominator) = parse_rational_multiplier(multiplier)?;
+                        (
+                            Some(numerator as f64 / denominator as f64),
+                            Some((numerator, denominator)),
+                        )
+                    }
+                    Some(_) => (
+                        value_t!(arg_matches, "ui_amount_multiplier", f64).ok(),
+                        None,
+                    ),
+                    None => (None, None),
+                };
 
             let transfer_fee = arg_matches.values_of("transfer_fee").map(|mut v| {
                 println_display(config,"transfer-fee has been deprecated and will be removed in a future release. Please specify --transfer-fee-basis-points and --transfer-fee-maximum-fee with a UI amount".to_string());
@@ -4602,6 +6228,12 @@ pub async fn process_command(
                 pubkey_of_signer(arg_matches, "permissioned_burn", &mut wallet_manager).unwrap();
             let enable_permissioned_burn = arg_matches.is_present("enable_permissioned_burn")
                 || permissioned_burn_authority.is_some();
+            let non_transferable_recovery_authority = pubkey_of_signer(
+                arg_matches,
+                "non_transferable_recovery_authority",
+                &mut wallet_manager,
+            )
+            .unwrap();
 
             let confidential_transfer_auto_approve = arg_matches
                 .value_of("enable_confidential_transfers")
@@ -4618,22 +6250,26 @@ pub async fn process_command(
                 arg_matches.is_present("enable_freeze"),
                 arg_matches.is_present("enable_close"),
                 arg_matches.is_present("enable_non_transferable"),
+                non_transferable_recovery_authority,
                 arg_matches.is_present("enable_permanent_delegate"),
                 memo,
                 metadata_address,
                 group_address,
                 member_address,
                 rate_bps,
+                interest_terms,
                 default_account_state,
                 transfer_fee,
                 confidential_transfer_auto_approve,
                 enable_confidential_mint_burn,
                 transfer_hook_program_id,
                 arg_matches.is_present("enable_metadata"),
+                arg_matches.get_one::<u32>("metadata_size_limit").copied(),
                 arg_matches.is_present("enable_group"),
                 arg_matches.is_present("enable_member"),
                 arg_matches.is_present("enable_transfer_hook"),
                 ui_multiplier,
+                exact_ui_multiplier,
                 arg_matches.is_present("enable_pause"),
                 enable_permissioned_burn,
                 permissioned_burn_authority,
@@ -4646,6 +6282,18 @@ pub async fn process_command(
                 .unwrap()
                 .unwrap();
             let rate_bps = value_t_or_exit!(arg_matches, "rate", i16);
+            let terms = if arg_matches.is_present("compounding")
+                || arg_matches.is_present("min_rate")
+                || arg_matches.is_present("max_rate")
+            {
+                Some((
+                    compounding_period_of(arg_matches, "compounding"),
+                    value_t!(arg_matches, "min_rate", i16).ok(),
+                    value_t!(arg_matches, "max_rate", i16).ok(),
+                ))
+            } else {
+                None
+            };
             let (rate_authority_signer, rate_authority_pubkey) =
                 config.signer_or_default(arg_matches, "rate_authority", &mut wallet_manager);
             let bulk_signers = vec![rate_authority_signer];
@@ -4655,6 +6303,7 @@ pub async fn process_command(
                 token_pubkey,
                 rate_authority_pubkey,
                 rate_bps,
+                terms,
                 bulk_signers,
             )
             .await
@@ -4709,29 +6358,44 @@ pub async fn process_command(
                 .unwrap();
             let (authority_signer, authority) =
                 config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
-            let field = arg_matches.value_of("field").unwrap();
-            let field = match field.to_lowercase().as_str() {
-                "name" => Field::Name,
-                "symbol" => Field::Symbol,
-                "uri" => Field::Uri,
-                _ => Field::Key(field.to_string()),
-            };
-            let value = arg_matches.value_of("value").map(|v| v.to_string());
             let transfer_lamports = arg_matches
                 .get_one::<u64>(TRANSFER_LAMPORTS_ARG.name)
                 .copied();
             let bulk_signers = vec![authority_signer];
 
-            command_update_metadata(
-                config,
-                token_pubkey,
-                authority,
-                field,
-                value,
-                transfer_lamports,
-                bulk_signers,
-            )
-            .await
+            if let Some(fields) = arg_matches.values_of("fields") {
+                let updates = fields
+                    .map(|update| {
+                        let (field, value) = parse_metadata_field_update(update).unwrap();
+                        FieldUpdate::Set {
+                            field: metadata_field(field),
+                            value: value.to_string(),
+                        }
+                    })
+                    .collect();
+                command_update_metadata_fields(
+                    config,
+                    token_pubkey,
+                    authority,
+                    updates,
+                    transfer_lamports,
+                    bulk_signers,
+                )
+                .await
+            } else {
+                let field = metadata_field(arg_matches.value_of("field").unwrap());
+                let value = arg_matches.value_of("value").map(|v| v.to_string());
+                command_update_metadata(
+                    config,
+                    token_pubkey,
+                    authority,
+                    field,
+                    value,
+                    transfer_lamports,
+                    bulk_signers,
+                )
+                .await
+            }
         }
         (CommandName::InitializeGroup, arg_matches) => {
             let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
@@ -4800,6 +6464,78 @@ pub async fn process_command(
             )
             .await
         }
+        (CommandName::RemoveMember, arg_matches) => {
+            let member_token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let group_token_pubkey =
+                pubkey_of_signer(arg_matches, "group_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (authority_signer, authority) =
+                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
+            let bulk_signers = vec![authority_signer];
+
+            command_remove_member(
+                config,
+                member_token_pubkey,
+                group_token_pubkey,
+                authority,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::MoveMember, arg_matches) => {
+            let member_token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let group_token_pubkey =
+                pubkey_of_signer(arg_matches, "group_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let new_group_token_pubkey =
+                pubkey_of_signer(arg_matches, "new_group_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (mint_authority_signer, mint_authority) =
+                config.signer_or_default(arg_matches, "mint_authority", &mut wallet_manager);
+            let (group_update_authority_signer, group_update_authority) = config.signer_or_default(
+                arg_matches,
+                "group_update_authority",
+                &mut wallet_manager,
+            );
+            let mut bulk_signers = vec![mint_authority_signer];
+            push_signer_with_dedup(group_update_authority_signer, &mut bulk_signers);
+
+            command_move_member(
+                config,
+                member_token_pubkey,
+                mint_authority,
+                group_token_pubkey,
+                new_group_token_pubkey,
+                group_update_authority,
+                bulk_signers,
+            )
+            .await
+        }
+        (c @ CommandName::FreezeGroupMembership, arg_matches)
+        | (c @ CommandName::ThawGroupMembership, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (update_authority_signer, update_authority) =
+                config.signer_or_default(arg_matches, "update_authority", &mut wallet_manager);
+            let bulk_signers = vec![update_authority_signer];
+
+            command_set_group_membership_frozen(
+                config,
+                token_pubkey,
+                update_authority,
+                c == CommandName::FreezeGroupMembership,
+                bulk_signers,
+            )
+            .await
+        }
         (CommandName::CreateAccount, arg_matches) => {
             let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
@@ -4813,6 +6549,13 @@ pub async fn process_command(
                 },
             );
 
+            let attestor = get_signer(arg_matches, "attestor", &mut wallet_manager).map(
+                |(signer, attestor)| {
+                    push_signer_with_dedup(signer, &mut bulk_signers);
+                    attestor
+                },
+            );
+
             let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
             command_create_account(
                 config,
@@ -4820,6 +6563,7 @@ pub async fn process_command(
                 owner,
                 account,
                 arg_matches.is_present("immutable"),
+                attestor,
                 bulk_signers,
             )
             .await
@@ -4997,6 +6741,38 @@ pub async fn process_command(
             )
             .await
         }
+        (CommandName::RecoveryTransfer, arg_matches) => {
+            let source = pubkey_of_signer(arg_matches, "source", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let destination = pubkey_of_signer(arg_matches, "destination", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+
+            let (recovery_authority_signer, recovery_authority) =
+                config.signer_or_default(arg_matches, "recovery_authority", &mut wallet_manager);
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(recovery_authority_signer, &mut bulk_signers);
+            }
+
+            let amount = *arg_matches.get_one::<Amount>("amount").unwrap();
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            let mint_decimals = arg_matches
+                .get_one(MINT_DECIMALS_ARG.name)
+                .map(|v: &String| v.parse::<u8>().unwrap());
+            command_recovery_transfer(
+                config,
+                source,
+                destination,
+                recovery_authority,
+                amount,
+                mint_address,
+                mint_decimals,
+                bulk_signers,
+            )
+            .await
+        }
         (CommandName::Mint, arg_matches) => {
             let (mint_authority_signer, mint_authority) =
                 config.signer_or_default(arg_matches, "mint_authority", &mut wallet_manager);
@@ -5252,12 +7028,19 @@ pub async fn process_command(
                 AccountFilter::All
             };
 
+            let unix_timestamp = if let Some(timestamp) = arg_matches.value_of("timestamp") {
+                timestamp.parse::<i64>().unwrap()
+            } else {
+                cluster_unix_timestamp(config).await?
+            };
+
             command_accounts(
                 config,
                 token,
                 owner,
                 filter,
                 arg_matches.is_present("addresses_only"),
+                unix_timestamp,
             )
             .await
         }
@@ -5324,7 +7107,27 @@ pub async fn process_command(
                 .await;
             command_sync_native(config, address?).await
         }
-        (CommandName::EnableRequiredTransferMemos, arg_matches) => {
+        (CommandName::EnableRequiredTransferMemos, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(owner_signer, &mut bulk_signers);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let outgoing = arg_matches.is_present("outgoing");
+            command_required_transfer_memos(
+                config,
+                token_account,
+                owner,
+                bulk_signers,
+                true,
+                outgoing,
+            )
+            .await
+        }
+        (CommandName::DisableRequiredTransferMemos, arg_matches) => {
             let (owner_signer, owner) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if config.multisigner_pubkeys.is_empty() {
@@ -5333,9 +7136,18 @@ pub async fn process_command(
             // Since account is required argument it will always be present
             let token_account =
                 config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
-            command_required_transfer_memos(config, token_account, owner, bulk_signers, true).await
+            let outgoing = arg_matches.is_present("outgoing");
+            command_required_transfer_memos(
+                config,
+                token_account,
+                owner,
+                bulk_signers,
+                false,
+                outgoing,
+            )
+            .await
         }
-        (CommandName::DisableRequiredTransferMemos, arg_matches) => {
+        (CommandName::SetMemoTransferPolicy, arg_matches) => {
             let (owner_signer, owner) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if config.multisigner_pubkeys.is_empty() {
@@ -5344,7 +7156,18 @@ pub async fn process_command(
             // Since account is required argument it will always be present
             let token_account =
                 config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
-            command_required_transfer_memos(config, token_account, owner, bulk_signers, false).await
+            let min_len = arg_matches.get_one::<u16>("min_len").copied().unwrap_or(0);
+            let prefix = arg_matches.value_of("prefix").unwrap_or_default();
+            let numeric_tag_len = arg_matches
+                .get_one::<u8>("numeric_tag_len")
+                .copied()
+                .unwrap_or(0);
+            let policy = MemoTransferPolicy::new(min_len, prefix.as_bytes(), numeric_tag_len)
+                .map_err(|_| {
+                    format!("Memo prefix must be at most {} bytes", MAX_MEMO_PREFIX_LEN)
+                })?;
+            command_set_memo_transfer_policy(config, token_account, owner, bulk_signers, policy)
+                .await
         }
         (CommandName::EnableCpiGuard, arg_matches) => {
             let (owner_signer, owner) =
@@ -5368,6 +7191,43 @@ pub async fn process_command(
                 config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
             command_cpi_guard(config, token_account, owner, bulk_signers, false).await
         }
+        (CommandName::SetCpiGuardPolicy, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(owner_signer, &mut bulk_signers);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let allowed_operations = arg_matches.values_of("allow").unwrap_or_default().fold(
+                0,
+                |operations, operation| {
+                    operations
+                        | match operation {
+                            "transfer" => CpiGuardPolicy::TRANSFER,
+                            "burn" => CpiGuardPolicy::BURN,
+                            "approve" => CpiGuardPolicy::APPROVE,
+                            "close-account" => CpiGuardPolicy::CLOSE_ACCOUNT,
+                            "set-authority" => CpiGuardPolicy::SET_AUTHORITY,
+                            _ => unreachable!(),
+                        }
+                },
+            );
+            let allowed_programs = arg_matches
+                .values_of("allowed_program")
+                .unwrap_or_default()
+                .map(|s| Pubkey::from_str(s).unwrap_or_else(print_error_and_exit))
+                .collect::<Vec<_>>();
+            let policy =
+                CpiGuardPolicy::new(allowed_operations, &allowed_programs).map_err(|_| {
+                    format!(
+                        "At most {} allowed programs can be specified",
+                        MAX_CPI_GUARD_ALLOWED_PROGRAMS
+                    )
+                })?;
+            command_set_cpi_guard_policy(config, token_account, owner, bulk_signers, policy).await
+        }
         (CommandName::UpdateDefaultAccountState, arg_matches) => {
             // Since account is required argument it will always be present
             let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
@@ -5393,6 +7253,26 @@ pub async fn process_command(
             )
             .await
         }
+        (CommandName::SetDefaultAccountStateAttestor, arg_matches) => {
+            // Since account is required argument it will always be present
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (freeze_authority_signer, freeze_authority) =
+                config.signer_or_default(arg_matches, "freeze_authority", &mut wallet_manager);
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(freeze_authority_signer, &mut bulk_signers);
+            }
+            let attestor = pubkey_of_signer(arg_matches, "attestor", &mut wallet_manager).unwrap();
+            command_set_default_account_state_attestor(
+                config,
+                token,
+                freeze_authority,
+                attestor,
+                bulk_signers,
+            )
+            .await
+        }
         (CommandName::UpdateMetadataAddress, arg_matches) => {
             // Since account is required argument it will always be present
             let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
@@ -5499,6 +7379,10 @@ pub async fn process_command(
             let transfer_fee_basis_points =
                 value_t_or_exit!(arg_matches, "transfer_fee_basis_points", u16);
             let maximum_fee = *arg_matches.get_one::<Amount>("maximum_fee").unwrap();
+            let fee_tiers = arg_matches
+                .get_many::<(Amount, u16)>("fee_tier")
+                .map(|tiers| tiers.copied().collect::<Vec<_>>());
+            let minimum_fee = arg_matches.get_one::<Amount>("minimum_fee").copied();
             let (transfer_fee_authority_signer, transfer_fee_authority_pubkey) = config
                 .signer_or_default(arg_matches, "transfer_fee_authority", &mut wallet_manager);
             let mint_decimals = arg_matches.get_one::<u8>(MINT_DECIMALS_ARG.name).copied();
@@ -5510,11 +7394,55 @@ pub async fn process_command(
                 transfer_fee_authority_pubkey,
                 transfer_fee_basis_points,
                 maximum_fee,
+                fee_tiers,
+                minimum_fee,
                 mint_decimals,
                 bulk_signers,
             )
             .await
         }
+        (CommandName::EnableTransferFeeExemption, arg_matches) => {
+            let (transfer_fee_authority_signer, transfer_fee_authority) = config.signer_or_default(
+                arg_matches,
+                "transfer_fee_authority",
+                &mut wallet_manager,
+            );
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(transfer_fee_authority_signer, &mut bulk_signers);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_transfer_fee_exemption(
+                config,
+                token_account,
+                transfer_fee_authority,
+                bulk_signers,
+                true,
+            )
+            .await
+        }
+        (CommandName::DisableTransferFeeExemption, arg_matches) => {
+            let (transfer_fee_authority_signer, transfer_fee_authority) = config.signer_or_default(
+                arg_matches,
+                "transfer_fee_authority",
+                &mut wallet_manager,
+            );
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(transfer_fee_authority_signer, &mut bulk_signers);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_transfer_fee_exemption(
+                config,
+                token_account,
+                transfer_fee_authority,
+                bulk_signers,
+                false,
+            )
+            .await
+        }
         (CommandName::WithdrawExcessLamports, arg_matches) => {
             let (signer, authority) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
@@ -5589,18 +7517,46 @@ pub async fn process_command(
                     None
                 };
 
+            let elgamal_registry =
+                pubkey_of_signer(arg_matches, "elgamal_registry", &mut wallet_manager).unwrap();
+
             command_configure_confidential_transfer_account(
                 config,
                 token,
                 owner,
                 account,
                 maximum_credit_counter,
+                elgamal_registry,
                 &elgamal_keypair,
                 &aes_key,
                 bulk_signers,
             )
             .await
         }
+        (c @ CommandName::CreateElgamalRegistry, arg_matches)
+        | (c @ CommandName::UpdateElgamalRegistry, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+
+            // Derive the ElGamal key the same way as
+            // `configure-confidential-transfer-account`, so that accounts configured
+            // with or without the registry use the same key.
+            let (elgamal_keypair, _) = derive_confidential_keys(&*owner_signer, b"").unwrap();
+            push_signer_with_dedup(owner_signer, &mut bulk_signers);
+
+            command_create_update_elgamal_registry(
+                config,
+                owner,
+                &elgamal_keypair,
+                c == CommandName::UpdateElgamalRegistry,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::ShowElgamalRegistry, arg_matches) => {
+            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
+            command_show_elgamal_registry(config, owner).await
+        }
         (c @ CommandName::EnableConfidentialCredits, arg_matches)
         | (c @ CommandName::DisableConfidentialCredits, arg_matches)
         | (c @ CommandName::EnableNonConfidentialCredits, arg_matches)
@@ -5729,19 +7685,206 @@ pub async fn process_command(
 
             command_apply_pending_burn(config, token, mint_authority, bulk_signers).await
         }
+        (CommandName::ApproveConfidentialTransferAccount, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (authority_signer, authority_pubkey) = config.signer_or_default(
+                arg_matches,
+                "confidential_transfer_authority",
+                &mut wallet_manager,
+            );
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(authority_signer, &mut bulk_signers);
+            }
+
+            command_approve_confidential_transfer_account(
+                config,
+                token_pubkey,
+                account,
+                authority_pubkey,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::EmptyConfidentialTransferAccount, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
+
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+
+            let account = pubkey_of_signer(arg_matches, "address", &mut wallet_manager).unwrap();
+
+            // Deriving ElGamal key from signer. Custom ElGamal keys will be
+            // supported in the future once upgrading to clap-v3.
+            //
+            // NOTE:: Seed bytes are hardcoded to be empty bytes for now. They will be
+            // updated once custom ElGamal keys are supported.
+            let (elgamal_keypair, _) = derive_confidential_keys(&*owner_signer, b"").unwrap();
+
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(owner_signer, &mut bulk_signers);
+            }
+
+            command_empty_confidential_transfer_account(
+                config,
+                token,
+                owner,
+                account,
+                bulk_signers,
+                &elgamal_keypair,
+            )
+            .await
+        }
+        (CommandName::WithdrawConfidentialWithheldTokens, arg_matches) => {
+            let (authority_signer, authority) = config.signer_or_default(
+                arg_matches,
+                "withdraw_withheld_authority",
+                &mut wallet_manager,
+            );
+            let (destination_owner_signer, _) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+
+            // Deriving ElGamal and AES keys from signers, matching the keys
+            // set up by `create-token` and `configure-confidential-transfer-account`.
+            let (withdraw_withheld_authority_elgamal_keypair, _) =
+                derive_confidential_keys(&*authority_signer, b"").unwrap();
+            let (_, destination_aes_key) =
+                derive_confidential_keys(&*destination_owner_signer, b"").unwrap();
+
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(authority_signer, &mut bulk_signers);
+            }
+            // Since destination is required it will always be present
+            let destination_token_account =
+                pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let include_mint = arg_matches.is_present("include_mint");
+            let source_accounts = arg_matches
+                .values_of("source")
+                .unwrap_or_default()
+                .map(|s| Pubkey::from_str(s).unwrap_or_else(print_error_and_exit))
+                .collect::<Vec<_>>();
+            command_withdraw_confidential_withheld_tokens(
+                config,
+                destination_token_account,
+                source_accounts,
+                authority,
+                include_mint,
+                &withdraw_withheld_authority_elgamal_keypair,
+                &destination_aes_key,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::HarvestConfidentialWithheldTokens, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let source_accounts = arg_matches
+                .values_of("source")
+                .unwrap_or_default()
+                .map(|s| Pubkey::from_str(s).unwrap_or_else(print_error_and_exit))
+                .collect::<Vec<_>>();
+            command_harvest_confidential_withheld_tokens(config, token_pubkey, source_accounts)
+                .await
+        }
+        (c @ CommandName::EnableConfidentialHarvestToMint, arg_matches)
+        | (c @ CommandName::DisableConfidentialHarvestToMint, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (authority_signer, authority_pubkey) = config.signer_or_default(
+                arg_matches,
+                "confidential_transfer_fee_authority",
+                &mut wallet_manager,
+            );
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(authority_signer, &mut bulk_signers);
+            }
+
+            let enable_harvest_to_mint = match c {
+                CommandName::EnableConfidentialHarvestToMint => true,
+                CommandName::DisableConfidentialHarvestToMint => false,
+                _ => panic!("Instruction not supported"),
+            };
+
+            command_enable_disable_confidential_harvest_to_mint(
+                config,
+                token_pubkey,
+                authority_pubkey,
+                bulk_signers,
+                enable_harvest_to_mint,
+            )
+            .await
+        }
+        (CommandName::RotateConfidentialSupplyElgamalPubkey, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let new_supply_elgamal_pubkey =
+                elgamal_pubkey_of(arg_matches, "new_supply_elgamal_pubkey")?;
+            let (mint_authority_signer, mint_authority) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+
+            // The supply keys are derived the same way as in `create-token`
+            let (supply_elgamal_keypair, supply_aes_key) =
+                derive_confidential_keys(&*mint_authority_signer, b"").unwrap();
+
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(mint_authority_signer, &mut bulk_signers);
+            }
+
+            command_rotate_confidential_supply_elgamal_pubkey(
+                config,
+                token_pubkey,
+                mint_authority,
+                new_supply_elgamal_pubkey,
+                &supply_elgamal_keypair,
+                &supply_aes_key,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::UpdateConfidentialDecryptableSupply, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (mint_authority_signer, mint_authority) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+
+            // The supply keys are derived the same way as in `create-token`
+            let (supply_elgamal_keypair, supply_aes_key) =
+                derive_confidential_keys(&*mint_authority_signer, b"").unwrap();
+
+            if config.multisigner_pubkeys.is_empty() {
+                push_signer_with_dedup(mint_authority_signer, &mut bulk_signers);
+            }
+
+            command_update_confidential_decryptable_supply(
+                config,
+                token_pubkey,
+                mint_authority,
+                &supply_elgamal_keypair,
+                &supply_aes_key,
+                bulk_signers,
+            )
+            .await
+        }
         (CommandName::UpdateUiAmountMultiplier, arg_matches) => {
             let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            let new_multiplier = value_t_or_exit!(arg_matches, "multiplier", f64);
+            let new_multiplier = arg_matches.value_of("multiplier").unwrap();
             let new_multiplier_effective_timestamp =
                 if let Some(timestamp) = arg_matches.value_of("timestamp") {
                     timestamp.parse::<i64>().unwrap()
                 } else {
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs() as i64
+                    cluster_unix_timestamp(config).await?
                 };
             let (ui_multiplier_authority_signer, ui_multiplier_authority_pubkey) = config
                 .signer_or_default(arg_matches, "ui_multiplier_authority", &mut wallet_manager);
@@ -5753,6 +7896,25 @@ pub async fn process_command(
                 ui_multiplier_authority_pubkey,
                 new_multiplier,
                 new_multiplier_effective_timestamp,
+                arg_matches.is_present("schedule"),
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::CancelUiAmountMultiplier, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let effective_timestamp = value_t_or_exit!(arg_matches, "timestamp", i64);
+            let (ui_multiplier_authority_signer, ui_multiplier_authority_pubkey) = config
+                .signer_or_default(arg_matches, "ui_multiplier_authority", &mut wallet_manager);
+            let bulk_signers = vec![ui_multiplier_authority_signer];
+
+            command_cancel_multiplier(
+                config,
+                token_pubkey,
+                ui_multiplier_authority_pubkey,
+                effective_timestamp,
                 bulk_signers,
             )
             .await
@@ -5772,6 +7934,17 @@ pub async fn process_command(
                 CommandName::Resume => true,
                 _ => panic!("Instruction not supported"),
             };
+            let operations = arg_matches.values_of("operations").map(|values| {
+                values.fold(0, |operations, operation| {
+                    operations
+                        | match operation {
+                            "mint" => PausedOperations::MINT,
+                            "burn" => PausedOperations::BURN,
+                            "transfer" => PausedOperations::TRANSFER,
+                            _ => unreachable!(),
+                        }
+                })
+            });
 
             command_pause_resume(
                 config,
@@ -5779,6 +7952,7 @@ pub async fn process_command(
                 pause_authority_pubkey,
                 bulk_signers,
                 allow_mint_burn_transfer,
+                operations,
             )
             .await
         }
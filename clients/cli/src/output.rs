@@ -189,6 +189,42 @@ impl fmt::Display for CliMultisig {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliElGamalRegistry {
+    pub(crate) address: String,
+    pub(crate) owner: String,
+    pub(crate) elgamal_pubkey: String,
+    pub(crate) labelled_keys: Vec<CliLabelledElGamalPubkey>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliLabelledElGamalPubkey {
+    pub(crate) label: String,
+    pub(crate) elgamal_pubkey: String,
+}
+
+impl QuietDisplay for CliElGamalRegistry {}
+impl VerboseDisplay for CliElGamalRegistry {}
+
+impl fmt::Display for CliElGamalRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "{}", style("ElGamal Registry").bold())?;
+        writeln_name_value(f, "  Address:", &self.address)?;
+        writeln_name_value(f, "  Owner:", &self.owner)?;
+        writeln_name_value(f, "  ElGamal pubkey:", &self.elgamal_pubkey)?;
+        if !self.labelled_keys.is_empty() {
+            writeln!(f, "  {}", style("Labelled keys:").bold())?;
+            for key in &self.labelled_keys {
+                writeln_name_value(f, &format!("    {}:", key.label), &key.elgamal_pubkey)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CliTokenAccount {
@@ -294,6 +330,37 @@ pub(crate) struct CliMint {
     pub(crate) epoch: u64,
     #[serde(flatten)]
     pub(crate) mint: UiMint,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) exact_ui_amount_multiplier: Option<CliExactUiAmountMultiplier>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) ui_amount_multiplier_schedule: Option<Vec<CliScheduledMultiplier>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) interest_bearing_terms: Option<CliInterestBearingTerms>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) metadata_size_limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliExactUiAmountMultiplier {
+    pub(crate) multiplier: String,
+    pub(crate) new_multiplier_effective_timestamp: i64,
+    pub(crate) new_multiplier: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliScheduledMultiplier {
+    pub(crate) effective_timestamp: i64,
+    pub(crate) multiplier: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliInterestBearingTerms {
+    pub(crate) compounding_period: String,
+    pub(crate) minimum_rate: Option<i16>,
+    pub(crate) maximum_rate: Option<i16>,
 }
 
 impl QuietDisplay for CliMint {}
@@ -329,6 +396,69 @@ impl fmt::Display for CliMint {
             }
         }
 
+        if let Some(CliExactUiAmountMultiplier {
+            multiplier,
+            new_multiplier_effective_timestamp,
+            new_multiplier,
+        }) = &self.exact_ui_amount_multiplier
+        {
+            writeln!(f, "  {}", style("Exact UI amount multiplier:").bold())?;
+            writeln_name_value(f, "    Multiplier:", multiplier)?;
+            writeln_name_value(
+                f,
+                "    New multiplier effective timestamp:",
+                &new_multiplier_effective_timestamp.to_string(),
+            )?;
+            writeln_name_value(f, "    New multiplier:", new_multiplier)?;
+        }
+
+        if let Some(schedule) = &self.ui_amount_multiplier_schedule {
+            writeln!(f, "  {}", style("UI amount multiplier schedule:").bold())?;
+            for CliScheduledMultiplier {
+                effective_timestamp,
+                multiplier,
+            } in schedule
+            {
+                writeln_name_value(
+                    f,
+                    &format!("    {}:", effective_timestamp),
+                    &multiplier.to_string(),
+                )?;
+            }
+        }
+
+        if let Some(CliInterestBearingTerms {
+            compounding_period,
+            minimum_rate,
+            maximum_rate,
+        }) = &self.interest_bearing_terms
+        {
+            writeln!(f, "  {}", style("Interest-bearing terms:").bold())?;
+            writeln_name_value(f, "    Compounding:", compounding_period)?;
+            writeln_name_value(
+                f,
+                "    Minimum rate:",
+                &minimum_rate
+                    .map(|rate| format!("{}bps", rate))
+                    .unwrap_or_else(|| "none".to_string()),
+            )?;
+            writeln_name_value(
+                f,
+                "    Maximum rate:",
+                &maximum_rate
+                    .map(|rate| format!("{}bps", rate))
+                    .unwrap_or_else(|| "none".to_string()),
+            )?;
+        }
+
+        if let Some(metadata_size_limit) = self.metadata_size_limit {
+            writeln_name_value(
+                f,
+                "  Metadata size limit:",
+                &format!("{} bytes", metadata_size_limit),
+            )?;
+        }
+
         Ok(())
     }
 }
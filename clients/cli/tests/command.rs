@@ -14,24 +14,34 @@ use {
     solana_sdk_ids::bpf_loader_upgradeable,
     solana_system_interface::{instruction as system_instruction, program as system_program},
     solana_test_validator::{TestValidator, TestValidatorGenesis, UpgradeableProgramInfo},
-    solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+    solana_zk_sdk::encryption::elgamal::ElGamalKeypair,
+    solana_zk_sdk_pod::encryption::elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
     spl_associated_token_account_interface::address::get_associated_token_address_with_program_id,
     spl_token_2022_interface::{
         extension::{
+            confidential_mint_burn::ConfidentialMintBurn,
             confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
-            confidential_transfer_fee::ConfidentialTransferFeeConfig,
-            cpi_guard::CpiGuard,
-            default_account_state::DefaultAccountState,
+            confidential_transfer_fee::{
+                ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
+            },
+            cpi_guard::{CpiGuard, CpiGuardPolicy},
+            default_account_state::{DefaultAccountState, DefaultAccountStateAttestor},
             group_member_pointer::GroupMemberPointer,
             group_pointer::GroupPointer,
-            interest_bearing_mint::InterestBearingConfig,
-            memo_transfer::MemoTransfer,
+            interest_bearing_mint::{
+                CompoundingPeriod, InterestBearingConfig, InterestBearingTerms,
+            },
+            memo_transfer::{MemoTransfer, MemoTransferPolicy, OutgoingMemoTransfer},
             metadata_pointer::MetadataPointer,
-            non_transferable::NonTransferable,
-            pausable::PausableConfig,
+            non_transferable::{NonTransferable, NonTransferableRecovery},
+            pausable::{PausableConfig, PausedOperations},
             permissioned_burn::PermissionedBurnConfig,
-            scaled_ui_amount::ScaledUiAmountConfig,
-            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            scaled_ui_amount::{
+                RationalMultiplier, ScaledUiAmountConfig, ScaledUiAmountRationalConfig,
+                ScaledUiAmountSchedule,
+            },
+            token_metadata::TokenMetadataSizeLimit,
+            transfer_fee::{TransferFeeAmount, TransferFeeConfig, TransferFeeExemption},
             transfer_hook::TransferHook,
             BaseStateWithExtensions, ExtensionType, StateWithExtensionsOwned,
         },
@@ -106,6 +116,7 @@ async fn main() {
         async_trial!(create_token_2022, test_validator, payer),
         async_trial!(create_token_interest_bearing, test_validator, payer),
         async_trial!(set_interest_rate, test_validator, payer),
+        async_trial!(interest_bearing_terms, test_validator, payer),
         async_trial!(supply, test_validator, payer),
         async_trial!(create_account_default, test_validator, payer),
         async_trial!(account_info, test_validator, payer),
@@ -136,7 +147,9 @@ async fn main() {
         async_trial!(default_account_state, test_validator, payer),
         async_trial!(transfer_fee, test_validator, payer),
         async_trial!(transfer_fee_basis_point, test_validator, payer),
+        async_trial!(transfer_fee_exemption, test_validator, payer),
         async_trial!(confidential_transfer, test_validator, payer),
+        async_trial!(elgamal_registry, test_validator, payer),
         async_trial!(multisig_transfer, test_validator, payer),
         async_trial!(offline_multisig_transfer_with_nonce, test_validator, payer),
         async_trial!(
@@ -152,10 +165,14 @@ async fn main() {
         async_trial!(transfer_hook, test_validator, payer),
         async_trial!(transfer_hook_with_transfer_fee, test_validator, payer),
         async_trial!(metadata, test_validator, payer),
+        async_trial!(metadata_fields, test_validator, payer),
         async_trial!(group, test_validator, payer),
         async_trial!(confidential_transfer_with_fee, test_validator, payer),
         async_trial!(compute_budget, test_validator, payer),
         async_trial!(scaled_ui_amount, test_validator, payer),
+        async_trial!(exact_scaled_ui_amount, test_validator, payer),
+        async_trial!(scaled_ui_amount_schedule, test_validator, payer),
+        async_trial!(accounts_ui_amounts_at_timestamp, test_validator, payer),
         async_trial!(pause, test_validator, payer),
         async_trial!(multisig_pause, test_validator, payer),
         async_trial!(permissioned_burn, test_validator, payer),
@@ -195,6 +212,12 @@ async fn new_validator_for_test() -> (TestValidator, Keypair) {
             program_path: PathBuf::from("../rust-legacy/tests/fixtures/spl_record.so"),
             upgrade_authority: Pubkey::new_unique(),
         },
+        UpgradeableProgramInfo {
+            program_id: spl_elgamal_registry::id(),
+            loader: bpf_loader_upgradeable::id(),
+            program_path: PathBuf::from("../../target/deploy/spl_elgamal_registry.so"),
+            upgrade_authority: Pubkey::new_unique(),
+        },
     ]);
     test_validator_genesis.start_async().await
 }
@@ -615,6 +638,115 @@ async fn set_interest_rate(test_validator: &TestValidator, payer: &Keypair) {
     assert_eq!(i16::from(extension.current_rate), new_rate);
 }
 
+async fn interest_bearing_terms(test_validator: &TestValidator, payer: &Keypair) {
+    let mut config =
+        test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token = token.pubkey();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--interest-rate",
+            "500",
+            "--interest-compounding",
+            "monthly",
+            "--min-interest-rate",
+            "-100",
+            "--max-interest-rate",
+            "1000",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token).await.unwrap();
+    let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let terms = mint_account
+        .get_extension::<InterestBearingTerms>()
+        .unwrap();
+    assert_eq!(
+        *terms,
+        InterestBearingTerms::new(CompoundingPeriod::Monthly, Some(-100), Some(1_000))
+    );
+
+    // out of bounds
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetInterestRate.into(),
+            &token.to_string(),
+            "2000",
+        ],
+    )
+    .await
+    .unwrap_err();
+
+    // raise the cap, keeping monthly compounding
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetInterestRate.into(),
+            &token.to_string(),
+            "2000",
+            "--max-rate",
+            "3000",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token).await.unwrap();
+    let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let terms = mint_account
+        .get_extension::<InterestBearingTerms>()
+        .unwrap();
+    assert_eq!(
+        *terms,
+        InterestBearingTerms::new(CompoundingPeriod::Monthly, None, Some(3_000))
+    );
+    let extension = mint_account
+        .get_extension::<InterestBearingConfig>()
+        .unwrap();
+    assert_eq!(i16::from(extension.current_rate), 2_000);
+
+    // switch to daily compounding
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetInterestRate.into(),
+            &token.to_string(),
+            "2000",
+            "--compounding",
+            "daily",
+        ],
+    )
+    .await
+    .unwrap();
+
+    config.output_format = OutputFormat::Display;
+    let result = process_test_command(
+        &config,
+        payer,
+        &["spl-token", CommandName::Display.into(), &token.to_string()],
+    )
+    .await
+    .unwrap();
+    assert!(result.contains("Interest-bearing terms:"));
+    assert!(result.contains("daily"));
+}
+
 async fn supply(test_validator: &TestValidator, payer: &Keypair) {
     for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
         let config = test_config_with_default_signer(test_validator, payer, program_id);
@@ -2330,6 +2462,54 @@ async fn required_transfer_memos(test_validator: &TestValidator, payer: &Keypair
         spl_token_2022::ui_amount_to_amount(1.0, TEST_DECIMALS)
     );
 
+    // memo policy is enforced
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetMemoTransferPolicy.into(),
+            &destination_account.to_string(),
+            "--prefix",
+            "DEP-",
+            "--numeric-tag-len",
+            "4",
+        ],
+    )
+    .await
+    .unwrap();
+    let extensions = StateWithExtensionsOwned::<Account>::unpack(
+        config
+            .rpc_client
+            .get_account(&destination_account)
+            .await
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let policy = extensions.get_extension::<MemoTransferPolicy>().unwrap();
+    assert_eq!(*policy, MemoTransferPolicy::new(0, b"DEP-", 4).unwrap());
+
+    for (memo, valid) in [("memo", false), ("DEP-0042", true)] {
+        let result = process_test_command(
+            &config,
+            payer,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                "--from",
+                &token_account.to_string(),
+                "--with-memo",
+                memo,
+                &token.to_string(),
+                "1",
+                &destination_account.to_string(),
+            ],
+        )
+        .await;
+        assert_eq!(result.is_ok(), valid);
+    }
+
     // disable works
     process_test_command(
         &config,
@@ -2354,6 +2534,80 @@ async fn required_transfer_memos(test_validator: &TestValidator, payer: &Keypair
     let memo_transfer = extensions.get_extension::<MemoTransfer>().unwrap();
     let enabled: bool = memo_transfer.require_incoming_transfer_memos.into();
     assert!(!enabled);
+
+    // outgoing memos are required from the source
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::EnableRequiredTransferMemos.into(),
+            &token_account.to_string(),
+            "--outgoing",
+        ],
+    )
+    .await
+    .unwrap();
+    let extensions = StateWithExtensionsOwned::<Account>::unpack(
+        config
+            .rpc_client
+            .get_account(&token_account)
+            .await
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let outgoing_memo_transfer = extensions.get_extension::<OutgoingMemoTransfer>().unwrap();
+    let enabled: bool = outgoing_memo_transfer
+        .require_outgoing_transfer_memos
+        .into();
+    assert!(enabled);
+
+    for (memo, valid) in [(None, false), (Some("memo"), true)] {
+        let token_account = token_account.to_string();
+        let token = token.to_string();
+        let destination_account = destination_account.to_string();
+        let mut args = vec![
+            "spl-token",
+            CommandName::Transfer.into(),
+            "--from",
+            token_account.as_str(),
+        ];
+        if let Some(memo) = memo {
+            args.extend(["--with-memo", memo]);
+        }
+        args.extend([token.as_str(), "1", destination_account.as_str()]);
+        let result = process_test_command(&config, payer, args).await;
+        assert_eq!(result.is_ok(), valid);
+    }
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::DisableRequiredTransferMemos.into(),
+            &token_account.to_string(),
+            "--outgoing",
+        ],
+    )
+    .await
+    .unwrap();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Transfer.into(),
+            "--from",
+            &token_account.to_string(),
+            &token.to_string(),
+            "1",
+            &destination_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
 }
 
 async fn cpi_guard(test_validator: &TestValidator, payer: &Keypair) {
@@ -2411,6 +2665,42 @@ async fn cpi_guard(test_validator: &TestValidator, payer: &Keypair) {
     let cpi_guard = extensions.get_extension::<CpiGuard>().unwrap();
     let enabled: bool = cpi_guard.lock_cpi.into();
     assert!(!enabled);
+
+    // policy works
+    let allowed_program = Pubkey::new_unique();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetCpiGuardPolicy.into(),
+            &token_account.to_string(),
+            "--allow",
+            "transfer,burn",
+            "--allowed-program",
+            &allowed_program.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+    let extensions = StateWithExtensionsOwned::<Account>::unpack(
+        config
+            .rpc_client
+            .get_account(&token_account)
+            .await
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let policy = extensions.get_extension::<CpiGuardPolicy>().unwrap();
+    assert_eq!(
+        *policy,
+        CpiGuardPolicy::new(
+            CpiGuardPolicy::TRANSFER | CpiGuardPolicy::BURN,
+            &[allowed_program]
+        )
+        .unwrap()
+    );
 }
 
 async fn immutable_accounts(test_validator: &TestValidator, payer: &Keypair) {
@@ -2559,6 +2849,64 @@ async fn non_transferable(test_validator: &TestValidator, payer: &Keypair) {
     )
     .await
     .unwrap_err();
+
+    // the recovery authority can move tokens
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token_pubkey = token.pubkey();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--enable-non-transferable",
+            "--non-transferable-recovery-authority",
+            &payer.pubkey().to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint
+        .get_extension::<NonTransferableRecovery>()
+        .unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(extension.authority),
+        Some(payer.pubkey())
+    );
+
+    let associated_account =
+        create_associated_account(&config, payer, &token_pubkey, &payer.pubkey()).await;
+    let aux_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+    mint_tokens(&config, payer, token_pubkey, 100.0, associated_account)
+        .await
+        .unwrap();
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::RecoveryTransfer.into(),
+            &associated_account.to_string(),
+            &aux_account.to_string(),
+            "100",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&aux_account).await.unwrap();
+    let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    assert_eq!(
+        token_account.base.amount,
+        spl_token_2022::ui_amount_to_amount(100.0, TEST_DECIMALS)
+    );
 }
 
 async fn default_account_state(test_validator: &TestValidator, payer: &Keypair) {
@@ -2599,23 +2947,88 @@ async fn default_account_state(test_validator: &TestValidator, payer: &Keypair)
     let account = StateWithExtensionsOwned::<Account>::unpack(token_account.data).unwrap();
     assert_eq!(account.base.state, AccountState::Frozen);
 
+    let attestor = Keypair::new();
+    let attestor_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&attestor, &attestor_keypair_file).unwrap();
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateDefaultAccountState.into(),
+            CommandName::SetDefaultAccountStateAttestor.into(),
             &token_pubkey.to_string(),
-            "initialized",
+            &attestor.pubkey().to_string(),
         ],
     )
     .await
     .unwrap();
-    let unfrozen_account = create_auxiliary_account(&config, payer, token_pubkey).await;
-    let token_account = config
-        .rpc_client
-        .get_account(&unfrozen_account)
-        .await
+    let mint_account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let mint = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data).unwrap();
+    let extension = mint.get_extension::<DefaultAccountStateAttestor>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(extension.attestor),
+        Some(attestor.pubkey())
+    );
+
+    let attested = Keypair::new();
+    let attested_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&attested, &attested_keypair_file).unwrap();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateAccount.into(),
+            &token_pubkey.to_string(),
+            attested_keypair_file.path().to_str().unwrap(),
+            "--attestor",
+            attestor_keypair_file.path().to_str().unwrap(),
+        ],
+    )
+    .await
+    .unwrap();
+    let token_account = config
+        .rpc_client
+        .get_account(&attested.pubkey())
+        .await
+        .unwrap();
+    let account = StateWithExtensionsOwned::<Account>::unpack(token_account.data).unwrap();
+    assert_eq!(account.base.state, AccountState::Initialized);
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetDefaultAccountStateAttestor.into(),
+            &token_pubkey.to_string(),
+            "--disable",
+        ],
+    )
+    .await
+    .unwrap();
+    let mint_account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let mint = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data).unwrap();
+    let extension = mint.get_extension::<DefaultAccountStateAttestor>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(extension.attestor), None);
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateDefaultAccountState.into(),
+            &token_pubkey.to_string(),
+            "initialized",
+        ],
+    )
+    .await
+    .unwrap();
+    let unfrozen_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+    let token_account = config
+        .rpc_client
+        .get_account(&unfrozen_account)
+        .await
         .unwrap();
     let account = StateWithExtensionsOwned::<Account>::unpack(token_account.data).unwrap();
     assert_eq!(account.base.state, AccountState::Initialized);
@@ -2932,9 +3345,70 @@ async fn transfer_fee_basis_point(test_validator: &TestValidator, payer: &Keypai
     );
 }
 
-async fn confidential_transfer(test_validator: &TestValidator, payer: &Keypair) {
-    use solana_zk_sdk::encryption::elgamal::ElGamalKeypair;
+async fn transfer_fee_exemption(test_validator: &TestValidator, payer: &Keypair) {
+    let config =
+        test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
+
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token_pubkey = token.pubkey();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--transfer-fee",
+            "100",
+            "1000000000",
+        ],
+    )
+    .await
+    .unwrap();
+    let token_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+
+    // enable works
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::EnableTransferFeeExemption.into(),
+            &token_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+    let account = config.rpc_client.get_account(&token_account).await.unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<TransferFeeExemption>()
+        .unwrap();
+    assert!(bool::from(extension.exempt));
+
+    // disable works
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::DisableTransferFeeExemption.into(),
+            &token_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+    let account = config.rpc_client.get_account(&token_account).await.unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<TransferFeeExemption>()
+        .unwrap();
+    assert!(!bool::from(extension.exempt));
+}
 
+async fn confidential_transfer(test_validator: &TestValidator, payer: &Keypair) {
     let config =
         test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
 
@@ -2979,6 +3453,56 @@ async fn confidential_transfer(test_validator: &TestValidator, payer: &Keypair)
         None,
     );
 
+    // approve a confidential transfer account while approval is manual
+    let unapproved_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ConfigureConfidentialTransferAccount.into(),
+            "--address",
+            &unapproved_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config
+        .rpc_client
+        .get_account(&unapproved_account)
+        .await
+        .unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert!(!bool::from(extension.approved));
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ApproveConfidentialTransferAccount.into(),
+            &token_pubkey.to_string(),
+            &unapproved_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config
+        .rpc_client
+        .get_account(&unapproved_account)
+        .await
+        .unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert!(bool::from(extension.approved));
+
     // update confidential transfer mint settings
     let auditor_keypair = ElGamalKeypair::new_rand();
     let auditor_pubkey: PodElGamalPubkey = (*auditor_keypair.pubkey()).into();
@@ -3212,6 +3736,31 @@ async fn confidential_transfer(test_validator: &TestValidator, payer: &Keypair)
     .await
     .unwrap();
 
+    // empty the confidential balance so that the account can be closed
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::EmptyConfidentialTransferAccount.into(),
+            "--address",
+            &destination_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config
+        .rpc_client
+        .get_account(&destination_account)
+        .await
+        .unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert_eq!(extension.available_balance, PodElGamalCiphertext::default());
+
     // disable confidential transfers for mint
     process_test_command(
         &config,
@@ -3235,113 +3784,230 @@ async fn confidential_transfer(test_validator: &TestValidator, payer: &Keypair)
     assert_eq!(Option::<Pubkey>::from(extension.authority), None,);
 }
 
-async fn confidential_transfer_with_fee(test_validator: &TestValidator, payer: &Keypair) {
+async fn elgamal_registry(test_validator: &TestValidator, payer: &Keypair) {
     let config =
         test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
 
-    // create token with confidential transfers enabled
-    let auto_approve = true;
-    let confidential_transfer_mint_authority = payer.pubkey();
-
-    let token = Keypair::new();
-    let token_keypair_file = NamedTempFile::new().unwrap();
-    write_keypair_file(&token, &token_keypair_file).unwrap();
-    let token_pubkey = token.pubkey();
+    // create the registry of the default signer
     process_test_command(
         &config,
         payer,
-        &[
-            "spl-token",
-            CommandName::CreateToken.into(),
-            token_keypair_file.path().to_str().unwrap(),
-            "--enable-confidential-transfers",
-            "auto",
-            "--transfer-fee",
-            "100",
-            "1000000000",
-        ],
+        &["spl-token", CommandName::CreateElgamalRegistry.into()],
     )
     .await
     .unwrap();
 
-    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
-    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = test_mint
-        .get_extension::<ConfidentialTransferMint>()
-        .unwrap();
-
-    assert_eq!(
-        Option::<Pubkey>::from(extension.authority),
-        Some(confidential_transfer_mint_authority),
-    );
-    assert_eq!(
-        bool::from(extension.auto_approve_new_accounts),
-        auto_approve,
-    );
-    assert_eq!(
-        Option::<PodElGamalPubkey>::from(extension.auditor_elgamal_pubkey),
-        None,
-    );
-
-    let extension = test_mint
-        .get_extension::<ConfidentialTransferFeeConfig>()
-        .unwrap();
-    assert_eq!(
-        Option::<Pubkey>::from(extension.authority),
-        Some(confidential_transfer_mint_authority),
-    );
-
-    // Create and configure source and destination accounts
-    let source_account =
-        create_associated_account(&config, payer, &token_pubkey, &payer.pubkey()).await;
-
-    process_test_command(
+    let result = process_test_command(
         &config,
         payer,
-        &[
-            "spl-token",
-            CommandName::ConfigureConfidentialTransferAccount.into(),
-            &token_pubkey.to_string(),
-        ],
+        &["spl-token", CommandName::ShowElgamalRegistry.into()],
     )
     .await
     .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let elgamal_registry = value["address"].as_str().unwrap().to_string();
+    let elgamal_pubkey = value["elgamalPubkey"].as_str().unwrap().to_string();
+    assert_eq!(value["owner"], payer.pubkey().to_string());
+    assert_eq!(value["labelledKeys"].as_array().unwrap().len(), 0);
 
-    let destination_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+    // creating it twice fails
     process_test_command(
         &config,
         payer,
-        &[
-            "spl-token",
-            CommandName::ConfigureConfidentialTransferAccount.into(),
-            "--address",
-            &destination_account.to_string(),
-        ],
+        &["spl-token", CommandName::CreateElgamalRegistry.into()],
     )
     .await
-    .unwrap();
-
-    // Mint tokens to source
-    let deposit_amount = 100.0;
-    mint_tokens(&config, payer, token_pubkey, deposit_amount, source_account)
-        .await
-        .unwrap();
+    .unwrap_err();
 
-    // Deposit into confidential balance
+    // updating keeps the key derived from the owner
     process_test_command(
         &config,
         payer,
-        &[
-            "spl-token",
-            CommandName::DepositConfidentialTokens.into(),
-            &token_pubkey.to_string(),
-            &deposit_amount.to_string(),
-        ],
+        &["spl-token", CommandName::UpdateElgamalRegistry.into()],
     )
     .await
     .unwrap();
 
-    process_test_command(
+    let result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ShowElgamalRegistry.into(),
+            "--owner",
+            &payer.pubkey().to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(value["elgamalPubkey"], elgamal_pubkey);
+
+    // configure a token account from the registry
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token_pubkey = token.pubkey();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--enable-confidential-transfers",
+            "auto",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let token_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ConfigureConfidentialTransferAccount.into(),
+            "--address",
+            &token_account.to_string(),
+            "--elgamal-registry",
+            &elgamal_registry,
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_account).await.unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert_eq!(extension.elgamal_pubkey.to_string(), elgamal_pubkey);
+
+    // no registry for other owners
+    let other = Keypair::new();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ShowElgamalRegistry.into(),
+            "--owner",
+            &other.pubkey().to_string(),
+        ],
+    )
+    .await
+    .unwrap_err();
+}
+
+async fn confidential_transfer_with_fee(test_validator: &TestValidator, payer: &Keypair) {
+    let config =
+        test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
+
+    // create token with confidential transfers enabled
+    let auto_approve = true;
+    let confidential_transfer_mint_authority = payer.pubkey();
+
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token_pubkey = token.pubkey();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--enable-confidential-transfers",
+            "auto",
+            "--transfer-fee",
+            "100",
+            "1000000000",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint
+        .get_extension::<ConfidentialTransferMint>()
+        .unwrap();
+
+    assert_eq!(
+        Option::<Pubkey>::from(extension.authority),
+        Some(confidential_transfer_mint_authority),
+    );
+    assert_eq!(
+        bool::from(extension.auto_approve_new_accounts),
+        auto_approve,
+    );
+    assert_eq!(
+        Option::<PodElGamalPubkey>::from(extension.auditor_elgamal_pubkey),
+        None,
+    );
+
+    let extension = test_mint
+        .get_extension::<ConfidentialTransferFeeConfig>()
+        .unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(extension.authority),
+        Some(confidential_transfer_mint_authority),
+    );
+
+    // Create and configure source and destination accounts
+    let source_account =
+        create_associated_account(&config, payer, &token_pubkey, &payer.pubkey()).await;
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ConfigureConfidentialTransferAccount.into(),
+            &token_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let destination_account = create_auxiliary_account(&config, payer, token_pubkey).await;
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::ConfigureConfidentialTransferAccount.into(),
+            "--address",
+            &destination_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Mint tokens to source
+    let deposit_amount = 100.0;
+    mint_tokens(&config, payer, token_pubkey, deposit_amount, source_account)
+        .await
+        .unwrap();
+
+    // Deposit into confidential balance
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::DepositConfidentialTokens.into(),
+            &token_pubkey.to_string(),
+            &deposit_amount.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    process_test_command(
         &config,
         payer,
         &[
@@ -3385,82 +4051,203 @@ async fn confidential_transfer_with_fee(test_validator: &TestValidator, payer: &
     )
     .await
     .unwrap();
-}
-
-async fn multisig_transfer(test_validator: &TestValidator, payer: &Keypair) {
-    let m = 3;
-    let n = 5u8;
-    // need to add "payer" to make the config provide the right signer
-    let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
-        std::iter::once(clone_keypair(payer))
-            .chain(std::iter::repeat_with(Keypair::new).take((n - 2) as usize))
-            .map(|s| {
-                let keypair_file = NamedTempFile::new().unwrap();
-                write_keypair_file(&s, &keypair_file).unwrap();
-                (s.pubkey(), keypair_file)
-            })
-            .unzip();
-    for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
-        let config = test_config_with_default_signer(test_validator, payer, program_id);
-        let token = create_token(&config, payer).await;
-        let multisig = Arc::new(Keypair::new());
-        let multisig_pubkey = multisig.pubkey();
 
-        // add the multisig as a member to itself, make it self-owned
-        let multisig_members = std::iter::once(multisig_pubkey)
-            .chain(multisig_members.iter().cloned())
-            .collect::<Vec<_>>();
-        let multisig_path = NamedTempFile::new().unwrap();
-        write_keypair_file(&multisig, &multisig_path).unwrap();
-        let multisig_paths = std::iter::once(&multisig_path)
-            .chain(multisig_paths.iter())
-            .collect::<Vec<_>>();
+    // Withdraw the withheld fee directly from the destination account
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::WithdrawConfidentialWithheldTokens.into(),
+            &source_account.to_string(),
+            &destination_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
 
-        let multisig_strings = multisig_members
-            .iter()
-            .map(|p| p.to_string())
-            .collect::<Vec<_>>();
-        process_test_command(
-            &config,
-            payer,
-            [
-                "spl-token",
-                CommandName::CreateMultisig.into(),
-                "--address-keypair",
-                multisig_path.path().to_str().unwrap(),
-                "--program-id",
-                &program_id.to_string(),
-                &m.to_string(),
-            ]
-            .into_iter()
-            .chain(multisig_strings.iter().map(|p| p.as_str())),
-        )
+    let account = config
+        .rpc_client
+        .get_account(&destination_account)
         .await
         .unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<ConfidentialTransferFeeAmount>()
+        .unwrap();
+    assert_eq!(extension.withheld_amount, PodElGamalCiphertext::default());
 
-        let account = config
-            .rpc_client
-            .get_account(&multisig_pubkey)
-            .await
-            .unwrap();
-        let multisig = Multisig::unpack(&account.data).unwrap();
-        assert_eq!(multisig.m, m);
-        assert_eq!(multisig.n, n);
-
-        let source = create_associated_account(&config, payer, &token, &multisig_pubkey).await;
-        let destination = create_auxiliary_account(&config, payer, token).await;
-        let ui_amount = 100.0;
-        mint_tokens(&config, payer, token, ui_amount, source)
-            .await
-            .unwrap();
-
-        exec_test_cmd(
-            &config,
-            &[
-                "spl-token",
-                CommandName::Transfer.into(),
-                &token.to_string(),
-                "10",
+    // Transfer again, then harvest the fee to the mint and withdraw from there
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Transfer.into(),
+            &token_pubkey.to_string(),
+            "50",
+            &source_account.to_string(),
+            "--from",
+            &destination_account.to_string(),
+            "--confidential",
+            "--expected-fee",
+            "0.5",
+        ],
+    )
+    .await
+    .unwrap();
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::HarvestConfidentialWithheldTokens.into(),
+            &token_pubkey.to_string(),
+            &source_account.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config
+        .rpc_client
+        .get_account(&source_account)
+        .await
+        .unwrap();
+    let account_state = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+    let extension = account_state
+        .get_extension::<ConfidentialTransferFeeAmount>()
+        .unwrap();
+    assert_eq!(extension.withheld_amount, PodElGamalCiphertext::default());
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::WithdrawConfidentialWithheldTokens.into(),
+            &destination_account.to_string(),
+            "--include-mint",
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Disable and enable harvesting to the mint
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::DisableConfidentialHarvestToMint.into(),
+            &token_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint
+        .get_extension::<ConfidentialTransferFeeConfig>()
+        .unwrap();
+    assert!(!bool::from(extension.harvest_to_mint_enabled));
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::EnableConfidentialHarvestToMint.into(),
+            &token_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint
+        .get_extension::<ConfidentialTransferFeeConfig>()
+        .unwrap();
+    assert!(bool::from(extension.harvest_to_mint_enabled));
+}
+
+async fn multisig_transfer(test_validator: &TestValidator, payer: &Keypair) {
+    let m = 3;
+    let n = 5u8;
+    // need to add "payer" to make the config provide the right signer
+    let (multisig_members, multisig_paths): (Vec<_>, Vec<_>) =
+        std::iter::once(clone_keypair(payer))
+            .chain(std::iter::repeat_with(Keypair::new).take((n - 2) as usize))
+            .map(|s| {
+                let keypair_file = NamedTempFile::new().unwrap();
+                write_keypair_file(&s, &keypair_file).unwrap();
+                (s.pubkey(), keypair_file)
+            })
+            .unzip();
+    for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+        let config = test_config_with_default_signer(test_validator, payer, program_id);
+        let token = create_token(&config, payer).await;
+        let multisig = Arc::new(Keypair::new());
+        let multisig_pubkey = multisig.pubkey();
+
+        // add the multisig as a member to itself, make it self-owned
+        let multisig_members = std::iter::once(multisig_pubkey)
+            .chain(multisig_members.iter().cloned())
+            .collect::<Vec<_>>();
+        let multisig_path = NamedTempFile::new().unwrap();
+        write_keypair_file(&multisig, &multisig_path).unwrap();
+        let multisig_paths = std::iter::once(&multisig_path)
+            .chain(multisig_paths.iter())
+            .collect::<Vec<_>>();
+
+        let multisig_strings = multisig_members
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>();
+        process_test_command(
+            &config,
+            payer,
+            [
+                "spl-token",
+                CommandName::CreateMultisig.into(),
+                "--address-keypair",
+                multisig_path.path().to_str().unwrap(),
+                "--program-id",
+                &program_id.to_string(),
+                &m.to_string(),
+            ]
+            .into_iter()
+            .chain(multisig_strings.iter().map(|p| p.as_str())),
+        )
+        .await
+        .unwrap();
+
+        let account = config
+            .rpc_client
+            .get_account(&multisig_pubkey)
+            .await
+            .unwrap();
+        let multisig = Multisig::unpack(&account.data).unwrap();
+        assert_eq!(multisig.m, m);
+        assert_eq!(multisig.n, n);
+
+        let source = create_associated_account(&config, payer, &token, &multisig_pubkey).await;
+        let destination = create_auxiliary_account(&config, payer, token).await;
+        let ui_amount = 100.0;
+        mint_tokens(&config, payer, token, ui_amount, source)
+            .await
+            .unwrap();
+
+        exec_test_cmd(
+            &config,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                &token.to_string(),
+                "10",
                 &destination.to_string(),
                 "--multisig-signer",
                 multisig_paths[0].path().to_str().unwrap(),
@@ -3804,185 +4591,553 @@ async fn withdraw_excess_lamports_from_multisig(test_validator: &TestValidator,
         .await
         .unwrap();
 
-    exec_test_cmd(
+    exec_test_cmd(
+        &config,
+        &[
+            "spl-token",
+            CommandName::WithdrawExcessLamports.into(),
+            &multisig_pubkey.to_string(),
+            &receiver.pubkey().to_string(),
+            "--owner",
+            &multisig_pubkey.to_string(),
+            "--multisig-signer",
+            multisig_paths[0].path().to_str().unwrap(),
+            "--multisig-signer",
+            multisig_paths[1].path().to_str().unwrap(),
+            "--multisig-signer",
+            multisig_paths[2].path().to_str().unwrap(),
+            "--fee-payer",
+            fee_payer_keypair_file.path().to_str().unwrap(),
+            "--program-id",
+            &program_id.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        excess_lamports,
+        config
+            .rpc_client
+            .get_balance(&receiver.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+async fn withdraw_excess_lamports_from_mint(test_validator: &TestValidator, payer: &Keypair) {
+    let program_id = &spl_token_2022_interface::id();
+    let config = test_config_with_default_signer(test_validator, payer, program_id);
+    let owner_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(payer, &owner_keypair_file).unwrap();
+
+    let receiver = Keypair::new();
+
+    let token_keypair = Keypair::new();
+    let token_path = NamedTempFile::new().unwrap();
+    write_keypair_file(&token_keypair, &token_path).unwrap();
+    let token_pubkey = token_keypair.pubkey();
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_path.path().to_str().unwrap(),
+            "--program-id",
+            &program_id.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let excess_lamports = 4000 * 1_000_000_000;
+    config
+        .rpc_client
+        .send_and_confirm_transaction(&Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &token_pubkey,
+                excess_lamports,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            config.rpc_client.get_latest_blockhash().await.unwrap(),
+        ))
+        .await
+        .unwrap();
+
+    exec_test_cmd(
+        &config,
+        &[
+            "spl-token",
+            CommandName::WithdrawExcessLamports.into(),
+            &token_pubkey.to_string(),
+            &receiver.pubkey().to_string(),
+            "--owner",
+            owner_keypair_file.path().to_str().unwrap(),
+            "--program-id",
+            &program_id.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        excess_lamports,
+        config
+            .rpc_client
+            .get_balance(&receiver.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+async fn withdraw_excess_lamports_from_account(test_validator: &TestValidator, payer: &Keypair) {
+    let program_id = &spl_token_2022_interface::id();
+    let config = test_config_with_default_signer(test_validator, payer, program_id);
+    let owner_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(payer, &owner_keypair_file).unwrap();
+
+    let receiver = Keypair::new();
+
+    let token_keypair = Keypair::new();
+    let token_path = NamedTempFile::new().unwrap();
+    write_keypair_file(&token_keypair, &token_path).unwrap();
+    let token_pubkey = token_keypair.pubkey();
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_path.path().to_str().unwrap(),
+            "--program-id",
+            &program_id.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let excess_lamports = 4000 * 1_000_000_000;
+    let token_account =
+        create_associated_account(&config, payer, &token_pubkey, &payer.pubkey()).await;
+
+    config
+        .rpc_client
+        .send_and_confirm_transaction(&Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &token_account,
+                excess_lamports,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            config.rpc_client.get_latest_blockhash().await.unwrap(),
+        ))
+        .await
+        .unwrap();
+
+    exec_test_cmd(
+        &config,
+        &[
+            "spl-token",
+            CommandName::WithdrawExcessLamports.into(),
+            &token_account.to_string(),
+            &receiver.pubkey().to_string(),
+            "--owner",
+            owner_keypair_file.path().to_str().unwrap(),
+            "--program-id",
+            &program_id.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        excess_lamports,
+        config
+            .rpc_client
+            .get_balance(&receiver.pubkey())
+            .await
+            .unwrap()
+    );
+}
+
+async fn metadata_pointer(test_validator: &TestValidator, payer: &Keypair) {
+    let program_id = spl_token_2022_interface::id();
+    let config = test_config_with_default_signer(test_validator, payer, &program_id);
+    let metadata_address = Pubkey::new_unique();
+
+    let result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            "--program-id",
+            &program_id.to_string(),
+            "--metadata-address",
+            &metadata_address.to_string(),
+        ],
+    )
+    .await;
+
+    let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+
+    let extension = mint_state.get_extension::<MetadataPointer>().unwrap();
+
+    assert_eq!(
+        extension.metadata_address,
+        Some(metadata_address).try_into().unwrap()
+    );
+
+    let new_metadata_address = Pubkey::new_unique();
+
+    let _new_result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateMetadataAddress.into(),
+            &mint.to_string(),
+            &new_metadata_address.to_string(),
+        ],
+    )
+    .await;
+
+    let new_account = config.rpc_client.get_account(&mint).await.unwrap();
+    let new_mint_state = StateWithExtensionsOwned::<Mint>::unpack(new_account.data).unwrap();
+
+    let new_extension = new_mint_state.get_extension::<MetadataPointer>().unwrap();
+
+    assert_eq!(
+        new_extension.metadata_address,
+        Some(new_metadata_address).try_into().unwrap()
+    );
+
+    let _result_with_disable = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateMetadataAddress.into(),
+            &mint.to_string(),
+            "--disable",
+        ],
+    )
+    .await;
+
+    let new_account_disable = config.rpc_client.get_account(&mint).await.unwrap();
+    let new_mint_state_disable =
+        StateWithExtensionsOwned::<Mint>::unpack(new_account_disable.data).unwrap();
+
+    let new_extension_disable = new_mint_state_disable
+        .get_extension::<MetadataPointer>()
+        .unwrap();
+
+    assert_eq!(
+        new_extension_disable.metadata_address,
+        None.try_into().unwrap()
+    );
+}
+
+async fn group_pointer(test_validator: &TestValidator, payer: &Keypair) {
+    let program_id = spl_token_2022_interface::id();
+    let config = test_config_with_default_signer(test_validator, payer, &program_id);
+    let group_address = Pubkey::new_unique();
+
+    let result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            "--program-id",
+            &program_id.to_string(),
+            "--group-address",
+            &group_address.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+
+    let extension = mint_state.get_extension::<GroupPointer>().unwrap();
+
+    assert_eq!(
+        extension.group_address,
+        Some(group_address).try_into().unwrap()
+    );
+
+    let new_group_address = Pubkey::new_unique();
+
+    let _new_result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateGroupAddress.into(),
+            &mint.to_string(),
+            &new_group_address.to_string(),
+        ],
+    )
+    .await;
+
+    let new_account = config.rpc_client.get_account(&mint).await.unwrap();
+    let new_mint_state = StateWithExtensionsOwned::<Mint>::unpack(new_account.data).unwrap();
+
+    let new_extension = new_mint_state.get_extension::<GroupPointer>().unwrap();
+
+    assert_eq!(
+        new_extension.group_address,
+        Some(new_group_address).try_into().unwrap()
+    );
+
+    let _result_with_disable = process_test_command(
         &config,
+        payer,
         &[
             "spl-token",
-            CommandName::WithdrawExcessLamports.into(),
-            &multisig_pubkey.to_string(),
-            &receiver.pubkey().to_string(),
-            "--owner",
-            &multisig_pubkey.to_string(),
-            "--multisig-signer",
-            multisig_paths[0].path().to_str().unwrap(),
-            "--multisig-signer",
-            multisig_paths[1].path().to_str().unwrap(),
-            "--multisig-signer",
-            multisig_paths[2].path().to_str().unwrap(),
-            "--fee-payer",
-            fee_payer_keypair_file.path().to_str().unwrap(),
-            "--program-id",
-            &program_id.to_string(),
+            CommandName::UpdateGroupAddress.into(),
+            &mint.to_string(),
+            "--disable",
         ],
     )
     .await
     .unwrap();
 
+    let new_account_disable = config.rpc_client.get_account(&mint).await.unwrap();
+    let new_mint_state_disable =
+        StateWithExtensionsOwned::<Mint>::unpack(new_account_disable.data).unwrap();
+
+    let new_extension_disable = new_mint_state_disable
+        .get_extension::<GroupPointer>()
+        .unwrap();
+
     assert_eq!(
-        excess_lamports,
-        config
-            .rpc_client
-            .get_balance(&receiver.pubkey())
-            .await
-            .unwrap()
+        new_extension_disable.group_address,
+        None.try_into().unwrap()
     );
 }
 
-async fn withdraw_excess_lamports_from_mint(test_validator: &TestValidator, payer: &Keypair) {
-    let program_id = &spl_token_2022_interface::id();
-    let config = test_config_with_default_signer(test_validator, payer, program_id);
-    let owner_keypair_file = NamedTempFile::new().unwrap();
-    write_keypair_file(payer, &owner_keypair_file).unwrap();
-
-    let receiver = Keypair::new();
-
-    let token_keypair = Keypair::new();
-    let token_path = NamedTempFile::new().unwrap();
-    write_keypair_file(&token_keypair, &token_path).unwrap();
-    let token_pubkey = token_keypair.pubkey();
+async fn group_member_pointer(test_validator: &TestValidator, payer: &Keypair) {
+    let program_id = spl_token_2022_interface::id();
+    let config = test_config_with_default_signer(test_validator, payer, &program_id);
+    let member_address = Pubkey::new_unique();
 
-    process_test_command(
+    let result = process_test_command(
         &config,
         payer,
         &[
             "spl-token",
             CommandName::CreateToken.into(),
-            token_path.path().to_str().unwrap(),
             "--program-id",
             &program_id.to_string(),
+            "--member-address",
+            &member_address.to_string(),
         ],
     )
     .await
     .unwrap();
 
-    let excess_lamports = 4000 * 1_000_000_000;
-    config
-        .rpc_client
-        .send_and_confirm_transaction(&Transaction::new_signed_with_payer(
-            &[system_instruction::transfer(
-                &payer.pubkey(),
-                &token_pubkey,
-                excess_lamports,
-            )],
-            Some(&payer.pubkey()),
-            &[&payer],
-            config.rpc_client.get_latest_blockhash().await.unwrap(),
-        ))
-        .await
-        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
 
-    exec_test_cmd(
+    let extension = mint_state.get_extension::<GroupMemberPointer>().unwrap();
+
+    assert_eq!(
+        extension.member_address,
+        Some(member_address).try_into().unwrap()
+    );
+
+    let new_member_address = Pubkey::new_unique();
+
+    let _new_result = process_test_command(
         &config,
+        payer,
         &[
             "spl-token",
-            CommandName::WithdrawExcessLamports.into(),
-            &token_pubkey.to_string(),
-            &receiver.pubkey().to_string(),
-            "--owner",
-            owner_keypair_file.path().to_str().unwrap(),
-            "--program-id",
-            &program_id.to_string(),
+            CommandName::UpdateMemberAddress.into(),
+            &mint.to_string(),
+            &new_member_address.to_string(),
         ],
     )
     .await
     .unwrap();
 
+    let new_account = config.rpc_client.get_account(&mint).await.unwrap();
+    let new_mint_state = StateWithExtensionsOwned::<Mint>::unpack(new_account.data).unwrap();
+
+    let new_extension = new_mint_state
+        .get_extension::<GroupMemberPointer>()
+        .unwrap();
+
     assert_eq!(
-        excess_lamports,
-        config
-            .rpc_client
-            .get_balance(&receiver.pubkey())
-            .await
-            .unwrap()
+        new_extension.member_address,
+        Some(new_member_address).try_into().unwrap()
     );
-}
 
-async fn withdraw_excess_lamports_from_account(test_validator: &TestValidator, payer: &Keypair) {
-    let program_id = &spl_token_2022_interface::id();
-    let config = test_config_with_default_signer(test_validator, payer, program_id);
-    let owner_keypair_file = NamedTempFile::new().unwrap();
-    write_keypair_file(payer, &owner_keypair_file).unwrap();
+    let _result_with_disable = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateMemberAddress.into(),
+            &mint.to_string(),
+            "--disable",
+        ],
+    )
+    .await;
 
-    let receiver = Keypair::new();
+    let new_account_disable = config.rpc_client.get_account(&mint).await.unwrap();
+    let new_mint_state_disable =
+        StateWithExtensionsOwned::<Mint>::unpack(new_account_disable.data).unwrap();
 
-    let token_keypair = Keypair::new();
-    let token_path = NamedTempFile::new().unwrap();
-    write_keypair_file(&token_keypair, &token_path).unwrap();
-    let token_pubkey = token_keypair.pubkey();
+    let new_extension_disable = new_mint_state_disable
+        .get_extension::<GroupMemberPointer>()
+        .unwrap();
 
-    process_test_command(
+    assert_eq!(
+        new_extension_disable.member_address,
+        None.try_into().unwrap()
+    );
+}
+
+async fn transfer_hook(test_validator: &TestValidator, payer: &Keypair) {
+    let program_id = spl_token_2022_interface::id();
+    let mut config = test_config_with_default_signer(test_validator, payer, &program_id);
+    let transfer_hook_program_id = Pubkey::new_unique();
+
+    let result = process_test_command(
         &config,
         payer,
         &[
             "spl-token",
             CommandName::CreateToken.into(),
-            token_path.path().to_str().unwrap(),
             "--program-id",
             &program_id.to_string(),
+            "--transfer-hook",
+            &transfer_hook_program_id.to_string(),
+        ],
+    )
+    .await;
+
+    let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = mint_state.get_extension::<TransferHook>().unwrap();
+
+    assert_eq!(
+        extension.program_id,
+        Some(transfer_hook_program_id).try_into().unwrap()
+    );
+
+    let new_transfer_hook_program_id = Pubkey::new_unique();
+
+    let _new_result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetTransferHook.into(),
+            &mint.to_string(),
+            &new_transfer_hook_program_id.to_string(),
         ],
     )
     .await
     .unwrap();
 
-    let excess_lamports = 4000 * 1_000_000_000;
-    let token_account =
-        create_associated_account(&config, payer, &token_pubkey, &payer.pubkey()).await;
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = mint_state.get_extension::<TransferHook>().unwrap();
 
-    config
-        .rpc_client
-        .send_and_confirm_transaction(&Transaction::new_signed_with_payer(
-            &[system_instruction::transfer(
-                &payer.pubkey(),
-                &token_account,
-                excess_lamports,
-            )],
-            Some(&payer.pubkey()),
-            &[&payer],
-            config.rpc_client.get_latest_blockhash().await.unwrap(),
-        ))
-        .await
-        .unwrap();
+    assert_eq!(
+        extension.program_id,
+        Some(new_transfer_hook_program_id).try_into().unwrap()
+    );
 
-    exec_test_cmd(
+    // Make sure that parsing transfer hook accounts works
+    let real_program_client = config.program_client;
+    let blockhash = Hash::default();
+    let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
+        ProgramOfflineClient::new(blockhash, ProgramRpcClientSendTransaction),
+    );
+    config.program_client = program_client;
+    let _result = exec_test_cmd(
         &config,
         &[
             "spl-token",
-            CommandName::WithdrawExcessLamports.into(),
-            &token_account.to_string(),
-            &receiver.pubkey().to_string(),
+            CommandName::Transfer.into(),
+            &mint.to_string(),
+            "10",
+            &Pubkey::new_unique().to_string(),
+            "--blockhash",
+            &blockhash.to_string(),
+            "--nonce",
+            &Pubkey::new_unique().to_string(),
+            "--nonce-authority",
+            &Pubkey::new_unique().to_string(),
+            "--sign-only",
+            "--mint-decimals",
+            &format!("{}", TEST_DECIMALS),
+            "--from",
+            &Pubkey::new_unique().to_string(),
             "--owner",
-            owner_keypair_file.path().to_str().unwrap(),
-            "--program-id",
-            &program_id.to_string(),
+            &Pubkey::new_unique().to_string(),
+            "--transfer-hook-account",
+            &format!("{}:readonly", Pubkey::new_unique()),
+            "--transfer-hook-account",
+            &format!("{}:writable", Pubkey::new_unique()),
+            "--transfer-hook-account",
+            &format!("{}:readonly-signer", Pubkey::new_unique()),
+            "--transfer-hook-account",
+            &format!("{}:writable-signer", Pubkey::new_unique()),
+        ],
+    )
+    .await
+    .unwrap();
+
+    config.program_client = real_program_client;
+    let _result_with_disable = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::SetTransferHook.into(),
+            &mint.to_string(),
+            "--disable",
         ],
     )
     .await
     .unwrap();
 
-    assert_eq!(
-        excess_lamports,
-        config
-            .rpc_client
-            .get_balance(&receiver.pubkey())
-            .await
-            .unwrap()
-    );
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = mint_state.get_extension::<TransferHook>().unwrap();
+
+    assert_eq!(extension.program_id, None.try_into().unwrap());
 }
 
-async fn metadata_pointer(test_validator: &TestValidator, payer: &Keypair) {
+async fn transfer_hook_with_transfer_fee(test_validator: &TestValidator, payer: &Keypair) {
     let program_id = spl_token_2022_interface::id();
-    let config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let metadata_address = Pubkey::new_unique();
+    let mut config = test_config_with_default_signer(test_validator, payer, &program_id);
+    let transfer_hook_program_id = Pubkey::new_unique();
+
+    let transfer_fee_basis_points = 100;
+    let maximum_fee: u64 = 10_000_000_000;
 
     let result = process_test_command(
         &config,
@@ -3992,78 +5147,97 @@ async fn metadata_pointer(test_validator: &TestValidator, payer: &Keypair) {
             CommandName::CreateToken.into(),
             "--program-id",
             &program_id.to_string(),
-            "--metadata-address",
-            &metadata_address.to_string(),
+            "--transfer-hook",
+            &transfer_hook_program_id.to_string(),
+            "--transfer-fee",
+            &transfer_fee_basis_points.to_string(),
+            &maximum_fee.to_string(),
         ],
     )
     .await;
 
+    // Check that the transfer-hook extension is correctly configured
     let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
     let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-
-    let extension = mint_state.get_extension::<MetadataPointer>().unwrap();
-
+    let extension = mint_state.get_extension::<TransferHook>().unwrap();
     assert_eq!(
-        extension.metadata_address,
-        Some(metadata_address).try_into().unwrap()
+        extension.program_id,
+        Some(transfer_hook_program_id).try_into().unwrap()
     );
 
-    let new_metadata_address = Pubkey::new_unique();
-
-    let _new_result = process_test_command(
-        &config,
-        payer,
-        &[
-            "spl-token",
-            CommandName::UpdateMetadataAddress.into(),
-            &mint.to_string(),
-            &new_metadata_address.to_string(),
-        ],
-    )
-    .await;
-
-    let new_account = config.rpc_client.get_account(&mint).await.unwrap();
-    let new_mint_state = StateWithExtensionsOwned::<Mint>::unpack(new_account.data).unwrap();
-
-    let new_extension = new_mint_state.get_extension::<MetadataPointer>().unwrap();
-
+    // Check that the transfer-fee extension is correctly configured
+    let extension = mint_state.get_extension::<TransferFeeConfig>().unwrap();
     assert_eq!(
-        new_extension.metadata_address,
-        Some(new_metadata_address).try_into().unwrap()
+        u16::from(extension.older_transfer_fee.transfer_fee_basis_points),
+        transfer_fee_basis_points
+    );
+    assert_eq!(
+        u64::from(extension.older_transfer_fee.maximum_fee),
+        maximum_fee
+    );
+    assert_eq!(
+        u16::from(extension.newer_transfer_fee.transfer_fee_basis_points),
+        transfer_fee_basis_points
+    );
+    assert_eq!(
+        u64::from(extension.newer_transfer_fee.maximum_fee),
+        maximum_fee
     );
 
-    let _result_with_disable = process_test_command(
+    // Make sure that parsing transfer hook accounts and expected-fee works
+    let blockhash = Hash::default();
+    let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
+        ProgramOfflineClient::new(blockhash, ProgramRpcClientSendTransaction),
+    );
+    config.program_client = program_client;
+
+    let _result = exec_test_cmd(
         &config,
-        payer,
         &[
             "spl-token",
-            CommandName::UpdateMetadataAddress.into(),
+            CommandName::Transfer.into(),
             &mint.to_string(),
-            "--disable",
+            "100",
+            &Pubkey::new_unique().to_string(),
+            "--blockhash",
+            &blockhash.to_string(),
+            "--nonce",
+            &Pubkey::new_unique().to_string(),
+            "--nonce-authority",
+            &Pubkey::new_unique().to_string(),
+            "--sign-only",
+            "--mint-decimals",
+            &format!("{}", TEST_DECIMALS),
+            "--from",
+            &Pubkey::new_unique().to_string(),
+            "--owner",
+            &Pubkey::new_unique().to_string(),
+            "--transfer-hook-account",
+            &format!("{}:readonly", Pubkey::new_unique()),
+            "--transfer-hook-account",
+            &format!("{}:writable", Pubkey::new_unique()),
+            "--transfer-hook-account",
+            &format!("{}:readonly-signer", Pubkey::new_unique()),
+            "--transfer-hook-account",
+            &format!("{}:writable-signer", Pubkey::new_unique()),
+            "--expected-fee",
+            "1",
+            "--program-id",
+            &program_id.to_string(),
         ],
     )
-    .await;
-
-    let new_account_disable = config.rpc_client.get_account(&mint).await.unwrap();
-    let new_mint_state_disable =
-        StateWithExtensionsOwned::<Mint>::unpack(new_account_disable.data).unwrap();
-
-    let new_extension_disable = new_mint_state_disable
-        .get_extension::<MetadataPointer>()
-        .unwrap();
-
-    assert_eq!(
-        new_extension_disable.metadata_address,
-        None.try_into().unwrap()
-    );
+    .await
+    .unwrap();
 }
 
-async fn group_pointer(test_validator: &TestValidator, payer: &Keypair) {
+async fn metadata(test_validator: &TestValidator, payer: &Keypair) {
     let program_id = spl_token_2022_interface::id();
     let config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let group_address = Pubkey::new_unique();
+    let name = "this";
+    let symbol = "is";
+    let uri = "METADATA!";
 
     let result = process_test_command(
         &config,
@@ -4073,165 +5247,162 @@ async fn group_pointer(test_validator: &TestValidator, payer: &Keypair) {
             CommandName::CreateToken.into(),
             "--program-id",
             &program_id.to_string(),
-            "--group-address",
-            &group_address.to_string(),
+            "--enable-metadata",
         ],
     )
-    .await
-    .unwrap();
+    .await;
 
-    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
     let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
 
-    let extension = mint_state.get_extension::<GroupPointer>().unwrap();
-
-    assert_eq!(
-        extension.group_address,
-        Some(group_address).try_into().unwrap()
-    );
-
-    let new_group_address = Pubkey::new_unique();
+    let extension = mint_state.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(extension.metadata_address, Some(mint).try_into().unwrap());
 
-    let _new_result = process_test_command(
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateGroupAddress.into(),
+            CommandName::InitializeMetadata.into(),
             &mint.to_string(),
-            &new_group_address.to_string(),
+            name,
+            symbol,
+            uri,
         ],
     )
-    .await;
-
-    let new_account = config.rpc_client.get_account(&mint).await.unwrap();
-    let new_mint_state = StateWithExtensionsOwned::<Mint>::unpack(new_account.data).unwrap();
-
-    let new_extension = new_mint_state.get_extension::<GroupPointer>().unwrap();
+    .await
+    .unwrap();
 
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata.name, name);
+    assert_eq!(fetched_metadata.symbol, symbol);
+    assert_eq!(fetched_metadata.uri, uri);
+    assert_eq!(fetched_metadata.mint, mint);
     assert_eq!(
-        new_extension.group_address,
-        Some(new_group_address).try_into().unwrap()
+        fetched_metadata.update_authority,
+        Some(payer.pubkey()).try_into().unwrap()
     );
+    assert_eq!(fetched_metadata.additional_metadata, []);
 
-    let _result_with_disable = process_test_command(
+    // update canonical field
+    let new_value = "THIS!";
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateGroupAddress.into(),
+            CommandName::UpdateMetadata.into(),
             &mint.to_string(),
-            "--disable",
+            "NAME",
+            new_value,
         ],
     )
     .await
     .unwrap();
-
-    let new_account_disable = config.rpc_client.get_account(&mint).await.unwrap();
-    let new_mint_state_disable =
-        StateWithExtensionsOwned::<Mint>::unpack(new_account_disable.data).unwrap();
-
-    let new_extension_disable = new_mint_state_disable
-        .get_extension::<GroupPointer>()
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
         .unwrap();
+    assert_eq!(fetched_metadata.name, new_value);
 
+    // add new field
+    let field = "My field!";
+    let value = "Try and stop me";
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateMetadata.into(),
+            &mint.to_string(),
+            field,
+            value,
+        ],
+    )
+    .await
+    .unwrap();
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
     assert_eq!(
-        new_extension_disable.group_address,
-        None.try_into().unwrap()
+        fetched_metadata.additional_metadata,
+        [(field.to_string(), value.to_string())]
     );
-}
 
-async fn group_member_pointer(test_validator: &TestValidator, payer: &Keypair) {
-    let program_id = spl_token_2022_interface::id();
-    let config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let member_address = Pubkey::new_unique();
-
-    let result = process_test_command(
+    // remove it
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::CreateToken.into(),
-            "--program-id",
-            &program_id.to_string(),
-            "--member-address",
-            &member_address.to_string(),
+            CommandName::UpdateMetadata.into(),
+            &mint.to_string(),
+            field,
+            "--remove",
         ],
     )
     .await
     .unwrap();
-
-    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
-    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata.additional_metadata, []);
 
-    let extension = mint_state.get_extension::<GroupMemberPointer>().unwrap();
-
-    assert_eq!(
-        extension.member_address,
-        Some(member_address).try_into().unwrap()
-    );
-
-    let new_member_address = Pubkey::new_unique();
-
-    let _new_result = process_test_command(
+    // fail to remove name
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateMemberAddress.into(),
+            CommandName::UpdateMetadata.into(),
             &mint.to_string(),
-            &new_member_address.to_string(),
+            "name",
+            "--remove",
         ],
     )
     .await
-    .unwrap();
-
-    let new_account = config.rpc_client.get_account(&mint).await.unwrap();
-    let new_mint_state = StateWithExtensionsOwned::<Mint>::unpack(new_account.data).unwrap();
-
-    let new_extension = new_mint_state
-        .get_extension::<GroupMemberPointer>()
-        .unwrap();
-
-    assert_eq!(
-        new_extension.member_address,
-        Some(new_member_address).try_into().unwrap()
-    );
+    .unwrap_err();
 
-    let _result_with_disable = process_test_command(
+    // update authority
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateMemberAddress.into(),
+            CommandName::Authorize.into(),
+            &mint.to_string(),
+            "metadata",
             &mint.to_string(),
-            "--disable",
         ],
     )
-    .await;
-
-    let new_account_disable = config.rpc_client.get_account(&mint).await.unwrap();
-    let new_mint_state_disable =
-        StateWithExtensionsOwned::<Mint>::unpack(new_account_disable.data).unwrap();
+    .await
+    .unwrap();
 
-    let new_extension_disable = new_mint_state_disable
-        .get_extension::<GroupMemberPointer>()
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
         .unwrap();
-
     assert_eq!(
-        new_extension_disable.member_address,
-        None.try_into().unwrap()
+        fetched_metadata.update_authority,
+        Some(mint).try_into().unwrap()
     );
 }
 
-async fn transfer_hook(test_validator: &TestValidator, payer: &Keypair) {
+async fn metadata_fields(test_validator: &TestValidator, payer: &Keypair) {
     let program_id = spl_token_2022_interface::id();
-    let mut config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let transfer_hook_program_id = Pubkey::new_unique();
+    let config = test_config_with_default_signer(test_validator, payer, &program_id);
 
     let result = process_test_command(
         &config,
@@ -4241,8 +5412,9 @@ async fn transfer_hook(test_validator: &TestValidator, payer: &Keypair) {
             CommandName::CreateToken.into(),
             "--program-id",
             &program_id.to_string(),
-            "--transfer-hook",
-            &transfer_hook_program_id.to_string(),
+            "--enable-metadata",
+            "--metadata-size-limit",
+            "200",
         ],
     )
     .await;
@@ -4251,107 +5423,89 @@ async fn transfer_hook(test_validator: &TestValidator, payer: &Keypair) {
     let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = mint_state.get_extension::<TransferHook>().unwrap();
-
-    assert_eq!(
-        extension.program_id,
-        Some(transfer_hook_program_id).try_into().unwrap()
-    );
-
-    let new_transfer_hook_program_id = Pubkey::new_unique();
+    let extension = mint_state
+        .get_extension::<TokenMetadataSizeLimit>()
+        .unwrap();
+    assert_eq!(u32::from(extension.max_len), 200);
 
-    let _new_result = process_test_command(
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::SetTransferHook.into(),
+            CommandName::InitializeMetadata.into(),
             &mint.to_string(),
-            &new_transfer_hook_program_id.to_string(),
+            "this",
+            "is",
+            "METADATA!",
         ],
     )
     .await
     .unwrap();
 
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = mint_state.get_extension::<TransferHook>().unwrap();
-
-    assert_eq!(
-        extension.program_id,
-        Some(new_transfer_hook_program_id).try_into().unwrap()
-    );
-
-    // Make sure that parsing transfer hook accounts works
-    let real_program_client = config.program_client;
-    let blockhash = Hash::default();
-    let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
-        ProgramOfflineClient::new(blockhash, ProgramRpcClientSendTransaction),
-    );
-    config.program_client = program_client;
-    let _result = exec_test_cmd(
+    // update several fields at once
+    process_test_command(
         &config,
+        payer,
         &[
             "spl-token",
-            CommandName::Transfer.into(),
+            CommandName::UpdateMetadata.into(),
             &mint.to_string(),
-            "10",
-            &Pubkey::new_unique().to_string(),
-            "--blockhash",
-            &blockhash.to_string(),
-            "--nonce",
-            &Pubkey::new_unique().to_string(),
-            "--nonce-authority",
-            &Pubkey::new_unique().to_string(),
-            "--sign-only",
-            "--mint-decimals",
-            &format!("{}", TEST_DECIMALS),
-            "--from",
-            &Pubkey::new_unique().to_string(),
-            "--owner",
-            &Pubkey::new_unique().to_string(),
-            "--transfer-hook-account",
-            &format!("{}:readonly", Pubkey::new_unique()),
-            "--transfer-hook-account",
-            &format!("{}:writable", Pubkey::new_unique()),
-            "--transfer-hook-account",
-            &format!("{}:readonly-signer", Pubkey::new_unique()),
-            "--transfer-hook-account",
-            &format!("{}:writable-signer", Pubkey::new_unique()),
+            "--field",
+            "name=THIS!",
+            "--field",
+            "first=one",
+            "--field",
+            "second=two=2",
         ],
     )
     .await
     .unwrap();
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata.name, "THIS!");
+    assert_eq!(
+        fetched_metadata.additional_metadata,
+        [
+            ("first".to_string(), "one".to_string()),
+            ("second".to_string(), "two=2".to_string())
+        ]
+    );
 
-    config.program_client = real_program_client;
-    let _result_with_disable = process_test_command(
+    // the size limit applies to the whole batch
+    let large_value = format!("third={}", "x".repeat(100));
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::SetTransferHook.into(),
+            CommandName::UpdateMetadata.into(),
             &mint.to_string(),
-            "--disable",
+            "--field",
+            "first=",
+            "--field",
+            &large_value,
         ],
     )
     .await
-    .unwrap();
-
+    .unwrap_err();
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = mint_state.get_extension::<TransferHook>().unwrap();
-
-    assert_eq!(extension.program_id, None.try_into().unwrap());
+    let fetched_metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata.additional_metadata.len(), 2);
 }
 
-async fn transfer_hook_with_transfer_fee(test_validator: &TestValidator, payer: &Keypair) {
+async fn group(test_validator: &TestValidator, payer: &Keypair) {
     let program_id = spl_token_2022_interface::id();
-    let mut config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let transfer_hook_program_id = Pubkey::new_unique();
-
-    let transfer_fee_basis_points = 100;
-    let maximum_fee: u64 = 10_000_000_000;
+    let config = test_config_with_default_signer(test_validator, payer, &program_id);
+    let max_size = 10;
 
+    // Create token
     let result = process_test_command(
         &config,
         payer,
@@ -4360,98 +5514,68 @@ async fn transfer_hook_with_transfer_fee(test_validator: &TestValidator, payer:
             CommandName::CreateToken.into(),
             "--program-id",
             &program_id.to_string(),
-            "--transfer-hook",
-            &transfer_hook_program_id.to_string(),
-            "--transfer-fee",
-            &transfer_fee_basis_points.to_string(),
-            &maximum_fee.to_string(),
+            "--enable-group",
         ],
     )
     .await;
 
-    // Check that the transfer-hook extension is correctly configured
     let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
     let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+
+    // Initialize the group
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::InitializeGroup.into(),
+            &mint.to_string(),
+            &max_size.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = mint_state.get_extension::<TransferHook>().unwrap();
-    assert_eq!(
-        extension.program_id,
-        Some(transfer_hook_program_id).try_into().unwrap()
-    );
 
-    // Check that the transfer-fee extension is correctly configured
-    let extension = mint_state.get_extension::<TransferFeeConfig>().unwrap();
-    assert_eq!(
-        u16::from(extension.older_transfer_fee.transfer_fee_basis_points),
-        transfer_fee_basis_points
-    );
-    assert_eq!(
-        u64::from(extension.older_transfer_fee.maximum_fee),
-        maximum_fee
-    );
+    let extension = mint_state.get_extension::<TokenGroup>().unwrap();
     assert_eq!(
-        u16::from(extension.newer_transfer_fee.transfer_fee_basis_points),
-        transfer_fee_basis_points
+        extension.update_authority,
+        Some(payer.pubkey()).try_into().unwrap()
     );
+    assert_eq!(extension.max_size, max_size.into());
+
+    let extension_pointer = mint_state.get_extension::<GroupPointer>().unwrap();
     assert_eq!(
-        u64::from(extension.newer_transfer_fee.maximum_fee),
-        maximum_fee
+        extension_pointer.group_address,
+        Some(mint).try_into().unwrap()
     );
 
-    // Make sure that parsing transfer hook accounts and expected-fee works
-    let blockhash = Hash::default();
-    let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
-        ProgramOfflineClient::new(blockhash, ProgramRpcClientSendTransaction),
-    );
-    config.program_client = program_client;
+    let new_max_size = 12;
 
-    let _result = exec_test_cmd(
+    // Update token-group max-size
+    process_test_command(
         &config,
-        &[
-            "spl-token",
-            CommandName::Transfer.into(),
-            &mint.to_string(),
-            "100",
-            &Pubkey::new_unique().to_string(),
-            "--blockhash",
-            &blockhash.to_string(),
-            "--nonce",
-            &Pubkey::new_unique().to_string(),
-            "--nonce-authority",
-            &Pubkey::new_unique().to_string(),
-            "--sign-only",
-            "--mint-decimals",
-            &format!("{}", TEST_DECIMALS),
-            "--from",
-            &Pubkey::new_unique().to_string(),
-            "--owner",
-            &Pubkey::new_unique().to_string(),
-            "--transfer-hook-account",
-            &format!("{}:readonly", Pubkey::new_unique()),
-            "--transfer-hook-account",
-            &format!("{}:writable", Pubkey::new_unique()),
-            "--transfer-hook-account",
-            &format!("{}:readonly-signer", Pubkey::new_unique()),
-            "--transfer-hook-account",
-            &format!("{}:writable-signer", Pubkey::new_unique()),
-            "--expected-fee",
-            "1",
-            "--program-id",
-            &program_id.to_string(),
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateGroupMaxSize.into(),
+            &mint.to_string(),
+            &new_max_size.to_string(),
         ],
     )
     .await
     .unwrap();
-}
 
-async fn metadata(test_validator: &TestValidator, payer: &Keypair) {
-    let program_id = spl_token_2022_interface::id();
-    let config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let name = "this";
-    let symbol = "is";
-    let uri = "METADATA!";
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+
+    let updated_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+
+    let updated_extension = updated_mint_state.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(updated_extension.max_size, new_max_size.into());
 
+    // Create member token
     let result = process_test_command(
         &config,
         payer,
@@ -4460,132 +5584,107 @@ async fn metadata(test_validator: &TestValidator, payer: &Keypair) {
             CommandName::CreateToken.into(),
             "--program-id",
             &program_id.to_string(),
-            "--enable-metadata",
+            "--enable-member",
         ],
     )
-    .await;
-
-    let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
-    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    .await
+    .unwrap();
 
-    let extension = mint_state.get_extension::<MetadataPointer>().unwrap();
-    assert_eq!(extension.metadata_address, Some(mint).try_into().unwrap());
+    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let member_mint =
+        Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
 
+    // Initialize it as a member of the group
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::InitializeMetadata.into(),
+            CommandName::InitializeMember.into(),
+            &member_mint.to_string(),
             &mint.to_string(),
-            name,
-            symbol,
-            uri,
         ],
     )
     .await
     .unwrap();
 
     let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let fetched_metadata = mint_state
-        .get_variable_len_extension::<TokenMetadata>()
+    let group_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = group_mint_state.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(u64::from(extension.size), 1);
+
+    let account = config.rpc_client.get_account(&member_mint).await.unwrap();
+    let member_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = member_mint_state
+        .get_extension::<TokenGroupMember>()
         .unwrap();
-    assert_eq!(fetched_metadata.name, name);
-    assert_eq!(fetched_metadata.symbol, symbol);
-    assert_eq!(fetched_metadata.uri, uri);
-    assert_eq!(fetched_metadata.mint, mint);
-    assert_eq!(
-        fetched_metadata.update_authority,
-        Some(payer.pubkey()).try_into().unwrap()
-    );
-    assert_eq!(fetched_metadata.additional_metadata, []);
+    assert_eq!(extension.group, mint);
+    assert_eq!(extension.mint, member_mint);
+    assert_eq!(u64::from(extension.member_number), 1);
 
-    // update canonical field
-    let new_value = "THIS!";
+    // Freeze the group membership, members can't leave
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateMetadata.into(),
+            CommandName::FreezeGroupMembership.into(),
             &mint.to_string(),
-            "NAME",
-            new_value,
         ],
     )
     .await
     .unwrap();
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let fetched_metadata = mint_state
-        .get_variable_len_extension::<TokenMetadata>()
-        .unwrap();
-    assert_eq!(fetched_metadata.name, new_value);
 
-    // add new field
-    let field = "My field!";
-    let value = "Try and stop me";
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateMetadata.into(),
+            CommandName::RemoveMember.into(),
+            &member_mint.to_string(),
             &mint.to_string(),
-            field,
-            value,
         ],
     )
     .await
-    .unwrap();
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let fetched_metadata = mint_state
-        .get_variable_len_extension::<TokenMetadata>()
-        .unwrap();
-    assert_eq!(
-        fetched_metadata.additional_metadata,
-        [(field.to_string(), value.to_string())]
-    );
+    .unwrap_err();
 
-    // remove it
+    // Thaw it and remove the member
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateMetadata.into(),
+            CommandName::ThawGroupMembership.into(),
             &mint.to_string(),
-            field,
-            "--remove",
         ],
     )
     .await
     .unwrap();
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let fetched_metadata = mint_state
-        .get_variable_len_extension::<TokenMetadata>()
-        .unwrap();
-    assert_eq!(fetched_metadata.additional_metadata, []);
 
-    // fail to remove name
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateMetadata.into(),
+            CommandName::RemoveMember.into(),
+            &member_mint.to_string(),
             &mint.to_string(),
-            "name",
-            "--remove",
         ],
     )
     .await
-    .unwrap_err();
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&mint).await.unwrap();
+    let group_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = group_mint_state.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(u64::from(extension.size), 0);
+
+    let account = config.rpc_client.get_account(&member_mint).await.unwrap();
+    let member_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = member_mint_state
+        .get_extension::<TokenGroupMember>()
+        .unwrap();
+    assert_eq!(extension.group, Pubkey::default());
 
     // update authority
     process_test_command(
@@ -4595,7 +5694,7 @@ async fn metadata(test_validator: &TestValidator, payer: &Keypair) {
             "spl-token",
             CommandName::Authorize.into(),
             &mint.to_string(),
-            "metadata",
+            "group",
             &mint.to_string(),
         ],
     )
@@ -4604,177 +5703,282 @@ async fn metadata(test_validator: &TestValidator, payer: &Keypair) {
 
     let account = config.rpc_client.get_account(&mint).await.unwrap();
     let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let fetched_metadata = mint_state
-        .get_variable_len_extension::<TokenMetadata>()
-        .unwrap();
+    let extension = mint_state.get_extension::<TokenGroup>().unwrap();
+    assert_eq!(extension.update_authority, Some(mint).try_into().unwrap());
+}
+
+async fn compute_budget(test_validator: &TestValidator, payer: &Keypair) {
+    for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
+        let mut config = test_config_with_default_signer(test_validator, payer, program_id);
+        config.compute_unit_price = Some(42);
+        config.compute_unit_limit = ComputeUnitLimit::Static(40_000);
+        run_transfer_test(&config, payer).await;
+    }
+}
+
+async fn scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
+    let mut config =
+        test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
+
+    // create token with scaled ui amount extension
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token_pubkey = token.pubkey();
+    let ui_multiplier = 1.0;
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--ui-amount-multiplier",
+            &ui_multiplier.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), ui_multiplier);
+
+    config.output_format = OutputFormat::Display;
+    let result = process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Display.into(),
+            &token_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(result.contains("Scaled UI amount:"));
+    assert!(result.contains("Multiplier:"));
+    config.output_format = OutputFormat::JsonCompact;
+
+    // update multiplier
+    let new_ui_multiplier = 5.0;
+    let new_ui_multiplier_timestamp = 0;
+
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateUiAmountMultiplier.into(),
+            &token_pubkey.to_string(),
+            &new_ui_multiplier.to_string(),
+            &new_ui_multiplier_timestamp.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), new_ui_multiplier);
+    assert_eq!(f64::from(extension.new_multiplier), new_ui_multiplier);
     assert_eq!(
-        fetched_metadata.update_authority,
-        Some(mint).try_into().unwrap()
+        i64::from(extension.new_multiplier_effective_timestamp),
+        new_ui_multiplier_timestamp
     );
+
+    // disable authority
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Authorize.into(),
+            &token_pubkey.to_string(),
+            "scaled-ui-amount",
+            "--disable",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(extension.authority), None,);
 }
 
-async fn group(test_validator: &TestValidator, payer: &Keypair) {
-    let program_id = spl_token_2022_interface::id();
-    let config = test_config_with_default_signer(test_validator, payer, &program_id);
-    let max_size = 10;
+async fn exact_scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
+    let mut config =
+        test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
 
-    // Create token
-    let result = process_test_command(
+    // create token with an exact multiplier
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token_pubkey = token.pubkey();
+
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
             CommandName::CreateToken.into(),
-            "--program-id",
-            &program_id.to_string(),
-            "--enable-group",
+            token_keypair_file.path().to_str().unwrap(),
+            "--ui-amount-multiplier",
+            "10/3",
         ],
     )
-    .await;
-
-    let value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
-    let mint = Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+    .await
+    .unwrap();
 
-    // Initialize the group
-    process_test_command(
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint
+        .get_extension::<ScaledUiAmountRationalConfig>()
+        .unwrap();
+    assert_eq!(extension.multiplier, RationalMultiplier::new(10, 3));
+    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), 10.0 / 3.0);
+
+    config.output_format = OutputFormat::Display;
+    let result = process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::InitializeGroup.into(),
-            &mint.to_string(),
-            &max_size.to_string(),
+            CommandName::Display.into(),
+            &token_pubkey.to_string(),
         ],
     )
     .await
     .unwrap();
+    assert!(result.contains("Exact UI amount multiplier:"));
+    assert!(result.contains("10/3"));
+    config.output_format = OutputFormat::JsonCompact;
 
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-
-    let extension = mint_state.get_extension::<TokenGroup>().unwrap();
-    assert_eq!(
-        extension.update_authority,
-        Some(payer.pubkey()).try_into().unwrap()
-    );
-    assert_eq!(extension.max_size, max_size.into());
-
-    let extension_pointer = mint_state.get_extension::<GroupPointer>().unwrap();
-    assert_eq!(
-        extension_pointer.group_address,
-        Some(mint).try_into().unwrap()
-    );
-
-    let new_max_size = 12;
-
-    // Update token-group max-size
+    // decimals are converted exactly on mints with an exact multiplier
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateGroupMaxSize.into(),
-            &mint.to_string(),
-            &new_max_size.to_string(),
+            CommandName::UpdateUiAmountMultiplier.into(),
+            &token_pubkey.to_string(),
+            "1.25",
+            "0",
         ],
     )
     .await
     .unwrap();
 
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-
-    let updated_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-
-    let updated_extension = updated_mint_state.get_extension::<TokenGroup>().unwrap();
-    assert_eq!(updated_extension.max_size, new_max_size.into());
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint
+        .get_extension::<ScaledUiAmountRationalConfig>()
+        .unwrap();
+    assert_eq!(extension.multiplier, RationalMultiplier::new(125, 100));
+    assert_eq!(extension.new_multiplier, RationalMultiplier::new(125, 100));
+    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), 1.25);
 
-    // Create member token
-    let result = process_test_command(
+    // a rational multiplier can't be set on a mint without an exact multiplier
+    let token = create_token(&config, payer).await;
+    process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::CreateToken.into(),
-            "--program-id",
-            &program_id.to_string(),
-            "--enable-member",
+            CommandName::UpdateUiAmountMultiplier.into(),
+            &token.to_string(),
+            "10/3",
         ],
     )
     .await
-    .unwrap();
+    .unwrap_err();
+}
 
-    let value: serde_json::Value = serde_json::from_str(&result).unwrap();
-    let member_mint =
-        Pubkey::from_str(value["commandOutput"]["address"].as_str().unwrap()).unwrap();
+async fn accounts_ui_amounts_at_timestamp(test_validator: &TestValidator, payer: &Keypair) {
+    let config =
+        test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
 
-    // Initialize it as a member of the group
+    let token = Keypair::new();
+    let token_keypair_file = NamedTempFile::new().unwrap();
+    write_keypair_file(&token, &token_keypair_file).unwrap();
+    let token = token.pubkey();
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::InitializeMember.into(),
-            &member_mint.to_string(),
-            &mint.to_string(),
+            CommandName::CreateToken.into(),
+            token_keypair_file.path().to_str().unwrap(),
+            "--ui-amount-multiplier",
+            "2",
         ],
     )
     .await
     .unwrap();
-
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let group_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = group_mint_state.get_extension::<TokenGroup>().unwrap();
-    assert_eq!(u64::from(extension.size), 1);
-
-    let account = config.rpc_client.get_account(&member_mint).await.unwrap();
-    let member_mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = member_mint_state
-        .get_extension::<TokenGroupMember>()
+    let account = create_associated_account(&config, payer, &token, &payer.pubkey()).await;
+    mint_tokens(&config, payer, token, 10.0, account)
+        .await
         .unwrap();
-    assert_eq!(extension.group, mint);
-    assert_eq!(extension.mint, member_mint);
-    assert_eq!(u64::from(extension.member_number), 1);
 
-    // update authority
+    let future_timestamp = 1_000_000_000_000_i64;
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::Authorize.into(),
-            &mint.to_string(),
-            "group",
-            &mint.to_string(),
+            CommandName::UpdateUiAmountMultiplier.into(),
+            &token.to_string(),
+            "3",
+            &future_timestamp.to_string(),
         ],
     )
     .await
     .unwrap();
 
-    let account = config.rpc_client.get_account(&mint).await.unwrap();
-    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = mint_state.get_extension::<TokenGroup>().unwrap();
-    assert_eq!(extension.update_authority, Some(mint).try_into().unwrap());
-}
-
-async fn compute_budget(test_validator: &TestValidator, payer: &Keypair) {
-    for program_id in VALID_TOKEN_PROGRAM_IDS.iter() {
-        let mut config = test_config_with_default_signer(test_validator, payer, program_id);
-        config.compute_unit_price = Some(42);
-        config.compute_unit_limit = ComputeUnitLimit::Static(40_000);
-        run_transfer_test(&config, payer).await;
+    let token_string = token.to_string();
+    let timestamp_string = future_timestamp.to_string();
+    for (extra_args, multiplier) in [
+        (vec![], 2.0),
+        (vec!["--timestamp", timestamp_string.as_str()], 3.0),
+    ] {
+        let mut args = vec!["spl-token", CommandName::Accounts.into(), &token_string];
+        args.extend(extra_args);
+        let result = process_test_command(&config, payer, &args).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let token_amount = &value["accounts"][0]["tokenAmount"];
+        let amount = token_amount["amount"]
+            .as_str()
+            .unwrap()
+            .parse::<u64>()
+            .unwrap();
+        let decimals = token_amount["decimals"].as_u64().unwrap();
+        let ui_amount = token_amount["uiAmountString"]
+            .as_str()
+            .unwrap()
+            .parse::<f64>()
+            .unwrap();
+        assert_eq!(
+            ui_amount,
+            amount as f64 * multiplier / 10f64.powi(decimals as i32)
+        );
     }
 }
 
-async fn scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
+async fn scaled_ui_amount_schedule(test_validator: &TestValidator, payer: &Keypair) {
     let mut config =
         test_config_with_default_signer(test_validator, payer, &spl_token_2022_interface::id());
 
-    // create token with scaled ui amount extension
     let token = Keypair::new();
     let token_keypair_file = NamedTempFile::new().unwrap();
     write_keypair_file(&token, &token_keypair_file).unwrap();
     let token_pubkey = token.pubkey();
-    let ui_multiplier = 1.0;
 
     process_test_command(
         &config,
@@ -4784,7 +5988,38 @@ async fn scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
             CommandName::CreateToken.into(),
             token_keypair_file.path().to_str().unwrap(),
             "--ui-amount-multiplier",
-            &ui_multiplier.to_string(),
+            "1",
+        ],
+    )
+    .await
+    .unwrap();
+
+    // queue a split, then a dividend adjustment, which no longer needs the flag
+    let split_timestamp = 1_000_000_000_000_i64;
+    let dividend_timestamp = split_timestamp + 86_400;
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateUiAmountMultiplier.into(),
+            &token_pubkey.to_string(),
+            "2",
+            &split_timestamp.to_string(),
+            "--schedule",
+        ],
+    )
+    .await
+    .unwrap();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateUiAmountMultiplier.into(),
+            &token_pubkey.to_string(),
+            "2.1",
+            &dividend_timestamp.to_string(),
         ],
     )
     .await
@@ -4792,8 +6027,15 @@ async fn scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
 
     let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
     let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
-    assert_eq!(f64::from(extension.multiplier), ui_multiplier);
+    let schedule = test_mint
+        .get_variable_len_extension::<ScaledUiAmountSchedule>()
+        .unwrap();
+    let timestamps = schedule
+        .entries
+        .iter()
+        .map(|entry| i64::from(entry.effective_timestamp))
+        .collect::<Vec<_>>();
+    assert_eq!(timestamps, vec![0, split_timestamp, dividend_timestamp]);
 
     config.output_format = OutputFormat::Display;
     let result = process_test_command(
@@ -4807,23 +6049,18 @@ async fn scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
     )
     .await
     .unwrap();
-    assert!(result.contains("Scaled UI amount:"));
-    assert!(result.contains("Multiplier:"));
+    assert!(result.contains("UI amount multiplier schedule:"));
+    assert!(result.contains(&dividend_timestamp.to_string()));
     config.output_format = OutputFormat::JsonCompact;
 
-    // update multiplier
-    let new_ui_multiplier = 5.0;
-    let new_ui_multiplier_timestamp = 0;
-
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::UpdateUiAmountMultiplier.into(),
+            CommandName::CancelUiAmountMultiplier.into(),
             &token_pubkey.to_string(),
-            &new_ui_multiplier.to_string(),
-            &new_ui_multiplier_timestamp.to_string(),
+            &dividend_timestamp.to_string(),
         ],
     )
     .await
@@ -4831,33 +6068,24 @@ async fn scaled_ui_amount(test_validator: &TestValidator, payer: &Keypair) {
 
     let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
     let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
-    assert_eq!(f64::from(extension.multiplier), new_ui_multiplier);
-    assert_eq!(f64::from(extension.new_multiplier), new_ui_multiplier);
-    assert_eq!(
-        i64::from(extension.new_multiplier_effective_timestamp),
-        new_ui_multiplier_timestamp
-    );
+    let schedule = test_mint
+        .get_variable_len_extension::<ScaledUiAmountSchedule>()
+        .unwrap();
+    assert_eq!(schedule.entries.len(), 2);
 
-    // disable authority
+    // nothing is queued at that time anymore
     process_test_command(
         &config,
         payer,
         &[
             "spl-token",
-            CommandName::Authorize.into(),
+            CommandName::CancelUiAmountMultiplier.into(),
             &token_pubkey.to_string(),
-            "scaled-ui-amount",
-            "--disable",
+            &dividend_timestamp.to_string(),
         ],
     )
     .await
-    .unwrap();
-
-    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
-    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
-    let extension = test_mint.get_extension::<ScaledUiAmountConfig>().unwrap();
-    assert_eq!(Option::<Pubkey>::from(extension.authority), None,);
+    .unwrap_err();
 }
 
 async fn pause(test_validator: &TestValidator, payer: &Keypair) {
@@ -4939,6 +6167,69 @@ async fn pause(test_validator: &TestValidator, payer: &Keypair) {
     let extension = test_mint.get_extension::<PausableConfig>().unwrap();
     assert!(!bool::from(extension.paused));
 
+    // pause only transfers and burns
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Pause.into(),
+            &token_pubkey.to_string(),
+            "--operations",
+            "transfer,burn",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<PausableConfig>().unwrap();
+    assert!(!bool::from(extension.paused));
+    let extension = test_mint.get_extension::<PausedOperations>().unwrap();
+    assert_eq!(
+        extension.operations,
+        PausedOperations::TRANSFER | PausedOperations::BURN
+    );
+
+    // resume burns
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Resume.into(),
+            &token_pubkey.to_string(),
+            "--operations",
+            "burn",
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<PausedOperations>().unwrap();
+    assert_eq!(extension.operations, PausedOperations::TRANSFER);
+
+    // resume everything
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::Resume.into(),
+            &token_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<PausedOperations>().unwrap();
+    assert_eq!(extension.operations, 0);
+
     // disable pause
     process_test_command(
         &config,
@@ -5228,4 +6519,38 @@ async fn confidential_mint_burn(test_validator: &TestValidator, payer: &Keypair)
     )
     .await
     .unwrap();
+
+    // Resynchronize the decryptable supply with the encrypted supply
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::UpdateConfidentialDecryptableSupply.into(),
+            &token_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Rotate the supply encryption key
+    let new_supply_elgamal_keypair = ElGamalKeypair::new_rand();
+    let new_supply_elgamal_pubkey: PodElGamalPubkey = (*new_supply_elgamal_keypair.pubkey()).into();
+    process_test_command(
+        &config,
+        payer,
+        &[
+            "spl-token",
+            CommandName::RotateConfidentialSupplyElgamalPubkey.into(),
+            &token_pubkey.to_string(),
+            &new_supply_elgamal_pubkey.to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+    let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+    let extension = test_mint.get_extension::<ConfidentialMintBurn>().unwrap();
+    assert_eq!(extension.supply_elgamal_pubkey, new_supply_elgamal_pubkey);
 }
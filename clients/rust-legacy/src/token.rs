@@ -52,6 +52,9 @@ use {
             create_associated_token_account, create_associated_token_account_idempotent,
         },
     },
+    spl_elgamal_registry::state::{
+        ElGamalRegistry, LabelledElGamalPubkey, ELGAMAL_REGISTRY_ACCOUNT_LEN,
+    },
     spl_record::state::RecordData,
     spl_token_2022::offchain,
     spl_token_2022_interface::{
@@ -62,13 +65,26 @@ use {
             confidential_transfer_fee::{
                 self, ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
             },
-            cpi_guard, default_account_state, group_member_pointer, group_pointer,
-            interest_bearing_mint, memo_transfer, metadata_pointer, pausable, permissioned_burn,
-            scaled_ui_amount, transfer_fee, transfer_hook, BaseStateWithExtensions, Extension,
-            ExtensionType, StateWithExtensionsOwned,
+            cpi_guard::{self, CpiGuardPolicy},
+            default_account_state::{self, DefaultAccountStateAttestor},
+            group_member_pointer, group_pointer,
+            interest_bearing_mint::{self, CompoundingPeriod},
+            memo_transfer::{self, MemoTransferPolicy},
+            metadata_pointer, non_transferable, partial_freeze,
+            pausable::{self, PausableSchedule, PausedOperations},
+            permissioned_burn,
+            scaled_ui_amount::{
+                self, ScaledUiAmountConfig, ScaledUiAmountSchedule, ScheduledMultiplier,
+            },
+            token_group::{self, TokenGroupMemberCounter, TokenGroupMembershipFreeze},
+            token_metadata::{self, instruction::FieldUpdate},
+            transfer_fee::{self, TransferFeeTier},
+            transfer_hook, BaseStateWithExtensions, Extension, ExtensionType,
+            StateWithExtensionsOwned,
         },
         instruction,
         state::{Account, AccountState, Mint, Multisig},
+        ui_amount::UiAmountConverter,
     },
     spl_token_confidential_transfer_proof_extraction::instruction::{
         zk_proof_type_to_instruction, ProofLocation,
@@ -210,6 +226,33 @@ pub enum ExtensionInitializationParams {
     PermissionedBurnConfig {
         authority: Address,
     },
+    NonTransferableRecovery {
+        authority: Address,
+    },
+    PartialFreezeConfig {
+        default_restrictions: u8,
+        default_reason: u16,
+    },
+    TieredTransferFeeConfig {
+        maximum_fee: u64,
+        minimum_fee: u64,
+        tiers: Vec<TransferFeeTier>,
+    },
+    TransferFeeCollector {
+        collector: Option<Address>,
+    },
+    ScaledUiAmountRational {
+        numerator: u64,
+        denominator: u64,
+    },
+    InterestBearingTerms {
+        compounding_period: CompoundingPeriod,
+        minimum_rate: Option<i16>,
+        maximum_rate: Option<i16>,
+    },
+    TokenMetadataSizeLimit {
+        max_len: u32,
+    },
     ConfidentialMintBurn {
         supply_elgamal_pubkey: PodElGamalPubkey,
         decryptable_supply: PodAeCiphertext,
@@ -236,6 +279,13 @@ impl ExtensionInitializationParams {
             Self::ScaledUiAmountConfig { .. } => ExtensionType::ScaledUiAmount,
             Self::PausableConfig { .. } => ExtensionType::Pausable,
             Self::PermissionedBurnConfig { .. } => ExtensionType::PermissionedBurn,
+            Self::NonTransferableRecovery { .. } => ExtensionType::NonTransferableRecovery,
+            Self::PartialFreezeConfig { .. } => ExtensionType::PartialFreeze,
+            Self::TieredTransferFeeConfig { .. } => ExtensionType::TieredTransferFeeConfig,
+            Self::TransferFeeCollector { .. } => ExtensionType::TransferFeeCollector,
+            Self::ScaledUiAmountRational { .. } => ExtensionType::ScaledUiAmountRational,
+            Self::InterestBearingTerms { .. } => ExtensionType::InterestBearingTerms,
+            Self::TokenMetadataSizeLimit { .. } => ExtensionType::TokenMetadataSizeLimit,
             Self::ConfidentialMintBurn { .. } => ExtensionType::ConfidentialMintBurn,
         }
     }
@@ -361,6 +411,63 @@ impl ExtensionInitializationParams {
             Self::PermissionedBurnConfig { authority } => {
                 permissioned_burn::instruction::initialize(token_program_id, mint, &authority)
             }
+            Self::NonTransferableRecovery { authority } => {
+                non_transferable::instruction::initialize_recovery_authority(
+                    token_program_id,
+                    mint,
+                    &authority,
+                )
+            }
+            Self::PartialFreezeConfig {
+                default_restrictions,
+                default_reason,
+            } => partial_freeze::instruction::initialize(
+                token_program_id,
+                mint,
+                default_restrictions,
+                default_reason,
+            ),
+            Self::TieredTransferFeeConfig {
+                maximum_fee,
+                minimum_fee,
+                tiers,
+            } => transfer_fee::instruction::initialize_tiered_transfer_fee_config(
+                token_program_id,
+                mint,
+                maximum_fee,
+                minimum_fee,
+                &tiers,
+            ),
+            Self::TransferFeeCollector { collector } => {
+                transfer_fee::instruction::initialize_transfer_fee_collector(
+                    token_program_id,
+                    mint,
+                    collector.as_ref(),
+                )
+            }
+            Self::ScaledUiAmountRational {
+                numerator,
+                denominator,
+            } => scaled_ui_amount::instruction::initialize_rational(
+                token_program_id,
+                mint,
+                numerator,
+                denominator,
+            ),
+            Self::InterestBearingTerms {
+                compounding_period,
+                minimum_rate,
+                maximum_rate,
+            } => interest_bearing_mint::instruction::initialize_terms(
+                token_program_id,
+                mint,
+                compounding_period,
+                minimum_rate,
+                maximum_rate,
+            ),
+            Self::TokenMetadataSizeLimit { max_len } => {
+                token_metadata::instruction::initialize_size_limit(token_program_id, mint, max_len)
+            }
             Self::ConfidentialMintBurn {
                 supply_elgamal_pubkey,
                 decryptable_supply,
@@ -754,6 +861,15 @@ where
             .map_err(TokenError::Client)
     }
 
+    /// Start collecting token instructions to send as a single `Batch`
+    /// instruction
+    pub fn batch(&self) -> TokenBatch<'_, T> {
+        TokenBatch {
+            token: self,
+            instructions: vec![],
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_mint<'a, S: Signers>(
         &self,
@@ -845,6 +961,74 @@ where
         self.process_ixs(&instructions, &[account]).await
     }
 
+    /// Create the ElGamal registry of `owner`, holding the public key of
+    /// `elgamal_keypair`
+    pub async fn create_elgamal_registry<S: Signers>(
+        &self,
+        owner: &Address,
+        elgamal_keypair: &ElGamalKeypair,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let elgamal_registry_address = self.get_elgamal_registry_address(owner);
+        let proof_data = build_pubkey_validity_proof_data(elgamal_keypair)
+            .map_err(|_| TokenError::ProofGeneration)?;
+        let proof_location = ProofLocation::InstructionOffset(1.try_into().unwrap(), &proof_data);
+
+        let mut instructions = vec![system_instruction::transfer(
+            &self.payer.pubkey(),
+            &elgamal_registry_address,
+            self.client
+                .get_minimum_balance_for_rent_exemption(ELGAMAL_REGISTRY_ACCOUNT_LEN)
+                .await
+                .map_err(TokenError::Client)?,
+        )];
+        instructions.extend(spl_elgamal_registry::instruction::create_registry(
+            owner,
+            proof_location,
+        )?);
+
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Replace the ElGamal public key in the registry of `owner` with the
+    /// public key of `elgamal_keypair`
+    pub async fn update_elgamal_registry<S: Signers>(
+        &self,
+        owner: &Address,
+        elgamal_keypair: &ElGamalKeypair,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let proof_data = build_pubkey_validity_proof_data(elgamal_keypair)
+            .map_err(|_| TokenError::ProofGeneration)?;
+        let proof_location = ProofLocation::InstructionOffset(1.try_into().unwrap(), &proof_data);
+
+        self.process_ixs(
+            &spl_elgamal_registry::instruction::update_registry(owner, proof_location)?,
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Get the address of the ElGamal registry of `owner`.
+    pub fn get_elgamal_registry_address(&self, owner: &Address) -> Address {
+        spl_elgamal_registry::get_elgamal_registry_address(owner, &spl_elgamal_registry::id())
+    }
+
+    /// Get the ElGamal registry of `owner` along with its labelled keys
+    pub async fn get_elgamal_registry_info(
+        &self,
+        owner: &Address,
+    ) -> TokenResult<(ElGamalRegistry, Vec<LabelledElGamalPubkey>)> {
+        let account = self
+            .get_account(self.get_elgamal_registry_address(owner))
+            .await?;
+        if account.owner != spl_elgamal_registry::id() {
+            return Err(TokenError::AccountInvalidOwner);
+        }
+        let (registry, labelled_keys) = ElGamalRegistry::unpack(&account.data)?;
+        Ok((*registry, labelled_keys.to_vec()))
+    }
+
     /// Get the address for the associated token account.
     pub fn get_associated_token_address(&self, owner: &Address) -> Address {
         get_associated_token_address_with_program_id(owner, &self.pubkey, &self.program_id)
@@ -880,6 +1064,35 @@ where
         account: &dyn Signer,
         owner: &Address,
         extensions: Vec<ExtensionType>,
+    ) -> TokenResult<T::Output> {
+        self.create_auxiliary_token_account_with_attestor(account, owner, extensions, None)
+            .await
+    }
+
+    /// Create and initialize a new token account, co-signed by the attestor
+    /// registered on the mint so that it skips the default account state.
+    pub async fn create_attested_auxiliary_token_account(
+        &self,
+        account: &dyn Signer,
+        owner: &Address,
+        extensions: Vec<ExtensionType>,
+        attestor: &dyn Signer,
+    ) -> TokenResult<T::Output> {
+        self.create_auxiliary_token_account_with_attestor(
+            account,
+            owner,
+            extensions,
+            Some(attestor),
+        )
+        .await
+    }
+
+    async fn create_auxiliary_token_account_with_attestor(
+        &self,
+        account: &dyn Signer,
+        owner: &Address,
+        extensions: Vec<ExtensionType>,
+        attestor: Option<&dyn Signer>,
     ) -> TokenResult<T::Output> {
         let state = self.get_mint_info().await?;
         let mut required_extensions = Vec::new();
@@ -911,14 +1124,24 @@ where
             )?)
         }
 
-        instructions.push(instruction::initialize_account(
+        let mut initialize_account_instruction = instruction::initialize_account(
             &self.program_id,
             &account.pubkey(),
             &self.pubkey,
             owner,
-        )?);
+        )?;
+        if let Some(attestor) = attestor {
+            initialize_account_instruction
+                .accounts
+                .push(AccountMeta::new_readonly(attestor.pubkey(), true));
+        }
+        instructions.push(initialize_account_instruction);
 
-        self.process_ixs(&instructions, &[account]).await
+        if let Some(attestor) = attestor {
+            self.process_ixs(&instructions, &[account, attestor]).await
+        } else {
+            self.process_ixs(&instructions, &[account]).await
+        }
     }
 
     /// Retrieve a raw account
@@ -956,6 +1179,13 @@ where
         self.unpack_mint_info(account)
     }
 
+    /// Retrieve a converter between raw and UI amounts of the mint, at any
+    /// timestamp.
+    pub async fn get_ui_amount_converter(&self) -> TokenResult<UiAmountConverter> {
+        let mint = self.get_mint_info().await?;
+        Ok(UiAmountConverter::new(mint.base.decimals, &mint)?)
+    }
+
     /// Retrieve account information.
     pub async fn get_account_info(
         &self,
@@ -1025,10 +1255,23 @@ where
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
-        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+        let instruction =
+            self.mint_to_instruction(destination, authority, amount, &signing_pubkeys)?;
 
-        let instructions = if let Some(decimals) = self.decimals {
-            [instruction::mint_to_checked(
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    fn mint_to_instruction(
+        &self,
+        destination: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<Instruction> {
+        let multisig_signers = self.get_multisig_signers(authority, signing_pubkeys);
+
+        Ok(if let Some(decimals) = self.decimals {
+            instruction::mint_to_checked(
                 &self.program_id,
                 &self.pubkey,
                 destination,
@@ -1036,19 +1279,17 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?]
+            )?
         } else {
-            [instruction::mint_to(
+            instruction::mint_to(
                 &self.program_id,
                 &self.pubkey,
                 destination,
                 authority,
                 &multisig_signers,
                 amount,
-            )?]
-        };
-
-        self.process_ixs(&instructions, signing_keypairs).await
+            )?
+        })
     }
 
     /// Transfer tokens to another account
@@ -1062,7 +1303,22 @@ where
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
-        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+        let instruction = self
+            .transfer_instruction(source, destination, authority, amount, &signing_pubkeys)
+            .await?;
+
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    async fn transfer_instruction(
+        &self,
+        source: &Address,
+        destination: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<Instruction> {
+        let multisig_signers = self.get_multisig_signers(authority, signing_pubkeys);
 
         let fetch_account_data_fn = |address| {
             self.client
@@ -1070,7 +1326,7 @@ where
                 .map_ok(|opt| opt.map(|acc| acc.data))
         };
 
-        let instruction = if let Some(decimals) = self.decimals {
+        Ok(if let Some(decimals) = self.decimals {
             if let Some(transfer_hook_accounts) = &self.transfer_hook_accounts {
                 let mut instruction = instruction::transfer_checked(
                     &self.program_id,
@@ -1109,9 +1365,7 @@ where
                 &multisig_signers,
                 amount,
             )?
-        };
-
-        self.process_ixs(&[instruction], signing_keypairs).await
+        })
     }
 
     /// Transfer tokens to an associated account, creating it if it does not
@@ -1220,7 +1474,30 @@ where
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
-        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+        let instruction = self
+            .transfer_with_fee_instruction(
+                source,
+                destination,
+                authority,
+                amount,
+                fee,
+                &signing_pubkeys,
+            )
+            .await?;
+
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    async fn transfer_with_fee_instruction(
+        &self,
+        source: &Address,
+        destination: &Address,
+        authority: &Address,
+        amount: u64,
+        fee: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<Instruction> {
+        let multisig_signers = self.get_multisig_signers(authority, signing_pubkeys);
         let decimals = self.decimals.ok_or(TokenError::MissingDecimals)?;
 
         let fetch_account_data_fn = |address| {
@@ -1229,36 +1506,83 @@ where
                 .map_ok(|opt| opt.map(|acc| acc.data))
         };
 
-        let instruction = if let Some(transfer_hook_accounts) = &self.transfer_hook_accounts {
-            let mut instruction = transfer_fee::instruction::transfer_checked_with_fee(
-                &self.program_id,
-                source,
-                self.get_address(),
-                destination,
-                authority,
-                &multisig_signers,
-                amount,
-                decimals,
-                fee,
-            )?;
+        Ok(
+            if let Some(transfer_hook_accounts) = &self.transfer_hook_accounts {
+                let mut instruction = transfer_fee::instruction::transfer_checked_with_fee(
+                    &self.program_id,
+                    source,
+                    self.get_address(),
+                    destination,
+                    authority,
+                    &multisig_signers,
+                    amount,
+                    decimals,
+                    fee,
+                )?;
+                instruction.accounts.extend(transfer_hook_accounts.clone());
+                instruction
+            } else {
+                offchain::create_transfer_checked_with_fee_instruction_with_extra_metas(
+                    &self.program_id,
+                    source,
+                    self.get_address(),
+                    destination,
+                    authority,
+                    &multisig_signers,
+                    amount,
+                    decimals,
+                    fee,
+                    fetch_account_data_fn,
+                )
+                .await
+                .map_err(|_| TokenError::AccountNotFound)?
+            },
+        )
+    }
+
+    /// Move non-transferable tokens to another account as the mint's recovery
+    /// authority
+    pub async fn recovery_transfer<S: Signers>(
+        &self,
+        source: &Address,
+        destination: &Address,
+        recovery_authority: &Address,
+        amount: u64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(recovery_authority, &signing_pubkeys);
+        let decimals = self.decimals.ok_or(TokenError::MissingDecimals)?;
+
+        let mut instruction = non_transferable::instruction::recovery_transfer(
+            &self.program_id,
+            source,
+            self.get_address(),
+            destination,
+            recovery_authority,
+            &multisig_signers,
+            amount,
+            decimals,
+        )?;
+        if let Some(transfer_hook_accounts) = &self.transfer_hook_accounts {
             instruction.accounts.extend(transfer_hook_accounts.clone());
-            instruction
         } else {
-            offchain::create_transfer_checked_with_fee_instruction_with_extra_metas(
-                &self.program_id,
+            offchain::add_extra_account_metas(
+                &mut instruction,
                 source,
                 self.get_address(),
                 destination,
-                authority,
-                &multisig_signers,
+                recovery_authority,
                 amount,
-                decimals,
-                fee,
-                fetch_account_data_fn,
+                |address| {
+                    self.client
+                        .get_account(address)
+                        .map_ok(|opt| opt.map(|acc| acc.data))
+                },
             )
             .await
-            .map_err(|_| TokenError::AccountNotFound)?
-        };
+            .map_err(|_| TokenError::AccountNotFound)?;
+        }
 
         self.process_ixs(&[instruction], signing_keypairs).await
     }
@@ -1272,10 +1596,22 @@ where
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
-        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+        let instruction = self.burn_instruction(source, authority, amount, &signing_pubkeys)?;
 
-        let instructions = if let Some(decimals) = self.decimals {
-            [instruction::burn_checked(
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    fn burn_instruction(
+        &self,
+        source: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<Instruction> {
+        let multisig_signers = self.get_multisig_signers(authority, signing_pubkeys);
+
+        Ok(if let Some(decimals) = self.decimals {
+            instruction::burn_checked(
                 &self.program_id,
                 source,
                 &self.pubkey,
@@ -1283,19 +1619,17 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?]
+            )?
         } else {
-            [instruction::burn(
+            instruction::burn(
                 &self.program_id,
                 source,
                 &self.pubkey,
                 authority,
                 &multisig_signers,
                 amount,
-            )?]
-        };
-
-        self.process_ixs(&instructions, signing_keypairs).await
+            )?
+        })
     }
 
     /// Burn tokens from account with permissioned burn authority
@@ -1346,10 +1680,24 @@ where
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
-        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+        let instruction =
+            self.approve_instruction(source, delegate, authority, amount, &signing_pubkeys)?;
 
-        let instructions = if let Some(decimals) = self.decimals {
-            [instruction::approve_checked(
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    fn approve_instruction(
+        &self,
+        source: &Address,
+        delegate: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<Instruction> {
+        let multisig_signers = self.get_multisig_signers(authority, signing_pubkeys);
+
+        Ok(if let Some(decimals) = self.decimals {
+            instruction::approve_checked(
                 &self.program_id,
                 source,
                 &self.pubkey,
@@ -1358,19 +1706,17 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?]
+            )?
         } else {
-            [instruction::approve(
+            instruction::approve(
                 &self.program_id,
                 source,
                 delegate,
                 authority,
                 &multisig_signers,
                 amount,
-            )?]
-        };
-
-        self.process_ixs(&instructions, signing_keypairs).await
+            )?
+        })
     }
 
     /// Revoke a delegate
@@ -1431,21 +1777,60 @@ where
         self.process_ixs(&instructions, signing_keypairs).await
     }
 
-    /// Close an account, reclaiming its lamports and tokens
-    pub async fn empty_and_close_account<S: Signers>(
+    /// Close this mint while it's a member of a token-group, which removes it
+    /// from the group. Includes a transfer for any additional rent-exempt SOL
+    /// required by the group mint.
+    pub async fn close_group_member_mint_with_rent_transfer<S: Signers>(
         &self,
-        account_to_close: &Address,
+        payer: &Address,
+        group_mint: &Address,
         lamports_destination: &Address,
-        tokens_destination: &Address,
         authority: &Address,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
-        // this implicitly validates that the mint on self is correct
-        let account_state = self.get_account_info(account_to_close).await?;
-
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension_on::<TokenGroupMemberCounter>(group_mint)
+            .await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                group_mint,
+                additional_lamports,
+            ));
+        }
+        let mut close_instruction = instruction::close_account(
+            &self.program_id,
+            &self.pubkey,
+            lamports_destination,
+            authority,
+            &multisig_signers,
+        )?;
+        close_instruction
+            .accounts
+            .push(AccountMeta::new(*group_mint, false));
+        instructions.push(close_instruction);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Close an account, reclaiming its lamports and tokens
+    pub async fn empty_and_close_account<S: Signers>(
+        &self,
+        account_to_close: &Address,
+        lamports_destination: &Address,
+        tokens_destination: &Address,
+        authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        // this implicitly validates that the mint on self is correct
+        let account_state = self.get_account_info(account_to_close).await?;
+
         let mut instructions = vec![];
 
         if !self.is_native() && account_state.base.amount > 0 {
@@ -1545,6 +1930,59 @@ where
         .await
     }
 
+    /// Freeze only incoming or outgoing transfers on a token account, with an
+    /// optional reason code
+    pub async fn set_account_restrictions<S: Signers>(
+        &self,
+        account: &Address,
+        authority: &Address,
+        restrictions: u8,
+        reason: u16,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[partial_freeze::instruction::set_account_restrictions(
+                &self.program_id,
+                account,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                restrictions,
+                reason,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update the restrictions and reason code applied to new token accounts
+    pub async fn set_default_account_restrictions<S: Signers>(
+        &self,
+        authority: &Address,
+        restrictions: u8,
+        reason: u16,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[partial_freeze::instruction::update_default_restrictions(
+                &self.program_id,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                restrictions,
+                reason,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Wrap lamports into native account
     pub async fn wrap<S: Signers>(
         &self,
@@ -1688,6 +2126,84 @@ where
         .await
     }
 
+    /// Set a tiered transfer fee schedule, on a mint with the
+    /// `TieredTransferFeeConfig` extension
+    pub async fn set_tiered_transfer_fee<S: Signers>(
+        &self,
+        authority: &Address,
+        maximum_fee: u64,
+        minimum_fee: u64,
+        tiers: &[TransferFeeTier],
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_fee::instruction::set_tiered_transfer_fee(
+                &self.program_id,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                maximum_fee,
+                minimum_fee,
+                tiers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Set the token account receiving transfer fees, or stop routing fees
+    /// to a collector
+    pub async fn set_transfer_fee_collector<S: Signers>(
+        &self,
+        authority: &Address,
+        collector: Option<&Address>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_fee::instruction::set_transfer_fee_collector(
+                &self.program_id,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                collector,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Exempt an account from transfer fees, or remove its exemption
+    pub async fn set_transfer_fee_exemption<S: Signers>(
+        &self,
+        account: &Address,
+        authority: &Address,
+        exempt: bool,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_fee::instruction::set_transfer_fee_exemption(
+                &self.program_id,
+                account,
+                &self.pubkey,
+                &self.payer.pubkey(),
+                authority,
+                &multisig_signers,
+                exempt,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Set default account state on mint
     pub async fn set_default_account_state<S: Signers>(
         &self,
@@ -1713,6 +2229,42 @@ where
         .await
     }
 
+    /// Register or clear the attestor that can approve new accounts skipping
+    /// the default account state, transferring lamports to the mint to cover
+    /// the additional rent if needed
+    pub async fn set_default_account_state_attestor_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        authority: &Address,
+        attestor: Option<&Address>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension::<DefaultAccountStateAttestor>()
+            .await?;
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(
+            default_account_state::instruction::set_default_account_state_attestor(
+                &self.program_id,
+                &self.pubkey,
+                authority,
+                &multisig_signers,
+                attestor,
+            )?,
+        );
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
     /// Harvest withheld tokens to mint
     pub async fn harvest_withheld_tokens_to_mint(
         &self,
@@ -1851,9 +2403,10 @@ where
         .await
     }
 
-    /// Pause transferring, minting, and burning on the mint
-    pub async fn pause<S: Signers>(
+    /// Require memos on transfers out of this account
+    pub async fn enable_required_outgoing_transfer_memos<S: Signers>(
         &self,
+        account: &Address,
         authority: &Address,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
@@ -1861,20 +2414,23 @@ where
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[pausable::instruction::pause(
-                &self.program_id,
-                self.get_address(),
-                authority,
-                &multisig_signers,
-            )?],
+            &[
+                memo_transfer::instruction::enable_required_outgoing_transfer_memos(
+                    &self.program_id,
+                    account,
+                    authority,
+                    &multisig_signers,
+                )?,
+            ],
             signing_keypairs,
         )
         .await
     }
 
-    /// Resume transferring, minting, and burning on the mint
-    pub async fn resume<S: Signers>(
+    /// Stop requiring memos on transfers out of this account
+    pub async fn disable_required_outgoing_transfer_memos<S: Signers>(
         &self,
+        account: &Address,
         authority: &Address,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
@@ -1882,43 +2438,46 @@ where
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[pausable::instruction::resume(
-                &self.program_id,
-                self.get_address(),
-                authority,
-                &multisig_signers,
-            )?],
+            &[
+                memo_transfer::instruction::disable_required_outgoing_transfer_memos(
+                    &self.program_id,
+                    account,
+                    authority,
+                    &multisig_signers,
+                )?,
+            ],
             signing_keypairs,
         )
         .await
     }
 
-    /// Prevent unsafe usage of token account through CPI
-    pub async fn enable_cpi_guard<S: Signers>(
+    /// Set the policy on the content of memos for transfers into this account
+    pub async fn set_memo_transfer_policy<S: Signers>(
         &self,
         account: &Address,
         authority: &Address,
+        policy: &MemoTransferPolicy,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[cpi_guard::instruction::enable_cpi_guard(
+            &[memo_transfer::instruction::set_memo_transfer_policy(
                 &self.program_id,
                 account,
                 authority,
                 &multisig_signers,
+                policy,
             )?],
             signing_keypairs,
         )
         .await
     }
 
-    /// Stop preventing unsafe usage of token account through CPI
-    pub async fn disable_cpi_guard<S: Signers>(
+    /// Pause transferring, minting, and burning on the mint
+    pub async fn pause<S: Signers>(
         &self,
-        account: &Address,
         authority: &Address,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
@@ -1926,9 +2485,9 @@ where
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[cpi_guard::instruction::disable_cpi_guard(
+            &[pausable::instruction::pause(
                 &self.program_id,
-                account,
+                self.get_address(),
                 authority,
                 &multisig_signers,
             )?],
@@ -1937,124 +2496,493 @@ where
         .await
     }
 
-    /// Update interest rate
-    pub async fn update_interest_rate<S: Signers>(
+    /// Resume transferring, minting, and burning on the mint, including any
+    /// individually paused operations and scheduled pause window
+    pub async fn resume<S: Signers>(
         &self,
         authority: &Address,
-        new_rate: i16,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[interest_bearing_mint::instruction::update_rate(
+            &[pausable::instruction::resume(
                 &self.program_id,
                 self.get_address(),
                 authority,
                 &multisig_signers,
-                new_rate,
             )?],
             signing_keypairs,
         )
         .await
     }
 
-    /// Update multiplier
-    pub async fn update_multiplier<S: Signers>(
+    /// Pause only the given operations on the mint, as a bitmask of
+    /// `PausedOperations` flags
+    pub async fn pause_operations<S: Signers>(
         &self,
         authority: &Address,
-        new_multiplier: f64,
-        new_multiplier_effective_timestamp: i64,
+        operations: u8,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[scaled_ui_amount::instruction::update_multiplier(
+            &[pausable::instruction::pause_operations(
                 &self.program_id,
                 self.get_address(),
                 authority,
                 &multisig_signers,
-                new_multiplier,
-                new_multiplier_effective_timestamp,
+                operations,
             )?],
             signing_keypairs,
         )
         .await
     }
 
-    /// Update transfer hook program id
-    pub async fn update_transfer_hook_program_id<S: Signers>(
+    /// Pause only the given operations on the mint, transferring any lamports
+    /// required to store the paused operations from the payer
+    pub async fn pause_operations_with_rent_transfer<S: Signers>(
         &self,
+        payer: &Address,
         authority: &Address,
-        new_program_id: Option<Address>,
+        operations: u8,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension::<PausedOperations>()
+            .await?;
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
-        self.process_ixs(
-            &[transfer_hook::instruction::update(
-                &self.program_id,
-                self.get_address(),
-                authority,
-                &multisig_signers,
-                new_program_id,
-            )?],
-            signing_keypairs,
-        )
-        .await
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(pausable::instruction::pause_operations(
+            &self.program_id,
+            self.get_address(),
+            authority,
+            &multisig_signers,
+            operations,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
     }
 
-    /// Update metadata pointer address
-    pub async fn update_metadata_address<S: Signers>(
+    /// Resume the given operations on the mint, as a bitmask of
+    /// `PausedOperations` flags
+    pub async fn resume_operations<S: Signers>(
         &self,
         authority: &Address,
-        new_metadata_address: Option<Address>,
+        operations: u8,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[metadata_pointer::instruction::update(
+            &[pausable::instruction::resume_operations(
                 &self.program_id,
                 self.get_address(),
                 authority,
                 &multisig_signers,
-                new_metadata_address,
+                operations,
             )?],
             signing_keypairs,
         )
         .await
     }
 
-    /// Update group pointer address
-    pub async fn update_group_address<S: Signers>(
+    /// Schedule a window during which transferring, minting, and burning is
+    /// paused on the mint
+    pub async fn schedule_pause<S: Signers>(
         &self,
         authority: &Address,
-        new_group_address: Option<Address>,
+        pause_timestamp: i64,
+        resume_timestamp: i64,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[group_pointer::instruction::update(
+            &[pausable::instruction::schedule_pause(
                 &self.program_id,
                 self.get_address(),
                 authority,
                 &multisig_signers,
-                new_group_address,
+                pause_timestamp,
+                resume_timestamp,
             )?],
             signing_keypairs,
         )
         .await
     }
 
-    /// Update group member pointer address
+    /// Schedule a pause window on the mint, transferring any lamports
+    /// required to store the schedule from the payer
+    pub async fn schedule_pause_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        authority: &Address,
+        pause_timestamp: i64,
+        resume_timestamp: i64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension::<PausableSchedule>()
+            .await?;
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(pausable::instruction::schedule_pause(
+            &self.program_id,
+            self.get_address(),
+            authority,
+            &multisig_signers,
+            pause_timestamp,
+            resume_timestamp,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Prevent unsafe usage of token account through CPI
+    pub async fn enable_cpi_guard<S: Signers>(
+        &self,
+        account: &Address,
+        authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[cpi_guard::instruction::enable_cpi_guard(
+                &self.program_id,
+                account,
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Stop preventing unsafe usage of token account through CPI
+    pub async fn disable_cpi_guard<S: Signers>(
+        &self,
+        account: &Address,
+        authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[cpi_guard::instruction::disable_cpi_guard(
+                &self.program_id,
+                account,
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Set the operations and calling programs allowed through the CPI Guard
+    pub async fn set_cpi_guard_policy<S: Signers>(
+        &self,
+        account: &Address,
+        authority: &Address,
+        policy: &CpiGuardPolicy,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[cpi_guard::instruction::set_cpi_guard_policy(
+                &self.program_id,
+                account,
+                authority,
+                &multisig_signers,
+                policy,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update interest rate
+    pub async fn update_interest_rate<S: Signers>(
+        &self,
+        authority: &Address,
+        new_rate: i16,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[interest_bearing_mint::instruction::update_rate(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_rate,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update interest compounding period and rate bounds, along with the rate
+    pub async fn update_interest_terms<S: Signers>(
+        &self,
+        authority: &Address,
+        compounding_period: CompoundingPeriod,
+        minimum_rate: Option<i16>,
+        maximum_rate: Option<i16>,
+        new_rate: i16,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[interest_bearing_mint::instruction::update_terms(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                compounding_period,
+                minimum_rate,
+                maximum_rate,
+                new_rate,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update multiplier
+    pub async fn update_multiplier<S: Signers>(
+        &self,
+        authority: &Address,
+        new_multiplier: f64,
+        new_multiplier_effective_timestamp: i64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[scaled_ui_amount::instruction::update_multiplier(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_multiplier,
+                new_multiplier_effective_timestamp,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update the rational multiplier of a scaled UI amount mint
+    pub async fn update_rational_multiplier<S: Signers>(
+        &self,
+        authority: &Address,
+        numerator: u64,
+        denominator: u64,
+        new_multiplier_effective_timestamp: i64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[scaled_ui_amount::instruction::update_rational_multiplier(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                numerator,
+                denominator,
+                new_multiplier_effective_timestamp,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    async fn get_additional_rent_for_scheduled_multiplier(&self) -> TokenResult<u64> {
+        let account = self.get_account(self.pubkey).await?;
+        let account_lamports = account.lamports;
+        let mint_state = self.unpack_mint_info(account)?;
+        let mut schedule = match mint_state.get_variable_len_extension::<ScaledUiAmountSchedule>() {
+            Ok(schedule) => schedule,
+            Err(_) => {
+                let config = mint_state.get_extension::<ScaledUiAmountConfig>()?;
+                ScaledUiAmountSchedule::from_config(config)
+            }
+        };
+        // the new change may not replace a queued one, so make space for it
+        schedule.entries.push(ScheduledMultiplier::default());
+        let new_account_len = mint_state
+            .try_get_new_account_len_for_variable_len_extension::<ScaledUiAmountSchedule>(
+                &schedule,
+            )?;
+        let new_rent_exempt_minimum = self
+            .client
+            .get_minimum_balance_for_rent_exemption(new_account_len)
+            .await
+            .map_err(TokenError::Client)?;
+        Ok(new_rent_exempt_minimum.saturating_sub(account_lamports))
+    }
+
+    /// Queue a multiplier change in the mint's multiplier schedule. Includes a
+    /// transfer for any additional rent-exempt SOL required.
+    pub async fn schedule_multiplier_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        authority: &Address,
+        new_multiplier: f64,
+        new_multiplier_effective_timestamp: i64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let additional_lamports = self.get_additional_rent_for_scheduled_multiplier().await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(scaled_ui_amount::instruction::schedule_multiplier(
+            &self.program_id,
+            self.get_address(),
+            authority,
+            &multisig_signers,
+            new_multiplier,
+            new_multiplier_effective_timestamp,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Remove a queued change from the mint's multiplier schedule
+    pub async fn cancel_scheduled_multiplier<S: Signers>(
+        &self,
+        authority: &Address,
+        effective_timestamp: i64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[scaled_ui_amount::instruction::cancel_scheduled_multiplier(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                effective_timestamp,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update transfer hook program id
+    pub async fn update_transfer_hook_program_id<S: Signers>(
+        &self,
+        authority: &Address,
+        new_program_id: Option<Address>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[transfer_hook::instruction::update(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_program_id,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update metadata pointer address
+    pub async fn update_metadata_address<S: Signers>(
+        &self,
+        authority: &Address,
+        new_metadata_address: Option<Address>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[metadata_pointer::instruction::update(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_metadata_address,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update group pointer address
+    pub async fn update_group_address<S: Signers>(
+        &self,
+        authority: &Address,
+        new_group_address: Option<Address>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[group_pointer::instruction::update(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_group_address,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update group member pointer address
     pub async fn update_group_member_address<S: Signers>(
         &self,
         authority: &Address,
@@ -2183,6 +3111,31 @@ where
         .await
     }
 
+    /// Configures confidential transfers for a token account using a labelled
+    /// key of an ElGamal registry account
+    pub async fn confidential_transfer_configure_token_account_with_registry_labelled_key(
+        &self,
+        account: &Address,
+        elgamal_registry_account: &Address,
+        elgamal_key_label: &confidential_transfer::ElGamalKeyLabel,
+        payer: Option<&Address>,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs::<[&dyn Signer; 0]>(
+            &[
+                confidential_transfer::instruction::configure_account_with_registry_labelled_key(
+                    &self.program_id,
+                    account,
+                    &self.pubkey,
+                    elgamal_registry_account,
+                    elgamal_key_label,
+                    payer,
+                )?,
+            ],
+            &[],
+        )
+        .await
+    }
+
     /// Approves a token account for confidential transfers
     pub async fn confidential_transfer_approve_account<S: Signers>(
         &self,
@@ -3908,12 +4861,29 @@ where
         &self,
         field: Field,
         value: String,
+    ) -> TokenResult<u64> {
+        self.get_additional_rent_for_metadata_updates(&[FieldUpdate::Set { field, value }])
+            .await
+    }
+
+    async fn get_additional_rent_for_metadata_updates(
+        &self,
+        updates: &[FieldUpdate],
     ) -> TokenResult<u64> {
         let account = self.get_account(self.pubkey).await?;
         let account_lamports = account.lamports;
         let mint_state = self.unpack_mint_info(account)?;
         let mut token_metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
-        token_metadata.update(field, value);
+        for update in updates {
+            match update {
+                FieldUpdate::Set { field, value } => {
+                    token_metadata.update(field.clone(), value.clone())
+                }
+                FieldUpdate::Remove { key } => {
+                    token_metadata.remove_key(key);
+                }
+            }
+        }
         let new_account_len = mint_state
             .try_get_new_account_len_for_variable_len_extension::<TokenMetadata>(&token_metadata)?;
         let new_rent_exempt_minimum = self
@@ -3924,22 +4894,77 @@ where
         Ok(new_rent_exempt_minimum.saturating_sub(account_lamports))
     }
 
-    /// Update a token-metadata field on a mint. Includes a transfer for any
-    /// additional rent-exempt SOL required.
-    #[allow(clippy::too_many_arguments)]
-    pub async fn token_metadata_update_field_with_rent_transfer<S: Signers>(
+    /// Update a token-metadata field on a mint. Includes a transfer for any
+    /// additional rent-exempt SOL required.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn token_metadata_update_field_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        update_authority: &Address,
+        field: Field,
+        value: String,
+        transfer_lamports: Option<u64>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = if let Some(transfer_lamports) = transfer_lamports {
+            transfer_lamports
+        } else {
+            self.get_additional_rent_for_updated_metadata(field.clone(), value.clone())
+                .await?
+        };
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(spl_token_metadata_interface::instruction::update_field(
+            &self.program_id,
+            &self.pubkey,
+            update_authority,
+            field,
+            value,
+        ));
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Apply several token-metadata field updates on a mint in one
+    /// instruction
+    pub async fn token_metadata_update_fields<S: Signers>(
+        &self,
+        update_authority: &Address,
+        updates: Vec<FieldUpdate>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[token_metadata::instruction::update_fields(
+                &self.program_id,
+                &self.pubkey,
+                update_authority,
+                updates,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Apply several token-metadata field updates on a mint in one
+    /// instruction. Includes a transfer for any additional rent-exempt SOL
+    /// required.
+    pub async fn token_metadata_update_fields_with_rent_transfer<S: Signers>(
         &self,
         payer: &Address,
         update_authority: &Address,
-        field: Field,
-        value: String,
+        updates: Vec<FieldUpdate>,
         transfer_lamports: Option<u64>,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let additional_lamports = if let Some(transfer_lamports) = transfer_lamports {
             transfer_lamports
         } else {
-            self.get_additional_rent_for_updated_metadata(field.clone(), value.clone())
+            self.get_additional_rent_for_metadata_updates(&updates)
                 .await?
         };
         let mut instructions = vec![];
@@ -3950,13 +4975,12 @@ where
                 additional_lamports,
             ));
         }
-        instructions.push(spl_token_metadata_interface::instruction::update_field(
+        instructions.push(token_metadata::instruction::update_fields(
             &self.program_id,
             &self.pubkey,
             update_authority,
-            field,
-            value,
-        ));
+            updates,
+        )?);
         self.process_ixs(&instructions, signing_keypairs).await
     }
 
@@ -4025,9 +5049,21 @@ where
     async fn get_additional_rent_for_fixed_len_extension<V: Extension + Pod>(
         &self,
     ) -> TokenResult<u64> {
-        let account = self.get_account(self.pubkey).await?;
+        self.get_additional_rent_for_fixed_len_extension_on::<V>(&self.pubkey)
+            .await
+    }
+
+    async fn get_additional_rent_for_fixed_len_extension_on<V: Extension + Pod>(
+        &self,
+        mint: &Address,
+    ) -> TokenResult<u64> {
+        let account = self.get_account(*mint).await?;
+        if account.owner != self.program_id {
+            return Err(TokenError::AccountInvalidOwner);
+        }
         let account_lamports = account.lamports;
-        let mint_state = self.unpack_mint_info(account)?;
+        // the mint may not be this one, so its decimals aren't checked
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data)?;
         if mint_state.get_extension::<V>().is_ok() {
             Ok(0)
         } else {
@@ -4168,6 +5204,157 @@ where
         self.process_ixs(&instructions, signing_keypairs).await
     }
 
+    /// Remove this mint from its token-group. The authority may be the group
+    /// update authority or this mint's authority.
+    pub async fn token_group_remove_member<S: Signers>(
+        &self,
+        group_mint: &Address,
+        authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[token_group::instruction::remove_member(
+                &self.program_id,
+                &self.pubkey,
+                group_mint,
+                authority,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Remove this mint from its token-group. The authority may be the group
+    /// update authority or this mint's authority. Includes a transfer for any
+    /// additional rent-exempt SOL required by the group mint.
+    pub async fn token_group_remove_member_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        group_mint: &Address,
+        authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension_on::<TokenGroupMemberCounter>(group_mint)
+            .await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                group_mint,
+                additional_lamports,
+            ));
+        }
+        instructions.push(token_group::instruction::remove_member(
+            &self.program_id,
+            &self.pubkey,
+            group_mint,
+            authority,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Move this mint from its token-group to a new token-group
+    pub async fn token_group_move_member<S: Signers>(
+        &self,
+        mint_authority: &Address,
+        group_mint: &Address,
+        new_group_mint: &Address,
+        new_group_update_authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[token_group::instruction::move_member(
+                &self.program_id,
+                &self.pubkey,
+                mint_authority,
+                group_mint,
+                new_group_mint,
+                new_group_update_authority,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Move this mint from its token-group to a new token-group. Includes a
+    /// transfer for any additional rent-exempt SOL required by the current
+    /// group mint.
+    pub async fn token_group_move_member_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        mint_authority: &Address,
+        group_mint: &Address,
+        new_group_mint: &Address,
+        new_group_update_authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension_on::<TokenGroupMemberCounter>(group_mint)
+            .await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                group_mint,
+                additional_lamports,
+            ));
+        }
+        instructions.push(token_group::instruction::move_member(
+            &self.program_id,
+            &self.pubkey,
+            mint_authority,
+            group_mint,
+            new_group_mint,
+            new_group_update_authority,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Freeze the membership of the token-group on a mint. Includes a
+    /// transfer for any additional rent-exempt SOL required.
+    pub async fn token_group_freeze_membership_with_rent_transfer<S: Signers>(
+        &self,
+        payer: &Address,
+        update_authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let additional_lamports = self
+            .get_additional_rent_for_fixed_len_extension::<TokenGroupMembershipFreeze>()
+            .await?;
+        let mut instructions = vec![];
+        if additional_lamports > 0 {
+            instructions.push(system_instruction::transfer(
+                payer,
+                &self.pubkey,
+                additional_lamports,
+            ));
+        }
+        instructions.push(token_group::instruction::freeze_membership(
+            &self.program_id,
+            &self.pubkey,
+            update_authority,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Thaw the membership of the token-group on a mint
+    pub async fn token_group_thaw_membership<S: Signers>(
+        &self,
+        update_authority: &Address,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[token_group::instruction::thaw_membership(
+                &self.program_id,
+                &self.pubkey,
+                update_authority,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
     /// Get the pending balance for a confidential transfer account.
     ///
     /// This decrypts and combines the low 16 bits and high 48 bits of the pending balance
@@ -4245,6 +5432,225 @@ where
     }
 }
 
+/// Collects token instructions for a mint, to be executed in a single
+/// `Batch` instruction.
+///
+/// Instructions are built the same way as the corresponding `Token` methods,
+/// including transfer hook accounts. Since signing happens once for the whole
+/// batch, each method takes the pubkeys of the keypairs that will sign for
+/// the given authority, which is how multisig signers are detected.
+pub struct TokenBatch<'a, T> {
+    token: &'a Token<T>,
+    instructions: Vec<Instruction>,
+}
+
+impl<T> fmt::Debug for TokenBatch<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenBatch")
+            .field("token", &self.token)
+            .field("instructions", &self.instructions)
+            .finish()
+    }
+}
+
+impl<T> TokenBatch<'_, T>
+where
+    T: SendTransaction + SimulateTransaction,
+{
+    /// Add an instruction for the token program that has no helper here
+    pub fn add_instruction(&mut self, instruction: Instruction) -> &mut Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Mint new tokens
+    pub fn mint_to(
+        &mut self,
+        destination: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let instruction =
+            self.token
+                .mint_to_instruction(destination, authority, amount, signing_pubkeys)?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Transfer tokens to another account
+    pub async fn transfer(
+        &mut self,
+        source: &Address,
+        destination: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let instruction = self
+            .token
+            .transfer_instruction(source, destination, authority, amount, signing_pubkeys)
+            .await?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Transfer tokens to another account, given an expected fee
+    pub async fn transfer_with_fee(
+        &mut self,
+        source: &Address,
+        destination: &Address,
+        authority: &Address,
+        amount: u64,
+        fee: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let instruction = self
+            .token
+            .transfer_with_fee_instruction(
+                source,
+                destination,
+                authority,
+                amount,
+                fee,
+                signing_pubkeys,
+            )
+            .await?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Burn tokens from account
+    pub fn burn(
+        &mut self,
+        source: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let instruction =
+            self.token
+                .burn_instruction(source, authority, amount, signing_pubkeys)?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Approve a delegate to spend tokens
+    pub fn approve(
+        &mut self,
+        source: &Address,
+        delegate: &Address,
+        authority: &Address,
+        amount: u64,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let instruction =
+            self.token
+                .approve_instruction(source, delegate, authority, amount, signing_pubkeys)?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Revoke a delegate
+    pub fn revoke(
+        &mut self,
+        source: &Address,
+        authority: &Address,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let multisig_signers = self.token.get_multisig_signers(authority, signing_pubkeys);
+        let instruction =
+            instruction::revoke(&self.token.program_id, source, authority, &multisig_signers)?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Freeze a token account
+    pub fn freeze(
+        &mut self,
+        account: &Address,
+        authority: &Address,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let multisig_signers = self.token.get_multisig_signers(authority, signing_pubkeys);
+        let instruction = instruction::freeze_account(
+            &self.token.program_id,
+            account,
+            &self.token.pubkey,
+            authority,
+            &multisig_signers,
+        )?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Thaw / unfreeze a token account
+    pub fn thaw(
+        &mut self,
+        account: &Address,
+        authority: &Address,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let multisig_signers = self.token.get_multisig_signers(authority, signing_pubkeys);
+        let instruction = instruction::thaw_account(
+            &self.token.program_id,
+            account,
+            &self.token.pubkey,
+            authority,
+            &multisig_signers,
+        )?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Close an empty account and reclaim its lamports
+    pub fn close_account(
+        &mut self,
+        account: &Address,
+        lamports_destination: &Address,
+        authority: &Address,
+        signing_pubkeys: &[Address],
+    ) -> TokenResult<&mut Self> {
+        let multisig_signers = self.token.get_multisig_signers(authority, signing_pubkeys);
+        let instruction = instruction::close_account(
+            &self.token.program_id,
+            account,
+            lamports_destination,
+            authority,
+            &multisig_signers,
+        )?;
+        Ok(self.add_instruction(instruction))
+    }
+
+    /// Number of instructions collected so far
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Whether no instruction was collected yet
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Create the `Batch` instruction, for use in a custom transaction
+    pub fn instruction(&self) -> TokenResult<Instruction> {
+        Ok(instruction::batch(
+            &self.token.program_id,
+            &self.instructions,
+        )?)
+    }
+
+    /// Simulate the batch in a single transaction
+    pub async fn simulate<S: Signers>(
+        &self,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::SimulationOutput> {
+        self.token
+            .simulate_ixs(&[self.instruction()?], signing_keypairs)
+            .await
+    }
+
+    /// Send the batch in a single transaction. With an offline client, this
+    /// returns the signed transaction instead.
+    pub async fn send<S: Signers>(&self, signing_keypairs: &S) -> TokenResult<T::Output> {
+        self.token
+            .process_ixs(&[self.instruction()?], signing_keypairs)
+            .await
+    }
+}
+
 /// Calculates the maximum chunk size for a zero-knowledge proof record
 /// instruction to fit inside a single transaction.
 fn calculate_record_max_chunk_size<F>(
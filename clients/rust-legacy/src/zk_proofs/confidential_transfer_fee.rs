@@ -29,6 +29,22 @@ impl WithheldTokensInfo {
         }
     }
 
+    /// Decrypt the withheld amount using the withdraw withheld authority
+    /// ElGamal keypair.
+    pub fn decrypted_withheld_amount(
+        &self,
+        withdraw_withheld_authority_elgamal_keypair: &ElGamalKeypair,
+    ) -> Result<u64, TokenError> {
+        let withheld_amount: ElGamalCiphertext = self
+            .withheld_amount
+            .try_into()
+            .map_err(|_| TokenError::MalformedCiphertext)?;
+
+        withheld_amount
+            .decrypt_u32(withdraw_withheld_authority_elgamal_keypair.secret())
+            .ok_or(TokenError::AccountDecryption)
+    }
+
     /// Create withdraw withheld proof data.
     pub fn generate_proof_data(
         &self,
@@ -40,9 +56,8 @@ impl WithheldTokensInfo {
             .try_into()
             .map_err(|_| TokenError::MalformedCiphertext)?;
 
-        let decrypted_withheld_amount_in_mint = withheld_amount_in_mint
-            .decrypt_u32(withdraw_withheld_authority_elgamal_keypair.secret())
-            .ok_or(TokenError::AccountDecryption)?;
+        let decrypted_withheld_amount_in_mint =
+            self.decrypted_withheld_amount(withdraw_withheld_authority_elgamal_keypair)?;
 
         let destination_opening = PedersenOpening::new_rand();
 
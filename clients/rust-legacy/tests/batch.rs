@@ -14,6 +14,7 @@ use {
     },
     solana_system_interface::instruction as system_instruction,
     spl_token_2022_interface::{
+        error::TokenError,
         extension::ExtensionType,
         id,
         instruction::*,
@@ -1801,3 +1802,110 @@ async fn batch_reallocate_and_init_extension_owner_checks() {
     .await
     .unwrap();
 }
+
+#[tokio::test]
+async fn token_batch() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        token,
+        mint_authority,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    let mut batch = token.batch();
+    batch
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            100,
+            &[mint_authority.pubkey()],
+        )
+        .unwrap();
+    batch
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            40,
+            &[alice.pubkey()],
+        )
+        .await
+        .unwrap();
+    batch
+        .burn(&bob_account, &bob.pubkey(), 10, &[bob.pubkey()])
+        .unwrap()
+        .approve(
+            &alice_account,
+            &bob.pubkey(),
+            &alice.pubkey(),
+            5,
+            &[alice.pubkey()],
+        )
+        .unwrap();
+    assert_eq!(batch.len(), 4);
+
+    let instruction = batch.instruction().unwrap();
+    let decoded = BatchDecoder::new(&instruction)
+        .unwrap()
+        .map(|decoded| decoded.unwrap().instruction)
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        decoded.as_slice(),
+        [
+            BatchedInstructionKind::Token(TokenInstruction::MintToChecked { amount: 100, .. }),
+            BatchedInstructionKind::Token(TokenInstruction::TransferChecked { amount: 40, .. }),
+            BatchedInstructionKind::Token(TokenInstruction::BurnChecked { amount: 10, .. }),
+            BatchedInstructionKind::Token(TokenInstruction::ApproveChecked { amount: 5, .. }),
+        ]
+    ));
+
+    let signers: [&dyn Signer; 3] = [&mint_authority, &alice, &bob];
+    batch.simulate(&signers).await.unwrap();
+    batch.send(&signers).await.unwrap();
+
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 60);
+    assert_eq!(alice_state.base.delegate, Some(bob.pubkey()).into());
+    assert_eq!(alice_state.base.delegated_amount, 5);
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 30);
+    let mint_state = token.get_mint_info().await.unwrap();
+    assert_eq!(mint_state.base.supply, 90);
+
+    // the whole batch fails if one instruction fails
+    let mut batch = token.batch();
+    batch
+        .burn(&alice_account, &alice.pubkey(), 1, &[alice.pubkey()])
+        .unwrap()
+        .burn(&bob_account, &bob.pubkey(), 31, &[bob.pubkey()])
+        .unwrap();
+    let err = batch.send(&[&alice, &bob]).await.unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InsufficientFunds as u32)
+            ),
+        )))
+    );
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 60);
+}
@@ -20,7 +20,13 @@ use {
         zk_elgamal_proof_program::build_pubkey_validity_proof_data,
     },
     solana_zk_sdk_pod::encryption::elgamal::PodElGamalCiphertext,
-    spl_elgamal_registry::state::ELGAMAL_REGISTRY_ACCOUNT_LEN,
+    spl_elgamal_registry::{
+        instruction::{
+            close_registry, create_registry, remove_labelled_key, set_labelled_key,
+            transfer_registry_ownership,
+        },
+        state::{ElGamalRegistry, ELGAMAL_KEY_LABEL_LEN, ELGAMAL_REGISTRY_ACCOUNT_LEN},
+    },
     spl_token_2022_interface::{
         error::TokenError,
         extension::{
@@ -2715,6 +2721,238 @@ async fn confidential_transfer_configure_token_account_with_registry() {
     );
 }
 
+#[tokio::test]
+async fn confidential_transfer_elgamal_registry_labelled_keys_and_ownership() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint {
+                authority: None,
+                auto_approve_new_accounts: true,
+                auditor_elgamal_pubkey: None,
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext {
+        token, alice, bob, ..
+    } = context.token_context.unwrap();
+    let label = [7; ELGAMAL_KEY_LABEL_LEN];
+    let elgamal_keypair = ElGamalKeypair::new_rand();
+    let labelled_elgamal_keypair = ElGamalKeypair::new_rand();
+
+    let alice_registry_address = spl_elgamal_registry::get_elgamal_registry_address(
+        &alice.pubkey(),
+        &spl_elgamal_registry::id(),
+    );
+    let bob_registry_address = spl_elgamal_registry::get_elgamal_registry_address(
+        &bob.pubkey(),
+        &spl_elgamal_registry::id(),
+    );
+
+    // create ElGamal registry, then add a labelled key
+    let ctx = context.context.lock().await;
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let proof_data = build_pubkey_validity_proof_data(&elgamal_keypair).unwrap();
+    let proof_location = ProofLocation::InstructionOffset(1.try_into().unwrap(), &proof_data);
+    let instructions = [
+        vec![system_instruction::transfer(
+            &ctx.payer.pubkey(),
+            &alice_registry_address,
+            rent.minimum_balance(ELGAMAL_REGISTRY_ACCOUNT_LEN),
+        )],
+        create_registry(&alice.pubkey(), proof_location).unwrap(),
+    ]
+    .concat();
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &alice],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    let proof_data = build_pubkey_validity_proof_data(&labelled_elgamal_keypair).unwrap();
+    let proof_location = ProofLocation::InstructionOffset(1.try_into().unwrap(), &proof_data);
+
+    // fail, not funded for the new key
+    let tx = Transaction::new_signed_with_payer(
+        &set_labelled_key(&alice.pubkey(), &label, proof_location).unwrap(),
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &alice],
+        ctx.last_blockhash,
+    );
+    let err = ctx
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::AccountNotRentExempt)
+    );
+
+    let new_account_len = ElGamalRegistry::account_len(1);
+    let instructions = [
+        vec![system_instruction::transfer(
+            &ctx.payer.pubkey(),
+            &alice_registry_address,
+            rent.minimum_balance(new_account_len)
+                - rent.minimum_balance(ELGAMAL_REGISTRY_ACCOUNT_LEN),
+        )],
+        set_labelled_key(&alice.pubkey(), &label, proof_location).unwrap(),
+    ]
+    .concat();
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &alice],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    let registry_account = ctx
+        .banks_client
+        .get_account(alice_registry_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(registry_account.data.len(), new_account_len);
+    let (registry, labelled_keys) = ElGamalRegistry::unpack(&registry_account.data).unwrap();
+    assert_eq!(registry.owner, alice.pubkey());
+    assert_eq!(labelled_keys[0].label, label);
+    drop(ctx);
+
+    // configure account using the labelled key
+    let alice_account_keypair = Keypair::new();
+    token
+        .create_auxiliary_token_account_with_extension_space(
+            &alice_account_keypair,
+            &alice.pubkey(),
+            vec![ExtensionType::ConfidentialTransferAccount],
+        )
+        .await
+        .unwrap();
+
+    let err = token
+        .confidential_transfer_configure_token_account_with_registry_labelled_key(
+            &alice_account_keypair.pubkey(),
+            &alice_registry_address,
+            &[8; ELGAMAL_KEY_LABEL_LEN],
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+        )))
+    );
+
+    token
+        .confidential_transfer_configure_token_account_with_registry_labelled_key(
+            &alice_account_keypair.pubkey(),
+            &alice_registry_address,
+            &label,
+            None,
+        )
+        .await
+        .unwrap();
+    let state = token
+        .get_account_info(&alice_account_keypair.pubkey())
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert_eq!(
+        extension.elgamal_pubkey,
+        (*labelled_elgamal_keypair.pubkey()).into()
+    );
+
+    // remove the labelled key, transfer the registry to bob, then close it
+    let ctx = context.context.lock().await;
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            remove_labelled_key(&alice.pubkey(), &label),
+            transfer_registry_ownership(&alice.pubkey(), &bob.pubkey()),
+        ],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &alice, &bob],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(ctx
+        .banks_client
+        .get_account(alice_registry_address)
+        .await
+        .unwrap()
+        .is_none());
+    let registry_account = ctx
+        .banks_client
+        .get_account(bob_registry_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(registry_account.data.len(), ELGAMAL_REGISTRY_ACCOUNT_LEN);
+    assert_eq!(
+        registry_account.lamports,
+        rent.minimum_balance(new_account_len)
+    );
+    let (registry, _) = ElGamalRegistry::unpack(&registry_account.data).unwrap();
+    assert_eq!(registry.owner, bob.pubkey());
+    assert_eq!(registry.elgamal_pubkey, (*elgamal_keypair.pubkey()).into());
+
+    // fail, wrong owner
+    let destination = Pubkey::new_unique();
+    let mut instruction = close_registry(&bob.pubkey(), &destination);
+    instruction.accounts[2].pubkey = alice.pubkey();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &alice],
+        ctx.last_blockhash,
+    );
+    let err = ctx
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountOwner)
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[close_registry(&bob.pubkey(), &destination)],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &bob],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+    assert!(ctx
+        .banks_client
+        .get_account(bob_registry_address)
+        .await
+        .unwrap()
+        .is_none());
+    let destination_account = ctx
+        .banks_client
+        .get_account(destination)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        destination_account.lamports,
+        rent.minimum_balance(new_account_len)
+    );
+}
+
 #[tokio::test]
 async fn test_confidential_transfer_balance_decryption() {
     let authority = Keypair::new();
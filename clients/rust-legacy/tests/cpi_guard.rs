@@ -7,8 +7,13 @@ use {
         ProgramTest,
     },
     solana_sdk::{
-        instruction::InstructionError, pubkey::Pubkey, rent::Rent, signature::Signer,
-        signer::keypair::Keypair, transaction::Transaction, transaction::TransactionError,
+        instruction::{AccountMeta, InstructionError},
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::Signer,
+        signer::keypair::Keypair,
+        transaction::Transaction,
+        transaction::TransactionError,
         transport::TransportError,
     },
     solana_system_interface::instruction as system_instruction,
@@ -16,7 +21,7 @@ use {
     spl_token_2022_interface::{
         error::TokenError,
         extension::{
-            cpi_guard::{self, CpiGuard},
+            cpi_guard::{self, CpiGuard, CpiGuardPolicy},
             BaseStateWithExtensions, ExtensionType,
         },
         instruction::{self, AuthorityType},
@@ -222,6 +227,179 @@ async fn test_cpi_guard_enable_disable() {
     assert!(bool::from(extension.lock_cpi));
 }
 
+#[tokio::test]
+async fn test_cpi_guard_policy() {
+    let context = make_context_with_new_mint().await;
+    let TokenContext {
+        token,
+        mint_authority,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    // the top-level program of a cpi can be read from the instructions sysvar
+    // if it's passed to the token program
+    let mk_transfer = |with_instructions_sysvar| {
+        let mut instruction = instruction::transfer_checked(
+            &spl_token_2022_interface::id(),
+            &alice.pubkey(),
+            token.get_address(),
+            &bob.pubkey(),
+            &alice.pubkey(),
+            &[],
+            1,
+            9,
+        )
+        .unwrap();
+        if with_instructions_sysvar {
+            instruction.accounts.push(AccountMeta::new_readonly(
+                solana_sdk_ids::sysvar::instructions::id(),
+                false,
+            ));
+        }
+        wrap_instruction(
+            spl_instruction_padding_interface::id(),
+            instruction,
+            vec![],
+            0,
+        )
+        .unwrap()
+    };
+    let mk_burn = || {
+        wrap_instruction(
+            spl_instruction_padding_interface::id(),
+            instruction::burn_checked(
+                &spl_token_2022_interface::id(),
+                &alice.pubkey(),
+                token.get_address(),
+                &alice.pubkey(),
+                &[],
+                1,
+                9,
+            )
+            .unwrap(),
+            vec![],
+            0,
+        )
+        .unwrap()
+    };
+
+    let mut amount = 100;
+    token
+        .mint_to(
+            &alice.pubkey(),
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .enable_cpi_guard(&alice.pubkey(), &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    token
+        .reallocate(
+            &alice.pubkey(),
+            &alice.pubkey(),
+            &[ExtensionType::CpiGuardPolicy],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // allow burns from any program, but not transfers
+    let policy = CpiGuardPolicy::new(CpiGuardPolicy::BURN, &[]).unwrap();
+    token
+        .set_cpi_guard_policy(&alice.pubkey(), &alice.pubkey(), &policy, &[&alice])
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice.pubkey()).await.unwrap();
+    assert_eq!(
+        *alice_state.get_extension::<CpiGuardPolicy>().unwrap(),
+        policy
+    );
+
+    token.process_ixs(&[mk_burn()], &[&alice]).await.unwrap();
+    amount -= 1;
+
+    let error = token
+        .process_ixs(&[mk_transfer(true)], &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(error, client_error(TokenError::CpiGuardTransferBlocked));
+
+    // allow transfers from the padding program only
+    let policy = CpiGuardPolicy::new(
+        CpiGuardPolicy::TRANSFER,
+        &[spl_instruction_padding_interface::id()],
+    )
+    .unwrap();
+    token
+        .set_cpi_guard_policy(&alice.pubkey(), &alice.pubkey(), &policy, &[&alice])
+        .await
+        .unwrap();
+
+    let error = token
+        .process_ixs(&[mk_burn()], &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(error, client_error(TokenError::CpiGuardBurnBlocked));
+
+    // the calling program is unknown without the instructions sysvar
+    let error = token
+        .process_ixs(&[mk_transfer(false)], &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(error, client_error(TokenError::CpiGuardTransferBlocked));
+
+    token
+        .process_ixs(&[mk_transfer(true)], &[&alice])
+        .await
+        .unwrap();
+    amount -= 1;
+
+    // other calling programs are still blocked
+    let policy = CpiGuardPolicy::new(CpiGuardPolicy::TRANSFER, &[Pubkey::new_unique()]).unwrap();
+    token
+        .set_cpi_guard_policy(&alice.pubkey(), &alice.pubkey(), &policy, &[&alice])
+        .await
+        .unwrap();
+
+    let error = token
+        .process_ixs(&[mk_transfer(true)], &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(error, client_error(TokenError::CpiGuardTransferBlocked));
+
+    let alice_state = token.get_account_info(&alice.pubkey()).await.unwrap();
+    assert_eq!(alice_state.base.amount, amount);
+
+    // the policy can't be set through cpi
+    let error = token
+        .process_ixs(
+            &[wrap_instruction(
+                spl_instruction_padding_interface::id(),
+                cpi_guard::instruction::set_cpi_guard_policy(
+                    &spl_token_2022_interface::id(),
+                    &alice.pubkey(),
+                    &alice.pubkey(),
+                    &[],
+                    &CpiGuardPolicy::new(CpiGuardPolicy::ALL, &[]).unwrap(),
+                )
+                .unwrap(),
+                vec![],
+                0,
+            )
+            .unwrap()],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, client_error(TokenError::CpiGuardSettingsLocked));
+}
+
 #[tokio::test]
 async fn test_cpi_guard_transfer() {
     let context = make_context_with_new_mint().await;
@@ -1,16 +1,26 @@
 mod program_test;
 use {
     program_test::{TestContext, TokenContext},
+    solana_program_pack::Pack,
     solana_program_test::tokio,
     solana_sdk::{
-        instruction::InstructionError, program_option::COption, pubkey::Pubkey, signature::Signer,
-        signer::keypair::Keypair, transaction::TransactionError, transport::TransportError,
+        instruction::{AccountMeta, InstructionError},
+        program_option::COption,
+        pubkey::Pubkey,
+        signature::Signer,
+        signer::keypair::Keypair,
+        transaction::TransactionError,
+        transport::TransportError,
     },
+    solana_system_interface::instruction as system_instruction,
     spl_token_2022_interface::{
         error::TokenError,
-        extension::{default_account_state::DefaultAccountState, BaseStateWithExtensions},
-        instruction::AuthorityType,
-        state::AccountState,
+        extension::{
+            default_account_state::{DefaultAccountState, DefaultAccountStateAttestor},
+            BaseStateWithExtensions,
+        },
+        instruction::{self, AuthorityType},
+        state::{Account, AccountState},
     },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
     std::convert::TryFrom,
@@ -320,3 +330,148 @@ async fn end_to_end_default_account_state() {
         AccountState::Frozen,
     );
 }
+
+#[tokio::test]
+async fn success_attested_account_skips_default_state() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::DefaultAccountState {
+            state: AccountState::Frozen,
+        }])
+        .await
+        .unwrap();
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+    let TokenContext {
+        mint_authority,
+        freeze_authority,
+        token,
+        ..
+    } = context.token_context.unwrap();
+    let freeze_authority = freeze_authority.unwrap();
+    let attestor = Keypair::new();
+
+    // only the freeze authority can register an attestor
+    let err = token
+        .set_default_account_state_attestor_with_rent_transfer(
+            &payer_pubkey,
+            &mint_authority.pubkey(),
+            Some(&attestor.pubkey()),
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .set_default_account_state_attestor_with_rent_transfer(
+            &payer_pubkey,
+            &freeze_authority.pubkey(),
+            Some(&attestor.pubkey()),
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state
+        .get_extension::<DefaultAccountStateAttestor>()
+        .unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(extension.attestor),
+        Some(attestor.pubkey())
+    );
+
+    // attested accounts start initialized
+    let account = Keypair::new();
+    token
+        .create_attested_auxiliary_token_account(&account, &Pubkey::new_unique(), vec![], &attestor)
+        .await
+        .unwrap();
+    let account_state = token.get_account_info(&account.pubkey()).await.unwrap();
+    assert_eq!(account_state.base.state, AccountState::Initialized);
+
+    // other accounts keep the default state
+    let account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&account, &Pubkey::new_unique())
+        .await
+        .unwrap();
+    let account_state = token.get_account_info(&account.pubkey()).await.unwrap();
+    assert_eq!(account_state.base.state, AccountState::Frozen);
+
+    // a trailing account that doesn't sign is ignored, even if it's the attestor
+    let account = Keypair::new();
+    let mut initialize_account_instruction = instruction::initialize_account3(
+        &spl_token_2022_interface::id(),
+        &account.pubkey(),
+        token.get_address(),
+        &Pubkey::new_unique(),
+    )
+    .unwrap();
+    initialize_account_instruction
+        .accounts
+        .push(AccountMeta::new_readonly(attestor.pubkey(), false));
+    token
+        .process_ixs(
+            &[
+                system_instruction::create_account(
+                    &payer_pubkey,
+                    &account.pubkey(),
+                    1_000_000_000,
+                    Account::LEN as u64,
+                    &spl_token_2022_interface::id(),
+                ),
+                initialize_account_instruction,
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+    let account_state = token.get_account_info(&account.pubkey()).await.unwrap();
+    assert_eq!(account_state.base.state, AccountState::Frozen);
+
+    // an unregistered attestor is rejected
+    let err = token
+        .create_attested_auxiliary_token_account(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            vec![],
+            &Keypair::new(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::InvalidAccountStateAttestor as u32)
+            )
+        )))
+    );
+
+    // once cleared, the attestor is ignored
+    token
+        .set_default_account_state_attestor_with_rent_transfer(
+            &payer_pubkey,
+            &freeze_authority.pubkey(),
+            None,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let account = Keypair::new();
+    token
+        .create_attested_auxiliary_token_account(&account, &Pubkey::new_unique(), vec![], &attestor)
+        .await
+        .unwrap();
+    let account_state = token.get_account_info(&account.pubkey()).await.unwrap();
+    assert_eq!(account_state.base.state, AccountState::Frozen);
+}
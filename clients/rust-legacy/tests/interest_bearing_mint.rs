@@ -21,7 +21,12 @@ use {
     },
     spl_token_2022_interface::{
         error::TokenError,
-        extension::{interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions},
+        extension::{
+            interest_bearing_mint::{
+                CompoundingPeriod, InterestBearingConfig, InterestBearingTerms,
+            },
+            BaseStateWithExtensions,
+        },
         instruction::{amount_to_ui_amount, ui_amount_to_amount, AuthorityType},
     },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
@@ -144,6 +149,153 @@ async fn update_rate() {
     );
 }
 
+#[tokio::test]
+async fn rate_terms() {
+    let rate_authority = Keypair::new();
+    let initial_rate = 500;
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::InterestBearingConfig {
+                rate_authority: Some(rate_authority.pubkey()),
+                rate: initial_rate,
+            },
+            ExtensionInitializationParams::InterestBearingTerms {
+                compounding_period: CompoundingPeriod::Monthly,
+                minimum_rate: Some(0),
+                maximum_rate: Some(1_000),
+            },
+        ])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.take().unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let terms = state.get_extension::<InterestBearingTerms>().unwrap();
+    assert_eq!(terms.compounding_period(), Ok(CompoundingPeriod::Monthly));
+    assert_eq!(i16::from(terms.minimum_rate), 0);
+    assert_eq!(i16::from(terms.maximum_rate), 1_000);
+
+    // within bounds
+    token
+        .update_interest_rate(&rate_authority.pubkey(), 1_000, &[&rate_authority])
+        .await
+        .unwrap();
+
+    // out of bounds, either way
+    for rate in [-1, 1_001] {
+        let err = token
+            .update_interest_rate(&rate_authority.pubkey(), rate, &[&rate_authority])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TokenClientError::Client(Box::new(TransportError::TransactionError(
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(TokenError::InterestRateOutOfBounds as u32)
+                )
+            )))
+        );
+    }
+
+    // new terms must contain the new rate
+    let err = token
+        .update_interest_terms(
+            &rate_authority.pubkey(),
+            CompoundingPeriod::Daily,
+            None,
+            Some(1_500),
+            2_000,
+            &[&rate_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InterestRateOutOfBounds as u32)
+            )
+        )))
+    );
+
+    // wrong signer
+    let wrong_signer = Keypair::new();
+    let err = token
+        .update_interest_terms(
+            &wrong_signer.pubkey(),
+            CompoundingPeriod::Daily,
+            None,
+            None,
+            2_000,
+            &[&wrong_signer],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // switch to daily compounding, lifting the bounds
+    token
+        .update_interest_terms(
+            &rate_authority.pubkey(),
+            CompoundingPeriod::Daily,
+            None,
+            None,
+            2_000,
+            &[&rate_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let terms = state.get_extension::<InterestBearingTerms>().unwrap();
+    assert_eq!(
+        *terms,
+        InterestBearingTerms::new(CompoundingPeriod::Daily, None, None)
+    );
+    let extension = state.get_extension::<InterestBearingConfig>().unwrap();
+    assert_eq!(i16::from(extension.current_rate), 2_000);
+    token
+        .update_interest_rate(&rate_authority.pubkey(), i16::MIN, &[&rate_authority])
+        .await
+        .unwrap();
+
+    // the initial rate must fall within the bounds
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::InterestBearingConfig {
+                rate_authority: Some(rate_authority.pubkey()),
+                rate: initial_rate,
+            },
+            ExtensionInitializationParams::InterestBearingTerms {
+                compounding_period: CompoundingPeriod::Daily,
+                minimum_rate: Some(1_000),
+                maximum_rate: None,
+            },
+        ])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InterestRateOutOfBounds as u32)
+            )
+        )))
+    );
+}
+
 #[tokio::test]
 async fn set_authority() {
     let rate_authority = Keypair::new();
@@ -15,7 +15,10 @@ use {
     solana_system_interface::instruction as system_instruction,
     spl_token_2022_interface::{
         error::TokenError,
-        extension::{memo_transfer::MemoTransfer, BaseStateWithExtensions, ExtensionType},
+        extension::{
+            memo_transfer::{MemoTransfer, MemoTransferPolicy, OutgoingMemoTransfer},
+            BaseStateWithExtensions, ExtensionType,
+        },
     },
     spl_token_client::token::TokenError as TokenClientError,
     std::sync::Arc,
@@ -278,3 +281,202 @@ async fn require_memo_transfers_with_realloc() {
 
     test_memo_transfers(context.context, token_context, alice_account, bob_account).await;
 }
+
+#[tokio::test]
+async fn memo_transfer_policy() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    token
+        .create_auxiliary_token_account(&alice, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice.pubkey();
+    token
+        .create_auxiliary_token_account_with_extension_space(
+            &bob,
+            &bob.pubkey(),
+            vec![
+                ExtensionType::MemoTransfer,
+                ExtensionType::MemoTransferPolicy,
+            ],
+        )
+        .await
+        .unwrap();
+    let bob_account = bob.pubkey();
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            4242,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // only the owner can set the policy
+    let policy = MemoTransferPolicy::new(0, b"DEP-", 6).unwrap();
+    let err = token
+        .set_memo_transfer_policy(&bob_account, &alice.pubkey(), &policy, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .set_memo_transfer_policy(&bob_account, &bob.pubkey(), &policy, &[&bob])
+        .await
+        .unwrap();
+    token
+        .enable_required_transfer_memos(&bob_account, &bob.pubkey(), &[&bob])
+        .await
+        .unwrap();
+
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    let extension = bob_state.get_extension::<MemoTransferPolicy>().unwrap();
+    assert_eq!(*extension, policy);
+
+    // memos that don't satisfy the policy are rejected
+    for memo in ["", "DEP-12345", "DEP-12345a", "deposit"] {
+        let err = token
+            .with_memo(memo, vec![alice.pubkey()])
+            .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TokenClientError::Client(Box::new(TransportError::TransactionError(
+                TransactionError::InstructionError(
+                    1,
+                    InstructionError::Custom(TokenError::MemoPolicyViolation as u32)
+                )
+            )))
+        );
+    }
+
+    token
+        .with_memo("DEP-000042", vec![alice.pubkey()])
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 10);
+
+    // the policy is not enforced once memos are no longer required
+    token
+        .disable_required_transfer_memos(&bob_account, &bob.pubkey(), &[&bob])
+        .await
+        .unwrap();
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 20);
+}
+
+#[tokio::test]
+async fn require_outgoing_memo_transfers() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    token
+        .create_auxiliary_token_account_with_extension_space(
+            &alice,
+            &alice.pubkey(),
+            vec![ExtensionType::OutgoingMemoTransfer],
+        )
+        .await
+        .unwrap();
+    let alice_account = alice.pubkey();
+    token
+        .create_auxiliary_token_account(&bob, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob.pubkey();
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            4242,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // require memo transfers out of alice_account
+    token
+        .enable_required_outgoing_transfer_memos(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = alice_state.get_extension::<OutgoingMemoTransfer>().unwrap();
+    assert!(bool::from(extension.require_outgoing_transfer_memos));
+
+    // transfers out of alice_account and self-transfers need a memo
+    for destination in [bob_account, alice_account] {
+        let err = token
+            .transfer(&alice_account, &destination, &alice.pubkey(), 10, &[&alice])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TokenClientError::Client(Box::new(TransportError::TransactionError(
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(TokenError::NoMemo as u32)
+                )
+            )))
+        );
+    }
+
+    token
+        .with_memo("🦖", vec![alice.pubkey()])
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 10);
+
+    // incoming transfers don't need a memo
+    token
+        .transfer(&bob_account, &alice_account, &bob.pubkey(), 5, &[&bob])
+        .await
+        .unwrap();
+
+    // stop requiring memo transfers out of alice_account
+    token
+        .disable_required_outgoing_transfer_memos(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, 15);
+}
@@ -13,6 +13,7 @@ use {
             immutable_owner::ImmutableOwner, transfer_fee::TransferFee, BaseStateWithExtensions,
             ExtensionType,
         },
+        instruction::AuthorityType,
     },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
 };
@@ -413,3 +414,159 @@ async fn transfer_checked_with_fee() {
         )))
     );
 }
+
+#[tokio::test]
+async fn fail_recovery_without_non_transferable() {
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::NonTransferableRecovery {
+                authority: Address::new_unique(),
+            },
+        ])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InvalidExtensionCombination as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn recovery_transfer() {
+    let test_transfer_amount = 100;
+    let recovery_authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::NonTransferable,
+            ExtensionInitializationParams::NonTransferableRecovery {
+                authority: recovery_authority.pubkey(),
+            },
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    token
+        .create_auxiliary_token_account(&alice, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice.pubkey();
+    token
+        .create_auxiliary_token_account(&bob, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob.pubkey();
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            test_transfer_amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // regular transfer still fails
+    let error = token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            test_transfer_amount,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::NonTransferable as u32)
+            )
+        )))
+    );
+
+    // the owner can't perform a recovery transfer
+    let error = token
+        .recovery_transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            test_transfer_amount,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // the recovery authority can
+    token
+        .recovery_transfer(
+            &alice_account,
+            &bob_account,
+            &recovery_authority.pubkey(),
+            test_transfer_amount,
+            &[&recovery_authority],
+        )
+        .await
+        .unwrap();
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(alice_state.base.amount, 0);
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, test_transfer_amount);
+
+    // once the authority is removed, recovery is no longer possible
+    token
+        .set_authority(
+            token.get_address(),
+            &recovery_authority.pubkey(),
+            None,
+            AuthorityType::NonTransferableRecovery,
+            &[&recovery_authority],
+        )
+        .await
+        .unwrap();
+    let error = token
+        .recovery_transfer(
+            &bob_account,
+            &alice_account,
+            &recovery_authority.pubkey(),
+            test_transfer_amount,
+            &[&recovery_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::NoAuthorityExists as u32)
+            )
+        )))
+    );
+}
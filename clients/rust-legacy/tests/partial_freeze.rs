@@ -0,0 +1,308 @@
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{
+            partial_freeze::{
+                PartialFreezeAccount, PartialFreezeConfig, FREEZE_INCOMING, FREEZE_OUTGOING,
+            },
+            BaseStateWithExtensions,
+        },
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+};
+
+#[tokio::test]
+async fn success_init() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::PartialFreezeConfig {
+            default_restrictions: FREEZE_INCOMING,
+            default_reason: 7,
+        }])
+        .await
+        .unwrap();
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PartialFreezeConfig>().unwrap();
+    assert_eq!(extension.default_restrictions, FREEZE_INCOMING);
+    assert_eq!(u16::from(extension.default_reason), 7);
+
+    // new accounts get the default restrictions
+    let account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&account, &alice.pubkey())
+        .await
+        .unwrap();
+    let state = token.get_account_info(&account.pubkey()).await.unwrap();
+    let extension = state.get_extension::<PartialFreezeAccount>().unwrap();
+    assert_eq!(extension.restrictions, FREEZE_INCOMING);
+    assert_eq!(u16::from(extension.reason), 7);
+}
+
+#[tokio::test]
+async fn fail_init_no_authority_default_restrictions() {
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PartialFreezeConfig {
+            default_restrictions: FREEZE_OUTGOING,
+            default_reason: 0,
+        }])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::MintCannotFreeze as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn update_default_restrictions() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::PartialFreezeConfig {
+            default_restrictions: 0,
+            default_reason: 0,
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        freeze_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.unwrap();
+    let freeze_authority = freeze_authority.unwrap();
+
+    // fail, wrong authority
+    let err = token
+        .set_default_account_restrictions(&alice.pubkey(), FREEZE_OUTGOING, 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .set_default_account_restrictions(
+            &freeze_authority.pubkey(),
+            FREEZE_OUTGOING,
+            1,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PartialFreezeConfig>().unwrap();
+    assert_eq!(extension.default_restrictions, FREEZE_OUTGOING);
+    assert_eq!(u16::from(extension.default_reason), 1);
+}
+
+#[tokio::test]
+async fn partial_freeze_transfers() {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![ExtensionInitializationParams::PartialFreezeConfig {
+            default_restrictions: 0,
+            default_reason: 0,
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        freeze_authority,
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+    let freeze_authority = freeze_authority.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    let amount = 10;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail, unknown restriction
+    let err = token
+        .set_account_restrictions(
+            &alice_account,
+            &freeze_authority.pubkey(),
+            1 << 7,
+            0,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        )))
+    );
+
+    // freeze outgoing transfers from alice, with a reason
+    token
+        .set_account_restrictions(
+            &alice_account,
+            &freeze_authority.pubkey(),
+            FREEZE_OUTGOING,
+            42,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = state.get_extension::<PartialFreezeAccount>().unwrap();
+    assert_eq!(extension.restrictions, FREEZE_OUTGOING);
+    assert_eq!(u16::from(extension.reason), 42);
+
+    let err = token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AccountOutgoingFrozen as u32)
+            )
+        )))
+    );
+
+    let err = token
+        .burn(&alice_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AccountOutgoingFrozen as u32)
+            )
+        )))
+    );
+
+    // incoming credits still work
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // freeze incoming transfers to bob instead
+    token
+        .set_account_restrictions(
+            &alice_account,
+            &freeze_authority.pubkey(),
+            0,
+            0,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .set_account_restrictions(
+            &bob_account,
+            &freeze_authority.pubkey(),
+            FREEZE_INCOMING,
+            3,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+
+    let err = token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AccountIncomingFrozen as u32)
+            )
+        )))
+    );
+
+    let err = token
+        .mint_to(
+            &bob_account,
+            &mint_authority.pubkey(),
+            1,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AccountIncomingFrozen as u32)
+            )
+        )))
+    );
+
+    // alice can send again, just not to bob
+    let carol_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&carol_account, &bob.pubkey())
+        .await
+        .unwrap();
+    token
+        .transfer(
+            &alice_account,
+            &carol_account.pubkey(),
+            &alice.pubkey(),
+            1,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+}
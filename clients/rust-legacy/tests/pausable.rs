@@ -3,13 +3,13 @@ use {
     program_test::{TestContext, TokenContext},
     solana_program_test::tokio,
     solana_sdk::{
-        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
-        transaction::TransactionError, transport::TransportError,
+        clock::Clock, instruction::InstructionError, pubkey::Pubkey, signature::Signer,
+        signer::keypair::Keypair, transaction::TransactionError, transport::TransportError,
     },
     spl_token_2022_interface::{
         error::TokenError,
         extension::{
-            pausable::{PausableAccount, PausableConfig},
+            pausable::{PausableAccount, PausableConfig, PausableSchedule, PausedOperations},
             BaseStateWithExtensions,
         },
         instruction::AuthorityType,
@@ -352,3 +352,431 @@ async fn pause_transfer() {
         )))
     );
 }
+
+#[tokio::test]
+async fn scheduled_pause() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: authority.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+    let context = context.context;
+    let payer_pubkey = context.lock().await.payer.pubkey();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    let pause_timestamp = 1_000_000_000_000;
+    let resume_timestamp = pause_timestamp + 3_600;
+
+    // fail, resume before pause
+    let error = token
+        .schedule_pause_with_rent_transfer(
+            &payer_pubkey,
+            &authority.pubkey(),
+            resume_timestamp,
+            pause_timestamp,
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::InvalidPauseSchedule as u32)
+            )
+        )))
+    );
+
+    // fail, wrong authority
+    let wrong_authority = Keypair::new();
+    let error = token
+        .schedule_pause_with_rent_transfer(
+            &payer_pubkey,
+            &wrong_authority.pubkey(),
+            pause_timestamp,
+            resume_timestamp,
+            &[&wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .schedule_pause_with_rent_transfer(
+            &payer_pubkey,
+            &authority.pubkey(),
+            pause_timestamp,
+            resume_timestamp,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableSchedule>().unwrap();
+    assert_eq!(i64::from(extension.pause_timestamp), pause_timestamp);
+    assert_eq!(i64::from(extension.resume_timestamp), resume_timestamp);
+
+    // before the window, everything works
+    let amount = 10;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // inside the window, minting, burning, and transferring are paused
+    {
+        let context = context.lock().await;
+        context.set_sysvar(&Clock {
+            unix_timestamp: pause_timestamp,
+            ..Default::default()
+        });
+    }
+
+    let error = token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintPaused as u32)
+            )
+        )))
+    );
+
+    let error = token
+        .burn(&alice_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintPaused as u32)
+            )
+        )))
+    );
+
+    let error = token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintPaused as u32)
+            )
+        )))
+    );
+
+    // after the window, everything works again
+    {
+        let context = context.lock().await;
+        context.set_sysvar(&Clock {
+            unix_timestamp: resume_timestamp,
+            ..Default::default()
+        });
+    }
+
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+    token
+        .burn(&alice_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn resume_clears_scheduled_pause() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: authority.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.take().unwrap();
+    let context = context.context;
+    let payer_pubkey = context.lock().await.payer.pubkey();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    // open-ended pause starting now
+    let pause_timestamp = 1_000_000_000_000;
+    {
+        let context = context.lock().await;
+        context.set_sysvar(&Clock {
+            unix_timestamp: pause_timestamp,
+            ..Default::default()
+        });
+    }
+    token
+        .schedule_pause_with_rent_transfer(
+            &payer_pubkey,
+            &authority.pubkey(),
+            pause_timestamp,
+            0,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let amount = 10;
+    let error = token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintPaused as u32)
+            )
+        )))
+    );
+
+    token
+        .resume(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableSchedule>().unwrap();
+    assert_eq!(*extension, PausableSchedule::default());
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn pause_operations() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: authority.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    // fail, unknown operation
+    let error = token
+        .pause_operations_with_rent_transfer(
+            &payer_pubkey,
+            &authority.pubkey(),
+            1 << 7,
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::InvalidPausedOperations as u32)
+            )
+        )))
+    );
+
+    // pause transfers only
+    token
+        .pause_operations_with_rent_transfer(
+            &payer_pubkey,
+            &authority.pubkey(),
+            PausedOperations::TRANSFER,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausedOperations>().unwrap();
+    assert_eq!(extension.operations, PausedOperations::TRANSFER);
+
+    // minting and burning still work
+    let amount = 10;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .burn(&alice_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+
+    let error = token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintPaused as u32)
+            )
+        )))
+    );
+
+    // pause minting too, no more rent needed
+    token
+        .pause_operations(&authority.pubkey(), PausedOperations::MINT, &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausedOperations>().unwrap();
+    assert_eq!(
+        extension.operations,
+        PausedOperations::TRANSFER | PausedOperations::MINT
+    );
+
+    let error = token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintPaused as u32)
+            )
+        )))
+    );
+
+    // resume transfers
+    token
+        .resume_operations(
+            &authority.pubkey(),
+            PausedOperations::TRANSFER,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+
+    // full resume clears the remaining operations
+    token
+        .resume(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausedOperations>().unwrap();
+    assert_eq!(extension.operations, 0);
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+}
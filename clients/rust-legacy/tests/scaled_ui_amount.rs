@@ -22,7 +22,13 @@ use {
     },
     spl_token_2022_interface::{
         error::TokenError,
-        extension::{scaled_ui_amount::ScaledUiAmountConfig, BaseStateWithExtensions},
+        extension::{
+            scaled_ui_amount::{
+                RationalMultiplier, ScaledUiAmountConfig, ScaledUiAmountRationalConfig,
+                ScaledUiAmountSchedule,
+            },
+            BaseStateWithExtensions,
+        },
         instruction::{amount_to_ui_amount, ui_amount_to_amount, AuthorityType},
     },
     spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
@@ -428,3 +434,397 @@ async fn amount_conversions() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn rational_multiplier() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ScaledUiAmountConfig {
+                authority: Some(authority.pubkey()),
+                multiplier: 1.0,
+            },
+            ExtensionInitializationParams::ScaledUiAmountRational {
+                numerator: 10,
+                denominator: 3,
+            },
+        ])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.take().unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state
+        .get_extension::<ScaledUiAmountRationalConfig>()
+        .unwrap();
+    assert_eq!(extension.multiplier, RationalMultiplier::new(10, 3));
+    assert_eq!(extension.new_multiplier, RationalMultiplier::new(10, 3));
+    let extension = state.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), 10.0 / 3.0);
+
+    // the f64 multiplier can't be updated on its own
+    let err = token
+        .update_multiplier(&authority.pubkey(), 2.0, 0, &[&authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::RationalMultiplierRequired as u32)
+            )
+        )))
+    );
+
+    // zero is not a valid multiplier
+    let err = token
+        .update_rational_multiplier(&authority.pubkey(), 0, 1, 0, &[&authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InvalidScale as u32)
+            )
+        )))
+    );
+
+    // only the authority can update the multiplier
+    let wrong_signer = Keypair::new();
+    let err = token
+        .update_rational_multiplier(&wrong_signer.pubkey(), 7, 2, 0, &[&wrong_signer])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // both multipliers are updated together
+    let new_multiplier_timestamp = 1_000_000_000_000;
+    token
+        .update_rational_multiplier(
+            &authority.pubkey(),
+            7,
+            2,
+            new_multiplier_timestamp,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state
+        .get_extension::<ScaledUiAmountRationalConfig>()
+        .unwrap();
+    assert_eq!(extension.multiplier, RationalMultiplier::new(10, 3));
+    assert_eq!(extension.new_multiplier, RationalMultiplier::new(7, 2));
+    assert_eq!(
+        i64::from(extension.new_multiplier_effective_timestamp),
+        new_multiplier_timestamp
+    );
+    let extension = state.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), 10.0 / 3.0);
+    assert_eq!(f64::from(extension.new_multiplier), 3.5);
+    assert_eq!(
+        i64::from(extension.new_multiplier_effective_timestamp),
+        new_multiplier_timestamp
+    );
+}
+
+#[tokio::test]
+async fn fail_rational_without_scaled_ui_amount() {
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ScaledUiAmountRational {
+                numerator: 10,
+                denominator: 3,
+            },
+        ])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(1, InstructionError::InvalidAccountData)
+        )))
+    );
+}
+
+// test program to CPI into token to get exact ui amounts with a 10/3 multiplier
+fn process_rational_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _input: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    // just over 3 tokens, with 9 decimal places
+    let test_amount = 3_000_000_001;
+    invoke(
+        &amount_to_ui_amount(token_program.key, mint_info.key, test_amount)?,
+        &[mint_info.clone(), token_program.clone()],
+    )?;
+    let (_, return_data) = get_return_data().unwrap();
+    let ui_amount = String::from_utf8(return_data).unwrap();
+    msg!("ui amount: {}", ui_amount);
+    if ui_amount != "10.000000003" {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // the UI amount converts back to exactly the same raw amount
+    invoke(
+        &ui_amount_to_amount(token_program.key, mint_info.key, &ui_amount)?,
+        &[mint_info.clone(), token_program.clone()],
+    )?;
+    let (_, return_data) = get_return_data().unwrap();
+    let amount = u64::from_le_bytes(return_data[0..8].try_into().unwrap());
+    msg!("amount: {}", amount);
+    if amount != test_amount {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn rational_amount_conversions() {
+    let authority = Keypair::new();
+    let mut program_test = ProgramTest::default();
+    program_test.add_program("spl_token_2022", spl_token_2022_interface::id(), None);
+    program_test.prefer_bpf(false);
+    let program_id = Pubkey::new_unique();
+    program_test.add_program(
+        "rational_ui_amount_to_amount",
+        program_id,
+        processor!(process_rational_instruction),
+    );
+
+    let context = program_test.start_with_context().await;
+    let payer = keypair_clone(&context.payer);
+    let last_blockhash = context.last_blockhash;
+    let context = Arc::new(Mutex::new(context));
+    let mut context = TestContext {
+        context,
+        token_context: None,
+    };
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ScaledUiAmountConfig {
+                authority: Some(authority.pubkey()),
+                multiplier: 1.0,
+            },
+            ExtensionInitializationParams::ScaledUiAmountRational {
+                numerator: 10,
+                denominator: 3,
+            },
+        ])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.take().unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*token.get_address(), false),
+                AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
+            ],
+            data: vec![],
+        }],
+        Some(&payer.pubkey()),
+        &[&payer],
+        last_blockhash,
+    );
+    context
+        .context
+        .lock()
+        .await
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn multiplier_schedule() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::ScaledUiAmountConfig {
+            authority: Some(authority.pubkey()),
+            multiplier: 1.0,
+        }])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.take().unwrap();
+    let context = context.context;
+    let payer_pubkey = context.lock().await.payer.pubkey();
+
+    // queue a split, then a dividend adjustment
+    let split_timestamp = 1_000_000_000_000;
+    let dividend_timestamp = split_timestamp + 86_400;
+    for (multiplier, timestamp) in [(2.0, split_timestamp), (2.1, dividend_timestamp)] {
+        token
+            .schedule_multiplier_with_rent_transfer(
+                &payer_pubkey,
+                &authority.pubkey(),
+                multiplier,
+                timestamp,
+                &[&authority],
+            )
+            .await
+            .unwrap();
+    }
+
+    let state = token.get_mint_info().await.unwrap();
+    let schedule = state
+        .get_variable_len_extension::<ScaledUiAmountSchedule>()
+        .unwrap();
+    let entries = schedule
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                i64::from(entry.effective_timestamp),
+                f64::from(entry.multiplier),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![(0, 1.0), (split_timestamp, 2.0), (dividend_timestamp, 2.1)]
+    );
+    let extension = state.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), 1.0);
+    assert_eq!(f64::from(extension.new_multiplier), 2.0);
+    assert_eq!(
+        i64::from(extension.new_multiplier_effective_timestamp),
+        split_timestamp
+    );
+
+    // the single pending multiplier can't be overwritten anymore
+    let err = token
+        .update_multiplier(&authority.pubkey(), 3.0, 0, &[&authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MultiplierScheduleRequired as u32)
+            )
+        )))
+    );
+
+    // only the authority can change the schedule
+    let wrong_signer = Keypair::new();
+    let err = token
+        .cancel_scheduled_multiplier(&wrong_signer.pubkey(), dividend_timestamp, &[&wrong_signer])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // once the split takes effect, the config moves on to the next change
+    {
+        let context = context.lock().await;
+        context.set_sysvar(&Clock {
+            unix_timestamp: split_timestamp,
+            ..Default::default()
+        });
+    }
+
+    // past changes are history and can't be cancelled
+    let err = token
+        .cancel_scheduled_multiplier(&authority.pubkey(), split_timestamp, &[&authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+        )))
+    );
+
+    token
+        .cancel_scheduled_multiplier(&authority.pubkey(), dividend_timestamp, &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let schedule = state
+        .get_variable_len_extension::<ScaledUiAmountSchedule>()
+        .unwrap();
+    assert_eq!(schedule.entries.len(), 2);
+    // historical UI amounts can still be reconstructed
+    assert_eq!(
+        schedule
+            .amount_to_ui_amount(1_000_000_000, 9, split_timestamp - 1)
+            .unwrap(),
+        "1"
+    );
+    assert_eq!(
+        schedule
+            .amount_to_ui_amount(1_000_000_000, 9, split_timestamp)
+            .unwrap(),
+        "2"
+    );
+    let extension = state.get_extension::<ScaledUiAmountConfig>().unwrap();
+    assert_eq!(f64::from(extension.multiplier), 2.0);
+    assert_eq!(f64::from(extension.new_multiplier), 2.0);
+}
+
+#[tokio::test]
+async fn ui_amount_converter() {
+    let authority = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::ScaledUiAmountConfig {
+            authority: Some(authority.pubkey()),
+            multiplier: 2.0,
+        }])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.take().unwrap();
+
+    let future_timestamp = 1_000_000_000_000;
+    token
+        .update_multiplier(&authority.pubkey(), 3.0, future_timestamp, &[&authority])
+        .await
+        .unwrap();
+
+    let converter = token.get_ui_amount_converter().await.unwrap();
+    assert!(converter.is_time_dependent());
+    let one = 10u64.pow(converter.decimals() as u32);
+
+    // present and future balances
+    assert_eq!(
+        converter.amounts_to_ui_amounts(&[one, 2 * one], 0),
+        Some(vec!["2".to_string(), "4".to_string()])
+    );
+    assert_eq!(
+        converter.amounts_to_ui_amounts(&[one, 2 * one], future_timestamp),
+        Some(vec!["3".to_string(), "6".to_string()])
+    );
+    assert_eq!(
+        converter.try_ui_amounts_into_amounts(&["2", "6"], future_timestamp),
+        Ok(vec![one * 2 / 3, 2 * one])
+    );
+}
@@ -0,0 +1,506 @@
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::{tokio, ProgramTest},
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{
+            token_group::{TokenGroupMemberCounter, TokenGroupMembershipFreeze},
+            BaseStateWithExtensions,
+        },
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    spl_token_group_interface::{
+        error::TokenGroupError,
+        state::{TokenGroup, TokenGroupMember},
+    },
+    std::sync::Arc,
+};
+
+fn setup_program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    program_test.add_program("spl_token_2022", spl_token_2022_interface::id(), None);
+    program_test
+}
+
+async fn setup_group(context: &TestContext, group_authority: &Pubkey) -> TokenContext {
+    let mut group_context = TestContext {
+        context: context.context.clone(),
+        token_context: None,
+    };
+    let group_mint = Keypair::new();
+    let group_address = Some(group_mint.pubkey());
+    group_context
+        .init_token_with_mint_keypair_and_freeze_authority(
+            group_mint,
+            vec![ExtensionInitializationParams::GroupPointer {
+                authority: Some(*group_authority),
+                group_address,
+            }],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+    let group_token_context = group_context.token_context.take().unwrap();
+    group_token_context
+        .token
+        .token_group_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &group_token_context.mint_authority.pubkey(),
+            group_authority,
+            2,
+            &[&group_token_context.mint_authority],
+        )
+        .await
+        .unwrap();
+    group_token_context
+}
+
+async fn setup_member(
+    context: &TestContext,
+    group_token_context: &TokenContext,
+    group_authority: &Keypair,
+) -> (TokenContext, Keypair) {
+    let mut member_context = TestContext {
+        context: context.context.clone(),
+        token_context: None,
+    };
+    let member_mint = Keypair::new();
+    let member_address = Some(member_mint.pubkey());
+    let close_authority = Keypair::new();
+    member_context
+        .init_token_with_mint_keypair_and_freeze_authority(
+            member_mint,
+            vec![
+                ExtensionInitializationParams::GroupMemberPointer {
+                    authority: Some(close_authority.pubkey()),
+                    member_address,
+                },
+                ExtensionInitializationParams::MintCloseAuthority {
+                    close_authority: Some(close_authority.pubkey()),
+                },
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+    let member_token_context = member_context.token_context.take().unwrap();
+    member_token_context
+        .token
+        .token_group_initialize_member_with_rent_transfer(
+            &payer_pubkey,
+            &member_token_context.mint_authority.pubkey(),
+            group_token_context.token.get_address(),
+            &group_authority.pubkey(),
+            &[&member_token_context.mint_authority, group_authority],
+        )
+        .await
+        .unwrap();
+    (member_token_context, close_authority)
+}
+
+async fn group_size(group_token_context: &TokenContext) -> u64 {
+    let mint_info = group_token_context.token.get_mint_info().await.unwrap();
+    u64::from(mint_info.get_extension::<TokenGroup>().unwrap().size)
+}
+
+async fn member_of(member_token_context: &TokenContext) -> TokenGroupMember {
+    let mint_info = member_token_context.token.get_mint_info().await.unwrap();
+    *mint_info.get_extension::<TokenGroupMember>().unwrap()
+}
+
+#[tokio::test]
+async fn success_remove_and_move() {
+    let program_test = setup_program_test();
+    let context = program_test.start_with_context().await;
+    let context = TestContext {
+        context: Arc::new(tokio::sync::Mutex::new(context)),
+        token_context: None,
+    };
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+
+    let group_authority = Keypair::new();
+    let group1 = setup_group(&context, &group_authority.pubkey()).await;
+    let group2 = setup_group(&context, &group_authority.pubkey()).await;
+    let (member1, _) = setup_member(&context, &group1, &group_authority).await;
+    let (member2, close_authority) = setup_member(&context, &group1, &group_authority).await;
+    assert_eq!(group_size(&group1).await, 2);
+
+    // wrong authority
+    let wrong_authority = Keypair::new();
+    let error = member1
+        .token
+        .token_group_remove_member(
+            group1.token.get_address(),
+            &wrong_authority.pubkey(),
+            &[&wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenGroupError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+
+    // wrong group
+    let error = member1
+        .token
+        .token_group_remove_member(
+            group2.token.get_address(),
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintMismatch as u32)
+            )
+        )))
+    );
+
+    // the group update authority removes a member
+    member1
+        .token
+        .token_group_remove_member_with_rent_transfer(
+            &payer_pubkey,
+            group1.token.get_address(),
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group1).await, 1);
+    let member = member_of(&member1).await;
+    assert_eq!(member.group, Pubkey::default());
+    assert_eq!(u64::from(member.member_number), 0);
+
+    // the removed member joins another group
+    member1
+        .token
+        .token_group_initialize_member(
+            &member1.mint_authority.pubkey(),
+            group2.token.get_address(),
+            &group_authority.pubkey(),
+            &[&member1.mint_authority, &group_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group2).await, 1);
+    let member = member_of(&member1).await;
+    assert_eq!(member.group, *group2.token.get_address());
+    assert_eq!(u64::from(member.member_number), 1);
+
+    // move a member between groups
+    member2
+        .token
+        .token_group_move_member(
+            &member2.mint_authority.pubkey(),
+            group1.token.get_address(),
+            group2.token.get_address(),
+            &group_authority.pubkey(),
+            &[&member2.mint_authority, &group_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group1).await, 0);
+    assert_eq!(group_size(&group2).await, 2);
+    let member = member_of(&member2).await;
+    assert_eq!(member.group, *group2.token.get_address());
+    assert_eq!(u64::from(member.member_number), 2);
+
+    // a member mint can't be closed while in a group
+    let error = member2
+        .token
+        .close_account(
+            member2.token.get_address(),
+            &payer_pubkey,
+            &close_authority.pubkey(),
+            &[&close_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintIsGroupMember as u32)
+            )
+        )))
+    );
+
+    // the member mint authority removes its own mint
+    member2
+        .token
+        .token_group_remove_member_with_rent_transfer(
+            &payer_pubkey,
+            group2.token.get_address(),
+            &member2.mint_authority.pubkey(),
+            &[&member2.mint_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group2).await, 1);
+
+    // and can then be closed
+    member2
+        .token
+        .close_account(
+            member2.token.get_address(),
+            &payer_pubkey,
+            &close_authority.pubkey(),
+            &[&close_authority],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn success_unique_member_numbers() {
+    let program_test = setup_program_test();
+    let context = program_test.start_with_context().await;
+    let context = TestContext {
+        context: Arc::new(tokio::sync::Mutex::new(context)),
+        token_context: None,
+    };
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+
+    let group_authority = Keypair::new();
+    let group = setup_group(&context, &group_authority.pubkey()).await;
+    let (member1, _) = setup_member(&context, &group, &group_authority).await;
+    let (member2, _) = setup_member(&context, &group, &group_authority).await;
+    assert_eq!(u64::from(member_of(&member1).await.member_number), 1);
+    assert_eq!(u64::from(member_of(&member2).await.member_number), 2);
+
+    member1
+        .token
+        .token_group_remove_member_with_rent_transfer(
+            &payer_pubkey,
+            group.token.get_address(),
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group).await, 1);
+
+    // the next member doesn't reuse a number still held by a member
+    let (member3, _) = setup_member(&context, &group, &group_authority).await;
+    assert_eq!(group_size(&group).await, 2);
+    assert_eq!(u64::from(member_of(&member2).await.member_number), 2);
+    assert_eq!(u64::from(member_of(&member3).await.member_number), 3);
+    let mint_info = group.token.get_mint_info().await.unwrap();
+    let counter = mint_info
+        .get_extension::<TokenGroupMemberCounter>()
+        .unwrap();
+    assert_eq!(u64::from(counter.last_member_number), 3);
+
+    // nor does a removed member rejoining
+    member3
+        .token
+        .token_group_remove_member_with_rent_transfer(
+            &payer_pubkey,
+            group.token.get_address(),
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap();
+    member1
+        .token
+        .token_group_initialize_member(
+            &member1.mint_authority.pubkey(),
+            group.token.get_address(),
+            &group_authority.pubkey(),
+            &[&member1.mint_authority, &group_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(u64::from(member_of(&member1).await.member_number), 4);
+}
+
+#[tokio::test]
+async fn success_close_member_mint() {
+    let program_test = setup_program_test();
+    let context = program_test.start_with_context().await;
+    let context = TestContext {
+        context: Arc::new(tokio::sync::Mutex::new(context)),
+        token_context: None,
+    };
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+
+    let group_authority = Keypair::new();
+    let group = setup_group(&context, &group_authority.pubkey()).await;
+    let (member, close_authority) = setup_member(&context, &group, &group_authority).await;
+
+    // even a frozen membership doesn't keep the member mint from closing
+    group
+        .token
+        .token_group_freeze_membership_with_rent_transfer(
+            &payer_pubkey,
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap();
+
+    // the group mint is needed
+    let error = member
+        .token
+        .close_account(
+            member.token.get_address(),
+            &payer_pubkey,
+            &close_authority.pubkey(),
+            &[&close_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintIsGroupMember as u32)
+            )
+        )))
+    );
+
+    member
+        .token
+        .close_group_member_mint_with_rent_transfer(
+            &payer_pubkey,
+            group.token.get_address(),
+            &payer_pubkey,
+            &close_authority.pubkey(),
+            &[&close_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group).await, 0);
+    let mint_info = group.token.get_mint_info().await.unwrap();
+    let counter = mint_info
+        .get_extension::<TokenGroupMemberCounter>()
+        .unwrap();
+    assert_eq!(u64::from(counter.last_member_number), 1);
+}
+
+#[tokio::test]
+async fn fail_frozen_membership() {
+    let program_test = setup_program_test();
+    let context = program_test.start_with_context().await;
+    let context = TestContext {
+        context: Arc::new(tokio::sync::Mutex::new(context)),
+        token_context: None,
+    };
+    let payer_pubkey = context.context.lock().await.payer.pubkey();
+
+    let group_authority = Keypair::new();
+    let group1 = setup_group(&context, &group_authority.pubkey()).await;
+    let group2 = setup_group(&context, &group_authority.pubkey()).await;
+    let (member, _) = setup_member(&context, &group1, &group_authority).await;
+
+    // only the update authority can freeze
+    let wrong_authority = Keypair::new();
+    let error = group1
+        .token
+        .token_group_freeze_membership_with_rent_transfer(
+            &payer_pubkey,
+            &wrong_authority.pubkey(),
+            &[&wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenGroupError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+
+    group1
+        .token
+        .token_group_freeze_membership_with_rent_transfer(
+            &payer_pubkey,
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap();
+    let mint_info = group1.token.get_mint_info().await.unwrap();
+    let membership = mint_info
+        .get_extension::<TokenGroupMembershipFreeze>()
+        .unwrap();
+    assert!(bool::from(membership.frozen));
+
+    let frozen_error = TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::GroupMembershipFrozen as u32),
+        ),
+    )));
+
+    // members can't leave
+    let error = member
+        .token
+        .token_group_remove_member(
+            group1.token.get_address(),
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, frozen_error);
+
+    // or move out
+    let error = member
+        .token
+        .token_group_move_member(
+            &member.mint_authority.pubkey(),
+            group1.token.get_address(),
+            group2.token.get_address(),
+            &group_authority.pubkey(),
+            &[&member.mint_authority, &group_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error, frozen_error);
+    assert_eq!(group_size(&group1).await, 1);
+
+    // thaw, then leave
+    group1
+        .token
+        .token_group_thaw_membership(&group_authority.pubkey(), &[&group_authority])
+        .await
+        .unwrap();
+    member
+        .token
+        .token_group_remove_member_with_rent_transfer(
+            &payer_pubkey,
+            group1.token.get_address(),
+            &group_authority.pubkey(),
+            &[&group_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(group_size(&group1).await, 0);
+}
@@ -0,0 +1,321 @@
+#![allow(clippy::items_after_test_module)]
+mod program_test;
+use {
+    program_test::TestContext,
+    solana_program_test::{tokio, ProgramTest},
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{token_metadata::instruction::FieldUpdate, BaseStateWithExtensions},
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    spl_token_metadata_interface::{
+        error::TokenMetadataError,
+        state::{Field, TokenMetadata},
+    },
+    std::{convert::TryInto, sync::Arc},
+};
+
+fn setup_program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::default();
+    program_test.add_program("spl_token_2022", spl_token_2022_interface::id(), None);
+    program_test
+}
+
+async fn setup(mint: Keypair, authority: &Pubkey, max_len: Option<u32>) -> TestContext {
+    let program_test = setup_program_test();
+
+    let context = program_test.start_with_context().await;
+    let context = Arc::new(tokio::sync::Mutex::new(context));
+    let mut context = TestContext {
+        context,
+        token_context: None,
+    };
+    let metadata_address = Some(mint.pubkey());
+    let mut extensions = vec![ExtensionInitializationParams::MetadataPointer {
+        authority: Some(*authority),
+        metadata_address,
+    }];
+    if let Some(max_len) = max_len {
+        extensions.push(ExtensionInitializationParams::TokenMetadataSizeLimit { max_len });
+    }
+    context
+        .init_token_with_mint_keypair_and_freeze_authority(mint, extensions, None)
+        .await
+        .unwrap();
+    context
+}
+
+#[tokio::test]
+async fn success_update_fields() {
+    let authority = Keypair::new();
+    let mint_keypair = Keypair::new();
+    let mut test_context = setup(mint_keypair, &authority.pubkey(), None).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let token_context = test_context.token_context.take().unwrap();
+
+    let update_authority = Keypair::new();
+    let mut token_metadata = TokenMetadata {
+        name: "MySuperCoolToken".to_string(),
+        symbol: "MINE".to_string(),
+        uri: "my.super.cool.token".to_string(),
+        update_authority: Some(update_authority.pubkey()).try_into().unwrap(),
+        mint: *token_context.token.get_address(),
+        ..Default::default()
+    };
+
+    token_context
+        .token
+        .token_metadata_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            &token_context.mint_authority.pubkey(),
+            token_metadata.name.clone(),
+            token_metadata.symbol.clone(),
+            token_metadata.uri.clone(),
+            &[&token_context.mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let updates = vec![
+        FieldUpdate::Set {
+            field: Field::Name,
+            value: "My even cooler token".to_string(),
+        },
+        FieldUpdate::Set {
+            field: Field::Key("first".to_string()),
+            value: "first value".to_string(),
+        },
+        FieldUpdate::Set {
+            field: Field::Key("second".to_string()),
+            value: "second value".to_string(),
+        },
+        FieldUpdate::Set {
+            field: Field::Key("third".to_string()),
+            value: "third value".to_string(),
+        },
+        FieldUpdate::Remove {
+            key: "second".to_string(),
+        },
+    ];
+    token_metadata.update(Field::Name, "My even cooler token".to_string());
+    token_metadata.update(Field::Key("first".to_string()), "first value".to_string());
+    token_metadata.update(Field::Key("third".to_string()), "third value".to_string());
+
+    token_context
+        .token
+        .token_metadata_update_fields_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            updates,
+            None,
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+
+    let mint_info = token_context.token.get_mint_info().await.unwrap();
+    let fetched_metadata = mint_info
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata, token_metadata);
+
+    // removing a missing key fails the whole batch
+    let error = token_context
+        .token
+        .token_metadata_update_fields(
+            &update_authority.pubkey(),
+            vec![
+                FieldUpdate::Set {
+                    field: Field::Symbol,
+                    value: "NEW".to_string(),
+                },
+                FieldUpdate::Remove {
+                    key: "second".to_string(),
+                },
+            ],
+            &[&update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenMetadataError::KeyNotFound as u32)
+            )
+        )))
+    );
+
+    // wrong authority
+    let wrong_authority = Keypair::new();
+    let error = token_context
+        .token
+        .token_metadata_update_fields(
+            &wrong_authority.pubkey(),
+            vec![FieldUpdate::Set {
+                field: Field::Name,
+                value: "new_name".to_string(),
+            }],
+            &[&wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenMetadataError::IncorrectUpdateAuthority as u32)
+            )
+        )))
+    );
+
+    let mint_info = token_context.token.get_mint_info().await.unwrap();
+    let fetched_metadata = mint_info
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap();
+    assert_eq!(fetched_metadata, token_metadata);
+}
+
+#[tokio::test]
+async fn fail_size_limit() {
+    let authority = Keypair::new();
+    let mint_keypair = Keypair::new();
+    let mut test_context = setup(mint_keypair, &authority.pubkey(), Some(200)).await;
+    let payer_pubkey = test_context.context.lock().await.payer.pubkey();
+    let token_context = test_context.token_context.take().unwrap();
+
+    // metadata larger than the limit can't be initialized
+    let update_authority = Keypair::new();
+    let error = token_context
+        .token
+        .token_metadata_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            &token_context.mint_authority.pubkey(),
+            "MySuperCoolToken".to_string(),
+            "MINE".to_string(),
+            "x".repeat(200),
+            &[&token_context.mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(TokenError::TokenMetadataTooLarge as u32)
+            )
+        )))
+    );
+
+    token_context
+        .token
+        .token_metadata_initialize_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            &token_context.mint_authority.pubkey(),
+            "MySuperCoolToken".to_string(),
+            "MINE".to_string(),
+            "my.super.cool.token".to_string(),
+            &[&token_context.mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // small updates fit
+    token_context
+        .token
+        .token_metadata_update_fields_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            vec![FieldUpdate::Set {
+                field: Field::Key("small".to_string()),
+                value: "value".to_string(),
+            }],
+            None,
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+
+    // a single large field is rejected
+    let error = token_context
+        .token
+        .token_metadata_update_field(
+            &update_authority.pubkey(),
+            Field::Key("large".to_string()),
+            "x".repeat(100),
+            &[&update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::TokenMetadataTooLarge as u32)
+            )
+        )))
+    );
+
+    // a batch is rejected if the final metadata is too large
+    let error = token_context
+        .token
+        .token_metadata_update_fields_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            vec![
+                FieldUpdate::Set {
+                    field: Field::Key("first".to_string()),
+                    value: "x".repeat(40),
+                },
+                FieldUpdate::Set {
+                    field: Field::Key("second".to_string()),
+                    value: "x".repeat(40),
+                },
+            ],
+            Some(0),
+            &[&update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::TokenMetadataTooLarge as u32)
+            )
+        )))
+    );
+
+    // but fits once a field is removed in the same batch
+    token_context
+        .token
+        .token_metadata_update_fields_with_rent_transfer(
+            &payer_pubkey,
+            &update_authority.pubkey(),
+            vec![
+                FieldUpdate::Remove {
+                    key: "small".to_string(),
+                },
+                FieldUpdate::Set {
+                    field: Field::Key("first".to_string()),
+                    value: "x".repeat(40),
+                },
+            ],
+            None,
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+}
@@ -9,8 +9,10 @@ use {
     spl_token_2022_interface::{
         error::TokenError,
         extension::{
+            partial_freeze::FREEZE_INCOMING,
             transfer_fee::{
-                TransferFee, TransferFeeAmount, TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                TieredTransferFeeConfig, TransferFee, TransferFeeAmount, TransferFeeConfig,
+                TransferFeeExemption, TransferFeeTier, MAX_FEE_BASIS_POINTS,
             },
             BaseStateWithExtensions,
         },
@@ -1711,3 +1713,648 @@ async fn fail_close_with_withheld() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn tiered_transfer_fee() {
+    let TransferFeeConfigWithKeypairs {
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        transfer_fee_config,
+    } = test_transfer_fee_config_with_keypairs();
+    let transfer_fee_config_params = ExtensionInitializationParams::TransferFeeConfig {
+        transfer_fee_config_authority: transfer_fee_config_authority.pubkey().into(),
+        withdraw_withheld_authority: withdraw_withheld_authority.pubkey().into(),
+        transfer_fee_basis_points: TEST_FEE_BASIS_POINTS,
+        maximum_fee: TEST_MAXIMUM_FEE,
+    };
+    let tiers = vec![
+        TransferFeeTier {
+            minimum_amount: 0.into(),
+            transfer_fee_basis_points: 100.into(),
+        },
+        TransferFeeTier {
+            minimum_amount: 10_000.into(),
+            transfer_fee_basis_points: 10.into(),
+        },
+    ];
+    let tiered_transfer_fee_config_params =
+        ExtensionInitializationParams::TieredTransferFeeConfig {
+            maximum_fee: 5_000,
+            minimum_fee: 2,
+            tiers: tiers.clone(),
+        };
+
+    // fail, tiered fees need the transfer fee extension
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![tiered_transfer_fee_config_params.clone()])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InvalidExtensionCombination as u32),
+            )
+        )))
+    );
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            transfer_fee_config_params,
+            tiered_transfer_fee_config_params,
+        ])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TieredTransferFeeConfig>().unwrap();
+    assert_eq!(extension.newer_transfer_fee.tiers(), &tiers[..]);
+    assert_eq!(extension.newer_transfer_fee.minimum_fee, 2.into());
+    assert_eq!(extension.newer_transfer_fee.maximum_fee, 5_000.into());
+    let tiered_transfer_fee_config = *extension;
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+    let alice_amount = 1_000_000;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            alice_amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // the flat fee is no longer accepted
+    let transfer_amount = 1_000;
+    let fee = transfer_fee_config
+        .calculate_epoch_fee(0, transfer_amount)
+        .unwrap();
+    let error = token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            fee,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::FeeMismatch as u32)
+            )
+        )))
+    );
+
+    // minimum fee, first tier and second tier
+    let mut withheld_amount = 0;
+    let mut bob_amount = 0;
+    for (transfer_amount, expected_fee) in [(100, 2), (1_000, 10), (100_000, 100)] {
+        let fee = tiered_transfer_fee_config
+            .calculate_epoch_fee(0, transfer_amount)
+            .unwrap();
+        assert_eq!(fee, expected_fee);
+        token
+            .transfer_with_fee(
+                &alice_account,
+                &bob_account,
+                &alice.pubkey(),
+                transfer_amount,
+                fee,
+                &[&alice],
+            )
+            .await
+            .unwrap();
+        withheld_amount += fee;
+        bob_amount += transfer_amount - fee;
+    }
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, bob_amount);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, withheld_amount.into());
+
+    // fail, wrong authority
+    let error = token
+        .set_tiered_transfer_fee(&alice.pubkey(), 5_000, 0, &tiers, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // fail, tiers must start at 0
+    let error = token
+        .set_tiered_transfer_fee(
+            &transfer_fee_config_authority.pubkey(),
+            5_000,
+            0,
+            &tiers[1..],
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InvalidTransferFeeTiers as u32)
+            )
+        )))
+    );
+
+    // new schedule is set, but only used two epochs later
+    token
+        .set_tiered_transfer_fee(
+            &transfer_fee_config_authority.pubkey(),
+            10_000,
+            0,
+            &tiers[..1],
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TieredTransferFeeConfig>().unwrap();
+    assert_eq!(
+        extension.older_transfer_fee,
+        tiered_transfer_fee_config.newer_transfer_fee
+    );
+    assert_eq!(extension.newer_transfer_fee.tiers(), &tiers[..1]);
+    assert_eq!(
+        u64::from(extension.newer_transfer_fee.epoch),
+        u64::from(extension.older_transfer_fee.epoch) + 2
+    );
+}
+
+#[tokio::test]
+async fn transfer_fee_exemption() {
+    let TransferFeeConfigWithKeypairs {
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        transfer_fee_config,
+    } = test_transfer_fee_config_with_keypairs();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::TransferFeeConfig {
+            transfer_fee_config_authority: transfer_fee_config_authority.pubkey().into(),
+            withdraw_withheld_authority: withdraw_withheld_authority.pubkey().into(),
+            transfer_fee_basis_points: TEST_FEE_BASIS_POINTS,
+            maximum_fee: TEST_MAXIMUM_FEE,
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+    let alice_amount = 1_000_000;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            alice_amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail, wrong authority
+    let error = token
+        .set_transfer_fee_exemption(&bob_account, &bob.pubkey(), true, &[&bob])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // exempt bob, the account grows to fit the extension
+    token
+        .set_transfer_fee_exemption(
+            &bob_account,
+            &transfer_fee_config_authority.pubkey(),
+            true,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    let extension = bob_state.get_extension::<TransferFeeExemption>().unwrap();
+    assert!(bool::from(extension.exempt));
+
+    // no fee into or out of bob
+    let transfer_amount = 1_000;
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            0,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer_with_fee(
+            &bob_account,
+            &alice_account,
+            &bob.pubkey(),
+            transfer_amount / 2,
+            0,
+            &[&bob],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, transfer_amount / 2);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, 0.into());
+    let alice_state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = alice_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, 0.into());
+
+    // remove the exemption, fees are charged again
+    token
+        .set_transfer_fee_exemption(
+            &bob_account,
+            &transfer_fee_config_authority.pubkey(),
+            false,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    let error = token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            0,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::FeeMismatch as u32)
+            )
+        )))
+    );
+    let fee = transfer_fee_config
+        .calculate_epoch_fee(0, transfer_amount)
+        .unwrap();
+    token
+        .transfer_with_fee(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            fee,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, fee.into());
+}
+
+#[tokio::test]
+async fn transfer_fee_collector() {
+    let TransferFeeConfigWithKeypairs {
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        transfer_fee_config,
+    } = test_transfer_fee_config_with_keypairs();
+    let transfer_fee_config_params = ExtensionInitializationParams::TransferFeeConfig {
+        transfer_fee_config_authority: transfer_fee_config_authority.pubkey().into(),
+        withdraw_withheld_authority: withdraw_withheld_authority.pubkey().into(),
+        transfer_fee_basis_points: TEST_FEE_BASIS_POINTS,
+        maximum_fee: TEST_MAXIMUM_FEE,
+    };
+    let transfer_fee_collector_params =
+        ExtensionInitializationParams::TransferFeeCollector { collector: None };
+
+    // fail, the collector needs the transfer fee extension
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![transfer_fee_collector_params.clone()])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InvalidExtensionCombination as u32),
+            )
+        )))
+    );
+
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            transfer_fee_config_params,
+            transfer_fee_collector_params,
+        ])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+    let collector_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&collector_account, &transfer_fee_config_authority.pubkey())
+        .await
+        .unwrap();
+    let collector_account = collector_account.pubkey();
+    let alice_amount = 1_000_000;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            alice_amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail, wrong authority
+    let error = token
+        .set_transfer_fee_collector(&alice.pubkey(), Some(&collector_account), &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    token
+        .set_transfer_fee_collector(
+            &transfer_fee_config_authority.pubkey(),
+            Some(&collector_account),
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+
+    // the client adds the collector, which receives the fee directly
+    let transfer_amount = 10_000;
+    let fee = transfer_fee_config
+        .calculate_epoch_fee(0, transfer_amount)
+        .unwrap();
+    token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(bob_state.base.amount, transfer_amount - fee);
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, 0.into());
+    let collector_state = token.get_account_info(&collector_account).await.unwrap();
+    assert_eq!(collector_state.base.amount, fee);
+
+    // fail, collector not provided
+    let error = token
+        .process_ixs(
+            &[instruction::transfer_checked(
+                &spl_token_2022_interface::id(),
+                &alice_account,
+                token.get_address(),
+                &bob_account,
+                &alice.pubkey(),
+                &[],
+                transfer_amount,
+                token.get_mint_info().await.unwrap().base.decimals,
+            )
+            .unwrap()],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MissingFeeCollector as u32)
+            )
+        )))
+    );
+
+    // remove the collector, fees are withheld again
+    token
+        .set_transfer_fee_collector(
+            &transfer_fee_config_authority.pubkey(),
+            None,
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            transfer_amount,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let bob_state = token.get_account_info(&bob_account).await.unwrap();
+    let extension = bob_state.get_extension::<TransferFeeAmount>().unwrap();
+    assert_eq!(extension.withheld_amount, fee.into());
+    let collector_state = token.get_account_info(&collector_account).await.unwrap();
+    assert_eq!(collector_state.base.amount, fee);
+}
+
+#[tokio::test]
+async fn transfer_fee_collector_incoming_frozen() {
+    let TransferFeeConfigWithKeypairs {
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        ..
+    } = test_transfer_fee_config_with_keypairs();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_freezing_mint(vec![
+            ExtensionInitializationParams::TransferFeeConfig {
+                transfer_fee_config_authority: transfer_fee_config_authority.pubkey().into(),
+                withdraw_withheld_authority: withdraw_withheld_authority.pubkey().into(),
+                transfer_fee_basis_points: TEST_FEE_BASIS_POINTS,
+                maximum_fee: TEST_MAXIMUM_FEE,
+            },
+            ExtensionInitializationParams::TransferFeeCollector { collector: None },
+            ExtensionInitializationParams::PartialFreezeConfig {
+                default_restrictions: 0,
+                default_reason: 0,
+            },
+        ])
+        .await
+        .unwrap();
+    let TokenContext {
+        freeze_authority,
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.take().unwrap();
+    let freeze_authority = freeze_authority.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+    let collector_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&collector_account, &transfer_fee_config_authority.pubkey())
+        .await
+        .unwrap();
+    let collector_account = collector_account.pubkey();
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            1_000_000,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .set_transfer_fee_collector(
+            &transfer_fee_config_authority.pubkey(),
+            Some(&collector_account),
+            &[&transfer_fee_config_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail, the collector can't receive fees while incoming transfers are
+    // frozen
+    token
+        .set_account_restrictions(
+            &collector_account,
+            &freeze_authority.pubkey(),
+            FREEZE_INCOMING,
+            0,
+            &[&freeze_authority],
+        )
+        .await
+        .unwrap();
+    let error = token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice.pubkey(),
+            10_000,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AccountIncomingFrozen as u32)
+            )
+        )))
+    );
+    let collector_state = token.get_account_info(&collector_account).await.unwrap();
+    assert_eq!(collector_state.base.amount, 0);
+}
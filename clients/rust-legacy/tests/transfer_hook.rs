@@ -24,7 +24,7 @@ use {
         extension::{
             transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig},
             transfer_hook::{TransferHook, TransferHookAccount},
-            BaseStateWithExtensions,
+            BaseStateWithExtensions, ExtensionType,
         },
         instruction,
     },
@@ -481,6 +481,63 @@ async fn success_transfer() {
     );
 }
 
+#[tokio::test]
+async fn success_transfer_with_required_memo() {
+    let authority = Keypair::new();
+    let program_id = Pubkey::new_unique();
+    let mint_keypair = Keypair::new();
+    let token_context = setup(mint_keypair, &program_id, &authority.pubkey())
+        .await
+        .token_context
+        .take()
+        .unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) =
+        setup_accounts(&token_context, Keypair::new(), Keypair::new(), amount).await;
+
+    token_context
+        .token
+        .reallocate(
+            &bob_account,
+            &token_context.bob.pubkey(),
+            &[ExtensionType::MemoTransfer],
+            &[&token_context.bob],
+        )
+        .await
+        .unwrap();
+    token_context
+        .token
+        .enable_required_transfer_memos(
+            &bob_account,
+            &token_context.bob.pubkey(),
+            &[&token_context.bob],
+        )
+        .await
+        .unwrap();
+
+    // the memo is forwarded to the hook program after the amount, which the
+    // example program ignores
+    token_context
+        .token
+        .with_memo("🦖", vec![token_context.alice.pubkey()])
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+
+    let destination = token_context
+        .token
+        .get_account_info(&bob_account)
+        .await
+        .unwrap();
+    assert_eq!(destination.base.amount, amount);
+}
+
 #[tokio::test]
 async fn success_transfer_with_fee() {
     let authority = Keypair::new();
@@ -1,5 +1,5 @@
 use {
-    crate::{get_elgamal_registry_address, id},
+    crate::{get_elgamal_registry_address, id, state::ELGAMAL_KEY_LABEL_LEN},
     alloc::{vec, vec::Vec},
     solana_address::Address,
     solana_instruction::{AccountMeta, Instruction},
@@ -43,6 +43,56 @@ pub enum RegistryInstruction {
         /// for the proof.
         proof_instruction_offset: i8,
     },
+    /// Close an ElGamal public key registry and reclaim its lamports.
+    ///
+    /// 0. `[writable]` The ElGamal registry account
+    /// 1. `[writable]` The destination account for the lamports
+    /// 2. `[signer]` The owner of the ElGamal public key registry
+    CloseRegistry,
+    /// Transfer an ElGamal public key registry to a new owner.
+    ///
+    /// Since registry addresses are derived from the owner, the registry is
+    /// moved to the address derived from the new owner, along with its keys
+    /// and lamports, and the old registry account is closed. The new owner
+    /// must not have a registry already.
+    ///
+    /// 0. `[writable]` The ElGamal registry account
+    /// 1. `[writable]` The ElGamal registry account of the new owner, to be
+    ///    created
+    /// 2. `[signer]` The owner of the ElGamal public key registry
+    /// 3. `[signer]` The new owner
+    /// 4. `[]` System program
+    TransferRegistryOwnership,
+    /// Add an ElGamal public key to a registry under a label, or replace the
+    /// key that has this label.
+    ///
+    /// Adding a key grows the registry account by
+    /// `LABELLED_ELGAMAL_PUBKEY_LEN` bytes, so it must be funded beforehand
+    /// to remain rent-exempt.
+    ///
+    /// 0. `[writable]` The ElGamal registry account
+    /// 1. `[]` Instructions sysvar if `VerifyPubkeyValidity` is included in the
+    ///    same transaction or context state account if `VerifyPubkeyValidity`
+    ///    is pre-verified into a context state account.
+    /// 2. `[signer]` The owner of the ElGamal public key registry
+    SetLabelledKey {
+        /// Relative location of the `ProofInstruction::PubkeyValidityProof`
+        /// instruction to the `SetLabelledKey` instruction in the
+        /// transaction. If the offset is `0`, then use a context state account
+        /// for the proof.
+        proof_instruction_offset: i8,
+        /// The label of the key
+        label: [u8; ELGAMAL_KEY_LABEL_LEN],
+    },
+    /// Remove a labelled ElGamal public key from a registry. The lamports
+    /// freed by shrinking the account are reclaimed when closing it.
+    ///
+    /// 0. `[writable]` The ElGamal registry account
+    /// 1. `[signer]` The owner of the ElGamal public key registry
+    RemoveLabelledKey {
+        /// The label of the key
+        label: [u8; ELGAMAL_KEY_LABEL_LEN],
+    },
 }
 
 impl RegistryInstruction {
@@ -67,6 +117,20 @@ impl RegistryInstruction {
                     proof_instruction_offset: proof_instruction_offset as i8,
                 }
             }
+            2 => Self::CloseRegistry,
+            3 => Self::TransferRegistryOwnership,
+            4 => {
+                let (&proof_instruction_offset, rest) = rest
+                    .split_first()
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                Self::SetLabelledKey {
+                    proof_instruction_offset: proof_instruction_offset as i8,
+                    label: unpack_label(rest)?,
+                }
+            }
+            5 => Self::RemoveLabelledKey {
+                label: unpack_label(rest)?,
+            },
             _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
@@ -87,6 +151,20 @@ impl RegistryInstruction {
                 buf.push(1);
                 buf.extend_from_slice(&proof_instruction_offset.to_le_bytes());
             }
+            Self::CloseRegistry => buf.push(2),
+            Self::TransferRegistryOwnership => buf.push(3),
+            Self::SetLabelledKey {
+                proof_instruction_offset,
+                label,
+            } => {
+                buf.push(4);
+                buf.extend_from_slice(&proof_instruction_offset.to_le_bytes());
+                buf.extend_from_slice(label);
+            }
+            Self::RemoveLabelledKey { label } => {
+                buf.push(5);
+                buf.extend_from_slice(label);
+            }
         };
         buf
     }
@@ -141,6 +219,91 @@ pub fn update_registry(
     Ok(instructions)
 }
 
+/// Create a `RegistryInstruction::CloseRegistry` instruction
+pub fn close_registry(owner_address: &Address, destination: &Address) -> Instruction {
+    let elgamal_registry_address = get_elgamal_registry_address(owner_address, &id());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(elgamal_registry_address, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner_address, true),
+        ],
+        data: RegistryInstruction::CloseRegistry.pack(),
+    }
+}
+
+/// Create a `RegistryInstruction::TransferRegistryOwnership` instruction
+pub fn transfer_registry_ownership(
+    owner_address: &Address,
+    new_owner_address: &Address,
+) -> Instruction {
+    let elgamal_registry_address = get_elgamal_registry_address(owner_address, &id());
+    let new_elgamal_registry_address = get_elgamal_registry_address(new_owner_address, &id());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(elgamal_registry_address, false),
+            AccountMeta::new(new_elgamal_registry_address, false),
+            AccountMeta::new_readonly(*owner_address, true),
+            AccountMeta::new_readonly(*new_owner_address, true),
+            AccountMeta::new_readonly(solana_sdk_ids::system_program::id(), false),
+        ],
+        data: RegistryInstruction::TransferRegistryOwnership.pack(),
+    }
+}
+
+/// Create a `RegistryInstruction::SetLabelledKey` instruction
+pub fn set_labelled_key(
+    owner_address: &Address,
+    label: &[u8; ELGAMAL_KEY_LABEL_LEN],
+    proof_location: ProofLocation<PubkeyValidityProofData>,
+) -> Result<Vec<Instruction>, ProgramError> {
+    let elgamal_registry_address = get_elgamal_registry_address(owner_address, &id());
+
+    let mut accounts = vec![AccountMeta::new(elgamal_registry_address, false)];
+    let proof_instruction_offset = proof_instruction_offset(&mut accounts, proof_location);
+    accounts.push(AccountMeta::new_readonly(*owner_address, true));
+
+    let mut instructions = vec![Instruction {
+        program_id: id(),
+        accounts,
+        data: RegistryInstruction::SetLabelledKey {
+            proof_instruction_offset,
+            label: *label,
+        }
+        .pack(),
+    }];
+    append_zk_elgamal_proof(&mut instructions, proof_location)?;
+    Ok(instructions)
+}
+
+/// Create a `RegistryInstruction::RemoveLabelledKey` instruction
+pub fn remove_labelled_key(
+    owner_address: &Address,
+    label: &[u8; ELGAMAL_KEY_LABEL_LEN],
+) -> Instruction {
+    let elgamal_registry_address = get_elgamal_registry_address(owner_address, &id());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(elgamal_registry_address, false),
+            AccountMeta::new_readonly(*owner_address, true),
+        ],
+        data: RegistryInstruction::RemoveLabelledKey { label: *label }.pack(),
+    }
+}
+
+fn unpack_label(input: &[u8]) -> Result<[u8; ELGAMAL_KEY_LABEL_LEN], ProgramError> {
+    input
+        .get(..ELGAMAL_KEY_LABEL_LEN)
+        .and_then(|label| label.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
 /// Takes a `ProofLocation`, updates the list of accounts, and returns a
 /// suitable proof location
 fn proof_instruction_offset(
@@ -1,13 +1,23 @@
 use {
     bytemuck::{Pod, Zeroable},
     solana_address::Address,
+    solana_program_error::ProgramError,
     solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
 };
 
 pub const ELGAMAL_REGISTRY_ACCOUNT_LEN: usize = 64;
 
+/// Length of a label for an additional ElGamal public key
+pub const ELGAMAL_KEY_LABEL_LEN: usize = 32;
+
+/// Length of each labelled ElGamal public key stored after the registry
+pub const LABELLED_ELGAMAL_PUBKEY_LEN: usize = 64;
+
 /// ElGamal public key registry. It contains an ElGamal public key that is
 /// associated with a wallet account, but independent of any specific mint.
+///
+/// The registry account may be followed by any number of
+/// `LabelledElGamalPubkey` entries for wallets that use several keys.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
 pub struct ElGamalRegistry {
@@ -16,3 +26,62 @@ pub struct ElGamalRegistry {
     /// The ElGamal public key associated with an account
     pub elgamal_pubkey: PodElGamalPubkey,
 }
+
+/// Additional ElGamal public key of a registry, identified by a label chosen
+/// by the owner
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct LabelledElGamalPubkey {
+    /// The label of the key, unique within the registry
+    pub label: [u8; ELGAMAL_KEY_LABEL_LEN],
+    /// The ElGamal public key
+    pub elgamal_pubkey: PodElGamalPubkey,
+}
+
+impl ElGamalRegistry {
+    /// Size of a registry account holding `labelled_key_count` additional keys
+    pub fn account_len(labelled_key_count: usize) -> usize {
+        ELGAMAL_REGISTRY_ACCOUNT_LEN + labelled_key_count * LABELLED_ELGAMAL_PUBKEY_LEN
+    }
+
+    /// Unpacks the registry and its labelled keys from account data
+    pub fn unpack(data: &[u8]) -> Result<(&Self, &[LabelledElGamalPubkey]), ProgramError> {
+        let (registry, labelled_keys) = data
+            .split_at_checked(ELGAMAL_REGISTRY_ACCOUNT_LEN)
+            .ok_or(ProgramError::InvalidArgument)?;
+        Ok((
+            bytemuck::try_from_bytes(registry).map_err(|_| ProgramError::InvalidArgument)?,
+            bytemuck::try_cast_slice(labelled_keys).map_err(|_| ProgramError::InvalidArgument)?,
+        ))
+    }
+
+    /// Unpacks the registry and its labelled keys from mutable account data
+    pub fn unpack_mut(
+        data: &mut [u8],
+    ) -> Result<(&mut Self, &mut [LabelledElGamalPubkey]), ProgramError> {
+        let (registry, labelled_keys) = data
+            .split_at_mut_checked(ELGAMAL_REGISTRY_ACCOUNT_LEN)
+            .ok_or(ProgramError::InvalidArgument)?;
+        Ok((
+            bytemuck::try_from_bytes_mut(registry).map_err(|_| ProgramError::InvalidArgument)?,
+            bytemuck::try_cast_slice_mut(labelled_keys)
+                .map_err(|_| ProgramError::InvalidArgument)?,
+        ))
+    }
+
+    /// Returns the key to use for the given label, or the main key if no label
+    /// is given
+    pub fn get_elgamal_pubkey<'a>(
+        &'a self,
+        labelled_keys: &'a [LabelledElGamalPubkey],
+        label: Option<&[u8; ELGAMAL_KEY_LABEL_LEN]>,
+    ) -> Option<&'a PodElGamalPubkey> {
+        match label {
+            None => Some(&self.elgamal_pubkey),
+            Some(label) => labelled_keys
+                .iter()
+                .find(|key| key.label == *label)
+                .map(|key| &key.elgamal_pubkey),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use {
     solana_msg::msg,
     solana_program_error::{ProgramError, ProgramResult},
     solana_rent::Rent,
+    solana_sdk_ids::system_program,
     solana_system_interface::instruction::{allocate, assign},
     solana_sysvar::Sysvar,
     solana_zk_elgamal_proof_interface::proof_data::pubkey_validity::{
@@ -13,7 +14,10 @@ use {
     spl_elgamal_registry_interface::{
         get_elgamal_registry_address_and_bump_seed,
         instruction::RegistryInstruction,
-        state::{ElGamalRegistry, ELGAMAL_REGISTRY_ACCOUNT_LEN},
+        state::{
+            ElGamalRegistry, LabelledElGamalPubkey, ELGAMAL_KEY_LABEL_LEN,
+            ELGAMAL_REGISTRY_ACCOUNT_LEN, LABELLED_ELGAMAL_PUBKEY_LEN,
+        },
         REGISTRY_ADDRESS_SEED,
     },
     spl_token_confidential_transfer_proof_extraction::instruction::verify_and_extract_context,
@@ -64,9 +68,7 @@ pub fn process_create_registry_account(
     )?;
 
     let elgamal_registry_account_data = &mut elgamal_registry_account_info.data.borrow_mut();
-    let elgamal_registry_account =
-        bytemuck::try_from_bytes_mut::<ElGamalRegistry>(elgamal_registry_account_data)
-            .map_err(|_| ProgramError::InvalidArgument)?;
+    let (elgamal_registry_account, _) = ElGamalRegistry::unpack_mut(elgamal_registry_account_data)?;
     elgamal_registry_account.owner = *wallet_account_info.key;
     elgamal_registry_account.elgamal_pubkey = proof_context.pubkey;
 
@@ -82,9 +84,7 @@ pub fn process_update_registry_account(
     let account_info_iter = &mut accounts.iter();
     let elgamal_registry_account_info = next_account_info(account_info_iter)?;
     let elgamal_registry_account_data = &mut elgamal_registry_account_info.data.borrow_mut();
-    let elgamal_registry_account =
-        bytemuck::try_from_bytes_mut::<ElGamalRegistry>(elgamal_registry_account_data)
-            .map_err(|_| ProgramError::InvalidArgument)?;
+    let (elgamal_registry_account, _) = ElGamalRegistry::unpack_mut(elgamal_registry_account_data)?;
 
     // zero-knowledge proof certifies that the supplied ElGamal public key is valid
     let proof_context = verify_and_extract_context::<
@@ -100,6 +100,189 @@ pub fn process_update_registry_account(
     Ok(())
 }
 
+/// Processes `CloseRegistry` instruction
+pub fn process_close_registry_account(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let elgamal_registry_account_info = next_account_info(account_info_iter)?;
+    let destination_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+
+    validate_program_owner(elgamal_registry_account_info, program_id)?;
+    {
+        let elgamal_registry_account_data = elgamal_registry_account_info.data.borrow();
+        let (elgamal_registry_account, _) =
+            ElGamalRegistry::unpack(&elgamal_registry_account_data)?;
+        validate_registry_owner(owner_info, &elgamal_registry_account.owner)?;
+    }
+
+    if elgamal_registry_account_info.key == destination_account_info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let destination_starting_lamports = destination_account_info.lamports();
+    **destination_account_info.lamports.borrow_mut() = destination_starting_lamports
+        .checked_add(elgamal_registry_account_info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **elgamal_registry_account_info.lamports.borrow_mut() = 0;
+
+    delete_account(elgamal_registry_account_info)
+}
+
+/// Processes `TransferRegistryOwnership` instruction
+pub fn process_transfer_registry_ownership(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let elgamal_registry_account_info = next_account_info(account_info_iter)?;
+    let new_elgamal_registry_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let new_owner_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    validate_program_owner(elgamal_registry_account_info, program_id)?;
+    {
+        let elgamal_registry_account_data = elgamal_registry_account_info.data.borrow();
+        let (elgamal_registry_account, _) =
+            ElGamalRegistry::unpack(&elgamal_registry_account_data)?;
+        validate_registry_owner(owner_info, &elgamal_registry_account.owner)?;
+    }
+    if !new_owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (new_elgamal_registry_account_address, bump_seed) =
+        get_elgamal_registry_address_and_bump_seed(new_owner_info.key, program_id);
+    if new_elgamal_registry_account_address != *new_elgamal_registry_account_info.key {
+        msg!("Error: ElGamal registry account address does not match seed derivation");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let new_elgamal_registry_account_seeds: &[&[_]] = &[
+        REGISTRY_ADDRESS_SEED,
+        new_owner_info.key.as_ref(),
+        &[bump_seed],
+    ];
+    let rent = Rent::get()?;
+
+    // the lamports of the registry cover the rent of the new registry, since
+    // both have the same size
+    let new_registry_starting_lamports = new_elgamal_registry_account_info.lamports();
+    **new_elgamal_registry_account_info.lamports.borrow_mut() = new_registry_starting_lamports
+        .checked_add(elgamal_registry_account_info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **elgamal_registry_account_info.lamports.borrow_mut() = 0;
+
+    create_pda_account(
+        &rent,
+        elgamal_registry_account_info.data_len(),
+        program_id,
+        system_program_info,
+        new_elgamal_registry_account_info,
+        new_elgamal_registry_account_seeds,
+    )?;
+
+    {
+        let elgamal_registry_account_data = elgamal_registry_account_info.data.borrow();
+        let new_elgamal_registry_account_data =
+            &mut new_elgamal_registry_account_info.data.borrow_mut();
+        new_elgamal_registry_account_data.copy_from_slice(&elgamal_registry_account_data);
+        let (new_elgamal_registry_account, _) =
+            ElGamalRegistry::unpack_mut(new_elgamal_registry_account_data)?;
+        new_elgamal_registry_account.owner = *new_owner_info.key;
+    }
+
+    delete_account(elgamal_registry_account_info)
+}
+
+/// Processes `SetLabelledKey` instruction
+pub fn process_set_labelled_key(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    proof_instruction_offset: i64,
+    label: &[u8; ELGAMAL_KEY_LABEL_LEN],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let elgamal_registry_account_info = next_account_info(account_info_iter)?;
+
+    // zero-knowledge proof certifies that the supplied ElGamal public key is valid
+    let proof_context = verify_and_extract_context::<
+        PubkeyValidityProofData,
+        PubkeyValidityProofContext,
+    >(account_info_iter, proof_instruction_offset, None)?;
+
+    let owner_info = next_account_info(account_info_iter)?;
+    validate_program_owner(elgamal_registry_account_info, program_id)?;
+
+    let existing_index = {
+        let elgamal_registry_account_data = elgamal_registry_account_info.data.borrow();
+        let (elgamal_registry_account, labelled_keys) =
+            ElGamalRegistry::unpack(&elgamal_registry_account_data)?;
+        validate_registry_owner(owner_info, &elgamal_registry_account.owner)?;
+        labelled_keys.iter().position(|key| key.label == *label)
+    };
+
+    let index = match existing_index {
+        Some(index) => index,
+        None => {
+            let labelled_key_count = elgamal_registry_account_info
+                .data_len()
+                .saturating_sub(ELGAMAL_REGISTRY_ACCOUNT_LEN)
+                / LABELLED_ELGAMAL_PUBKEY_LEN;
+            let new_account_len = ElGamalRegistry::account_len(labelled_key_count + 1);
+
+            let rent = Rent::get()?;
+            if !rent.is_exempt(elgamal_registry_account_info.lamports(), new_account_len) {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+            elgamal_registry_account_info.resize(new_account_len)?;
+            labelled_key_count
+        }
+    };
+
+    let elgamal_registry_account_data = &mut elgamal_registry_account_info.data.borrow_mut();
+    let (_, labelled_keys) = ElGamalRegistry::unpack_mut(elgamal_registry_account_data)?;
+    labelled_keys[index] = LabelledElGamalPubkey {
+        label: *label,
+        elgamal_pubkey: proof_context.pubkey,
+    };
+    Ok(())
+}
+
+/// Processes `RemoveLabelledKey` instruction
+pub fn process_remove_labelled_key(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    label: &[u8; ELGAMAL_KEY_LABEL_LEN],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let elgamal_registry_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+
+    validate_program_owner(elgamal_registry_account_info, program_id)?;
+    {
+        let elgamal_registry_account_data = &mut elgamal_registry_account_info.data.borrow_mut();
+        let (elgamal_registry_account, labelled_keys) =
+            ElGamalRegistry::unpack_mut(elgamal_registry_account_data)?;
+        validate_registry_owner(owner_info, &elgamal_registry_account.owner)?;
+
+        let index = labelled_keys
+            .iter()
+            .position(|key| key.label == *label)
+            .ok_or(ProgramError::InvalidArgument)?;
+        // keep the remaining keys in order, the removed key ends up last
+        labelled_keys[index..].rotate_left(1);
+    }
+
+    let new_account_len = elgamal_registry_account_info
+        .data_len()
+        .saturating_sub(LABELLED_ELGAMAL_PUBKEY_LEN);
+    elgamal_registry_account_info.resize(new_account_len)
+}
+
 /// Instruction processor
 pub fn process_instruction(
     program_id: &Address,
@@ -120,6 +303,30 @@ pub fn process_instruction(
             msg!("ElGamalRegistryInstruction::UpdateRegistry");
             process_update_registry_account(program_id, accounts, proof_instruction_offset as i64)
         }
+        RegistryInstruction::CloseRegistry => {
+            msg!("ElGamalRegistryInstruction::CloseRegistry");
+            process_close_registry_account(program_id, accounts)
+        }
+        RegistryInstruction::TransferRegistryOwnership => {
+            msg!("ElGamalRegistryInstruction::TransferRegistryOwnership");
+            process_transfer_registry_ownership(program_id, accounts)
+        }
+        RegistryInstruction::SetLabelledKey {
+            proof_instruction_offset,
+            label,
+        } => {
+            msg!("ElGamalRegistryInstruction::SetLabelledKey");
+            process_set_labelled_key(
+                program_id,
+                accounts,
+                proof_instruction_offset as i64,
+                &label,
+            )
+        }
+        RegistryInstruction::RemoveLabelledKey { label } => {
+            msg!("ElGamalRegistryInstruction::RemoveLabelledKey");
+            process_remove_labelled_key(program_id, accounts, &label)
+        }
     }
 }
 
@@ -140,6 +347,12 @@ fn validate_program_owner(registry_info: &AccountInfo, program_id: &Address) ->
     Ok(())
 }
 
+/// Helper function to delete a registry account, once its lamports are moved
+fn delete_account(account_info: &AccountInfo) -> ProgramResult {
+    account_info.assign(&system_program::id());
+    account_info.resize(0)
+}
+
 /// Allocate ElGamal registry account using Program Derived Address for the
 /// given seeds
 pub fn create_pda_account<'a>(
@@ -262,12 +262,61 @@ pub enum TokenError {
     /// Invalid scale for scaled ui amount
     #[error("Invalid scale for scaled ui amount")]
     InvalidScale,
-    /// Transferring, minting, and burning is paused on this mint
-    #[error("Transferring, minting, and burning is paused on this mint")]
+    /// Transferring, minting, or burning is paused on this mint
+    #[error("Transferring, minting, or burning is paused on this mint")]
     MintPaused,
     /// Pending supply is not zero
     #[error("Key rotation attempted while pending balance is not zero")]
     PendingBalanceNonZero,
+    /// Scheduled pause window is invalid
+    #[error("Scheduled pause window is invalid")]
+    InvalidPauseSchedule,
+
+    // 70
+    /// Invalid set of operations to pause or resume
+    #[error("Invalid set of operations to pause or resume")]
+    InvalidPausedOperations,
+    /// Transfers out of the account are frozen
+    #[error("Transfers out of the account are frozen")]
+    AccountOutgoingFrozen,
+    /// Transfers into the account are frozen
+    #[error("Transfers into the account are frozen")]
+    AccountIncomingFrozen,
+    /// Transfer fee tiers are invalid
+    #[error("Transfer fee tiers are invalid")]
+    InvalidTransferFeeTiers,
+    /// The mint routes fees to a collector account, which was not provided
+    #[error("The mint routes fees to a collector account, which was not provided")]
+    MissingFeeCollector,
+
+    // 75
+    /// The mint uses a rational multiplier, which must be updated instead
+    #[error("The mint uses a rational multiplier, which must be updated instead")]
+    RationalMultiplierRequired,
+    /// The mint has a multiplier schedule, which must be used to change the
+    /// multiplier
+    #[error("The mint has a multiplier schedule, which must be used to change the multiplier")]
+    MultiplierScheduleRequired,
+    /// The interest rate is outside of the bounds configured for the mint
+    #[error("The interest rate is outside of the bounds configured for the mint")]
+    InterestRateOutOfBounds,
+    /// The token-metadata exceeds the size limit configured for the mint
+    #[error("The token-metadata exceeds the size limit configured for the mint")]
+    TokenMetadataTooLarge,
+    /// The membership of the group is frozen
+    #[error("The membership of the group is frozen")]
+    GroupMembershipFrozen,
+
+    // 80
+    /// The mint is still a member of a group
+    #[error("The mint is still a member of a group")]
+    MintIsGroupMember,
+    /// The memo does not satisfy the memo policy of the destination account
+    #[error("The memo does not satisfy the memo policy of the destination account")]
+    MemoPolicyViolation,
+    /// The account state attestor does not match the one registered on the mint
+    #[error("The account state attestor does not match the one registered on the mint")]
+    InvalidAccountStateAttestor,
 }
 impl From<TokenError> for ProgramError {
     fn from(e: TokenError) -> Self {
@@ -460,11 +509,49 @@ impl ToStr for TokenError {
                 "Invalid scale for scaled ui amount"
             }
             TokenError::MintPaused => {
-                "Transferring, minting, and burning is paused on this mint"
+                "Transferring, minting, or burning is paused on this mint"
             }
             TokenError::PendingBalanceNonZero => {
                 "Key rotation attempted while pending balance is not zero"
             }
+            TokenError::InvalidPauseSchedule => {
+                "Scheduled pause window is invalid"
+            }
+            TokenError::InvalidPausedOperations => {
+                "Invalid set of operations to pause or resume"
+            }
+            TokenError::AccountOutgoingFrozen => {
+                "Transfers out of the account are frozen"
+            }
+            TokenError::AccountIncomingFrozen => {
+                "Transfers into the account are frozen"
+            }
+            TokenError::InvalidTransferFeeTiers => {
+                "Transfer fee tiers are invalid"
+            }
+            TokenError::MissingFeeCollector => {
+                "The mint routes fees to a collector account, which was not provided"
+            }
+            TokenError::RationalMultiplierRequired => {
+                "The mint uses a rational multiplier, which must be updated instead"
+            }
+            TokenError::MultiplierScheduleRequired => {
+                "The mint has a multiplier schedule, which must be used to change the multiplier"
+            }
+            TokenError::InterestRateOutOfBounds => {
+                "The interest rate is outside of the bounds configured for the mint"
+            }
+            TokenError::TokenMetadataTooLarge => {
+                "The token-metadata exceeds the size limit configured for the mint"
+            }
+            TokenError::GroupMembershipFrozen => "The membership of the group is frozen",
+            TokenError::MintIsGroupMember => "The mint is still a member of a group",
+            TokenError::MemoPolicyViolation => {
+                "The memo does not satisfy the memo policy of the destination account"
+            }
+            TokenError::InvalidAccountStateAttestor => {
+                "The account state attestor does not match the one registered on the mint"
+            }
         }
     }
 }
@@ -492,7 +492,9 @@ pub enum ConfidentialTransferInstruction {
     ///   4. `[]` (Optional) System program for reallocation funding
     ///
     /// Data expected by this instruction:
-    ///   None
+    ///   None to use the main key of the registry, or
+    ///   `ConfigureAccountWithRegistryInstructionData` to use one of its
+    ///   labelled keys
     ConfigureAccountWithRegistry,
 }
 
@@ -682,6 +684,17 @@ pub struct TransferWithFeeInstructionData {
     pub range_proof_instruction_offset: i8,
 }
 
+/// Data expected by `ConfidentialTransferInstruction::ConfigureAccountWithRegistry`
+/// to use a labelled key of the registry
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ConfigureAccountWithRegistryInstructionData {
+    /// The label of the registry key to use
+    pub elgamal_key_label: ElGamalKeyLabel,
+}
+
 /// Create a `InitializeMint` instruction
 pub fn initialize_mint(
     token_program_id: &Address,
@@ -1676,3 +1689,24 @@ pub fn configure_account_with_registry(
         &(),
     ))
 }
+
+/// Create a `ConfigureAccountWithRegistry` instruction using a labelled key of
+/// the registry
+pub fn configure_account_with_registry_labelled_key(
+    token_program_id: &Address,
+    token_account: &Address,
+    mint: &Address,
+    elgamal_registry_account: &Address,
+    elgamal_key_label: &ElGamalKeyLabel,
+    payer: Option<&Address>,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = configure_account_with_registry(
+        token_program_id,
+        token_account,
+        mint,
+        elgamal_registry_account,
+        payer,
+    )?;
+    instruction.data.extend_from_slice(elgamal_key_label);
+    Ok(instruction)
+}
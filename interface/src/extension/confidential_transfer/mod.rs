@@ -32,6 +32,9 @@ pub mod instruction;
 pub type EncryptedBalance = PodElGamalCiphertext;
 /// Authenticated encryption containing an account balance
 pub type DecryptableBalance = PodAeCiphertext;
+/// Label of an additional ElGamal public key in an ElGamal registry, sized
+/// as `spl_elgamal_registry_interface::state::ELGAMAL_KEY_LABEL_LEN`
+pub type ElGamalKeyLabel = [u8; 32];
 
 /// Confidential transfer mint configuration
 #[repr(C)]
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use {
     crate::{
         check_program_account,
+        extension::cpi_guard::CpiGuardPolicy,
         instruction::{encode_instruction, TokenInstruction},
     },
     alloc::vec,
@@ -52,6 +53,30 @@ pub enum CpiGuardInstruction {
     ///   1. `[]`  The account's multisignature owner.
     ///   2. `..2+M` `[signer]` M signer accounts.
     Disable,
+    /// Set the operations allowed through CPI while the CPI Guard is enabled,
+    /// optionally restricted to a set of calling programs. Adds the
+    /// `CpiGuardPolicy` extension to the Account, if it doesn't already
+    /// exist. A zeroed policy allows nothing.
+    ///
+    /// The calling program is only known for direct CPIs from a top-level
+    /// instruction which include the Instructions sysvar in their accounts.
+    /// Operations restricted to an allowlist are blocked otherwise.
+    ///
+    /// Like the CPI Guard itself, the policy cannot be set via CPI.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The account to update.
+    ///   1. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The account to update.
+    ///   1. `[]` The account's multisignature owner.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `CpiGuardPolicy`
+    SetPolicy,
 }
 
 /// Create an `Enable` instruction
@@ -101,3 +126,28 @@ pub fn disable_cpi_guard(
         &(),
     ))
 }
+
+/// Create a `SetPolicy` instruction
+pub fn set_cpi_guard_policy(
+    token_program_id: &Address,
+    account: &Address,
+    owner: &Address,
+    signers: &[&Address],
+    policy: &CpiGuardPolicy,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*owner, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::CpiGuardExtension,
+        CpiGuardInstruction::SetPolicy,
+        policy,
+    ))
+}
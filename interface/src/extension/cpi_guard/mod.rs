@@ -6,6 +6,8 @@ use {
         state::Account,
     },
     bytemuck::{Pod, Zeroable},
+    solana_address::Address,
+    solana_program_error::ProgramError,
     solana_zero_copy::unaligned::Bool,
 };
 
@@ -32,3 +34,134 @@ pub fn cpi_guard_enabled(account_state: &StateWithExtensionsMut<Account>) -> boo
     }
     false
 }
+
+/// Maximum number of calling programs in a `CpiGuardPolicy` allowlist
+pub const MAX_CPI_GUARD_ALLOWED_PROGRAMS: usize = 4;
+
+/// Exceptions to the CPI Guard for an Account, as a bitmask of
+/// `CpiGuardPolicy::TRANSFER`, `CpiGuardPolicy::BURN`,
+/// `CpiGuardPolicy::APPROVE`, `CpiGuardPolicy::CLOSE_ACCOUNT`, and
+/// `CpiGuardPolicy::SET_AUTHORITY`, optionally restricted to an allowlist of
+/// calling programs.
+///
+/// Only consulted while the CPI Guard is enabled. A zeroed policy allows
+/// nothing, matching the behavior without the extension.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct CpiGuardPolicy {
+    /// Bitmask of the operations allowed through CPI
+    pub allowed_operations: u8,
+    /// Number of programs in the allowlist
+    pub allowed_programs_len: u8,
+    /// Calling programs the allowed operations are restricted to, padded with
+    /// default addresses. If empty, any calling program is allowed.
+    pub allowed_programs: [Address; MAX_CPI_GUARD_ALLOWED_PROGRAMS],
+}
+impl CpiGuardPolicy {
+    /// Owner-signed transfers, including unwrapping lamports, withdrawing
+    /// excess lamports, and confidential transfers
+    pub const TRANSFER: u8 = 1 << 0;
+    /// Owner-signed burns, including confidential burns
+    pub const BURN: u8 = 1 << 1;
+    /// Approving a delegate
+    pub const APPROVE: u8 = 1 << 2;
+    /// Closing the account to a destination other than the owner
+    pub const CLOSE_ACCOUNT: u8 = 1 << 3;
+    /// Setting a close authority. Changing the owner is always blocked while
+    /// the CPI Guard is enabled.
+    pub const SET_AUTHORITY: u8 = 1 << 4;
+    /// All operations that can be allowed
+    pub const ALL: u8 =
+        Self::TRANSFER | Self::BURN | Self::APPROVE | Self::CLOSE_ACCOUNT | Self::SET_AUTHORITY;
+
+    /// Create a new policy, failing if the operations are unknown or there are
+    /// too many programs
+    pub fn new(allowed_operations: u8, allowed_programs: &[Address]) -> Result<Self, ProgramError> {
+        if allowed_programs.len() > MAX_CPI_GUARD_ALLOWED_PROGRAMS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut policy = Self {
+            allowed_operations,
+            allowed_programs_len: allowed_programs.len() as u8,
+            ..Default::default()
+        };
+        policy.allowed_programs[..allowed_programs.len()].copy_from_slice(allowed_programs);
+        if !policy.is_valid() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(policy)
+    }
+
+    /// Check that the policy only contains known operations and fits its
+    /// allowlist
+    pub fn is_valid(&self) -> bool {
+        self.allowed_operations & !Self::ALL == 0
+            && self.allowed_programs_len as usize <= MAX_CPI_GUARD_ALLOWED_PROGRAMS
+    }
+
+    /// Get the calling programs the allowed operations are restricted to
+    pub fn allowed_programs(&self) -> Result<&[Address], ProgramError> {
+        self.allowed_programs
+            .get(..self.allowed_programs_len as usize)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// Check if the operation is allowed through CPI from the given calling
+    /// program, which is `None` if it couldn't be determined
+    pub fn allows(&self, operation: u8, calling_program: Option<&Address>) -> bool {
+        if self.allowed_operations & operation == 0 {
+            return false;
+        }
+        match self.allowed_programs() {
+            Ok([]) => true,
+            Ok(allowed_programs) => calling_program
+                .is_some_and(|calling_program| allowed_programs.contains(calling_program)),
+            Err(_) => false,
+        }
+    }
+}
+impl Extension for CpiGuardPolicy {
+    const TYPE: ExtensionType = ExtensionType::CpiGuardPolicy;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpi_guard_policy() {
+        let program = Address::new_unique();
+        let other_program = Address::new_unique();
+
+        let policy = CpiGuardPolicy::default();
+        assert!(policy.is_valid());
+        assert!(!policy.allows(CpiGuardPolicy::TRANSFER, None));
+        assert!(!policy.allows(CpiGuardPolicy::TRANSFER, Some(&program)));
+
+        let policy = CpiGuardPolicy::new(CpiGuardPolicy::BURN, &[]).unwrap();
+        assert!(policy.allows(CpiGuardPolicy::BURN, None));
+        assert!(policy.allows(CpiGuardPolicy::BURN, Some(&program)));
+        assert!(!policy.allows(CpiGuardPolicy::APPROVE, Some(&program)));
+
+        let policy = CpiGuardPolicy::new(CpiGuardPolicy::ALL, &[program]).unwrap();
+        assert_eq!(policy.allowed_programs().unwrap(), [program]);
+        assert!(policy.allows(CpiGuardPolicy::TRANSFER, Some(&program)));
+        assert!(!policy.allows(CpiGuardPolicy::TRANSFER, Some(&other_program)));
+        assert!(!policy.allows(CpiGuardPolicy::TRANSFER, None));
+
+        assert_eq!(
+            CpiGuardPolicy::new(1 << 5, &[]).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            CpiGuardPolicy::new(
+                CpiGuardPolicy::TRANSFER,
+                &[program; MAX_CPI_GUARD_ALLOWED_PROGRAMS + 1]
+            )
+            .unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+}
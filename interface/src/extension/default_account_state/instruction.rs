@@ -1,17 +1,22 @@
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
 use {
     crate::{
         check_program_account, error::TokenError, instruction::TokenInstruction,
         state::AccountState,
     },
     alloc::{vec, vec::Vec},
+    bytemuck::{Pod, Zeroable},
     core::convert::TryFrom,
     num_enum::{IntoPrimitive, TryFromPrimitive},
     solana_address::Address,
     solana_instruction::{AccountMeta, Instruction},
+    solana_nullable::MaybeNull,
     solana_program_error::ProgramError,
 };
+#[cfg(feature = "serde")]
+use {
+    serde::{Deserialize, Serialize},
+    serde_with::{As, DisplayFromStr},
+};
 
 /// Default Account State extension instructions
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -52,6 +57,41 @@ pub enum DefaultAccountStateInstruction {
     /// Data expected by this instruction:
     ///   `crate::state::AccountState`
     Update,
+    /// Register or clear the signer that can approve new Accounts starting in
+    /// the `Initialized` state, by signing `InitializeAccount`,
+    /// `InitializeAccount2` or `InitializeAccount3` as an additional, trailing
+    /// account. Only supported for mints that include the
+    /// `DefaultAccountState` extension.
+    ///
+    /// Adds the `DefaultAccountStateAttestor` extension to the mint if it
+    /// doesn't already exist, reallocating the mint, so it must hold enough
+    /// lamports for the new rent-exemption.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The mint freeze authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature freeze authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::default_account_state::instruction::SetAttestorInstructionData`
+    SetAttestor,
+}
+
+/// Data expected by `SetAttestor`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct SetAttestorInstructionData {
+    /// The new attestor, or none to clear it
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DisplayFromStr>>"))]
+    pub attestor: MaybeNull<Address>,
 }
 
 /// Utility function for decoding a `DefaultAccountState` instruction and its
@@ -124,3 +164,35 @@ pub fn update_default_account_state(
         state,
     ))
 }
+
+/// Create a `SetAttestor` instruction
+pub fn set_default_account_state_attestor(
+    token_program_id: &Address,
+    mint: &Address,
+    freeze_authority: &Address,
+    signers: &[&Address],
+    attestor: Option<&Address>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let data = SetAttestorInstructionData {
+        attestor: attestor
+            .copied()
+            .try_into()
+            .map_err(|_| ProgramError::InvalidArgument)?,
+    };
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*freeze_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    let mut instruction_data = TokenInstruction::DefaultAccountStateExtension.pack();
+    instruction_data.push(DefaultAccountStateInstruction::SetAttestor.into());
+    instruction_data.extend_from_slice(bytemuck::bytes_of(&data));
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: instruction_data,
+    })
+}
@@ -1,8 +1,13 @@
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
 use {
     crate::extension::{Extension, ExtensionType},
     bytemuck::{Pod, Zeroable},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+};
+#[cfg(feature = "serde")]
+use {
+    serde::{Deserialize, Serialize},
+    serde_with::{As, DisplayFromStr},
 };
 
 /// Default Account state extension instructions
@@ -21,4 +26,22 @@ impl Extension for DefaultAccountState {
     const TYPE: ExtensionType = ExtensionType::DefaultAccountState;
 }
 
+/// Signer registered by the freeze authority of a mint with the
+/// `DefaultAccountState` extension, which can approve new Accounts starting in
+/// the `Initialized` state instead of the default state by co-signing their
+/// initialization
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct DefaultAccountStateAttestor {
+    /// Signer, possibly a program-derived address, that can approve new
+    /// Accounts starting in the `Initialized` state
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DisplayFromStr>>"))]
+    pub attestor: MaybeNull<Address>,
+}
+impl Extension for DefaultAccountStateAttestor {
+    const TYPE: ExtensionType = ExtensionType::DefaultAccountStateAttestor;
+}
+
 type PodAccountState = u8;
@@ -1,7 +1,7 @@
 use {
     crate::{
         check_program_account,
-        extension::interest_bearing_mint::BasisPoints,
+        extension::interest_bearing_mint::{BasisPoints, CompoundingPeriod, InterestBearingTerms},
         instruction::{encode_instruction, TokenInstruction},
     },
     alloc::vec,
@@ -57,6 +57,41 @@ pub enum InterestBearingMintInstruction {
     /// Data expected by this instruction:
     ///   `crate::extension::interest_bearing_mint::BasisPoints`
     UpdateRate,
+    /// Initialize the compounding period and rate bounds of a new
+    /// interest-bearing mint.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`, and after `InterestBearing::Initialize`, whose rate
+    /// must fall within the bounds.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::interest_bearing_mint::InterestBearingTerms`
+    InitializeTerms,
+    /// Update the compounding period and rate bounds, along with the interest
+    /// rate. Only supported for mints that include the `InterestBearingTerms`
+    /// extension.
+    ///
+    /// Interest accrued so far is preserved: the average rate is settled under
+    /// the previous compounding period, then converted to the new one.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The mint rate authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature rate authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::interest_bearing_mint::instruction::UpdateTermsInstructionData`
+    UpdateTerms,
 }
 
 /// Data expected by `InterestBearing::Initialize`
@@ -72,6 +107,18 @@ pub struct InitializeInstructionData {
     pub rate: BasisPoints,
 }
 
+/// Data expected by `InterestBearing::UpdateTerms`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct UpdateTermsInstructionData {
+    /// The new compounding period and rate bounds
+    pub terms: InterestBearingTerms,
+    /// The new interest rate, which must fall within the new bounds
+    pub rate: BasisPoints,
+}
+
 /// Create an `Initialize` instruction
 pub fn initialize(
     token_program_id: &Address,
@@ -119,3 +166,54 @@ pub fn update_rate(
         &BasisPoints::from(rate),
     ))
 }
+
+/// Create an `InitializeTerms` instruction
+pub fn initialize_terms(
+    token_program_id: &Address,
+    mint: &Address,
+    compounding_period: CompoundingPeriod,
+    minimum_rate: Option<i16>,
+    maximum_rate: Option<i16>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::InterestBearingMintExtension,
+        InterestBearingMintInstruction::InitializeTerms,
+        &InterestBearingTerms::new(compounding_period, minimum_rate, maximum_rate),
+    ))
+}
+
+/// Create an `UpdateTerms` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn update_terms(
+    token_program_id: &Address,
+    mint: &Address,
+    rate_authority: &Address,
+    signers: &[&Address],
+    compounding_period: CompoundingPeriod,
+    minimum_rate: Option<i16>,
+    maximum_rate: Option<i16>,
+    rate: i16,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*rate_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::InterestBearingMintExtension,
+        InterestBearingMintInstruction::UpdateTerms,
+        &UpdateTermsInstructionData {
+            terms: InterestBearingTerms::new(compounding_period, minimum_rate, maximum_rate),
+            rate: rate.into(),
+        },
+    ))
+}
@@ -6,6 +6,7 @@ use {
     alloc::{format, string::String},
     bytemuck::{Pod, Zeroable},
     core::convert::TryInto,
+    num_enum::{IntoPrimitive, TryFromPrimitive},
     num_traits::{pow, Float},
     solana_address::Address,
     solana_nullable::MaybeNull,
@@ -29,6 +30,82 @@ const SECONDS_PER_YEAR: f64 = 60. * 60. * 24. * 365.24;
 /// `UnixTimestamp` expressed with an alignment-independent type
 pub type UnixTimestamp = I64;
 
+/// Period over which interest compounds
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum CompoundingPeriod {
+    /// Interest compounds continuously
+    #[default]
+    Continuous,
+    /// Interest compounds 365 times a year
+    Daily,
+    /// Interest compounds 12 times a year
+    Monthly,
+}
+impl CompoundingPeriod {
+    /// Number of compounding periods in a year, or `None` if interest
+    /// compounds continuously
+    pub fn periods_per_year(&self) -> Option<f64> {
+        match self {
+            CompoundingPeriod::Continuous => None,
+            CompoundingPeriod::Daily => Some(365.),
+            CompoundingPeriod::Monthly => Some(12.),
+        }
+    }
+
+    /// Continuously-compounded annual rate accruing the same interest as
+    /// `rate` compounded over this period, as a fraction
+    fn growth_rate(&self, rate: i16) -> f64 {
+        let rate = rate as f64 / ONE_IN_BASIS_POINTS;
+        match self.periods_per_year() {
+            None => rate,
+            Some(periods) => periods * Float::ln_1p(rate / periods),
+        }
+    }
+
+    /// Rate compounded over this period, in basis points, accruing the same
+    /// interest as the continuously-compounded annual `growth_rate`
+    fn rate_from_growth_rate(&self, growth_rate: f64) -> Option<i16> {
+        let rate = match self.periods_per_year() {
+            None => growth_rate,
+            Some(periods) => periods * Float::exp_m1(growth_rate / periods),
+        };
+        let rate = Float::round(rate * ONE_IN_BASIS_POINTS);
+        if rate.is_nan() || rate < i16::MIN as f64 || rate > i16::MAX as f64 {
+            None
+        } else {
+            Some(rate as i16)
+        }
+    }
+
+    /// Exponent of the interest accrued at `rate` over `timespan` seconds
+    fn exponent(&self, rate: i16, timespan: i64) -> Option<f64> {
+        match self {
+            CompoundingPeriod::Continuous => {
+                let numerator = (rate as i128).checked_mul(timespan as i128)? as f64;
+                Some(numerator / SECONDS_PER_YEAR / ONE_IN_BASIS_POINTS)
+            }
+            _ => Some(self.growth_rate(rate) * timespan as f64 / SECONDS_PER_YEAR),
+        }
+    }
+
+    /// Convert a rate compounded over this period into the rate compounded
+    /// over `other` that accrues the same interest, rounded to the nearest
+    /// basis point
+    pub fn equivalent_rate(&self, rate: i16, other: CompoundingPeriod) -> Option<i16> {
+        if *self == other {
+            Some(rate)
+        } else {
+            other.rate_from_growth_rate(self.growth_rate(rate))
+        }
+    }
+}
+
+/// `CompoundingPeriod` expressed with an alignment-independent type
+pub type PodCompoundingPeriod = u8;
+
 /// Interest-bearing extension data for mints
 ///
 /// Tokens accrue interest at an annual rate expressed by `current_rate`,
@@ -59,10 +136,11 @@ impl InterestBearingConfig {
         i64::from(self.last_update_timestamp).checked_sub(self.initialization_timestamp.into())
     }
 
-    fn pre_update_exp(&self) -> Option<f64> {
-        let numerator = (i16::from(self.pre_update_average_rate) as i128)
-            .checked_mul(self.pre_update_timespan()? as i128)? as f64;
-        let exponent = numerator / SECONDS_PER_YEAR / ONE_IN_BASIS_POINTS;
+    fn pre_update_exp(&self, compounding_period: CompoundingPeriod) -> Option<f64> {
+        let exponent = compounding_period.exponent(
+            self.pre_update_average_rate.into(),
+            self.pre_update_timespan()?,
+        )?;
         Some(Float::exp(exponent))
     }
 
@@ -70,17 +148,27 @@ impl InterestBearingConfig {
         unix_timestamp.checked_sub(self.last_update_timestamp.into())
     }
 
-    fn post_update_exp(&self, unix_timestamp: i64) -> Option<f64> {
-        let numerator = (i16::from(self.current_rate) as i128)
-            .checked_mul(self.post_update_timespan(unix_timestamp)? as i128)?
-            as f64;
-        let exponent = numerator / SECONDS_PER_YEAR / ONE_IN_BASIS_POINTS;
+    fn post_update_exp(
+        &self,
+        unix_timestamp: i64,
+        compounding_period: CompoundingPeriod,
+    ) -> Option<f64> {
+        let exponent = compounding_period.exponent(
+            self.current_rate.into(),
+            self.post_update_timespan(unix_timestamp)?,
+        )?;
         Some(Float::exp(exponent))
     }
 
-    fn total_scale(&self, decimals: u8, unix_timestamp: i64) -> Option<f64> {
+    fn total_scale(
+        &self,
+        decimals: u8,
+        unix_timestamp: i64,
+        compounding_period: CompoundingPeriod,
+    ) -> Option<f64> {
         Some(
-            self.pre_update_exp()? * self.post_update_exp(unix_timestamp)?
+            self.pre_update_exp(compounding_period)?
+                * self.post_update_exp(unix_timestamp, compounding_period)?
                 / pow(10_f64, decimals as usize),
         )
     }
@@ -92,9 +180,27 @@ impl InterestBearingConfig {
         amount: u64,
         decimals: u8,
         unix_timestamp: i64,
+    ) -> Option<String> {
+        self.amount_to_ui_amount_with_compounding(
+            amount,
+            decimals,
+            unix_timestamp,
+            CompoundingPeriod::Continuous,
+        )
+    }
+
+    /// Convert a raw amount to its UI representation using the given decimals
+    /// field, with interest compounded over the given period. Excess zeroes or
+    /// unneeded decimal point are trimmed.
+    pub fn amount_to_ui_amount_with_compounding(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+        compounding_period: CompoundingPeriod,
     ) -> Option<String> {
         let scaled_amount_with_interest =
-            (amount as f64) * self.total_scale(decimals, unix_timestamp)?;
+            (amount as f64) * self.total_scale(decimals, unix_timestamp, compounding_period)?;
         let ui_amount = format!("{scaled_amount_with_interest:.*}", decimals as usize);
         Some(trim_ui_amount_string(ui_amount, decimals))
     }
@@ -106,13 +212,31 @@ impl InterestBearingConfig {
         ui_amount: &str,
         decimals: u8,
         unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        self.try_ui_amount_into_amount_with_compounding(
+            ui_amount,
+            decimals,
+            unix_timestamp,
+            CompoundingPeriod::Continuous,
+        )
+    }
+
+    /// Try to convert a UI representation of a token amount to its raw amount
+    /// using the given decimals field, with interest compounded over the given
+    /// period
+    pub fn try_ui_amount_into_amount_with_compounding(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+        compounding_period: CompoundingPeriod,
     ) -> Result<u64, ProgramError> {
         let scaled_amount = ui_amount
             .parse::<f64>()
             .map_err(|_| ProgramError::InvalidArgument)?;
         let amount = scaled_amount
             / self
-                .total_scale(decimals, unix_timestamp)
+                .total_scale(decimals, unix_timestamp, compounding_period)
                 .ok_or(ProgramError::InvalidArgument)?;
         if amount > (u64::MAX as f64) || amount < (u64::MIN as f64) || amount.is_nan() {
             Err(ProgramError::InvalidArgument)
@@ -153,11 +277,98 @@ impl InterestBearingConfig {
         };
         average_rate.try_into().ok()
     }
+
+    /// Time-weighted average rate for interest compounded over the given
+    /// period. With `m` periods a year, this solves for r such that:
+    ///
+    /// ```text
+    /// (1 + r_1 / m)^(m * t_1) * (1 + r_2 / m)^(m * t_2) = (1 + r / m)^(m * (t_1 + t_2))
+    ///
+    /// r = m * (exp((t_1 * ln(1 + r_1 / m) + t_2 * ln(1 + r_2 / m)) / (t_1 + t_2)) - 1)
+    /// ```
+    ///
+    /// Continuous compounding falls back to `time_weighted_average_rate`.
+    pub fn time_weighted_average_rate_with_compounding(
+        &self,
+        current_timestamp: i64,
+        compounding_period: CompoundingPeriod,
+    ) -> Option<i16> {
+        if compounding_period == CompoundingPeriod::Continuous {
+            return self.time_weighted_average_rate(current_timestamp);
+        }
+        let t_1 = self.pre_update_timespan()?;
+        let t_2 = self.post_update_timespan(current_timestamp)?;
+        let total_timespan = t_1.checked_add(t_2)?;
+        if total_timespan == 0 {
+            return Some(self.current_rate.into());
+        }
+        let g_1 = compounding_period.growth_rate(self.pre_update_average_rate.into());
+        let g_2 = compounding_period.growth_rate(self.current_rate.into());
+        let average_growth_rate = (g_1 * t_1 as f64 + g_2 * t_2 as f64) / total_timespan as f64;
+        compounding_period.rate_from_growth_rate(average_growth_rate)
+    }
 }
 impl Extension for InterestBearingConfig {
     const TYPE: ExtensionType = ExtensionType::InterestBearingConfig;
 }
 
+/// Interest-bearing terms extension data for mints
+///
+/// Used alongside `InterestBearingConfig`, whose rates are then compounded over
+/// `compounding_period` rather than continuously. The rate authority can only
+/// set rates between `minimum_rate` and `maximum_rate`, inclusive.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct InterestBearingTerms {
+    /// Period over which interest compounds, as a `CompoundingPeriod`
+    pub compounding_period: PodCompoundingPeriod,
+    /// Lowest rate that can be set, `i16::MIN` if unbounded
+    pub minimum_rate: BasisPoints,
+    /// Highest rate that can be set, `i16::MAX` if unbounded
+    pub maximum_rate: BasisPoints,
+}
+impl InterestBearingTerms {
+    /// Create new terms, with missing bounds left unbounded
+    pub fn new(
+        compounding_period: CompoundingPeriod,
+        minimum_rate: Option<i16>,
+        maximum_rate: Option<i16>,
+    ) -> Self {
+        Self {
+            compounding_period: compounding_period.into(),
+            minimum_rate: minimum_rate.unwrap_or(i16::MIN).into(),
+            maximum_rate: maximum_rate.unwrap_or(i16::MAX).into(),
+        }
+    }
+
+    /// Get the compounding period, failing if it is unknown
+    pub fn compounding_period(&self) -> Result<CompoundingPeriod, ProgramError> {
+        CompoundingPeriod::try_from(self.compounding_period)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Check that the compounding period is known and the bounds are in order
+    pub fn is_valid(&self) -> bool {
+        self.compounding_period().is_ok()
+            && i16::from(self.minimum_rate) <= i16::from(self.maximum_rate)
+    }
+
+    /// Check if the rate falls within the bounds
+    pub fn contains_rate(&self, rate: i16) -> bool {
+        i16::from(self.minimum_rate) <= rate && rate <= i16::from(self.maximum_rate)
+    }
+}
+impl Default for InterestBearingTerms {
+    fn default() -> Self {
+        Self::new(CompoundingPeriod::Continuous, None, None)
+    }
+}
+impl Extension for InterestBearingTerms {
+    const TYPE: ExtensionType = ExtensionType::InterestBearingTerms;
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, proptest::prelude::*};
@@ -410,6 +621,134 @@ mod tests {
         }
     }
 
+    #[test]
+    fn specific_amount_to_ui_amount_with_compounding() {
+        const ONE: u64 = 1_000_000;
+        // constant 5%
+        let config = InterestBearingConfig {
+            rate_authority: MaybeNull::<Address>::default(),
+            initialization_timestamp: 0.into(),
+            pre_update_average_rate: 500.into(),
+            last_update_timestamp: INT_SECONDS_PER_YEAR.into(),
+            current_rate: 500.into(),
+        };
+        // 1 year at 5% compounded monthly gives (1 + 0.05 / 12)^12 = 1.0511618978817
+        let ui_amount = config
+            .amount_to_ui_amount_with_compounding(
+                ONE,
+                6,
+                INT_SECONDS_PER_YEAR,
+                CompoundingPeriod::Monthly,
+            )
+            .unwrap();
+        assert_eq!(ui_amount, "1.051162");
+        // compounded daily gives (1 + 0.05 / 365)^365 = 1.0512674964674
+        let ui_amount = config
+            .amount_to_ui_amount_with_compounding(
+                ONE,
+                6,
+                INT_SECONDS_PER_YEAR,
+                CompoundingPeriod::Daily,
+            )
+            .unwrap();
+        assert_eq!(ui_amount, "1.051267");
+        // continuous matches the plain conversion
+        assert_eq!(
+            config.amount_to_ui_amount_with_compounding(
+                ONE,
+                6,
+                INT_SECONDS_PER_YEAR,
+                CompoundingPeriod::Continuous,
+            ),
+            config.amount_to_ui_amount(ONE, 6, INT_SECONDS_PER_YEAR),
+        );
+
+        // 2 years at 5% compounded monthly
+        let amount = config
+            .try_ui_amount_into_amount_with_compounding(
+                "1.104941",
+                6,
+                INT_SECONDS_PER_YEAR * 2,
+                CompoundingPeriod::Monthly,
+            )
+            .unwrap();
+        assert_eq!(amount, ONE);
+    }
+
+    #[test]
+    fn equivalent_rates() {
+        // 5% compounded daily accrues about as much as 5.01% compounded monthly
+        assert_eq!(
+            CompoundingPeriod::Daily.equivalent_rate(500, CompoundingPeriod::Monthly),
+            Some(501)
+        );
+        assert_eq!(
+            CompoundingPeriod::Monthly.equivalent_rate(501, CompoundingPeriod::Daily),
+            Some(500)
+        );
+        assert_eq!(
+            CompoundingPeriod::Continuous.equivalent_rate(500, CompoundingPeriod::Continuous),
+            Some(500)
+        );
+        assert_eq!(
+            CompoundingPeriod::Monthly.equivalent_rate(i16::MAX, CompoundingPeriod::Continuous),
+            Some(28_971)
+        );
+    }
+
+    #[test]
+    fn time_weighted_average_with_compounding() {
+        // 5% then 10% for a year each, compounded monthly
+        let config = InterestBearingConfig {
+            rate_authority: MaybeNull::<Address>::default(),
+            initialization_timestamp: 0.into(),
+            pre_update_average_rate: 500.into(),
+            last_update_timestamp: INT_SECONDS_PER_YEAR.into(),
+            current_rate: 1_000.into(),
+        };
+        assert_eq!(
+            config.time_weighted_average_rate_with_compounding(
+                INT_SECONDS_PER_YEAR * 2,
+                CompoundingPeriod::Monthly
+            ),
+            Some(750)
+        );
+        assert_eq!(
+            config.time_weighted_average_rate_with_compounding(
+                INT_SECONDS_PER_YEAR * 2,
+                CompoundingPeriod::Continuous
+            ),
+            config.time_weighted_average_rate(INT_SECONDS_PER_YEAR * 2),
+        );
+    }
+
+    #[test]
+    fn terms_bounds() {
+        let terms = InterestBearingTerms::new(CompoundingPeriod::Daily, Some(-100), Some(500));
+        assert!(terms.is_valid());
+        assert_eq!(terms.compounding_period(), Ok(CompoundingPeriod::Daily));
+        assert!(terms.contains_rate(-100));
+        assert!(terms.contains_rate(500));
+        assert!(!terms.contains_rate(-101));
+        assert!(!terms.contains_rate(501));
+
+        let unbounded = InterestBearingTerms::default();
+        assert!(unbounded.contains_rate(i16::MIN));
+        assert!(unbounded.contains_rate(i16::MAX));
+
+        let reversed = InterestBearingTerms::new(CompoundingPeriod::Daily, Some(500), Some(-100));
+        assert!(!reversed.is_valid());
+        let unknown = InterestBearingTerms {
+            compounding_period: 3,
+            ..InterestBearingTerms::default()
+        };
+        assert!(!unknown.is_valid());
+        assert_eq!(
+            unknown.compounding_period(),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
     prop_compose! {
         /// Three values in ascending order
         fn low_middle_high()
@@ -444,6 +783,30 @@ mod tests {
             }
         }
 
+        #[test]
+        fn time_weighted_average_calc_with_compounding(
+            current_rate in i16::MIN..i16::MAX,
+            pre_update_average_rate in i16::MIN..i16::MAX,
+            (initialization_timestamp, last_update_timestamp, current_timestamp) in low_middle_high(),
+            compounding_period in prop_oneof![
+                Just(CompoundingPeriod::Daily),
+                Just(CompoundingPeriod::Monthly),
+            ],
+        ) {
+            let config = InterestBearingConfig {
+                rate_authority: MaybeNull::<Address>::default(),
+                initialization_timestamp: initialization_timestamp.into(),
+                pre_update_average_rate: pre_update_average_rate.into(),
+                last_update_timestamp: last_update_timestamp.into(),
+                current_rate: current_rate.into(),
+            };
+            let new_rate = config
+                .time_weighted_average_rate_with_compounding(current_timestamp, compounding_period)
+                .unwrap();
+            assert!(new_rate >= pre_update_average_rate.min(current_rate));
+            assert!(new_rate <= pre_update_average_rate.max(current_rate));
+        }
+
         #[test]
         fn amount_to_ui_amount(
             current_rate in i16::MIN..i16::MAX,
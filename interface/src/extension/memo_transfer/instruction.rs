@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use {
     crate::{
         check_program_account,
+        extension::memo_transfer::MemoTransferPolicy,
         instruction::{encode_instruction, TokenInstruction},
     },
     alloc::vec,
@@ -46,6 +47,54 @@ pub enum RequiredMemoTransfersInstruction {
     ///   1. `[]`  The account's multisignature owner.
     ///   2. `..2+M` `[signer]` M signer accounts.
     Disable,
+    /// Set the policy on the content of memos for transfers into this
+    /// Account. Adds the `MemoTransferPolicy` extension to the Account, if it
+    /// doesn't already exist. A zeroed policy accepts any memo.
+    ///
+    /// The policy is only enforced while memos are required.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The account to update.
+    ///   1. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The account to update.
+    ///   1. `[]` The account's multisignature owner.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `MemoTransferPolicy`
+    SetPolicy,
+    /// Require memos for transfers out of this Account. Adds the
+    /// `OutgoingMemoTransfer` extension to the Account, if it doesn't already
+    /// exist.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The account to update.
+    ///   1. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The account to update.
+    ///   1. `[]` The account's multisignature owner.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    EnableOutgoing,
+    /// Stop requiring memos for transfers out of this Account.
+    ///
+    /// Implicitly initializes the extension in the case where it is not
+    /// present.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The account to update.
+    ///   1. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The account to update.
+    ///   1. `[]` The account's multisignature owner.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    DisableOutgoing,
 }
 
 /// Create an `Enable` instruction
@@ -95,3 +144,76 @@ pub fn disable_required_transfer_memos(
         &(),
     ))
 }
+
+/// Create an `EnableOutgoing` instruction
+pub fn enable_required_outgoing_transfer_memos(
+    token_program_id: &Address,
+    account: &Address,
+    owner: &Address,
+    signers: &[&Address],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*owner, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::MemoTransferExtension,
+        RequiredMemoTransfersInstruction::EnableOutgoing,
+        &(),
+    ))
+}
+
+/// Create a `DisableOutgoing` instruction
+pub fn disable_required_outgoing_transfer_memos(
+    token_program_id: &Address,
+    account: &Address,
+    owner: &Address,
+    signers: &[&Address],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*owner, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::MemoTransferExtension,
+        RequiredMemoTransfersInstruction::DisableOutgoing,
+        &(),
+    ))
+}
+
+/// Create a `SetPolicy` instruction
+pub fn set_memo_transfer_policy(
+    token_program_id: &Address,
+    account: &Address,
+    owner: &Address,
+    signers: &[&Address],
+    policy: &MemoTransferPolicy,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*owner, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::MemoTransferExtension,
+        RequiredMemoTransfersInstruction::SetPolicy,
+        policy,
+    ))
+}
@@ -1,9 +1,13 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use {
-    crate::extension::{BaseState, BaseStateWithExtensions, Extension, ExtensionType},
+    crate::{
+        error::TokenError,
+        extension::{BaseState, BaseStateWithExtensions, Extension, ExtensionType},
+    },
     bytemuck::{Pod, Zeroable},
-    solana_zero_copy::unaligned::Bool,
+    solana_program_error::ProgramError,
+    solana_zero_copy::unaligned::{Bool, U16},
 };
 
 /// Memo Transfer extension instructions
@@ -29,3 +33,130 @@ pub fn memo_required<BSE: BaseStateWithExtensions<S>, S: BaseState>(account_stat
     }
     false
 }
+
+/// Outgoing Memo Transfer extension for Accounts
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct OutgoingMemoTransfer {
+    /// Require transfers out of this account to be accompanied by a memo
+    pub require_outgoing_transfer_memos: Bool,
+}
+impl Extension for OutgoingMemoTransfer {
+    const TYPE: ExtensionType = ExtensionType::OutgoingMemoTransfer;
+}
+
+/// Determine if a memo is required for transfers out of this account
+pub fn outgoing_memo_required<BSE: BaseStateWithExtensions<S>, S: BaseState>(
+    account_state: &BSE,
+) -> bool {
+    if let Ok(extension) = account_state.get_extension::<OutgoingMemoTransfer>() {
+        return extension.require_outgoing_transfer_memos.into();
+    }
+    false
+}
+
+/// Maximum length of the prefix required by a `MemoTransferPolicy`
+pub const MAX_MEMO_PREFIX_LEN: usize = 32;
+
+/// Policy on the content of memos accompanying transfers into an Account,
+/// enforced whenever the Account requires incoming transfer memos.
+///
+/// A zeroed policy accepts any memo.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct MemoTransferPolicy {
+    /// Minimum length of the memo, in bytes
+    pub min_len: U16,
+    /// If non-zero, the memo must consist of the prefix followed by exactly
+    /// this many ASCII digits
+    pub numeric_tag_len: u8,
+    /// Length of the prefix the memo must start with
+    pub prefix_len: u8,
+    /// Prefix the memo must start with, padded with zeroes
+    pub prefix: [u8; MAX_MEMO_PREFIX_LEN],
+}
+impl MemoTransferPolicy {
+    /// Create a new policy, failing if the prefix is too long
+    pub fn new(min_len: u16, prefix: &[u8], numeric_tag_len: u8) -> Result<Self, ProgramError> {
+        if prefix.len() > MAX_MEMO_PREFIX_LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut policy = Self {
+            min_len: min_len.into(),
+            numeric_tag_len,
+            prefix_len: prefix.len() as u8,
+            ..Default::default()
+        };
+        policy.prefix[..prefix.len()].copy_from_slice(prefix);
+        Ok(policy)
+    }
+
+    /// Get the prefix the memo must start with
+    pub fn prefix(&self) -> Result<&[u8], ProgramError> {
+        self.prefix
+            .get(..self.prefix_len as usize)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// Check that the memo satisfies the policy
+    pub fn check(&self, memo: &[u8]) -> Result<(), ProgramError> {
+        if memo.len() < u16::from(self.min_len) as usize {
+            return Err(TokenError::MemoPolicyViolation.into());
+        }
+        let tag = memo
+            .strip_prefix(self.prefix()?)
+            .ok_or(TokenError::MemoPolicyViolation)?;
+        if self.numeric_tag_len != 0
+            && (tag.len() != self.numeric_tag_len as usize || !tag.iter().all(u8::is_ascii_digit))
+        {
+            return Err(TokenError::MemoPolicyViolation.into());
+        }
+        Ok(())
+    }
+}
+impl Extension for MemoTransferPolicy {
+    const TYPE: ExtensionType = ExtensionType::MemoTransferPolicy;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_transfer_policy() {
+        let policy = MemoTransferPolicy::default();
+        policy.check(b"").unwrap();
+        policy.check(b"anything").unwrap();
+
+        let policy = MemoTransferPolicy::new(4, b"", 0).unwrap();
+        policy.check(b"abcd").unwrap();
+        assert_eq!(
+            policy.check(b"abc").unwrap_err(),
+            TokenError::MemoPolicyViolation.into()
+        );
+
+        let policy = MemoTransferPolicy::new(0, b"DEP-", 6).unwrap();
+        policy.check(b"DEP-012345").unwrap();
+        for memo in [
+            &b"DEP-12345"[..],
+            b"DEP-1234567",
+            b"DEP-01234a",
+            b"dep-012345",
+            b"012345",
+        ] {
+            assert_eq!(
+                policy.check(memo).unwrap_err(),
+                TokenError::MemoPolicyViolation.into()
+            );
+        }
+
+        assert_eq!(
+            MemoTransferPolicy::new(0, &[0; MAX_MEMO_PREFIX_LEN + 1], 0).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+}
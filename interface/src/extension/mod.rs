@@ -12,21 +12,27 @@ use {
             confidential_transfer_fee::{
                 ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
             },
-            cpi_guard::CpiGuard,
-            default_account_state::DefaultAccountState,
+            cpi_guard::{CpiGuard, CpiGuardPolicy},
+            default_account_state::{DefaultAccountState, DefaultAccountStateAttestor},
             group_member_pointer::GroupMemberPointer,
             group_pointer::GroupPointer,
             immutable_owner::ImmutableOwner,
-            interest_bearing_mint::InterestBearingConfig,
-            memo_transfer::MemoTransfer,
+            interest_bearing_mint::{InterestBearingConfig, InterestBearingTerms},
+            memo_transfer::{MemoTransfer, MemoTransferPolicy, OutgoingMemoTransfer},
             metadata_pointer::MetadataPointer,
             mint_close_authority::MintCloseAuthority,
-            non_transferable::{NonTransferable, NonTransferableAccount},
-            pausable::{PausableAccount, PausableConfig},
+            non_transferable::{NonTransferable, NonTransferableAccount, NonTransferableRecovery},
+            partial_freeze::{PartialFreezeAccount, PartialFreezeConfig},
+            pausable::{PausableAccount, PausableConfig, PausableSchedule, PausedOperations},
             permanent_delegate::PermanentDelegate,
             permissioned_burn::PermissionedBurnConfig,
-            scaled_ui_amount::ScaledUiAmountConfig,
-            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            scaled_ui_amount::{ScaledUiAmountConfig, ScaledUiAmountRationalConfig},
+            token_group::{TokenGroupMemberCounter, TokenGroupMembershipFreeze},
+            token_metadata::TokenMetadataSizeLimit,
+            transfer_fee::{
+                TieredTransferFeeConfig, TransferFeeAmount, TransferFeeCollector,
+                TransferFeeConfig, TransferFeeExemption,
+            },
             transfer_hook::{TransferHook, TransferHookAccount},
         },
         pod::{PodAccount, PodMint},
@@ -74,6 +80,8 @@ pub mod metadata_pointer;
 pub mod mint_close_authority;
 /// Non Transferable extension
 pub mod non_transferable;
+/// Partial freeze extension
+pub mod partial_freeze;
 /// Pausable extension
 pub mod pausable;
 /// Permanent Delegate extension
@@ -791,6 +799,9 @@ pub trait BaseStateWithExtensionsMut<S: BaseState>: BaseStateWithExtensions<S> {
             ExtensionType::PausableAccount => {
                 self.init_extension::<PausableAccount>(true).map(|_| ())
             }
+            ExtensionType::PartialFreezeAccount => self
+                .init_extension::<PartialFreezeAccount>(true)
+                .map(|_| ()),
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => {
                 self.init_extension::<AccountPaddingTest>(true).map(|_| ())
@@ -1139,6 +1150,44 @@ pub enum ExtensionType {
     PausableAccount,
     /// Tokens burning requires approval from authority.
     PermissionedBurn,
+    /// Pause window scheduled on a pausable mint
+    PausableSchedule,
+    /// Individual operations paused on a pausable mint
+    PausedOperations,
+    /// Allows the freeze authority to freeze only incoming or outgoing
+    /// transfers on accounts
+    PartialFreeze,
+    /// Incoming or outgoing transfers frozen on the account
+    PartialFreezeAccount,
+    /// Tiered transfer fee schedule, used instead of the flat transfer fee
+    TieredTransferFeeConfig,
+    /// Account exempt from transfer fees, set by the transfer fee authority
+    TransferFeeExemption,
+    /// Transfer fees credited directly to a collector token account
+    TransferFeeCollector,
+    /// Exact rational multiplier, used instead of the `f64` scaled UI amount
+    /// multiplier
+    ScaledUiAmountRational,
+    /// Variable-length history and queue of scaled UI amount multipliers
+    ScaledUiAmountSchedule,
+    /// Compounding period and rate bounds for an interest-bearing mint
+    InterestBearingTerms,
+    /// Size limit on the token-metadata stored in the mint
+    TokenMetadataSizeLimit,
+    /// Membership freeze for a token group
+    TokenGroupMembershipFreeze,
+    /// Policy on the content of memos for transfers into an account
+    MemoTransferPolicy,
+    /// Indicates that the account requires memos for outgoing transfers
+    OutgoingMemoTransfer,
+    /// Operations and calling programs allowed through the CPI Guard
+    CpiGuardPolicy,
+    /// Signer that can approve new accounts skipping the default account state
+    DefaultAccountStateAttestor,
+    /// Authority that can move non-transferable balances between accounts
+    NonTransferableRecovery,
+    /// Last member number handed out by a token group
+    TokenGroupMemberCounter,
 
     /// Test variable-length mint extension
     #[cfg(test)]
@@ -1174,6 +1223,7 @@ impl ExtensionType {
     const fn sized(&self) -> bool {
         match self {
             ExtensionType::TokenMetadata => false,
+            ExtensionType::ScaledUiAmountSchedule => false,
             #[cfg(test)]
             ExtensionType::VariableLenMintTest => false,
             _ => true,
@@ -1221,6 +1271,24 @@ impl ExtensionType {
             ExtensionType::Pausable => size_of::<PausableConfig>(),
             ExtensionType::PausableAccount => size_of::<PausableAccount>(),
             ExtensionType::PermissionedBurn => size_of::<PermissionedBurnConfig>(),
+            ExtensionType::PausableSchedule => size_of::<PausableSchedule>(),
+            ExtensionType::PausedOperations => size_of::<PausedOperations>(),
+            ExtensionType::PartialFreeze => size_of::<PartialFreezeConfig>(),
+            ExtensionType::PartialFreezeAccount => size_of::<PartialFreezeAccount>(),
+            ExtensionType::TieredTransferFeeConfig => size_of::<TieredTransferFeeConfig>(),
+            ExtensionType::TransferFeeExemption => size_of::<TransferFeeExemption>(),
+            ExtensionType::TransferFeeCollector => size_of::<TransferFeeCollector>(),
+            ExtensionType::ScaledUiAmountRational => size_of::<ScaledUiAmountRationalConfig>(),
+            ExtensionType::ScaledUiAmountSchedule => unreachable!(),
+            ExtensionType::InterestBearingTerms => size_of::<InterestBearingTerms>(),
+            ExtensionType::TokenMetadataSizeLimit => size_of::<TokenMetadataSizeLimit>(),
+            ExtensionType::TokenGroupMembershipFreeze => size_of::<TokenGroupMembershipFreeze>(),
+            ExtensionType::MemoTransferPolicy => size_of::<MemoTransferPolicy>(),
+            ExtensionType::OutgoingMemoTransfer => size_of::<OutgoingMemoTransfer>(),
+            ExtensionType::CpiGuardPolicy => size_of::<CpiGuardPolicy>(),
+            ExtensionType::DefaultAccountStateAttestor => size_of::<DefaultAccountStateAttestor>(),
+            ExtensionType::NonTransferableRecovery => size_of::<NonTransferableRecovery>(),
+            ExtensionType::TokenGroupMemberCounter => size_of::<TokenGroupMemberCounter>(),
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => size_of::<AccountPaddingTest>(),
             #[cfg(test)]
@@ -1272,16 +1340,34 @@ impl ExtensionType {
             | ExtensionType::TokenGroupMember
             | ExtensionType::ScaledUiAmount
             | ExtensionType::Pausable
-            | ExtensionType::PermissionedBurn => AccountType::Mint,
+            | ExtensionType::PermissionedBurn
+            | ExtensionType::PausableSchedule
+            | ExtensionType::PausedOperations
+            | ExtensionType::PartialFreeze
+            | ExtensionType::TieredTransferFeeConfig
+            | ExtensionType::TransferFeeCollector
+            | ExtensionType::ScaledUiAmountRational
+            | ExtensionType::ScaledUiAmountSchedule
+            | ExtensionType::InterestBearingTerms
+            | ExtensionType::TokenMetadataSizeLimit
+            | ExtensionType::TokenGroupMembershipFreeze
+            | ExtensionType::DefaultAccountStateAttestor
+            | ExtensionType::NonTransferableRecovery
+            | ExtensionType::TokenGroupMemberCounter => AccountType::Mint,
             ExtensionType::ImmutableOwner
             | ExtensionType::TransferFeeAmount
             | ExtensionType::ConfidentialTransferAccount
             | ExtensionType::MemoTransfer
+            | ExtensionType::MemoTransferPolicy
+            | ExtensionType::OutgoingMemoTransfer
             | ExtensionType::NonTransferableAccount
             | ExtensionType::TransferHookAccount
             | ExtensionType::CpiGuard
+            | ExtensionType::CpiGuardPolicy
             | ExtensionType::ConfidentialTransferFeeAmount
-            | ExtensionType::PausableAccount => AccountType::Account,
+            | ExtensionType::PausableAccount
+            | ExtensionType::PartialFreezeAccount
+            | ExtensionType::TransferFeeExemption => AccountType::Account,
             #[cfg(test)]
             ExtensionType::VariableLenMintTest => AccountType::Mint,
             #[cfg(test)]
@@ -1302,6 +1388,7 @@ impl ExtensionType {
             ],
             ExtensionType::TransferHook => &[ExtensionType::TransferHookAccount],
             ExtensionType::Pausable => &[ExtensionType::PausableAccount],
+            ExtensionType::PartialFreeze => &[ExtensionType::PartialFreezeAccount],
             #[cfg(test)]
             ExtensionType::MintPaddingTest => &[ExtensionType::AccountPaddingTest],
             _ => &[],
@@ -1327,24 +1414,38 @@ impl ExtensionType {
         mint_extension_types: &[Self],
     ) -> Result<(), TokenError> {
         let mut transfer_fee_config = false;
+        let mut tiered_transfer_fee_config = false;
+        let mut transfer_fee_collector = false;
         let mut confidential_transfer_mint = false;
         let mut confidential_transfer_fee_config = false;
         let mut confidential_mint_burn = false;
         let mut interest_bearing = false;
+        let mut interest_bearing_terms = false;
         let mut scaled_ui_amount = false;
+        let mut scaled_ui_amount_rational = false;
         let mut non_transferable = false;
+        let mut non_transferable_recovery = false;
+        let mut metadata_pointer = false;
+        let mut token_metadata_size_limit = false;
 
         for extension_type in mint_extension_types {
             match extension_type {
                 ExtensionType::TransferFeeConfig => transfer_fee_config = true,
+                ExtensionType::TieredTransferFeeConfig => tiered_transfer_fee_config = true,
+                ExtensionType::TransferFeeCollector => transfer_fee_collector = true,
                 ExtensionType::ConfidentialTransferMint => confidential_transfer_mint = true,
                 ExtensionType::ConfidentialTransferFeeConfig => {
                     confidential_transfer_fee_config = true
                 }
                 ExtensionType::ConfidentialMintBurn => confidential_mint_burn = true,
                 ExtensionType::InterestBearingConfig => interest_bearing = true,
+                ExtensionType::InterestBearingTerms => interest_bearing_terms = true,
                 ExtensionType::ScaledUiAmount => scaled_ui_amount = true,
+                ExtensionType::ScaledUiAmountRational => scaled_ui_amount_rational = true,
                 ExtensionType::NonTransferable => non_transferable = true,
+                ExtensionType::NonTransferableRecovery => non_transferable_recovery = true,
+                ExtensionType::MetadataPointer => metadata_pointer = true,
+                ExtensionType::TokenMetadataSizeLimit => token_metadata_size_limit = true,
                 _ => (),
             }
         }
@@ -1358,6 +1459,16 @@ impl ExtensionType {
             return Err(TokenError::InvalidExtensionCombination);
         }
 
+        // Confidential transfer fee proofs only support a flat fee
+        if tiered_transfer_fee_config && (!transfer_fee_config || confidential_transfer_mint) {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
+        // Confidential withheld fees can't be routed to the collector
+        if transfer_fee_collector && (!transfer_fee_config || confidential_transfer_fee_config) {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
         if confidential_mint_burn && !confidential_transfer_mint {
             return Err(TokenError::InvalidExtensionCombination);
         }
@@ -1366,10 +1477,26 @@ impl ExtensionType {
             return Err(TokenError::InvalidExtensionCombination);
         }
 
+        if scaled_ui_amount_rational && !scaled_ui_amount {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
+        if interest_bearing_terms && !interest_bearing {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
+        if token_metadata_size_limit && !metadata_pointer {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
         if non_transferable && confidential_transfer_mint && !confidential_mint_burn {
             return Err(TokenError::InvalidExtensionCombination);
         }
 
+        if non_transferable_recovery && !non_transferable {
+            return Err(TokenError::InvalidExtensionCombination);
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,141 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use {
+    crate::{
+        check_program_account,
+        instruction::{encode_instruction, TokenInstruction},
+    },
+    alloc::{vec, vec::Vec},
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_address::Address,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_zero_copy::unaligned::U64,
+};
+
+/// Non-transferable extension instructions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum NonTransferableInstruction {
+    /// Initialize the recovery authority for a non-transferable mint.
+    ///
+    /// Fails if the mint has already been initialized, so must be called
+    /// before `InitializeMint`. The mint must also be initialized with
+    /// `InitializeNonTransferableMint`.
+    ///
+    /// The authority can be changed or removed later with `SetAuthority`,
+    /// using `AuthorityType::NonTransferableRecovery`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]`  The mint account to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::non_transferable::instruction::InitializeRecoveryAuthorityInstructionData`
+    InitializeRecoveryAuthority,
+    /// Move tokens of a non-transferable mint from one account to another,
+    /// e.g. from a lost wallet to a new wallet owned by the same holder.
+    ///
+    /// Only the recovery authority configured on the mint may sign, and no
+    /// transfer fee is charged. All other transfer checks, such as frozen
+    /// accounts, required memos, pausing and transfer hooks, still apply.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The source account.
+    ///   1. `[]` The token mint.
+    ///   2. `[writable]` The destination account.
+    ///   3. `[signer]` The mint's recovery authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The source account.
+    ///   1. `[]` The token mint.
+    ///   2. `[writable]` The destination account.
+    ///   3. `[]` The mint's multisignature recovery authority.
+    ///   4. `..4+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::non_transferable::instruction::RecoveryTransferInstructionData`
+    RecoveryTransfer,
+}
+
+/// Data expected by `NonTransferableInstruction::InitializeRecoveryAuthority`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct InitializeRecoveryAuthorityInstructionData {
+    /// The recovery authority
+    pub authority: Address,
+}
+
+/// Data expected by `NonTransferableInstruction::RecoveryTransfer`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct RecoveryTransferInstructionData {
+    /// The amount of tokens to move
+    pub amount: U64,
+    /// Expected number of base 10 digits to the right of the decimal place
+    pub decimals: u8,
+}
+
+/// Create an `InitializeRecoveryAuthority` instruction
+pub fn initialize_recovery_authority(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::NonTransferableExtension,
+        NonTransferableInstruction::InitializeRecoveryAuthority,
+        &InitializeRecoveryAuthorityInstructionData {
+            authority: *authority,
+        },
+    ))
+}
+
+/// Create a `RecoveryTransfer` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn recovery_transfer(
+    token_program_id: &Address,
+    source: &Address,
+    mint: &Address,
+    destination: &Address,
+    recovery_authority: &Address,
+    signer_pubkeys: &[&Address],
+    amount: u64,
+    decimals: u8,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = Vec::with_capacity(4 + signer_pubkeys.len());
+    accounts.push(AccountMeta::new(*source, false));
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new(*destination, false));
+    accounts.push(AccountMeta::new_readonly(
+        *recovery_authority,
+        signer_pubkeys.is_empty(),
+    ));
+    for signer_pubkey in signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::NonTransferableExtension,
+        NonTransferableInstruction::RecoveryTransfer,
+        &RecoveryTransferInstructionData {
+            amount: amount.into(),
+            decimals,
+        },
+    ))
+}
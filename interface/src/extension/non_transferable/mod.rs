@@ -0,0 +1,64 @@
+use {
+    crate::extension::{BaseState, BaseStateWithExtensions, Extension, ExtensionType},
+    bytemuck::{Pod, Zeroable},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+};
+#[cfg(feature = "serde")]
+use {
+    serde::{Deserialize, Serialize},
+    serde_with::{As, DisplayFromStr},
+};
+
+/// Instruction types for the non-transferable extension
+pub mod instruction;
+
+/// Indicates that the tokens from this mint can't be transferred
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct NonTransferable;
+
+/// Indicates that the tokens from this account belong to a non-transferable
+/// mint
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct NonTransferableAccount;
+
+/// Authority that can move balances of a non-transferable mint between
+/// accounts, e.g. to recover credentials from a lost wallet
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct NonTransferableRecovery {
+    /// Authority that can perform recovery transfers
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DisplayFromStr>>"))]
+    pub authority: MaybeNull<Address>,
+}
+
+impl Extension for NonTransferable {
+    const TYPE: ExtensionType = ExtensionType::NonTransferable;
+}
+
+impl Extension for NonTransferableAccount {
+    const TYPE: ExtensionType = ExtensionType::NonTransferableAccount;
+}
+
+impl Extension for NonTransferableRecovery {
+    const TYPE: ExtensionType = ExtensionType::NonTransferableRecovery;
+}
+
+/// Attempts to get the non-transferable recovery authority from the TLV data,
+/// returning None if the extension is not found or the authority is unset
+pub fn get_recovery_authority<S: BaseState, BSE: BaseStateWithExtensions<S>>(
+    state: &BSE,
+) -> Option<Address> {
+    state
+        .get_extension::<NonTransferableRecovery>()
+        .ok()
+        .and_then(|e| Option::<Address>::from(e.authority))
+}
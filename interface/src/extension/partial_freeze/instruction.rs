@@ -0,0 +1,165 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use {
+    crate::{
+        check_program_account,
+        instruction::{encode_instruction, TokenInstruction},
+    },
+    alloc::vec,
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_address::Address,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_zero_copy::unaligned::U16,
+};
+
+/// Partial freeze extension instructions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum PartialFreezeInstruction {
+    /// Initialize the partial freeze extension for the given mint account,
+    /// with the restrictions and reason code applied to new accounts.
+    ///
+    /// Fails if the account has already been initialized, so must be called
+    /// before `InitializeMint`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]`  The mint account to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::partial_freeze::instruction::RestrictionsInstructionData`
+    Initialize,
+    /// Update the restrictions and reason code applied to new accounts.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The mint freeze authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature freeze authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::partial_freeze::instruction::RestrictionsInstructionData`
+    UpdateDefaultRestrictions,
+    /// Set the restrictions and reason code on an account. Setting no
+    /// restrictions lifts the partial freeze. A full freeze through
+    /// `FreezeAccount` still applies regardless of these restrictions.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The account to update.
+    ///   1. `[]` The token mint.
+    ///   2. `[signer]` The mint freeze authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The account to update.
+    ///   1. `[]` The token mint.
+    ///   2. `[]` The mint's multisignature freeze authority.
+    ///   3. `..3+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::partial_freeze::instruction::RestrictionsInstructionData`
+    SetAccountRestrictions,
+}
+
+/// Data expected by all `PartialFreezeInstruction`s
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct RestrictionsInstructionData {
+    /// Bitmask of `FREEZE_OUTGOING` and `FREEZE_INCOMING`
+    pub restrictions: u8,
+    /// Reason code, 0 if none
+    pub reason: U16,
+}
+
+/// Create an `Initialize` instruction
+pub fn initialize(
+    token_program_id: &Address,
+    mint: &Address,
+    default_restrictions: u8,
+    default_reason: u16,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::PartialFreezeExtension,
+        PartialFreezeInstruction::Initialize,
+        &RestrictionsInstructionData {
+            restrictions: default_restrictions,
+            reason: default_reason.into(),
+        },
+    ))
+}
+
+/// Create an `UpdateDefaultRestrictions` instruction
+pub fn update_default_restrictions(
+    token_program_id: &Address,
+    mint: &Address,
+    freeze_authority: &Address,
+    signers: &[&Address],
+    default_restrictions: u8,
+    default_reason: u16,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*freeze_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::PartialFreezeExtension,
+        PartialFreezeInstruction::UpdateDefaultRestrictions,
+        &RestrictionsInstructionData {
+            restrictions: default_restrictions,
+            reason: default_reason.into(),
+        },
+    ))
+}
+
+/// Create a `SetAccountRestrictions` instruction
+pub fn set_account_restrictions(
+    token_program_id: &Address,
+    account: &Address,
+    mint: &Address,
+    freeze_authority: &Address,
+    signers: &[&Address],
+    restrictions: u8,
+    reason: u16,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*freeze_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::PartialFreezeExtension,
+        PartialFreezeInstruction::SetAccountRestrictions,
+        &RestrictionsInstructionData {
+            restrictions,
+            reason: reason.into(),
+        },
+    ))
+}
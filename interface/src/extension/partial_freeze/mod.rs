@@ -0,0 +1,71 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use {
+    crate::extension::{BaseState, BaseStateWithExtensions, Extension, ExtensionType},
+    bytemuck::{Pod, Zeroable},
+    solana_zero_copy::unaligned::U16,
+};
+
+/// Partial freeze extension instructions
+pub mod instruction;
+
+/// Blocks transfers, burns, and confidential transfers out of the account
+pub const FREEZE_OUTGOING: u8 = 1 << 0;
+/// Blocks transfers, mints, and confidential transfers into the account
+pub const FREEZE_INCOMING: u8 = 1 << 1;
+/// All partial freeze restrictions
+pub const FREEZE_ALL: u8 = FREEZE_OUTGOING | FREEZE_INCOMING;
+
+/// Partial freeze extension data for mints, managed by the mint's freeze
+/// authority
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PartialFreezeConfig {
+    /// Restrictions applied to new accounts on `InitializeAccount`, as a
+    /// bitmask of `FREEZE_OUTGOING` and `FREEZE_INCOMING`
+    pub default_restrictions: u8,
+    /// Reason code applied to new accounts on `InitializeAccount`, 0 if none
+    pub default_reason: U16,
+}
+impl Extension for PartialFreezeConfig {
+    const TYPE: ExtensionType = ExtensionType::PartialFreeze;
+}
+
+/// Partial freeze extension data for accounts
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PartialFreezeAccount {
+    /// Restrictions on the account, as a bitmask of `FREEZE_OUTGOING` and
+    /// `FREEZE_INCOMING`
+    pub restrictions: u8,
+    /// Reason code set by the freeze authority, 0 if none
+    pub reason: U16,
+}
+impl Extension for PartialFreezeAccount {
+    const TYPE: ExtensionType = ExtensionType::PartialFreezeAccount;
+}
+
+/// Check that the bitmask only contains known restrictions
+pub fn valid_restrictions(restrictions: u8) -> bool {
+    restrictions & !FREEZE_ALL == 0
+}
+
+/// Determine if transfers out of this account are frozen
+pub fn outgoing_frozen<BSE: BaseStateWithExtensions<S>, S: BaseState>(account_state: &BSE) -> bool {
+    if let Ok(extension) = account_state.get_extension::<PartialFreezeAccount>() {
+        return extension.restrictions & FREEZE_OUTGOING != 0;
+    }
+    false
+}
+
+/// Determine if transfers into this account are frozen
+pub fn incoming_frozen<BSE: BaseStateWithExtensions<S>, S: BaseState>(account_state: &BSE) -> bool {
+    if let Ok(extension) = account_state.get_extension::<PartialFreezeAccount>() {
+        return extension.restrictions & FREEZE_INCOMING != 0;
+    }
+    false
+}
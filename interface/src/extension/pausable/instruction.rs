@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use {
     crate::{
         check_program_account,
+        extension::pausable::UnixTimestamp,
         instruction::{encode_instruction, TokenInstruction},
     },
     alloc::vec,
@@ -45,6 +46,9 @@ pub enum PausableInstruction {
     Pause,
     /// Resume minting, burning, and transferring for the mint.
     ///
+    /// Also resumes any operations paused through `PauseOperations`, and
+    /// clears any pause window scheduled through `SchedulePause`.
+    ///
     /// Accounts expected by this instruction:
     ///
     ///   0. `[writable]` The mint to update.
@@ -55,6 +59,66 @@ pub enum PausableInstruction {
     ///   1. `[]` The mint's multisignature pause authority.
     ///   2. `..2+M` `[signer]` M signer accounts.
     Resume,
+    /// Schedule a window during which minting, burning, and transferring are
+    /// paused for the mint, enforced using the `Clock` sysvar.
+    ///
+    /// Overwrites any previously scheduled window. Setting both timestamps to
+    /// 0 clears the schedule. A resume timestamp of 0 keeps the mint paused
+    /// from the pause timestamp until the schedule is cleared.
+    ///
+    /// If the mint does not yet contain the `PausableSchedule` extension, the
+    /// account is reallocated, so it must already hold enough lamports to
+    /// remain rent-exempt.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[signer]` The mint's pause authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[]` The mint's multisignature pause authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::pausable::instruction::SchedulePauseInstructionData`
+    SchedulePause,
+    /// Pause only the given operations for the mint, leaving the others
+    /// active. Operations that are already paused remain paused.
+    ///
+    /// If the mint does not yet contain the `PausedOperations` extension, the
+    /// account is reallocated, so it must already hold enough lamports to
+    /// remain rent-exempt.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[signer]` The mint's pause authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[]` The mint's multisignature pause authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::pausable::instruction::OperationsInstructionData`
+    PauseOperations,
+    /// Resume the given operations for the mint, if they were paused through
+    /// `PauseOperations`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[signer]` The mint's pause authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint to update.
+    ///   1. `[]` The mint's multisignature pause authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::pausable::instruction::OperationsInstructionData`
+    ResumeOperations,
 }
 
 /// Data expected by `PausableInstruction::Initialize`
@@ -67,6 +131,30 @@ pub struct InitializeInstructionData {
     pub authority: Address,
 }
 
+/// Data expected by `PausableInstruction::SchedulePause`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct SchedulePauseInstructionData {
+    /// Unix timestamp at which the mint becomes paused, or 0 to clear the
+    /// schedule
+    pub pause_timestamp: UnixTimestamp,
+    /// Unix timestamp at which the mint resumes, or 0 for no scheduled resume
+    pub resume_timestamp: UnixTimestamp,
+}
+
+/// Data expected by `PausableInstruction::PauseOperations` and
+/// `PausableInstruction::ResumeOperations`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct OperationsInstructionData {
+    /// Bitmask of `crate::extension::pausable::PausedOperations` flags
+    pub operations: u8,
+}
+
 /// Create an `Initialize` instruction
 pub fn initialize(
     token_program_id: &Address,
@@ -133,3 +221,82 @@ pub fn resume(
         &(),
     ))
 }
+
+/// Create a `SchedulePause` instruction
+pub fn schedule_pause(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    pause_timestamp: i64,
+    resume_timestamp: i64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::PausableExtension,
+        PausableInstruction::SchedulePause,
+        &SchedulePauseInstructionData {
+            pause_timestamp: pause_timestamp.into(),
+            resume_timestamp: resume_timestamp.into(),
+        },
+    ))
+}
+
+/// Create a `PauseOperations` instruction
+pub fn pause_operations(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    operations: u8,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::PausableExtension,
+        PausableInstruction::PauseOperations,
+        &OperationsInstructionData { operations },
+    ))
+}
+
+/// Create a `ResumeOperations` instruction
+pub fn resume_operations(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    operations: u8,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::PausableExtension,
+        PausableInstruction::ResumeOperations,
+        &OperationsInstructionData { operations },
+    ))
+}
@@ -3,7 +3,7 @@ use {
     bytemuck::{Pod, Zeroable},
     solana_address::Address,
     solana_nullable::MaybeNull,
-    solana_zero_copy::unaligned::Bool,
+    solana_zero_copy::unaligned::{Bool, I64},
 };
 #[cfg(feature = "serde")]
 use {
@@ -14,6 +14,9 @@ use {
 /// Instruction types for the pausable extension
 pub mod instruction;
 
+/// `UnixTimestamp` expressed with an alignment-independent type
+pub type UnixTimestamp = I64;
+
 /// Indicates that the tokens from this mint can be paused
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
@@ -27,6 +30,75 @@ pub struct PausableConfig {
     pub paused: Bool,
 }
 
+/// Individual operations paused by the pause authority, as a bitmask of
+/// `PausedOperations::MINT`, `PausedOperations::BURN`, and
+/// `PausedOperations::TRANSFER`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PausedOperations {
+    /// Bitmask of the paused operations
+    pub operations: u8,
+}
+impl PausedOperations {
+    /// Minting tokens, including confidential mints
+    pub const MINT: u8 = 1 << 0;
+    /// Burning tokens, including confidential burns
+    pub const BURN: u8 = 1 << 1;
+    /// Transferring tokens, including confidential deposits, withdrawals, and
+    /// transfers
+    pub const TRANSFER: u8 = 1 << 2;
+    /// All operations that can be paused
+    pub const ALL: u8 = Self::MINT | Self::BURN | Self::TRANSFER;
+
+    /// Check that the bitmask is non-empty and only contains known operations
+    pub fn is_valid(operations: u8) -> bool {
+        operations != 0 && operations & !Self::ALL == 0
+    }
+
+    /// Check if any of the given operations is paused
+    pub fn is_paused(&self, operations: u8) -> bool {
+        self.operations & operations != 0
+    }
+}
+
+/// Pause window scheduled by the pause authority, enforced on minting,
+/// transferring, and burning using the `Clock` sysvar
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PausableSchedule {
+    /// Unix timestamp at which the mint becomes paused, or 0 if no pause is
+    /// scheduled
+    pub pause_timestamp: UnixTimestamp,
+    /// Unix timestamp at which the mint resumes, or 0 if the scheduled pause
+    /// lasts until it is cleared by the authority
+    pub resume_timestamp: UnixTimestamp,
+}
+impl PausableSchedule {
+    /// Check that the pause window is well-formed: a resume time requires a
+    /// pause time, and must come after it
+    pub fn is_valid(&self) -> bool {
+        let pause_timestamp = i64::from(self.pause_timestamp);
+        let resume_timestamp = i64::from(self.resume_timestamp);
+        if pause_timestamp == 0 {
+            return resume_timestamp == 0;
+        }
+        pause_timestamp > 0 && (resume_timestamp == 0 || resume_timestamp > pause_timestamp)
+    }
+
+    /// Check if the scheduled window covers the given unix timestamp
+    pub fn is_paused(&self, unix_timestamp: i64) -> bool {
+        let pause_timestamp = i64::from(self.pause_timestamp);
+        let resume_timestamp = i64::from(self.resume_timestamp);
+        pause_timestamp != 0
+            && unix_timestamp >= pause_timestamp
+            && (resume_timestamp == 0 || unix_timestamp < resume_timestamp)
+    }
+}
+
 /// Indicates that the tokens from this account belong to a pausable mint
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
@@ -38,6 +110,70 @@ impl Extension for PausableConfig {
     const TYPE: ExtensionType = ExtensionType::Pausable;
 }
 
+impl Extension for PausedOperations {
+    const TYPE: ExtensionType = ExtensionType::PausedOperations;
+}
+
+impl Extension for PausableSchedule {
+    const TYPE: ExtensionType = ExtensionType::PausableSchedule;
+}
+
 impl Extension for PausableAccount {
     const TYPE: ExtensionType = ExtensionType::PausableAccount;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(pause_timestamp: i64, resume_timestamp: i64) -> PausableSchedule {
+        PausableSchedule {
+            pause_timestamp: pause_timestamp.into(),
+            resume_timestamp: resume_timestamp.into(),
+        }
+    }
+
+    #[test]
+    fn paused_operations() {
+        assert!(!PausedOperations::is_valid(0));
+        assert!(!PausedOperations::is_valid(1 << 3));
+        assert!(PausedOperations::is_valid(PausedOperations::TRANSFER));
+        assert!(PausedOperations::is_valid(PausedOperations::ALL));
+
+        let paused = PausedOperations {
+            operations: PausedOperations::TRANSFER,
+        };
+        assert!(paused.is_paused(PausedOperations::TRANSFER));
+        assert!(!paused.is_paused(PausedOperations::MINT));
+        assert!(!paused.is_paused(PausedOperations::BURN));
+    }
+
+    #[test]
+    fn schedule_validity() {
+        assert!(schedule(0, 0).is_valid());
+        assert!(schedule(10, 0).is_valid());
+        assert!(schedule(10, 20).is_valid());
+        assert!(!schedule(0, 20).is_valid());
+        assert!(!schedule(20, 20).is_valid());
+        assert!(!schedule(20, 10).is_valid());
+        assert!(!schedule(-10, 0).is_valid());
+    }
+
+    #[test]
+    fn schedule_window() {
+        let cleared = schedule(0, 0);
+        assert!(!cleared.is_paused(0));
+        assert!(!cleared.is_paused(i64::MAX));
+
+        let open_ended = schedule(10, 0);
+        assert!(!open_ended.is_paused(9));
+        assert!(open_ended.is_paused(10));
+        assert!(open_ended.is_paused(i64::MAX));
+
+        let window = schedule(10, 20);
+        assert!(!window.is_paused(9));
+        assert!(window.is_paused(10));
+        assert!(window.is_paused(19));
+        assert!(!window.is_paused(20));
+    }
+}
@@ -1,7 +1,7 @@
 use {
     crate::{
         check_program_account,
-        extension::scaled_ui_amount::{PodF64, UnixTimestamp},
+        extension::scaled_ui_amount::{PodF64, RationalMultiplier, UnixTimestamp},
         instruction::{encode_instruction, TokenInstruction},
     },
     alloc::vec,
@@ -67,6 +67,96 @@ pub enum ScaledUiAmountMintInstruction {
     /// Data expected by this instruction:
     ///   `crate::extension::scaled_ui_amount::instruction::UpdateMultiplierInstructionData`
     UpdateMultiplier,
+    /// Initialize an exact rational multiplier on a new mint with scaled UI
+    /// amounts.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`, and after `ScaledUiAmountMintInstruction::Initialize`.
+    /// The `f64` multiplier in the `ScaledUiAmount` extension is overwritten
+    /// with the closest approximation of the rational multiplier.
+    ///
+    /// Fails if the numerator or denominator is 0.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::scaled_ui_amount::RationalMultiplier`
+    InitializeRational,
+    /// Update the rational multiplier. Only supported for mints that include
+    /// the `ScaledUiAmountRational` extension, which must use this instruction
+    /// instead of `ScaledUiAmountMintInstruction::UpdateMultiplier`.
+    ///
+    /// Fails if the numerator or denominator is 0.
+    ///
+    /// Behaves like `ScaledUiAmountMintInstruction::UpdateMultiplier`, and
+    /// also updates the `f64` multipliers in the `ScaledUiAmount` extension.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The multiplier authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature multiplier authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::scaled_ui_amount::instruction::UpdateRationalMultiplierInstructionData`
+    UpdateRationalMultiplier,
+    /// Queue a multiplier change in the mint's `ScaledUiAmountSchedule`,
+    /// creating the schedule from the current multipliers if needed. Not
+    /// supported for mints with a rational multiplier.
+    ///
+    /// Unlike `ScaledUiAmountMintInstruction::UpdateMultiplier`, which must not
+    /// be used once the mint has a schedule, queued changes are kept until
+    /// they take effect, and past multipliers stay in the schedule as history.
+    /// A change with the same timestamp as a queued one replaces it, and a
+    /// timestamp in the past is moved to the current time.
+    ///
+    /// The mint account is resized to fit the schedule, so it must hold enough
+    /// lamports to remain rent-exempt.
+    ///
+    /// Fails if the multiplier is less than or equal to 0 or if it's
+    /// [subnormal](https://en.wikipedia.org/wiki/Subnormal_number).
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The multiplier authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature multiplier authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::scaled_ui_amount::instruction::UpdateMultiplierInstructionData`
+    ScheduleMultiplier,
+    /// Remove a queued multiplier change from the mint's
+    /// `ScaledUiAmountSchedule`.
+    ///
+    /// Fails if no change is queued at the given timestamp, or if it has
+    /// already taken effect.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The multiplier authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature multiplier authority.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::scaled_ui_amount::instruction::CancelScheduledMultiplierInstructionData`
+    CancelScheduledMultiplier,
 }
 
 /// Data expected by `ScaledUiAmountMint::Initialize`
@@ -94,6 +184,28 @@ pub struct UpdateMultiplierInstructionData {
     pub effective_timestamp: UnixTimestamp,
 }
 
+/// Data expected by `ScaledUiAmountMint::UpdateRationalMultiplier`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct UpdateRationalMultiplierInstructionData {
+    /// The new multiplier
+    pub multiplier: RationalMultiplier,
+    /// Timestamp at which the new multiplier will take effect
+    pub effective_timestamp: UnixTimestamp,
+}
+
+/// Data expected by `ScaledUiAmountMint::CancelScheduledMultiplier`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct CancelScheduledMultiplierInstructionData {
+    /// Timestamp of the queued multiplier change
+    pub effective_timestamp: UnixTimestamp,
+}
+
 /// Create an `Initialize` instruction
 pub fn initialize(
     token_program_id: &Address,
@@ -145,3 +257,107 @@ pub fn update_multiplier(
         },
     ))
 }
+
+/// Create an `InitializeRational` instruction
+pub fn initialize_rational(
+    token_program_id: &Address,
+    mint: &Address,
+    numerator: u64,
+    denominator: u64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ScaledUiAmountExtension,
+        ScaledUiAmountMintInstruction::InitializeRational,
+        &RationalMultiplier::new(numerator, denominator),
+    ))
+}
+
+/// Create an `UpdateRationalMultiplier` instruction
+pub fn update_rational_multiplier(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    numerator: u64,
+    denominator: u64,
+    effective_timestamp: i64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ScaledUiAmountExtension,
+        ScaledUiAmountMintInstruction::UpdateRationalMultiplier,
+        &UpdateRationalMultiplierInstructionData {
+            effective_timestamp: effective_timestamp.into(),
+            multiplier: RationalMultiplier::new(numerator, denominator),
+        },
+    ))
+}
+
+/// Create a `ScheduleMultiplier` instruction
+pub fn schedule_multiplier(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    multiplier: f64,
+    effective_timestamp: i64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ScaledUiAmountExtension,
+        ScaledUiAmountMintInstruction::ScheduleMultiplier,
+        &UpdateMultiplierInstructionData {
+            effective_timestamp: effective_timestamp.into(),
+            multiplier: multiplier.into(),
+        },
+    ))
+}
+
+/// Create a `CancelScheduledMultiplier` instruction
+pub fn cancel_scheduled_multiplier(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    effective_timestamp: i64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ScaledUiAmountExtension,
+        ScaledUiAmountMintInstruction::CancelScheduledMultiplier,
+        &CancelScheduledMultiplierInstructionData {
+            effective_timestamp: effective_timestamp.into(),
+        },
+    ))
+}
@@ -3,13 +3,20 @@ use {
         extension::{Extension, ExtensionType},
         trim_ui_amount_string,
     },
-    alloc::{format, string::String},
+    alloc::{
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    },
     bytemuck::{Pod, Zeroable},
+    core::mem::size_of,
     num_traits::{pow, Float},
     solana_address::Address,
     solana_nullable::MaybeNull,
     solana_program_error::ProgramError,
-    solana_zero_copy::unaligned::I64,
+    solana_zero_copy::unaligned::{I64, U64},
+    spl_type_length_value::variable_len_pack::VariableLenPack,
 };
 #[cfg(feature = "serde")]
 use {
@@ -115,6 +122,329 @@ impl Extension for ScaledUiAmountConfig {
     const TYPE: ExtensionType = ExtensionType::ScaledUiAmount;
 }
 
+/// Exact multiplier expressed as `numerator / denominator`
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct RationalMultiplier {
+    /// Numerator of the multiplier
+    pub numerator: U64,
+    /// Denominator of the multiplier
+    pub denominator: U64,
+}
+impl RationalMultiplier {
+    /// Create a new multiplier from its numerator and denominator
+    pub fn new(numerator: u64, denominator: u64) -> Self {
+        Self {
+            numerator: numerator.into(),
+            denominator: denominator.into(),
+        }
+    }
+
+    /// Check that both the numerator and denominator are non-zero
+    pub fn is_valid(&self) -> bool {
+        u64::from(self.numerator) != 0 && u64::from(self.denominator) != 0
+    }
+
+    /// Closest `f64` to the multiplier, stored in `ScaledUiAmountConfig` for
+    /// readers that do not know about the rational multiplier
+    pub fn to_f64(&self) -> f64 {
+        u64::from(self.numerator) as f64 / u64::from(self.denominator) as f64
+    }
+}
+
+/// Exact scaled UI amount multiplier for mints, used instead of the `f64`
+/// multipliers in `ScaledUiAmountConfig`.
+///
+/// The authority of the `ScaledUiAmountConfig` controls this extension, and
+/// both are updated together so that the `f64` multipliers remain the closest
+/// approximation of the rational ones.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ScaledUiAmountRationalConfig {
+    /// Amount to multiply raw amounts by, outside of the decimal
+    pub multiplier: RationalMultiplier,
+    /// Unix timestamp at which `new_multiplier` comes into effective
+    pub new_multiplier_effective_timestamp: UnixTimestamp,
+    /// Next multiplier, once `new_multiplier_effective_timestamp` is reached
+    pub new_multiplier: RationalMultiplier,
+}
+impl ScaledUiAmountRationalConfig {
+    /// Get the multiplier in effect at the given timestamp
+    pub fn current_multiplier(&self, unix_timestamp: i64) -> RationalMultiplier {
+        if unix_timestamp >= self.new_multiplier_effective_timestamp.into() {
+            self.new_multiplier
+        } else {
+            self.multiplier
+        }
+    }
+
+    /// Convert a raw amount to its UI representation using the given decimals
+    /// field. Excess zeroes or unneeded decimal point are trimmed.
+    ///
+    /// The scaled amount is computed exactly and truncated towards 0 at the
+    /// given number of decimals.
+    pub fn amount_to_ui_amount(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Option<String> {
+        let multiplier = self.current_multiplier(unix_timestamp);
+        let numerator = u128::from(u64::from(multiplier.numerator));
+        let denominator = u128::from(u64::from(multiplier.denominator));
+        let scaled_amount = u128::from(amount)
+            .checked_mul(numerator)?
+            .checked_div(denominator)?;
+        let ui_amount = if decimals > 0 {
+            // Left-pad zeros to decimals + 1, so we at least have an integer zero
+            let mut ui_amount = format!("{:01$}", scaled_amount, decimals as usize + 1);
+            ui_amount.insert(ui_amount.len() - decimals as usize, '.');
+            ui_amount
+        } else {
+            scaled_amount.to_string()
+        };
+        Some(trim_ui_amount_string(ui_amount, decimals))
+    }
+
+    /// Try to convert a UI representation of a token amount to its raw amount
+    /// using the given decimals field.
+    ///
+    /// The UI amount must be a plain decimal number with at most `decimals`
+    /// digits after the decimal point. The result is the smallest raw amount
+    /// whose UI amount is at least the given one, so converting a raw amount
+    /// to its UI amount and back always gives the same raw amount when the
+    /// multiplier is at least 1. With smaller multipliers, several raw amounts
+    /// share a UI amount, and the smallest of them is returned.
+    pub fn try_ui_amount_into_amount(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        let decimals = decimals as usize;
+        let mut parts = ui_amount.split('.');
+        // splitting a string, even an empty one, will always yield an iterator
+        // of at least length == 1
+        let mut amount_str = parts.next().unwrap().to_string();
+        let after_decimal = parts.next().unwrap_or("");
+        let after_decimal = after_decimal.trim_end_matches('0');
+        if (amount_str.is_empty() && after_decimal.is_empty())
+            || parts.next().is_some()
+            || after_decimal.len() > decimals
+            || !amount_str.bytes().all(|b| b.is_ascii_digit())
+            || !after_decimal.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+        amount_str.push_str(after_decimal);
+        for _ in 0..decimals.saturating_sub(after_decimal.len()) {
+            amount_str.push('0');
+        }
+        let scaled_amount = amount_str
+            .parse::<u128>()
+            .map_err(|_| ProgramError::InvalidArgument)?;
+
+        // ceil(scaled_amount * denominator / numerator), split up so that no
+        // intermediate value can overflow
+        let multiplier = self.current_multiplier(unix_timestamp);
+        let numerator = u128::from(u64::from(multiplier.numerator));
+        let denominator = u128::from(u64::from(multiplier.denominator));
+        if numerator == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let quotient = scaled_amount / numerator;
+        let remainder = scaled_amount % numerator;
+        let amount = quotient
+            .checked_mul(denominator)
+            .and_then(|amount| amount.checked_add((remainder * denominator).div_ceil(numerator)))
+            .ok_or(ProgramError::InvalidArgument)?;
+        u64::try_from(amount).map_err(|_| ProgramError::InvalidArgument)
+    }
+}
+impl Extension for ScaledUiAmountRationalConfig {
+    const TYPE: ExtensionType = ExtensionType::ScaledUiAmountRational;
+}
+
+/// Multiplier taking effect at a given time, as recorded in a
+/// `ScaledUiAmountSchedule`
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ScheduledMultiplier {
+    /// Unix timestamp at which the multiplier comes into effect
+    pub effective_timestamp: UnixTimestamp,
+    /// Amount to multiply raw amounts by, outside of the decimal
+    pub multiplier: PodF64,
+}
+
+/// Variable-length schedule of multipliers for mints with scaled UI amounts.
+///
+/// Entries are sorted by effective timestamp. Entries that have already taken
+/// effect form the multiplier history, starting from when the schedule was
+/// created, and the later ones are queued changes.
+///
+/// The `ScaledUiAmountConfig` of the mint always holds the multiplier in
+/// effect and the next queued change as of the last schedule update, so
+/// readers that need to look further ahead must use the schedule.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScaledUiAmountSchedule {
+    /// Multipliers, sorted by effective timestamp
+    pub entries: Vec<ScheduledMultiplier>,
+}
+impl ScaledUiAmountSchedule {
+    /// Create a schedule from the multipliers in the given config
+    pub fn from_config(config: &ScaledUiAmountConfig) -> Self {
+        let mut entries = vec![];
+        if i64::from(config.new_multiplier_effective_timestamp) > 0 {
+            entries.push(ScheduledMultiplier {
+                effective_timestamp: 0.into(),
+                multiplier: config.multiplier,
+            });
+        }
+        entries.push(ScheduledMultiplier {
+            effective_timestamp: config.new_multiplier_effective_timestamp,
+            multiplier: config.new_multiplier,
+        });
+        Self { entries }
+    }
+
+    /// Get the entry in effect at the given timestamp
+    pub fn entry_at(&self, unix_timestamp: i64) -> Option<&ScheduledMultiplier> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| unix_timestamp >= i64::from(entry.effective_timestamp))
+    }
+
+    /// Get the first entry that takes effect after the given timestamp
+    pub fn next_entry(&self, unix_timestamp: i64) -> Option<&ScheduledMultiplier> {
+        self.entries
+            .iter()
+            .find(|entry| unix_timestamp < i64::from(entry.effective_timestamp))
+    }
+
+    /// Add an entry to the schedule, replacing any entry with the same
+    /// effective timestamp
+    pub fn schedule(&mut self, new_entry: ScheduledMultiplier) {
+        let effective_timestamp = i64::from(new_entry.effective_timestamp);
+        match self
+            .entries
+            .binary_search_by_key(&effective_timestamp, |entry| {
+                i64::from(entry.effective_timestamp)
+            }) {
+            Ok(index) => self.entries[index] = new_entry,
+            Err(index) => self.entries.insert(index, new_entry),
+        }
+    }
+
+    /// Remove the entry with the given effective timestamp, returning whether
+    /// it was found
+    pub fn cancel(&mut self, effective_timestamp: i64) -> bool {
+        let previous_len = self.entries.len();
+        self.entries
+            .retain(|entry| i64::from(entry.effective_timestamp) != effective_timestamp);
+        self.entries.len() != previous_len
+    }
+
+    /// Write the multiplier in effect and the next queued change at the given
+    /// timestamp into the config
+    pub fn update_config(&self, config: &mut ScaledUiAmountConfig, unix_timestamp: i64) {
+        if let Some(current) = self.entry_at(unix_timestamp) {
+            config.multiplier = current.multiplier;
+            let next = self.next_entry(unix_timestamp).unwrap_or(current);
+            config.new_multiplier_effective_timestamp = next.effective_timestamp;
+            config.new_multiplier = next.multiplier;
+        }
+    }
+
+    fn config_at(&self, unix_timestamp: i64) -> Option<ScaledUiAmountConfig> {
+        let entry = self.entry_at(unix_timestamp)?;
+        Some(ScaledUiAmountConfig {
+            multiplier: entry.multiplier,
+            new_multiplier: entry.multiplier,
+            ..Default::default()
+        })
+    }
+
+    /// Convert a raw amount to its UI representation using the given decimals
+    /// field and the multiplier in effect at the given timestamp, which may be
+    /// in the past to reconstruct historical UI amounts.
+    pub fn amount_to_ui_amount(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Option<String> {
+        self.config_at(unix_timestamp)?
+            .amount_to_ui_amount(amount, decimals, unix_timestamp)
+    }
+
+    /// Try to convert a UI representation of a token amount to its raw amount
+    /// using the given decimals field and the multiplier in effect at the
+    /// given timestamp.
+    pub fn try_ui_amount_into_amount(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        self.config_at(unix_timestamp)
+            .ok_or(ProgramError::InvalidArgument)?
+            .try_ui_amount_into_amount(ui_amount, decimals, unix_timestamp)
+    }
+}
+impl Extension for ScaledUiAmountSchedule {
+    const TYPE: ExtensionType = ExtensionType::ScaledUiAmountSchedule;
+}
+impl VariableLenPack for ScaledUiAmountSchedule {
+    fn pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let entries_len =
+            u32::try_from(self.entries.len()).map_err(|_| ProgramError::InvalidArgument)?;
+        let entries = bytemuck::cast_slice::<ScheduledMultiplier, u8>(&self.entries);
+        let entries_start = size_of::<u32>();
+        let entries_end = entries_start.saturating_add(entries.len());
+        if dst.len() < entries_end {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        dst[..entries_start].copy_from_slice(&entries_len.to_le_bytes());
+        dst[entries_start..entries_end].copy_from_slice(entries);
+        Ok(())
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let entries_start = size_of::<u32>();
+        let entries_len = src
+            .get(..entries_start)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)? as usize;
+        let entries_end = entries_len
+            .checked_mul(size_of::<ScheduledMultiplier>())
+            .and_then(|len| len.checked_add(entries_start))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let entries = src
+            .get(entries_start..entries_end)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let entries = bytemuck::try_cast_slice::<u8, ScheduledMultiplier>(entries)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok(Self {
+            entries: entries.to_vec(),
+        })
+    }
+
+    fn get_packed_len(&self) -> Result<usize, ProgramError> {
+        Ok(size_of::<u32>()
+            .saturating_add(size_of::<ScheduledMultiplier>().saturating_mul(self.entries.len())))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, proptest::prelude::*};
@@ -366,7 +696,295 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rational_multiplier_choice() {
+        let multiplier = RationalMultiplier::new(5, 1);
+        let new_multiplier = RationalMultiplier::new(10, 3);
+        let config = ScaledUiAmountRationalConfig {
+            multiplier,
+            new_multiplier,
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+        };
+        assert_eq!(config.current_multiplier(1), new_multiplier);
+        assert_eq!(config.current_multiplier(0), multiplier);
+        assert_eq!(config.current_multiplier(i64::MIN), multiplier);
+        assert_eq!(config.current_multiplier(i64::MAX), new_multiplier);
+    }
+
+    #[test]
+    fn specific_rational_amount_to_ui_amount() {
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(5, 1),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        assert_eq!(config.amount_to_ui_amount(1, 0, 0).unwrap(), "5");
+        assert_eq!(config.amount_to_ui_amount(1, 1, 0).unwrap(), "0.5");
+        assert_eq!(
+            config.amount_to_ui_amount(1, 10, 0).unwrap(),
+            "0.0000000005"
+        );
+        assert_eq!(
+            config.amount_to_ui_amount(10_000_000_000, 10, 0).unwrap(),
+            "5"
+        );
+
+        // large balances keep every digit
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(1, 1),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.amount_to_ui_amount(u64::MAX, 0, 0).unwrap(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            config.amount_to_ui_amount(u64::MAX - 1, 9, 0).unwrap(),
+            "18446744073.709551614"
+        );
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(u64::MAX, 1),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.amount_to_ui_amount(u64::MAX, 0, 0).unwrap(),
+            "340282366920938463426481119284349108225"
+        );
+
+        // truncation towards 0
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(99, 100),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        // This is really 0.9999, which truncates at 2 decimal places
+        assert_eq!(config.amount_to_ui_amount(101, 2, 0).unwrap(), "0.99");
+        assert_eq!(config.amount_to_ui_amount(0, 2, 0).unwrap(), "0");
+    }
+
+    #[test]
+    fn specific_rational_ui_amount_to_amount() {
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(3, 2),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        for (ui_amount, expected) in [
+            ("1.5", 100),
+            ("1.50", 100),
+            ("1.51", 101),
+            ("1.52", 102),
+            (".03", 2),
+            ("3.", 200),
+            ("0", 0),
+        ] {
+            assert_eq!(
+                config
+                    .try_ui_amount_into_amount(ui_amount, TEST_DECIMALS, 0)
+                    .unwrap(),
+                expected
+            );
+        }
+
+        // large balances keep every digit
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(u64::MAX, 1),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            config
+                .try_ui_amount_into_amount("340282366920938463426481119284349108225", 0, 0)
+                .unwrap(),
+            u64::MAX
+        );
+
+        // overflow u64 fail
+        let config = ScaledUiAmountRationalConfig {
+            multiplier: RationalMultiplier::new(1, 2),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(ProgramError::InvalidArgument),
+            config.try_ui_amount_into_amount("9223372036854775808", 0, 0)
+        );
+
+        // fail if invalid or too precise ui_amount passed in
+        for ui_amount in [
+            "", ".", "0.t", "1.001", "-1", "+1", "1e5", "inf", "NaN", "1.2.3",
+        ] {
+            assert_eq!(
+                Err(ProgramError::InvalidArgument),
+                config.try_ui_amount_into_amount(ui_amount, TEST_DECIMALS, 0),
+            );
+        }
+    }
+
+    #[test]
+    fn schedule_from_config() {
+        let config = ScaledUiAmountConfig {
+            multiplier: PodF64::from(5.0),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(0),
+            new_multiplier: PodF64::from(5.0),
+            ..Default::default()
+        };
+        let schedule = ScaledUiAmountSchedule::from_config(&config);
+        assert_eq!(
+            schedule.entries,
+            vec![ScheduledMultiplier {
+                effective_timestamp: 0.into(),
+                multiplier: 5.0.into(),
+            }]
+        );
+
+        let config = ScaledUiAmountConfig {
+            multiplier: PodF64::from(5.0),
+            new_multiplier_effective_timestamp: UnixTimestamp::from(100),
+            new_multiplier: PodF64::from(10.0),
+            ..Default::default()
+        };
+        let schedule = ScaledUiAmountSchedule::from_config(&config);
+        assert_eq!(
+            schedule.entries,
+            vec![
+                ScheduledMultiplier {
+                    effective_timestamp: 0.into(),
+                    multiplier: 5.0.into(),
+                },
+                ScheduledMultiplier {
+                    effective_timestamp: 100.into(),
+                    multiplier: 10.0.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn schedule_queue_and_history() {
+        let mut schedule = ScaledUiAmountSchedule {
+            entries: vec![ScheduledMultiplier {
+                effective_timestamp: 0.into(),
+                multiplier: 1.0.into(),
+            }],
+        };
+        // queue a split, then a dividend adjustment, out of order
+        schedule.schedule(ScheduledMultiplier {
+            effective_timestamp: 200.into(),
+            multiplier: 2.1.into(),
+        });
+        schedule.schedule(ScheduledMultiplier {
+            effective_timestamp: 100.into(),
+            multiplier: 2.0.into(),
+        });
+        let timestamps = schedule
+            .entries
+            .iter()
+            .map(|entry| i64::from(entry.effective_timestamp))
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![0, 100, 200]);
+
+        // historical and future UI amounts
+        assert_eq!(schedule.amount_to_ui_amount(100, 2, 99).unwrap(), "1");
+        assert_eq!(schedule.amount_to_ui_amount(100, 2, 100).unwrap(), "2");
+        assert_eq!(schedule.amount_to_ui_amount(100, 2, 250).unwrap(), "2.1");
+        assert_eq!(
+            schedule.try_ui_amount_into_amount("2", 2, 150).unwrap(),
+            100
+        );
+        assert_eq!(schedule.amount_to_ui_amount(100, 2, -1), None);
+
+        // the config follows the schedule
+        let mut config = ScaledUiAmountConfig::default();
+        schedule.update_config(&mut config, 50);
+        assert_eq!(f64::from(config.multiplier), 1.0);
+        assert_eq!(f64::from(config.new_multiplier), 2.0);
+        assert_eq!(i64::from(config.new_multiplier_effective_timestamp), 100);
+        schedule.update_config(&mut config, 150);
+        assert_eq!(f64::from(config.multiplier), 2.0);
+        assert_eq!(f64::from(config.new_multiplier), 2.1);
+        assert_eq!(i64::from(config.new_multiplier_effective_timestamp), 200);
+        schedule.update_config(&mut config, 250);
+        assert_eq!(f64::from(config.multiplier), 2.1);
+        assert_eq!(f64::from(config.new_multiplier), 2.1);
+        assert_eq!(i64::from(config.new_multiplier_effective_timestamp), 200);
+
+        // replacing and cancelling queued entries
+        schedule.schedule(ScheduledMultiplier {
+            effective_timestamp: 200.into(),
+            multiplier: 2.2.into(),
+        });
+        assert_eq!(schedule.entries.len(), 3);
+        assert_eq!(f64::from(schedule.entries[2].multiplier), 2.2);
+        assert!(schedule.cancel(200));
+        assert!(!schedule.cancel(200));
+        assert_eq!(schedule.entries.len(), 2);
+    }
+
+    #[test]
+    fn schedule_pack_unpack() {
+        let schedule = ScaledUiAmountSchedule {
+            entries: vec![
+                ScheduledMultiplier {
+                    effective_timestamp: 0.into(),
+                    multiplier: 1.0.into(),
+                },
+                ScheduledMultiplier {
+                    effective_timestamp: 100.into(),
+                    multiplier: 2.0.into(),
+                },
+            ],
+        };
+        let packed_len = schedule.get_packed_len().unwrap();
+        assert_eq!(packed_len, 4 + 2 * 16);
+        let mut buffer = vec![0; packed_len];
+        schedule.pack_into_slice(&mut buffer).unwrap();
+        assert_eq!(
+            ScaledUiAmountSchedule::unpack_from_slice(&buffer).unwrap(),
+            schedule
+        );
+
+        // too small
+        assert_eq!(
+            schedule.pack_into_slice(&mut buffer[..packed_len - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            ScaledUiAmountSchedule::unpack_from_slice(&buffer[..packed_len - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
     proptest! {
+        #[test]
+        fn rational_round_trip(
+            numerator in 1..=u64::MAX,
+            denominator in 1..=u64::MAX,
+            amount in 0..=u64::MAX,
+            decimals in 0u8..20u8,
+        ) {
+            let config = ScaledUiAmountRationalConfig {
+                multiplier: RationalMultiplier::new(numerator, denominator),
+                new_multiplier_effective_timestamp: UnixTimestamp::from(1),
+                ..Default::default()
+            };
+            let ui_amount = config.amount_to_ui_amount(amount, decimals, 0).unwrap();
+            let round_trip = config
+                .try_ui_amount_into_amount(&ui_amount, decimals, 0)
+                .unwrap();
+            if numerator >= denominator {
+                prop_assert_eq!(round_trip, amount);
+            } else {
+                prop_assert!(round_trip <= amount);
+                prop_assert_eq!(
+                    config.amount_to_ui_amount(round_trip, decimals, 0).unwrap(),
+                    ui_amount
+                );
+            }
+        }
+
         #[test]
         fn amount_to_ui_amount(
             scale in 0f64..=f64::MAX,
@@ -0,0 +1,164 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use {
+    crate::{
+        check_program_account,
+        instruction::{encode_instruction, TokenInstruction},
+    },
+    alloc::vec,
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_address::Address,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+};
+
+/// Token-2022 specific token-group extension instructions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum TokenGroupExtensionInstruction {
+    /// Remove a member from its group, decrementing the group size. The
+    /// member mint keeps its `TokenGroupMember` extension, pointing to the
+    /// default address, so it can later join another group through
+    /// `InitializeMember`.
+    ///
+    /// Member numbers are never reused. On the first removal from a group,
+    /// the group mint is reallocated to include the `TokenGroupMemberCounter`
+    /// extension, which numbers later members, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Fails if the membership of the group is frozen.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The member mint.
+    ///   1. `[writable]` The group mint.
+    ///   2. `[signer]` The group update authority or the member mint
+    ///      authority.
+    RemoveMember,
+    /// Move a member from its group to a new group, updating the size of both
+    /// groups and giving the member a new member number.
+    ///
+    /// As with `RemoveMember`, the current group mint may be reallocated to
+    /// include the `TokenGroupMemberCounter` extension, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Fails if the membership of either group is frozen.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The member mint.
+    ///   1. `[signer]` The member mint authority.
+    ///   2. `[writable]` The current group mint.
+    ///   3. `[writable]` The new group mint.
+    ///   4. `[signer]` The new group update authority.
+    MoveMember,
+    /// Freeze the membership of a group, so that members can't join, leave
+    /// or move out of the group.
+    ///
+    /// If the group mint doesn't have the `TokenGroupMembershipFreeze`
+    /// extension, it is reallocated to include it, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The group mint.
+    ///   1. `[signer]` The group update authority.
+    FreezeMembership,
+    /// Thaw the membership of a group.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The group mint.
+    ///   1. `[signer]` The group update authority.
+    ThawMembership,
+}
+
+/// Create a `RemoveMember` instruction
+pub fn remove_member(
+    token_program_id: &Address,
+    member_mint: &Address,
+    group_mint: &Address,
+    authority: &Address,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![
+        AccountMeta::new(*member_mint, false),
+        AccountMeta::new(*group_mint, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupExtensionInstruction::RemoveMember,
+        &(),
+    ))
+}
+
+/// Create a `MoveMember` instruction
+pub fn move_member(
+    token_program_id: &Address,
+    member_mint: &Address,
+    member_mint_authority: &Address,
+    group_mint: &Address,
+    new_group_mint: &Address,
+    new_group_update_authority: &Address,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![
+        AccountMeta::new(*member_mint, false),
+        AccountMeta::new_readonly(*member_mint_authority, true),
+        AccountMeta::new(*group_mint, false),
+        AccountMeta::new(*new_group_mint, false),
+        AccountMeta::new_readonly(*new_group_update_authority, true),
+    ];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupExtensionInstruction::MoveMember,
+        &(),
+    ))
+}
+
+/// Create a `FreezeMembership` instruction
+pub fn freeze_membership(
+    token_program_id: &Address,
+    group_mint: &Address,
+    update_authority: &Address,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![
+        AccountMeta::new(*group_mint, false),
+        AccountMeta::new_readonly(*update_authority, true),
+    ];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupExtensionInstruction::FreezeMembership,
+        &(),
+    ))
+}
+
+/// Create a `ThawMembership` instruction
+pub fn thaw_membership(
+    token_program_id: &Address,
+    group_mint: &Address,
+    update_authority: &Address,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![
+        AccountMeta::new(*group_mint, false),
+        AccountMeta::new_readonly(*update_authority, true),
+    ];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupExtensionInstruction::ThawMembership,
+        &(),
+    ))
+}
@@ -1,8 +1,16 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use {
     crate::extension::{Extension, ExtensionType},
+    bytemuck::{Pod, Zeroable},
+    solana_address::Address,
+    solana_zero_copy::unaligned::{Bool, U64},
     spl_token_group_interface::state::{TokenGroup, TokenGroupMember},
 };
 
+/// Token-2022 specific token-group instructions
+pub mod instruction;
+
 impl Extension for TokenGroup {
     const TYPE: ExtensionType = ExtensionType::TokenGroup;
 }
@@ -10,3 +18,38 @@ impl Extension for TokenGroup {
 impl Extension for TokenGroupMember {
     const TYPE: ExtensionType = ExtensionType::TokenGroupMember;
 }
+
+/// Check if a group member still belongs to a group. Removed members keep
+/// their extension, but point to the default address.
+pub fn is_group_member(member: &TokenGroupMember) -> bool {
+    member.group != Address::default()
+}
+
+/// Membership freeze for a group mint, set by the group update authority.
+/// While frozen, members can't join, leave, or move out of the group.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TokenGroupMembershipFreeze {
+    /// Whether the membership of the group is frozen
+    pub frozen: Bool,
+}
+impl Extension for TokenGroupMembershipFreeze {
+    const TYPE: ExtensionType = ExtensionType::TokenGroupMembershipFreeze;
+}
+
+/// Last member number handed out by a group mint. Only present once a member
+/// has left the group, since the group size then no longer matches the
+/// number of members added, and keeps member numbers unique.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TokenGroupMemberCounter {
+    /// The last member number handed out
+    pub last_member_number: U64,
+}
+impl Extension for TokenGroupMemberCounter {
+    const TYPE: ExtensionType = ExtensionType::TokenGroupMemberCounter;
+}
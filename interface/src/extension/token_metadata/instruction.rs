@@ -0,0 +1,251 @@
+use {
+    crate::{check_program_account, error::TokenError, instruction::TokenInstruction},
+    alloc::{string::String, vec, vec::Vec},
+    solana_address::Address,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    spl_token_metadata_interface::state::Field,
+};
+
+/// A single change applied by an `UpdateFields` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldUpdate {
+    /// Set a base field or additional field to the given value, adding the
+    /// additional field if it is not present
+    Set {
+        /// Field to set
+        field: Field,
+        /// Value to write into the field
+        value: String,
+    },
+    /// Remove an additional field, failing if it is not present
+    Remove {
+        /// Key of the additional field to remove
+        key: String,
+    },
+}
+
+/// Token-2022 specific token-metadata extension instructions
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum TokenMetadataExtensionInstruction {
+    /// Limit the packed size of the token-metadata stored in a mint.
+    ///
+    /// Fails if the account has already been initialized, so must be called
+    /// before `InitializeMint`. The mint must also include the
+    /// `MetadataPointer` extension.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    InitializeSizeLimit {
+        /// Maximum packed length of the token-metadata, in bytes
+        max_len: u32,
+    },
+    /// Apply a batch of field updates to the token-metadata in a mint, in
+    /// order, reallocating the account at most once. Either every update is
+    /// applied or none are.
+    ///
+    /// If the new metadata requires more space, the account must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint holding the token-metadata.
+    ///   1. `[signer]` The token-metadata update authority.
+    UpdateFields {
+        /// Updates to apply, in order
+        updates: Vec<FieldUpdate>,
+    },
+}
+impl TokenMetadataExtensionInstruction {
+    /// Unpacks a byte buffer into a `TokenMetadataExtensionInstruction`
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        use TokenError::InvalidInstruction;
+
+        let (&tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+        Ok(match tag {
+            0 => {
+                let (max_len, _) = rest.split_first_chunk::<4>().ok_or(InvalidInstruction)?;
+                Self::InitializeSizeLimit {
+                    max_len: u32::from_le_bytes(*max_len),
+                }
+            }
+            1 => {
+                let (&update_count, mut rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                let mut updates = Vec::with_capacity(update_count as usize);
+                for _ in 0..update_count {
+                    let (update, next) = Self::unpack_field_update(rest)?;
+                    updates.push(update);
+                    rest = next;
+                }
+                if !rest.is_empty() {
+                    return Err(InvalidInstruction.into());
+                }
+                Self::UpdateFields { updates }
+            }
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    /// Packs a `TokenMetadataExtensionInstruction` into a byte buffer.
+    pub fn pack(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::InitializeSizeLimit { max_len } => {
+                buffer.push(0);
+                buffer.extend_from_slice(&max_len.to_le_bytes());
+            }
+            Self::UpdateFields { updates } => {
+                buffer.push(1);
+                buffer.push(updates.len() as u8);
+                for update in updates {
+                    Self::pack_field_update(update, buffer);
+                }
+            }
+        }
+    }
+
+    fn unpack_field_update(input: &[u8]) -> Result<(FieldUpdate, &[u8]), ProgramError> {
+        let (&tag, rest) = input.split_first().ok_or(TokenError::InvalidInstruction)?;
+        match tag {
+            0 => {
+                let (&field_tag, rest) =
+                    rest.split_first().ok_or(TokenError::InvalidInstruction)?;
+                let (field, rest) = match field_tag {
+                    0 => (Field::Name, rest),
+                    1 => (Field::Symbol, rest),
+                    2 => (Field::Uri, rest),
+                    3 => {
+                        let (key, rest) = Self::unpack_string(rest)?;
+                        (Field::Key(key), rest)
+                    }
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+                let (value, rest) = Self::unpack_string(rest)?;
+                Ok((FieldUpdate::Set { field, value }, rest))
+            }
+            1 => {
+                let (key, rest) = Self::unpack_string(rest)?;
+                Ok((FieldUpdate::Remove { key }, rest))
+            }
+            _ => Err(TokenError::InvalidInstruction.into()),
+        }
+    }
+
+    fn pack_field_update(update: &FieldUpdate, buffer: &mut Vec<u8>) {
+        match update {
+            FieldUpdate::Set { field, value } => {
+                buffer.push(0);
+                match field {
+                    Field::Name => buffer.push(0),
+                    Field::Symbol => buffer.push(1),
+                    Field::Uri => buffer.push(2),
+                    Field::Key(key) => {
+                        buffer.push(3);
+                        Self::pack_string(key, buffer);
+                    }
+                }
+                Self::pack_string(value, buffer);
+            }
+            FieldUpdate::Remove { key } => {
+                buffer.push(1);
+                Self::pack_string(key, buffer);
+            }
+        }
+    }
+
+    fn unpack_string(input: &[u8]) -> Result<(String, &[u8]), ProgramError> {
+        let (len, rest) = input
+            .split_first_chunk::<4>()
+            .ok_or(TokenError::InvalidInstruction)?;
+        let (bytes, rest) = rest
+            .split_at_checked(u32::from_le_bytes(*len) as usize)
+            .ok_or(TokenError::InvalidInstruction)?;
+        let string =
+            String::from_utf8(bytes.to_vec()).map_err(|_| TokenError::InvalidInstruction)?;
+        Ok((string, rest))
+    }
+
+    fn pack_string(string: &str, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&(string.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(string.as_bytes());
+    }
+}
+
+fn encode_instruction_data(instruction: TokenMetadataExtensionInstruction) -> Vec<u8> {
+    let mut data = TokenInstruction::TokenMetadataExtension.pack();
+    instruction.pack(&mut data);
+    data
+}
+
+/// Create an `InitializeSizeLimit` instruction
+pub fn initialize_size_limit(
+    token_program_id: &Address,
+    mint: &Address,
+    max_len: u32,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data: encode_instruction_data(TokenMetadataExtensionInstruction::InitializeSizeLimit {
+            max_len,
+        }),
+    })
+}
+
+/// Create an `UpdateFields` instruction
+pub fn update_fields(
+    token_program_id: &Address,
+    metadata: &Address,
+    update_authority: &Address,
+    updates: Vec<FieldUpdate>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    if updates.len() > u8::MAX as usize {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data: encode_instruction_data(TokenMetadataExtensionInstruction::UpdateFields { updates }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloc::string::ToString};
+
+    #[test]
+    fn pack_unpack_update_fields() {
+        let instruction = TokenMetadataExtensionInstruction::UpdateFields {
+            updates: vec![
+                FieldUpdate::Set {
+                    field: Field::Name,
+                    value: "name".to_string(),
+                },
+                FieldUpdate::Set {
+                    field: Field::Key("key".to_string()),
+                    value: "value".to_string(),
+                },
+                FieldUpdate::Remove {
+                    key: "old".to_string(),
+                },
+            ],
+        };
+        let mut buffer = vec![];
+        instruction.pack(&mut buffer);
+        assert_eq!(
+            TokenMetadataExtensionInstruction::unpack(&buffer).unwrap(),
+            instruction
+        );
+
+        // truncated and trailing data are rejected
+        assert!(TokenMetadataExtensionInstruction::unpack(&buffer[..buffer.len() - 1]).is_err());
+        buffer.push(0);
+        assert!(TokenMetadataExtensionInstruction::unpack(&buffer).is_err());
+    }
+}
@@ -1,8 +1,77 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use {
-    crate::extension::{Extension, ExtensionType},
+    crate::{
+        error::TokenError,
+        extension::{Extension, ExtensionType},
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program_error::ProgramError,
+    solana_zero_copy::unaligned::U32,
     spl_token_metadata_interface::state::TokenMetadata,
+    spl_type_length_value::variable_len_pack::VariableLenPack,
 };
 
+/// Token-2022 specific token-metadata instructions
+pub mod instruction;
+
+/// Zero-copy token-metadata reader
+pub mod view;
+
 impl Extension for TokenMetadata {
     const TYPE: ExtensionType = ExtensionType::TokenMetadata;
 }
+
+/// Size limit on the token-metadata stored in a mint
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TokenMetadataSizeLimit {
+    /// Maximum packed length of the token-metadata, in bytes
+    pub max_len: U32,
+}
+impl TokenMetadataSizeLimit {
+    /// Check that the packed token-metadata fits in the limit
+    pub fn check(&self, token_metadata: &TokenMetadata) -> Result<(), ProgramError> {
+        let packed_len = token_metadata.get_packed_len()?;
+        if packed_len > u32::from(self.max_len) as usize {
+            Err(TokenError::TokenMetadataTooLarge.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+impl Extension for TokenMetadataSizeLimit {
+    const TYPE: ExtensionType = ExtensionType::TokenMetadataSizeLimit;
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        alloc::string::{String, ToString},
+        spl_token_metadata_interface::state::Field,
+    };
+
+    #[test]
+    fn size_limit() {
+        let mut token_metadata = TokenMetadata {
+            name: "name".to_string(),
+            symbol: "sym".to_string(),
+            uri: "uri".to_string(),
+            ..Default::default()
+        };
+        let packed_len = token_metadata.get_packed_len().unwrap();
+        let size_limit = TokenMetadataSizeLimit {
+            max_len: (packed_len as u32).into(),
+        };
+        size_limit.check(&token_metadata).unwrap();
+
+        token_metadata.update(Field::Key("key".to_string()), String::new());
+        assert_eq!(
+            size_limit.check(&token_metadata).unwrap_err(),
+            TokenError::TokenMetadataTooLarge.into()
+        );
+    }
+}
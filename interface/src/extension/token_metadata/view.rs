@@ -0,0 +1,199 @@
+//! Zero-copy reader for the token-metadata stored in a mint
+
+use {
+    solana_address::Address, solana_nullable::MaybeNull, solana_program_error::ProgramError,
+    spl_token_metadata_interface::state::Field,
+};
+
+/// Borrowed view over packed `TokenMetadata` bytes, as returned by
+/// `BaseStateWithExtensions::get_extension_bytes::<TokenMetadata>`.
+///
+/// The fixed fields and the name, symbol and uri are located when the view is
+/// created. Additional metadata is only walked on lookup, and keys that don't
+/// match are skipped without being decoded, so reading one attribute doesn't
+/// require deserializing and allocating the whole metadata.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenMetadataView<'data> {
+    update_authority: &'data MaybeNull<Address>,
+    mint: &'data Address,
+    name: &'data str,
+    symbol: &'data str,
+    uri: &'data str,
+    additional_metadata_count: u32,
+    additional_metadata: &'data [u8],
+}
+
+impl<'data> TokenMetadataView<'data> {
+    /// Create a view over packed token-metadata
+    pub fn unpack(data: &'data [u8]) -> Result<Self, ProgramError> {
+        let (update_authority, rest) = split_pod::<MaybeNull<Address>>(data)?;
+        let (mint, rest) = split_pod::<Address>(rest)?;
+        let (name, rest) = split_str(rest)?;
+        let (symbol, rest) = split_str(rest)?;
+        let (uri, rest) = split_str(rest)?;
+        let (additional_metadata_count, additional_metadata) = split_u32(rest)?;
+        Ok(Self {
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+            additional_metadata_count,
+            additional_metadata,
+        })
+    }
+
+    /// The authority that can sign to update the metadata
+    pub fn update_authority(&self) -> Option<Address> {
+        Option::<Address>::from(*self.update_authority)
+    }
+
+    /// The associated mint
+    pub fn mint(&self) -> &'data Address {
+        self.mint
+    }
+
+    /// The longer name of the token
+    pub fn name(&self) -> &'data str {
+        self.name
+    }
+
+    /// The shortened symbol for the token
+    pub fn symbol(&self) -> &'data str {
+        self.symbol
+    }
+
+    /// The URI pointing to richer metadata
+    pub fn uri(&self) -> &'data str {
+        self.uri
+    }
+
+    /// Look up a base field or additional field, returning `None` if the
+    /// additional field isn't present
+    pub fn get(&self, field: &Field) -> Result<Option<&'data str>, ProgramError> {
+        match field {
+            Field::Name => Ok(Some(self.name)),
+            Field::Symbol => Ok(Some(self.symbol)),
+            Field::Uri => Ok(Some(self.uri)),
+            Field::Key(key) => self.get_additional_metadata(key),
+        }
+    }
+
+    /// Look up the value of an additional field, returning `None` if the key
+    /// isn't present
+    pub fn get_additional_metadata(&self, key: &str) -> Result<Option<&'data str>, ProgramError> {
+        let mut rest = self.additional_metadata;
+        for _ in 0..self.additional_metadata_count {
+            let (entry_key, next) = split_bytes(rest)?;
+            let (value, next) = split_bytes(next)?;
+            if entry_key == key.as_bytes() {
+                return to_str(value).map(Some);
+            }
+            rest = next;
+        }
+        Ok(None)
+    }
+
+    /// Iterate through the additional metadata, in storage order
+    pub fn additional_metadata(
+        &self,
+    ) -> impl Iterator<Item = Result<(&'data str, &'data str), ProgramError>> {
+        let mut rest = self.additional_metadata;
+        (0..self.additional_metadata_count).map(move |_| {
+            let (key, next) = split_str(rest)?;
+            let (value, next) = split_str(next)?;
+            rest = next;
+            Ok((key, value))
+        })
+    }
+}
+
+fn split_pod<T: bytemuck::Pod>(data: &[u8]) -> Result<(&T, &[u8]), ProgramError> {
+    let (value, rest) = data
+        .split_at_checked(core::mem::size_of::<T>())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let value = bytemuck::try_from_bytes(value).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok((value, rest))
+}
+
+fn split_u32(data: &[u8]) -> Result<(u32, &[u8]), ProgramError> {
+    let (value, rest) = data
+        .split_first_chunk::<4>()
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok((u32::from_le_bytes(*value), rest))
+}
+
+fn split_bytes(data: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    let (len, rest) = split_u32(data)?;
+    rest.split_at_checked(len as usize)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+fn split_str(data: &[u8]) -> Result<(&str, &[u8]), ProgramError> {
+    let (bytes, rest) = split_bytes(data)?;
+    Ok((to_str(bytes)?, rest))
+}
+
+fn to_str(bytes: &[u8]) -> Result<&str, ProgramError> {
+    core::str::from_utf8(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        alloc::{string::ToString, vec, vec::Vec},
+        spl_token_metadata_interface::state::TokenMetadata,
+        spl_type_length_value::variable_len_pack::VariableLenPack,
+    };
+
+    #[test]
+    fn view_matches_unpacked_metadata() {
+        let token_metadata = TokenMetadata {
+            update_authority: Some(Address::new_from_array([1; 32])).try_into().unwrap(),
+            mint: Address::new_from_array([2; 32]),
+            name: "name".to_string(),
+            symbol: "sym".to_string(),
+            uri: "uri".to_string(),
+            additional_metadata: vec![
+                ("kyc_tier".to_string(), "2".to_string()),
+                ("region".to_string(), "eu".to_string()),
+            ],
+        };
+        let mut data = vec![0; token_metadata.get_packed_len().unwrap()];
+        token_metadata.pack_into_slice(&mut data).unwrap();
+
+        let view = TokenMetadataView::unpack(&data).unwrap();
+        assert_eq!(
+            view.update_authority(),
+            Option::<Address>::from(token_metadata.update_authority)
+        );
+        assert_eq!(*view.mint(), token_metadata.mint);
+        assert_eq!(view.get(&Field::Name).unwrap(), Some("name"));
+        assert_eq!(view.get(&Field::Symbol).unwrap(), Some("sym"));
+        assert_eq!(view.get(&Field::Uri).unwrap(), Some("uri"));
+        assert_eq!(view.get_additional_metadata("region").unwrap(), Some("eu"));
+        assert_eq!(
+            view.get(&Field::Key("kyc_tier".to_string())).unwrap(),
+            Some("2")
+        );
+        assert_eq!(view.get_additional_metadata("missing").unwrap(), None);
+        let additional_metadata = view
+            .additional_metadata()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(additional_metadata, [("kyc_tier", "2"), ("region", "eu")]);
+
+        // truncated data is rejected on creation or lookup
+        assert_eq!(
+            TokenMetadataView::unpack(&data[..70]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        let view = TokenMetadataView::unpack(&data[..data.len() - 1]).unwrap();
+        assert_eq!(view.get_additional_metadata("kyc_tier").unwrap(), Some("2"));
+        assert_eq!(
+            view.get_additional_metadata("region").unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+}
@@ -4,13 +4,19 @@ use {
     serde::{Deserialize, Serialize},
 };
 use {
-    crate::{check_program_account, error::TokenError, instruction::TokenInstruction},
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::transfer_fee::{TransferFeeTier, MAX_TRANSFER_FEE_TIERS},
+        instruction::TokenInstruction,
+    },
     alloc::{vec, vec::Vec},
-    core::convert::TryFrom,
+    core::{convert::TryFrom, mem::size_of},
     solana_address::Address,
     solana_instruction::{AccountMeta, Instruction},
     solana_program_error::ProgramError,
     solana_program_option::COption,
+    solana_sdk_ids::system_program,
 };
 
 /// Transfer Fee extension instructions
@@ -153,6 +159,116 @@ pub enum TransferFeeInstruction {
         /// Maximum fee assessed on transfers
         maximum_fee: u64,
     },
+    /// Initialize a tiered transfer fee schedule on a new mint, used instead
+    /// of the flat fee of the `TransferFeeConfig` extension, which must also
+    /// be initialized.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    InitializeTieredTransferFeeConfig {
+        /// Maximum fee assessed on transfers
+        maximum_fee: u64,
+        /// Minimum fee assessed on non-zero transfers
+        minimum_fee: u64,
+        /// Number of tiers provided
+        tier_count: u8,
+        /// Tiers sorted by increasing minimum amount, starting at 0. Only the
+        /// first `tier_count` are packed.
+        tiers: [TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+    },
+    /// Set the tiered transfer fee schedule. Only supported for mints that
+    /// include the `TieredTransferFeeConfig` extension. The new schedule
+    /// takes effect two epochs later, like `SetTransferFee`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The mint's fee account owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature fee account owner.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    SetTieredTransferFee {
+        /// Maximum fee assessed on transfers
+        maximum_fee: u64,
+        /// Minimum fee assessed on non-zero transfers
+        minimum_fee: u64,
+        /// Number of tiers provided
+        tier_count: u8,
+        /// Tiers sorted by increasing minimum amount, starting at 0. Only the
+        /// first `tier_count` are packed.
+        tiers: [TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+    },
+    /// Exempt a token account from transfer fees, or remove its exemption.
+    /// When either the source or the destination of a transfer is exempt, no
+    /// fee is charged. Only supported for mints that include the
+    /// `TransferFeeConfig` extension.
+    ///
+    /// If the account does not have the `TransferFeeExemption` extension yet,
+    /// it is reallocated to make room for it, and the payer funds any
+    /// additional rent.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The token mint.
+    ///   2. `[writable, signer]` The payer for any additional rent.
+    ///   3. `[]` System program for reallocation funding.
+    ///   4. `[signer]` The mint's fee account owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The token account.
+    ///   1. `[]` The token mint.
+    ///   2. `[writable, signer]` The payer for any additional rent.
+    ///   3. `[]` System program for reallocation funding.
+    ///   4. `[]` The mint's multisignature fee account owner.
+    ///   5. `..5+M` `[signer]` M signer accounts.
+    SetTransferFeeExemption {
+        /// If true, transfers into or out of the account are not charged a fee
+        exempt: bool,
+    },
+    /// Initialize the transfer fee collector on a new mint. When a collector
+    /// is set, fees are credited to the collector token account instead of
+    /// being withheld, and every transfer must provide the collector account
+    /// after the required accounts. Requires the `TransferFeeConfig`
+    /// extension, and can't be combined with `ConfidentialTransferFeeConfig`.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    InitializeTransferFeeCollector {
+        /// Token account receiving transfer fees, if any
+        #[cfg_attr(feature = "serde", serde(with = "coption_fromstr"))]
+        collector: COption<Address>,
+    },
+    /// Set the transfer fee collector. Only supported for mints that include
+    /// the `TransferFeeCollector` extension. Takes effect immediately.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The mint's fee account owner.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The mint's multisignature fee account owner.
+    ///   2. `..2+M` `[signer]` M signer accounts.
+    SetTransferFeeCollector {
+        /// Token account receiving transfer fees, if any
+        #[cfg_attr(feature = "serde", serde(with = "coption_fromstr"))]
+        collector: COption<Address>,
+    },
 }
 impl TransferFeeInstruction {
     /// Unpacks a byte buffer into a `TransferFeeInstruction`
@@ -198,6 +314,40 @@ impl TransferFeeInstruction {
                     maximum_fee,
                 }
             }
+            6 => {
+                let (maximum_fee, minimum_fee, tier_count, tiers) = Self::unpack_tiers(rest)?;
+                Self::InitializeTieredTransferFeeConfig {
+                    maximum_fee,
+                    minimum_fee,
+                    tier_count,
+                    tiers,
+                }
+            }
+            7 => {
+                let (maximum_fee, minimum_fee, tier_count, tiers) = Self::unpack_tiers(rest)?;
+                Self::SetTieredTransferFee {
+                    maximum_fee,
+                    minimum_fee,
+                    tier_count,
+                    tiers,
+                }
+            }
+            8 => {
+                let exempt = match rest.first() {
+                    Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(TokenError::InvalidInstruction.into()),
+                };
+                Self::SetTransferFeeExemption { exempt }
+            }
+            9 => {
+                let (collector, _) = TokenInstruction::unpack_pubkey_option(rest)?;
+                Self::InitializeTransferFeeCollector { collector }
+            }
+            10 => {
+                let (collector, _) = TokenInstruction::unpack_pubkey_option(rest)?;
+                Self::SetTransferFeeCollector { collector }
+            }
             _ => return Err(TokenError::InvalidInstruction.into()),
         })
     }
@@ -245,10 +395,87 @@ impl TransferFeeInstruction {
                 buffer.extend_from_slice(&transfer_fee_basis_points.to_le_bytes());
                 buffer.extend_from_slice(&maximum_fee.to_le_bytes());
             }
+            Self::InitializeTieredTransferFeeConfig {
+                maximum_fee,
+                minimum_fee,
+                tier_count,
+                ref tiers,
+            } => {
+                buffer.push(6);
+                Self::pack_tiers(maximum_fee, minimum_fee, tier_count, tiers, buffer);
+            }
+            Self::SetTieredTransferFee {
+                maximum_fee,
+                minimum_fee,
+                tier_count,
+                ref tiers,
+            } => {
+                buffer.push(7);
+                Self::pack_tiers(maximum_fee, minimum_fee, tier_count, tiers, buffer);
+            }
+            Self::SetTransferFeeExemption { exempt } => {
+                buffer.push(8);
+                buffer.push(exempt.into());
+            }
+            Self::InitializeTransferFeeCollector { ref collector } => {
+                buffer.push(9);
+                TokenInstruction::pack_pubkey_option(collector, buffer);
+            }
+            Self::SetTransferFeeCollector { ref collector } => {
+                buffer.push(10);
+                TokenInstruction::pack_pubkey_option(collector, buffer);
+            }
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn unpack_tiers(
+        input: &[u8],
+    ) -> Result<(u64, u64, u8, [TransferFeeTier; MAX_TRANSFER_FEE_TIERS]), ProgramError> {
+        let (maximum_fee, rest) = TokenInstruction::unpack_u64(input)?;
+        let (minimum_fee, rest) = TokenInstruction::unpack_u64(rest)?;
+        let (&tier_count, mut rest) = rest.split_first().ok_or(TokenError::InvalidInstruction)?;
+        if tier_count as usize > MAX_TRANSFER_FEE_TIERS {
+            return Err(TokenError::InvalidInstruction.into());
+        }
+        let mut tiers = [TransferFeeTier::default(); MAX_TRANSFER_FEE_TIERS];
+        for tier in tiers.iter_mut().take(tier_count as usize) {
+            let (tier_bytes, next) = rest
+                .split_at_checked(size_of::<TransferFeeTier>())
+                .ok_or(TokenError::InvalidInstruction)?;
+            *tier = bytemuck::pod_read_unaligned(tier_bytes);
+            rest = next;
+        }
+        Ok((maximum_fee, minimum_fee, tier_count, tiers))
+    }
+
+    fn pack_tiers(
+        maximum_fee: u64,
+        minimum_fee: u64,
+        tier_count: u8,
+        tiers: &[TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+        buffer: &mut Vec<u8>,
+    ) {
+        buffer.extend_from_slice(&maximum_fee.to_le_bytes());
+        buffer.extend_from_slice(&minimum_fee.to_le_bytes());
+        buffer.push(tier_count);
+        for tier in tiers.iter().take(tier_count as usize) {
+            buffer.extend_from_slice(bytemuck::bytes_of(tier));
         }
     }
 }
 
+fn tiers_array(
+    tiers: &[TransferFeeTier],
+) -> Result<(u8, [TransferFeeTier; MAX_TRANSFER_FEE_TIERS]), ProgramError> {
+    if tiers.len() > MAX_TRANSFER_FEE_TIERS {
+        return Err(TokenError::InvalidTransferFeeTiers.into());
+    }
+    let mut tiers_array = [TransferFeeTier::default(); MAX_TRANSFER_FEE_TIERS];
+    tiers_array[..tiers.len()].copy_from_slice(tiers);
+    Ok((tiers.len() as u8, tiers_array))
+}
+
 fn encode_instruction_data(transfer_fee_instruction: TransferFeeInstruction) -> Vec<u8> {
     let mut data = TokenInstruction::TransferFeeExtension.pack();
     transfer_fee_instruction.pack(&mut data);
@@ -419,6 +646,132 @@ pub fn set_transfer_fee(
     })
 }
 
+/// Create an `InitializeTieredTransferFeeConfig` instruction
+pub fn initialize_tiered_transfer_fee_config(
+    token_program_id: &Address,
+    mint: &Address,
+    maximum_fee: u64,
+    minimum_fee: u64,
+    tiers: &[TransferFeeTier],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let (tier_count, tiers) = tiers_array(tiers)?;
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data: encode_instruction_data(TransferFeeInstruction::InitializeTieredTransferFeeConfig {
+            maximum_fee,
+            minimum_fee,
+            tier_count,
+            tiers,
+        }),
+    })
+}
+
+/// Creates a `SetTieredTransferFee` instruction
+pub fn set_tiered_transfer_fee(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    maximum_fee: u64,
+    minimum_fee: u64,
+    tiers: &[TransferFeeTier],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let (tier_count, tiers) = tiers_array(tiers)?;
+    let mut accounts = Vec::with_capacity(2 + signers.len());
+    accounts.push(AccountMeta::new(*mint, false));
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: encode_instruction_data(TransferFeeInstruction::SetTieredTransferFee {
+            maximum_fee,
+            minimum_fee,
+            tier_count,
+            tiers,
+        }),
+    })
+}
+
+/// Create a `SetTransferFeeExemption` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn set_transfer_fee_exemption(
+    token_program_id: &Address,
+    account: &Address,
+    mint: &Address,
+    payer: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    exempt: bool,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = Vec::with_capacity(5 + signers.len());
+    accounts.push(AccountMeta::new(*account, false));
+    accounts.push(AccountMeta::new_readonly(*mint, false));
+    accounts.push(AccountMeta::new(*payer, true));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: encode_instruction_data(TransferFeeInstruction::SetTransferFeeExemption { exempt }),
+    })
+}
+
+/// Create a `InitializeTransferFeeCollector` instruction
+pub fn initialize_transfer_fee_collector(
+    token_program_id: &Address,
+    mint: &Address,
+    collector: Option<&Address>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let collector = collector.cloned().into();
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data: encode_instruction_data(TransferFeeInstruction::InitializeTransferFeeCollector {
+            collector,
+        }),
+    })
+}
+
+/// Create a `SetTransferFeeCollector` instruction
+pub fn set_transfer_fee_collector(
+    token_program_id: &Address,
+    mint: &Address,
+    authority: &Address,
+    signers: &[&Address],
+    collector: Option<&Address>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let collector = collector.cloned().into();
+    let mut accounts = Vec::with_capacity(2 + signers.len());
+    accounts.push(AccountMeta::new(*mint, false));
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer, true));
+    }
+
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data: encode_instruction_data(TransferFeeInstruction::SetTransferFeeCollector {
+            collector,
+        }),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -495,5 +848,85 @@ mod test {
         assert_eq!(packed, expect);
         let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
+
+        let mut tiers = [TransferFeeTier::default(); MAX_TRANSFER_FEE_TIERS];
+        tiers[1] = TransferFeeTier {
+            minimum_amount: 1_000.into(),
+            transfer_fee_basis_points: 50.into(),
+        };
+        let check = TransferFeeInstruction::InitializeTieredTransferFeeConfig {
+            maximum_fee: u64::MAX,
+            minimum_fee: 3,
+            tier_count: 2,
+            tiers,
+        };
+        let mut packed = vec![];
+        check.pack(&mut packed);
+        let mut expect = vec![6];
+        expect.extend_from_slice(&u64::MAX.to_le_bytes());
+        expect.extend_from_slice(&3u64.to_le_bytes());
+        expect.extend_from_slice(&[2]);
+        expect.extend_from_slice(&0u64.to_le_bytes());
+        expect.extend_from_slice(&0u16.to_le_bytes());
+        expect.extend_from_slice(&1_000u64.to_le_bytes());
+        expect.extend_from_slice(&50u16.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = TransferFeeInstruction::SetTieredTransferFee {
+            maximum_fee: 10,
+            minimum_fee: 0,
+            tier_count: 0,
+            tiers: [TransferFeeTier::default(); MAX_TRANSFER_FEE_TIERS],
+        };
+        let mut packed = vec![];
+        check.pack(&mut packed);
+        let mut expect = vec![7];
+        expect.extend_from_slice(&10u64.to_le_bytes());
+        expect.extend_from_slice(&0u64.to_le_bytes());
+        expect.extend_from_slice(&[0]);
+        assert_eq!(packed, expect);
+        let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        // too many tiers
+        let mut expect = vec![7];
+        expect.extend_from_slice(&10u64.to_le_bytes());
+        expect.extend_from_slice(&0u64.to_le_bytes());
+        expect.extend_from_slice(&[MAX_TRANSFER_FEE_TIERS as u8 + 1]);
+        assert!(TransferFeeInstruction::unpack(&expect).is_err());
+
+        let check = TransferFeeInstruction::SetTransferFeeExemption { exempt: true };
+        let mut packed = vec![];
+        check.pack(&mut packed);
+        let expect = [8, 1];
+        assert_eq!(packed, expect);
+        let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        // invalid bool
+        assert!(TransferFeeInstruction::unpack(&[8, 2]).is_err());
+
+        let check = TransferFeeInstruction::InitializeTransferFeeCollector {
+            collector: COption::Some(Address::new_from_array([5u8; 32])),
+        };
+        let mut packed = vec![];
+        check.pack(&mut packed);
+        let mut expect = vec![9, 1];
+        expect.extend_from_slice(&[5u8; 32]);
+        assert_eq!(packed, expect);
+        let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = TransferFeeInstruction::SetTransferFeeCollector {
+            collector: COption::None,
+        };
+        let mut packed = vec![];
+        check.pack(&mut packed);
+        let expect = vec![10, 0];
+        assert_eq!(packed, expect);
+        let unpacked = TransferFeeInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
     }
 }
@@ -1,7 +1,7 @@
 use {
     crate::{
         error::TokenError,
-        extension::{Extension, ExtensionType},
+        extension::{BaseState, BaseStateWithExtensions, Extension, ExtensionType},
     },
     bytemuck::{Pod, Zeroable},
     core::{
@@ -11,7 +11,7 @@ use {
     solana_address::Address,
     solana_nullable::MaybeNull,
     solana_program_error::ProgramResult,
-    solana_zero_copy::unaligned::{U16, U64},
+    solana_zero_copy::unaligned::{Bool, U16, U64},
 };
 #[cfg(feature = "serde")]
 use {
@@ -26,6 +26,9 @@ pub mod instruction;
 pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
 const ONE_IN_BASIS_POINTS: u128 = MAX_FEE_BASIS_POINTS as u128;
 
+/// Maximum number of tiers in a tiered transfer fee schedule
+pub const MAX_TRANSFER_FEE_TIERS: usize = 4;
+
 /// Transfer fee information
 #[repr(C)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -170,6 +173,226 @@ impl Extension for TransferFeeConfig {
     const TYPE: ExtensionType = ExtensionType::TransferFeeConfig;
 }
 
+/// Tier of a tiered transfer fee schedule
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferFeeTier {
+    /// Smallest transfer amount that the tier applies to
+    pub minimum_amount: U64,
+    /// Amount of transfer collected as fees, expressed as basis points of the
+    /// whole transfer amount
+    pub transfer_fee_basis_points: U16,
+}
+
+/// Tiered transfer fee schedule, where larger transfers may pay a different
+/// rate than smaller ones
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TieredTransferFee {
+    /// First epoch where the schedule takes effect
+    pub epoch: U64, // Epoch,
+    /// Maximum fee assessed on transfers, expressed as an amount of tokens
+    pub maximum_fee: U64,
+    /// Minimum fee assessed on non-zero transfers, expressed as an amount of
+    /// tokens. Never more than the transfer amount.
+    pub minimum_fee: U64,
+    /// Number of tiers in use
+    pub tier_count: u8,
+    /// Tiers sorted by increasing `minimum_amount`, only the first
+    /// `tier_count` are used
+    pub tiers: [TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+}
+impl TieredTransferFee {
+    /// Get the tiers in use
+    pub fn tiers(&self) -> &[TransferFeeTier] {
+        let tier_count = cmp::min(self.tier_count as usize, MAX_TRANSFER_FEE_TIERS);
+        &self.tiers[..tier_count]
+    }
+
+    /// Get the tier applying to the given transfer amount
+    pub fn get_tier(&self, pre_fee_amount: u64) -> Option<&TransferFeeTier> {
+        self.tiers()
+            .iter()
+            .rev()
+            .find(|tier| u64::from(tier.minimum_amount) <= pre_fee_amount)
+    }
+
+    /// Get the flat transfer fee equivalent to the given tier
+    fn tier_fee(&self, tier: &TransferFeeTier) -> TransferFee {
+        TransferFee {
+            epoch: self.epoch,
+            maximum_fee: self.maximum_fee,
+            transfer_fee_basis_points: tier.transfer_fee_basis_points,
+        }
+    }
+
+    /// Calculate the transfer fee
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+        if pre_fee_amount == 0 {
+            return Some(0);
+        }
+        let fee = match self.get_tier(pre_fee_amount) {
+            Some(tier) => self.tier_fee(tier).calculate_fee(pre_fee_amount)?,
+            None => 0,
+        };
+        let fee = cmp::max(fee, u64::from(self.minimum_fee));
+        Some(cmp::min(fee, pre_fee_amount))
+    }
+
+    /// Calculate the gross transfer amount after deducting fees
+    pub fn calculate_post_fee_amount(&self, pre_fee_amount: u64) -> Option<u64> {
+        pre_fee_amount.checked_sub(self.calculate_fee(pre_fee_amount)?)
+    }
+
+    /// Calculate the transfer amount that will result in a specified net
+    /// transfer amount.
+    ///
+    /// Each tier is tried in turn, and the smallest transfer amount that falls
+    /// within its tier is chosen. Since the rate changes at tier boundaries,
+    /// some net amounts cannot be produced by any transfer amount, in which
+    /// case `None` is returned.
+    pub fn calculate_pre_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        if post_fee_amount == 0 {
+            return Some(0);
+        }
+        let minimum_fee = u64::from(self.minimum_fee);
+        let tiers = self.tiers();
+        tiers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, tier)| {
+                let pre_fee_amount = self
+                    .tier_fee(tier)
+                    .calculate_pre_fee_amount(post_fee_amount)?;
+                let pre_fee_amount = if pre_fee_amount.checked_sub(post_fee_amount)? < minimum_fee {
+                    post_fee_amount.checked_add(minimum_fee)?
+                } else {
+                    pre_fee_amount
+                };
+                let above_minimum = pre_fee_amount >= u64::from(tier.minimum_amount);
+                let below_next = tiers
+                    .get(i.saturating_add(1))
+                    .is_none_or(|next| pre_fee_amount < u64::from(next.minimum_amount));
+                (above_minimum && below_next).then_some(pre_fee_amount)
+            })
+            .min()
+    }
+
+    /// Calculate the fee that would produce the given output
+    ///
+    /// As with `TransferFee::calculate_inverse_fee`, this is not an exact
+    /// inverse of `calculate_fee`.
+    pub fn calculate_inverse_fee(&self, post_fee_amount: u64) -> Option<u64> {
+        let pre_fee_amount = self.calculate_pre_fee_amount(post_fee_amount)?;
+        self.calculate_fee(pre_fee_amount)
+    }
+}
+
+/// Tiered transfer fee extension data for mints.
+///
+/// Requires the `TransferFeeConfig` extension, which provides the authorities
+/// and the withheld amount. When present, the tiered schedule is used instead
+/// of the flat transfer fee of `TransferFeeConfig`.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TieredTransferFeeConfig {
+    /// Older schedule, used if `current epoch < newer_transfer_fee.epoch`
+    pub older_transfer_fee: TieredTransferFee,
+    /// Newer schedule, used if `current epoch >= newer_transfer_fee.epoch`
+    pub newer_transfer_fee: TieredTransferFee,
+}
+impl TieredTransferFeeConfig {
+    /// Get the schedule for the given epoch
+    pub fn get_epoch_fee(&self, epoch: u64) -> &TieredTransferFee {
+        if epoch >= self.newer_transfer_fee.epoch.into() {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+    /// Calculate the fee for the given epoch and input amount
+    pub fn calculate_epoch_fee(&self, epoch: u64, pre_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch).calculate_fee(pre_fee_amount)
+    }
+    /// Calculate the fee for the given epoch and output amount
+    pub fn calculate_inverse_epoch_fee(&self, epoch: u64, post_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch)
+            .calculate_inverse_fee(post_fee_amount)
+    }
+}
+impl Extension for TieredTransferFeeConfig {
+    const TYPE: ExtensionType = ExtensionType::TieredTransferFeeConfig;
+}
+
+/// Calculate the fee for the given epoch and input amount on a mint, using
+/// the tiered schedule if the mint has one. Mints without transfer fees
+/// always charge 0.
+pub fn calculate_mint_epoch_fee<S: BaseState>(
+    mint: &impl BaseStateWithExtensions<S>,
+    epoch: u64,
+    pre_fee_amount: u64,
+) -> Option<u64> {
+    if let Ok(tiered_transfer_fee_config) = mint.get_extension::<TieredTransferFeeConfig>() {
+        tiered_transfer_fee_config.calculate_epoch_fee(epoch, pre_fee_amount)
+    } else if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        transfer_fee_config.calculate_epoch_fee(epoch, pre_fee_amount)
+    } else {
+        Some(0)
+    }
+}
+
+/// Calculate the fee for the given epoch and output amount on a mint, using
+/// the tiered schedule if the mint has one. Mints without transfer fees
+/// always charge 0.
+pub fn calculate_mint_inverse_epoch_fee<S: BaseState>(
+    mint: &impl BaseStateWithExtensions<S>,
+    epoch: u64,
+    post_fee_amount: u64,
+) -> Option<u64> {
+    if let Ok(tiered_transfer_fee_config) = mint.get_extension::<TieredTransferFeeConfig>() {
+        tiered_transfer_fee_config.calculate_inverse_epoch_fee(epoch, post_fee_amount)
+    } else if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        transfer_fee_config.calculate_inverse_epoch_fee(epoch, post_fee_amount)
+    } else {
+        Some(0)
+    }
+}
+
+/// Transfer fee collector extension data for mints.
+///
+/// Requires the `TransferFeeConfig` extension, and can't be combined with
+/// `ConfidentialTransferFeeConfig`. When a collector is set, fees are credited
+/// directly to the collector token account, which must be provided with every
+/// transfer, instead of being withheld in the destination account.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferFeeCollector {
+    /// Token account receiving transfer fees. If not set, fees are withheld
+    /// as usual.
+    #[cfg_attr(feature = "serde", serde(with = "As::<Option<DisplayFromStr>>"))]
+    pub collector: MaybeNull<Address>,
+}
+impl Extension for TransferFeeCollector {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeCollector;
+}
+
+/// Get the fee collector token account of a mint, if it routes fees to one
+pub fn get_fee_collector<S: BaseState, BSE: BaseStateWithExtensions<S>>(
+    mint: &BSE,
+) -> Option<Address> {
+    mint.get_extension::<TransferFeeCollector>()
+        .ok()
+        .and_then(|extension| Option::<Address>::from(extension.collector))
+}
+
 /// Transfer fee extension data for accounts.
 #[repr(C)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -193,6 +416,31 @@ impl Extension for TransferFeeAmount {
     const TYPE: ExtensionType = ExtensionType::TransferFeeAmount;
 }
 
+/// Transfer fee exemption extension data for accounts, managed by the mint's
+/// transfer fee config authority
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferFeeExemption {
+    /// If true, no fee is charged on transfers into or out of the account
+    pub exempt: Bool,
+}
+impl Extension for TransferFeeExemption {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeExemption;
+}
+
+/// Determine if transfers into or out of this account are exempt from
+/// transfer fees
+pub fn transfer_fee_exempt<BSE: BaseStateWithExtensions<S>, S: BaseState>(
+    account_state: &BSE,
+) -> bool {
+    if let Ok(extension) = account_state.get_extension::<TransferFeeExemption>() {
+        return extension.exempt.into();
+    }
+    false
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use {super::*, core::convert::TryFrom, proptest::prelude::*, solana_address::Address};
@@ -424,6 +672,91 @@ pub(crate) mod test {
         assert_eq!(0, transfer_fee.calculate_inverse_fee(0).unwrap());
     }
 
+    fn test_tiered_transfer_fee() -> TieredTransferFee {
+        let mut tiers = [TransferFeeTier::default(); MAX_TRANSFER_FEE_TIERS];
+        tiers[0].transfer_fee_basis_points = U16::from(100);
+        tiers[1] = TransferFeeTier {
+            minimum_amount: U64::from(10_000),
+            transfer_fee_basis_points: U16::from(50),
+        };
+        tiers[2] = TransferFeeTier {
+            minimum_amount: U64::from(1_000_000),
+            transfer_fee_basis_points: U16::from(10),
+        };
+        TieredTransferFee {
+            epoch: U64::from(0),
+            maximum_fee: U64::from(5_000),
+            minimum_fee: U64::from(5),
+            tier_count: 3,
+            tiers,
+        }
+    }
+
+    #[test]
+    fn tiered_fee() {
+        let transfer_fee = test_tiered_transfer_fee();
+        assert_eq!(transfer_fee.tiers().len(), 3);
+
+        // zero is always zero
+        assert_eq!(0, transfer_fee.calculate_fee(0).unwrap());
+        // minimum fee, but never more than the amount
+        assert_eq!(3, transfer_fee.calculate_fee(3).unwrap());
+        assert_eq!(5, transfer_fee.calculate_fee(100).unwrap());
+        // first tier, 1%
+        assert_eq!(99, transfer_fee.calculate_fee(9_900).unwrap());
+        assert_eq!(100, transfer_fee.calculate_fee(9_999).unwrap());
+        // second tier, 0.5% of the whole amount
+        assert_eq!(50, transfer_fee.calculate_fee(10_000).unwrap());
+        assert_eq!(4_000, transfer_fee.calculate_fee(799_999).unwrap());
+        // third tier, 0.1% up to the maximum
+        assert_eq!(1_000, transfer_fee.calculate_fee(1_000_000).unwrap());
+        assert_eq!(5_000, transfer_fee.calculate_fee(u64::MAX).unwrap());
+    }
+
+    #[test]
+    fn tiered_fee_exact_out() {
+        let transfer_fee = test_tiered_transfer_fee();
+        assert_eq!(0, transfer_fee.calculate_pre_fee_amount(0).unwrap());
+        // minimum fee
+        assert_eq!(105, transfer_fee.calculate_pre_fee_amount(100).unwrap());
+        // first tier
+        assert_eq!(9_900, transfer_fee.calculate_pre_fee_amount(9_801).unwrap());
+        // the second tier charges less, so some net amounts are skipped
+        assert_eq!(None, transfer_fee.calculate_pre_fee_amount(9_920));
+        // second tier
+        assert_eq!(
+            10_000,
+            transfer_fee.calculate_pre_fee_amount(9_950).unwrap()
+        );
+        // third tier, capped at the maximum
+        assert_eq!(
+            u64::MAX,
+            transfer_fee
+                .calculate_pre_fee_amount(u64::MAX - 5_000)
+                .unwrap()
+        );
+
+        for amount in [1, 7, 999, 9_999, 10_000, 123_456, 1_000_000, 9_000_000] {
+            let fee = transfer_fee.calculate_fee(amount).unwrap();
+            let fee_exact_out = transfer_fee.calculate_inverse_fee(amount - fee).unwrap();
+            assert!(fee >= fee_exact_out);
+        }
+    }
+
+    #[test]
+    fn tiered_epoch_fee() {
+        let transfer_fee = test_tiered_transfer_fee();
+        let mut newer_transfer_fee = transfer_fee;
+        newer_transfer_fee.epoch = U64::from(NEWER_EPOCH);
+        newer_transfer_fee.minimum_fee = U64::from(0);
+        let config = TieredTransferFeeConfig {
+            older_transfer_fee: transfer_fee,
+            newer_transfer_fee,
+        };
+        assert_eq!(5, config.calculate_epoch_fee(NEWER_EPOCH - 1, 100).unwrap());
+        assert_eq!(1, config.calculate_epoch_fee(NEWER_EPOCH, 100).unwrap());
+    }
+
     proptest! {
         #[test]
         fn round_trip_fee_calculation(
@@ -23,6 +23,12 @@ use {
 pub mod instruction;
 
 /// Transfer hook extension data for mints.
+///
+/// The hook program only receives the memo of a transfer, appended to the
+/// `Execute` instruction data after the amount, when the destination account
+/// requires incoming memos or the source account requires outgoing memos.
+/// Otherwise, the `Execute` instruction data is unchanged, even if the transfer
+/// comes with a memo.
 #[repr(C)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
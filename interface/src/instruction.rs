@@ -26,6 +26,8 @@ use {
     solana_program_error::ProgramError,
     solana_program_option::COption,
     solana_sdk_ids::{system_program, sysvar},
+    spl_token_group_interface::instruction::TokenGroupInstruction,
+    spl_token_metadata_interface::instruction::TokenMetadataInstruction,
 };
 
 /// Minimum number of multisignature signers (min N)
@@ -90,6 +92,9 @@ pub enum TokenInstruction<'a> {
     ///   1. `[]` The mint this account will be associated with.
     ///   2. `[]` The new account's owner/multisignature.
     ///   3. `[]` Rent sysvar
+    ///   4. `[signer]` (Optional) The attestor registered on the mint by the
+    ///      `DefaultAccountStateAttestor` extension, approving the account
+    ///      starting in the `Initialized` state. Ignored if it doesn't sign.
     InitializeAccount,
     /// Initializes a multisignature account with N provided signers.
     ///
@@ -257,6 +262,14 @@ pub enum TokenInstruction<'a> {
     /// Mints may be closed if they have the `MintCloseAuthority` extension and
     /// their token supply is zero
     ///
+    /// A mint that is a member of a token group leaves the group when closed,
+    /// decrementing the group size, so the group mint must be provided,
+    /// writable, after the accounts below. Otherwise, the instruction fails
+    /// with `TokenError::MintIsGroupMember`. No group authority is needed,
+    /// and the membership freeze of the group doesn't apply. The group mint
+    /// may be reallocated as with `RemoveMember`, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
     /// Accounts
     ///
     /// Accounts expected by this instruction:
@@ -265,12 +278,14 @@ pub enum TokenInstruction<'a> {
     ///   0. `[writable]` The account to close.
     ///   1. `[writable]` The destination account.
     ///   2. `[signer]` The account's owner.
+    ///   3. `[writable]` (Optional) The group mint of a member mint.
     ///
     ///   * Multisignature owner
     ///   0. `[writable]` The account to close.
     ///   1. `[writable]` The destination account.
     ///   2. `[]` The account's multisignature owner.
     ///   3. ..`3+M` `[signer]` M signer accounts.
+    ///   4. `3+M` `[writable]` (Optional) The group mint of a member mint.
     CloseAccount,
     // 10
     /// Freeze an Initialized account using the Mint's `freeze_authority` (if
@@ -426,6 +441,9 @@ pub enum TokenInstruction<'a> {
     ///   0. `[writable]`  The account to initialize.
     ///   1. `[]` The mint this account will be associated with.
     ///   2. `[]` Rent sysvar
+    ///   3. `[signer]` (Optional) The attestor registered on the mint by the
+    ///      `DefaultAccountStateAttestor` extension, approving the account
+    ///      starting in the `Initialized` state. Ignored if it doesn't sign.
     InitializeAccount2 {
         /// The new account's owner/multisignature.
         #[cfg_attr(feature = "serde", serde(with = "As::<DisplayFromStr>"))]
@@ -455,6 +473,9 @@ pub enum TokenInstruction<'a> {
     ///
     ///   0. `[writable]`  The account to initialize.
     ///   1. `[]` The mint this account will be associated with.
+    ///   2. `[signer]` (Optional) The attestor registered on the mint by the
+    ///      `DefaultAccountStateAttestor` extension, approving the account
+    ///      starting in the `Initialized` state. Ignored if it doesn't sign.
     InitializeAccount3 {
         /// The new account's owner/multisignature.
         #[cfg_attr(feature = "serde", serde(with = "As::<DisplayFromStr>"))]
@@ -761,6 +782,35 @@ pub enum TokenInstruction<'a> {
     },
     /// Instruction prefix for instructions to the permissioned burn extension
     PermissionedBurnExtension,
+    /// The common instruction prefix for partial freeze extension
+    /// instructions.
+    ///
+    /// See `extension::partial_freeze::instruction::PartialFreezeInstruction`
+    /// for further details about the extended instructions that share this
+    /// instruction prefix
+    PartialFreezeExtension,
+    /// The common instruction prefix for Token-2022 specific token-metadata
+    /// instructions.
+    ///
+    /// See `extension::token_metadata::instruction::TokenMetadataExtensionInstruction`
+    /// for further details about the extended instructions that share this
+    /// instruction prefix
+    TokenMetadataExtension,
+    /// The common instruction prefix for Token-2022 specific token-group
+    /// instructions.
+    ///
+    /// See `extension::token_group::instruction::TokenGroupExtensionInstruction`
+    /// for further details about the extended instructions that share this
+    /// instruction prefix
+    TokenGroupExtension,
+    // 50
+    /// The common instruction prefix for non-transferable extension
+    /// instructions.
+    ///
+    /// See `extension::non_transferable::instruction::NonTransferableInstruction`
+    /// for further details about the extended instructions that share this
+    /// instruction prefix
+    NonTransferableExtension,
     // 255
     /// Executes a batch of instructions. The instructions to be executed are
     /// specified in sequence on the instruction data. Each instruction
@@ -979,6 +1029,18 @@ impl<'a> TokenInstruction<'a> {
             &Self::PermissionedBurnExtension => {
                 buf.push(46);
             }
+            &Self::PartialFreezeExtension => {
+                buf.push(47);
+            }
+            &Self::TokenMetadataExtension => {
+                buf.push(48);
+            }
+            &Self::TokenGroupExtension => {
+                buf.push(49);
+            }
+            &Self::NonTransferableExtension => {
+                buf.push(50);
+            }
             Self::Batch { data } => {
                 buf.push(255);
                 buf.extend_from_slice(data);
@@ -1145,6 +1207,10 @@ impl<'a> TokenInstruction<'a> {
                 (Self::UnwrapLamports { amount }, rest)
             }
             46 => (Self::PermissionedBurnExtension, rest),
+            47 => (Self::PartialFreezeExtension, rest),
+            48 => (Self::TokenMetadataExtension, rest),
+            49 => (Self::TokenGroupExtension, rest),
+            50 => (Self::NonTransferableExtension, rest),
             255 => (
                 Self::Batch {
                     data: rest.to_vec(),
@@ -1275,6 +1341,8 @@ pub enum AuthorityType {
     Pause,
     /// Authority to perform a permissioned token burn
     PermissionedBurn,
+    /// Authority to move non-transferable tokens between accounts
+    NonTransferableRecovery,
 }
 
 impl AuthorityType {
@@ -1298,6 +1366,7 @@ impl AuthorityType {
             AuthorityType::ScaledUiAmount => 15,
             AuthorityType::Pause => 16,
             AuthorityType::PermissionedBurn => 17,
+            AuthorityType::NonTransferableRecovery => 18,
         }
     }
 
@@ -1322,6 +1391,7 @@ impl AuthorityType {
             15 => Ok(AuthorityType::ScaledUiAmount),
             16 => Ok(AuthorityType::Pause),
             17 => Ok(AuthorityType::PermissionedBurn),
+            18 => Ok(AuthorityType::NonTransferableRecovery),
             _ => Err(TokenError::InvalidInstruction.into()),
         }
     }
@@ -2231,9 +2301,210 @@ pub fn unwrap_lamports(
     })
 }
 
+/// Creates a `Batch` instruction that executes the given token instructions
+/// in sequence.
+///
+/// Accounts are not deduplicated: each instruction keeps its own slice of
+/// accounts, in order, so that the program can split them again using the
+/// account count written for each instruction.
+pub fn batch(
+    token_program_id: &Address,
+    instructions: &[Instruction],
+) -> Result<Instruction, ProgramError> {
+    let mut builder = BatchBuilder::new(token_program_id)?;
+    for instruction in instructions {
+        builder.add(instruction)?;
+    }
+    builder.build()
+}
+
+/// Builder for `Batch` instructions.
+///
+/// Each added instruction is validated against the `Batch` format: it must
+/// target the token program, must not be a `Batch` itself, and must fit its
+/// account count and data length in a `u8`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchBuilder {
+    token_program_id: Address,
+    accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+    instruction_count: usize,
+}
+
+impl BatchBuilder {
+    /// Creates an empty builder for the given token program
+    pub fn new(token_program_id: &Address) -> Result<Self, ProgramError> {
+        check_program_account(token_program_id)?;
+        Ok(Self {
+            token_program_id: *token_program_id,
+            accounts: vec![],
+            data: vec![255],
+            instruction_count: 0,
+        })
+    }
+
+    /// Appends an instruction to the batch, with all of its accounts
+    pub fn add(&mut self, instruction: &Instruction) -> Result<&mut Self, ProgramError> {
+        if instruction.program_id != self.token_program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        // nested batches are rejected by the program
+        if matches!(instruction.data.first(), None | Some(255)) {
+            return Err(TokenError::InvalidInstruction.into());
+        }
+        let account_count =
+            u8::try_from(instruction.accounts.len()).map_err(|_| TokenError::InvalidInstruction)?;
+        let data_length =
+            u8::try_from(instruction.data.len()).map_err(|_| TokenError::InvalidInstruction)?;
+
+        self.data.push(account_count);
+        self.data.push(data_length);
+        self.data.extend_from_slice(&instruction.data);
+        self.accounts.extend_from_slice(&instruction.accounts);
+        self.instruction_count += 1;
+        Ok(self)
+    }
+
+    /// Number of instructions added so far
+    pub fn len(&self) -> usize {
+        self.instruction_count
+    }
+
+    /// Whether no instruction was added yet
+    pub fn is_empty(&self) -> bool {
+        self.instruction_count == 0
+    }
+
+    /// Creates the `Batch` instruction, failing if the batch is empty
+    pub fn build(self) -> Result<Instruction, ProgramError> {
+        if self.is_empty() {
+            return Err(TokenError::InvalidInstruction.into());
+        }
+        Ok(Instruction {
+            program_id: self.token_program_id,
+            accounts: self.accounts,
+            data: self.data,
+        })
+    }
+}
+
+/// Instruction of any interface the program supports inside a `Batch`
+/// instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchedInstructionKind<'a> {
+    /// Token instruction. Extension instructions only decode to their prefix,
+    /// use `BatchedInstruction::data` to decode the extension instruction
+    /// itself.
+    Token(TokenInstruction<'a>),
+    /// Token-metadata interface instruction
+    TokenMetadata(TokenMetadataInstruction),
+    /// Token-group interface instruction
+    TokenGroup(TokenGroupInstruction),
+}
+
+/// An instruction decoded from a `Batch` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchedInstruction<'a> {
+    /// The decoded instruction
+    pub instruction: BatchedInstructionKind<'a>,
+    /// Raw instruction data, including the discriminator
+    pub data: &'a [u8],
+    /// Accounts used by the instruction
+    pub accounts: &'a [AccountMeta],
+}
+
+/// Decoder for `Batch` instructions, yielding the batched instructions in
+/// order of execution.
+///
+/// Splits the instruction data and accounts the same way the program does.
+/// Data that isn't a token instruction is decoded as a token-metadata or
+/// token-group interface instruction, which the program also accepts.
+/// Decoding stops after the first error.
+#[derive(Clone, Debug)]
+pub struct BatchDecoder<'a> {
+    data: &'a [u8],
+    accounts: &'a [AccountMeta],
+}
+
+impl<'a> BatchDecoder<'a> {
+    /// Creates a decoder for a `Batch` instruction of the token program
+    pub fn new(instruction: &'a Instruction) -> Result<Self, ProgramError> {
+        check_program_account(&instruction.program_id)?;
+        match instruction.data.split_first() {
+            Some((255, data)) if !data.is_empty() => Ok(Self {
+                data,
+                accounts: &instruction.accounts,
+            }),
+            _ => Err(TokenError::InvalidInstruction.into()),
+        }
+    }
+
+    /// Accounts not consumed by the instructions decoded so far
+    pub fn remaining_accounts(&self) -> &'a [AccountMeta] {
+        self.accounts
+    }
+
+    fn decode_next(&mut self) -> Result<BatchedInstruction<'a>, ProgramError> {
+        let (header, rest) = self
+            .data
+            .split_at_checked(2)
+            .ok_or(TokenError::InvalidInstruction)?;
+        let (data, rest) = rest
+            .split_at_checked(header[1] as usize)
+            .ok_or(TokenError::InvalidInstruction)?;
+        let (accounts, remaining_accounts) = self
+            .accounts
+            .split_at_checked(header[0] as usize)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let instruction = match TokenInstruction::unpack(data) {
+            Ok(TokenInstruction::Batch { .. }) => return Err(TokenError::InvalidInstruction.into()),
+            Ok(instruction) => BatchedInstructionKind::Token(instruction),
+            Err(err) => {
+                if let Ok(instruction) = TokenMetadataInstruction::unpack(data) {
+                    BatchedInstructionKind::TokenMetadata(instruction)
+                } else if let Ok(instruction) = TokenGroupInstruction::unpack(data) {
+                    BatchedInstructionKind::TokenGroup(instruction)
+                } else {
+                    return Err(err);
+                }
+            }
+        };
+
+        self.data = rest;
+        self.accounts = remaining_accounts;
+        Ok(BatchedInstruction {
+            instruction,
+            data,
+            accounts,
+        })
+    }
+}
+
+impl<'a> Iterator for BatchDecoder<'a> {
+    type Item = Result<BatchedInstruction<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let result = self.decode_next();
+        if result.is_err() {
+            self.data = &[];
+        }
+        Some(result)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use {super::*, proptest::prelude::*};
+    use {
+        super::*,
+        alloc::string::ToString,
+        proptest::prelude::*,
+        spl_token_group_interface::instruction::UpdateGroupMaxSize,
+        spl_token_metadata_interface::{instruction::UpdateField, state::Field},
+    };
 
     #[test]
     fn test_initialize_mint_packing() {
@@ -2856,6 +3127,168 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_batch() {
+        let token_program_id = crate::id();
+        let account = Address::new_unique();
+        let mint = Address::new_unique();
+        let owner = Address::new_unique();
+
+        let mint_to_ix = mint_to(&token_program_id, &mint, &account, &owner, &[], 10).unwrap();
+        let burn_ix = burn(&token_program_id, &account, &mint, &owner, &[], 5).unwrap();
+
+        let batch_ix = batch(&token_program_id, &[mint_to_ix.clone(), burn_ix.clone()]).unwrap();
+        let mut expect = vec![255, 3, mint_to_ix.data.len() as u8];
+        expect.extend_from_slice(&mint_to_ix.data);
+        expect.extend_from_slice(&[3, burn_ix.data.len() as u8]);
+        expect.extend_from_slice(&burn_ix.data);
+        assert_eq!(batch_ix.data, expect);
+        assert_eq!(
+            batch_ix.accounts,
+            [mint_to_ix.accounts.clone(), burn_ix.accounts.clone()].concat()
+        );
+
+        // empty batch
+        assert_eq!(
+            batch(&token_program_id, &[]),
+            Err(TokenError::InvalidInstruction.into())
+        );
+
+        // nested batch
+        assert_eq!(
+            batch(&token_program_id, &[batch_ix]),
+            Err(TokenError::InvalidInstruction.into())
+        );
+
+        // instruction for another program
+        let mut other_ix = burn_ix.clone();
+        other_ix.program_id = Address::new_unique();
+        assert_eq!(
+            batch(&token_program_id, &[other_ix]),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        // too many accounts for a single instruction
+        let mut wide_ix = burn_ix;
+        wide_ix.accounts = vec![AccountMeta::new_readonly(owner, false); 256];
+        assert_eq!(
+            batch(&token_program_id, &[wide_ix]),
+            Err(TokenError::InvalidInstruction.into())
+        );
+    }
+
+    #[test]
+    fn test_batch_decoder() {
+        let token_program_id = crate::id();
+        let account = Address::new_unique();
+        let mint = Address::new_unique();
+        let owner = Address::new_unique();
+
+        let mint_to_ix = mint_to(&token_program_id, &mint, &account, &owner, &[], 10).unwrap();
+        let burn_ix = burn(&token_program_id, &account, &mint, &owner, &[], 5).unwrap();
+        let update_field_ix = spl_token_metadata_interface::instruction::update_field(
+            &token_program_id,
+            &mint,
+            &owner,
+            Field::Name,
+            "name".to_string(),
+        );
+        let update_max_size_ix = spl_token_group_interface::instruction::update_group_max_size(
+            &token_program_id,
+            &mint,
+            &owner,
+            3,
+        );
+
+        let mut builder = BatchBuilder::new(&token_program_id).unwrap();
+        assert!(builder.is_empty());
+        builder
+            .add(&mint_to_ix)
+            .unwrap()
+            .add(&update_field_ix)
+            .unwrap()
+            .add(&update_max_size_ix)
+            .unwrap()
+            .add(&burn_ix)
+            .unwrap();
+        assert_eq!(builder.len(), 4);
+        let batch_ix = builder.build().unwrap();
+
+        let decoded = BatchDecoder::new(&batch_ix)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            decoded,
+            vec![
+                BatchedInstruction {
+                    instruction: BatchedInstructionKind::Token(TokenInstruction::MintTo {
+                        amount: 10
+                    }),
+                    data: &mint_to_ix.data,
+                    accounts: &mint_to_ix.accounts,
+                },
+                BatchedInstruction {
+                    instruction: BatchedInstructionKind::TokenMetadata(
+                        TokenMetadataInstruction::UpdateField(UpdateField {
+                            field: Field::Name,
+                            value: "name".to_string(),
+                        })
+                    ),
+                    data: &update_field_ix.data,
+                    accounts: &update_field_ix.accounts,
+                },
+                BatchedInstruction {
+                    instruction: BatchedInstructionKind::TokenGroup(
+                        TokenGroupInstruction::UpdateGroupMaxSize(UpdateGroupMaxSize {
+                            max_size: 3.into(),
+                        })
+                    ),
+                    data: &update_max_size_ix.data,
+                    accounts: &update_max_size_ix.accounts,
+                },
+                BatchedInstruction {
+                    instruction: BatchedInstructionKind::Token(TokenInstruction::Burn {
+                        amount: 5
+                    }),
+                    data: &burn_ix.data,
+                    accounts: &burn_ix.accounts,
+                },
+            ]
+        );
+
+        // not a batch
+        assert_eq!(
+            BatchDecoder::new(&burn_ix).unwrap_err(),
+            TokenError::InvalidInstruction.into()
+        );
+
+        // missing accounts for the last instruction
+        let mut short_batch_ix = batch_ix.clone();
+        short_batch_ix.accounts.pop();
+        let mut decoder = BatchDecoder::new(&short_batch_ix).unwrap();
+        for _ in 0..3 {
+            assert!(decoder.next().unwrap().is_ok());
+        }
+        assert_eq!(
+            decoder.next().unwrap(),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+        assert_eq!(decoder.next(), None);
+
+        // truncated data
+        let mut truncated_batch_ix = batch_ix;
+        truncated_batch_ix.data.pop();
+        let mut decoder = BatchDecoder::new(&truncated_batch_ix).unwrap();
+        for _ in 0..3 {
+            assert!(decoder.next().unwrap().is_ok());
+        }
+        assert_eq!(
+            decoder.next().unwrap(),
+            Err(TokenError::InvalidInstruction.into())
+        );
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(1024))]
         #[test]
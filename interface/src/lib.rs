@@ -16,6 +16,7 @@ pub mod pod;
 #[cfg(feature = "serde")]
 pub mod serialization;
 pub mod state;
+pub mod ui_amount;
 
 // Export current sdk types for downstream users building with a different sdk
 // version
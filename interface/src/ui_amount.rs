@@ -0,0 +1,352 @@
+//! Conversions between raw token amounts and their UI representation
+
+use {
+    crate::{
+        extension::{
+            interest_bearing_mint::{
+                CompoundingPeriod, InterestBearingConfig, InterestBearingTerms,
+            },
+            scaled_ui_amount::{
+                ScaledUiAmountConfig, ScaledUiAmountRationalConfig, ScaledUiAmountSchedule,
+            },
+            BaseState, BaseStateWithExtensions,
+        },
+        trim_ui_amount_string,
+    },
+    alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    },
+    solana_program_error::ProgramError,
+};
+
+/// Convert a raw amount to its UI representation using the given decimals
+/// field. Excess zeroes or unneeded decimal point are trimmed.
+pub fn amount_to_ui_amount_string_trimmed(amount: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let ui_amount = if decimals > 0 {
+        // Left-pad zeros to decimals + 1, so we at least have an integer zero
+        let mut s = format!("{:01$}", amount, decimals + 1);
+        // Add the decimal point (Sorry, "," locales!)
+        s.insert(s.len() - decimals, '.');
+        s
+    } else {
+        amount.to_string()
+    };
+    trim_ui_amount_string(ui_amount, decimals as u8)
+}
+
+/// Try to convert a UI representation of a token amount to its raw amount
+/// using the given decimals field
+pub fn try_ui_amount_into_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let decimals = decimals as usize;
+    let mut parts = ui_amount.split('.');
+    // splitting a string, even an empty one, will always yield an iterator of at
+    // least length == 1
+    let mut amount_str = parts.next().unwrap().to_string();
+    let after_decimal = parts.next().unwrap_or("");
+    let after_decimal = after_decimal.trim_end_matches('0');
+    if (amount_str.is_empty() && after_decimal.is_empty())
+        || parts.next().is_some()
+        || after_decimal.len() > decimals
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    amount_str.push_str(after_decimal);
+    for _ in 0..decimals.saturating_sub(after_decimal.len()) {
+        amount_str.push('0');
+    }
+    amount_str
+        .parse::<u64>()
+        .map_err(|_| ProgramError::InvalidArgument)
+}
+
+/// Conversion used for a mint, in order of precedence
+#[derive(Clone, Debug, PartialEq)]
+enum Conversion {
+    InterestBearing {
+        config: InterestBearingConfig,
+        compounding_period: CompoundingPeriod,
+    },
+    ScaledUiAmountRational(ScaledUiAmountRationalConfig),
+    ScaledUiAmountSchedule(ScaledUiAmountSchedule),
+    ScaledUiAmount(ScaledUiAmountConfig),
+    Decimals,
+}
+
+/// Converts between raw amounts and UI amounts for a mint at a given timestamp.
+///
+/// The extensions are chosen with the same precedence as the
+/// `AmountToUiAmount` and `UiAmountToAmount` instructions, so the results
+/// match the program when given the cluster's current timestamp.
+///
+/// Only the current state of the mint is known, so conversions at past
+/// timestamps are exact only after the last change of the mint's
+/// configuration, ie. the last rate update for `InterestBearingConfig`, or the
+/// last multiplier change for the scaled UI amount extensions. Earlier
+/// timestamps give the values that the current configuration would have
+/// produced, not the historical ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiAmountConverter {
+    decimals: u8,
+    conversion: Conversion,
+}
+impl UiAmountConverter {
+    /// Create a converter for a mint with the given decimals, reading any
+    /// extensions that change its UI amounts
+    pub fn new<S: BaseState>(
+        decimals: u8,
+        mint: &impl BaseStateWithExtensions<S>,
+    ) -> Result<Self, ProgramError> {
+        let conversion = if let Ok(config) = mint.get_extension::<InterestBearingConfig>() {
+            let compounding_period = match mint.get_extension::<InterestBearingTerms>() {
+                Ok(terms) => terms.compounding_period()?,
+                Err(_) => CompoundingPeriod::Continuous,
+            };
+            Conversion::InterestBearing {
+                config: *config,
+                compounding_period,
+            }
+        } else if let Ok(config) = mint.get_extension::<ScaledUiAmountRationalConfig>() {
+            Conversion::ScaledUiAmountRational(*config)
+        } else if let Ok(schedule) = mint.get_variable_len_extension::<ScaledUiAmountSchedule>() {
+            Conversion::ScaledUiAmountSchedule(schedule)
+        } else if let Ok(config) = mint.get_extension::<ScaledUiAmountConfig>() {
+            Conversion::ScaledUiAmount(*config)
+        } else {
+            Conversion::Decimals
+        };
+        Ok(Self {
+            decimals,
+            conversion,
+        })
+    }
+
+    /// The mint's decimals
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Check if conversions depend on the timestamp, ie. if the UI amount of a
+    /// constant raw amount can change over time
+    pub fn is_time_dependent(&self) -> bool {
+        self.conversion != Conversion::Decimals
+    }
+
+    /// Convert a raw amount to its UI representation at the given timestamp.
+    /// Excess zeroes or unneeded decimal point are trimmed.
+    pub fn amount_to_ui_amount(&self, amount: u64, unix_timestamp: i64) -> Option<String> {
+        match &self.conversion {
+            Conversion::InterestBearing {
+                config,
+                compounding_period,
+            } => config.amount_to_ui_amount_with_compounding(
+                amount,
+                self.decimals,
+                unix_timestamp,
+                *compounding_period,
+            ),
+            Conversion::ScaledUiAmountRational(config) => {
+                config.amount_to_ui_amount(amount, self.decimals, unix_timestamp)
+            }
+            Conversion::ScaledUiAmountSchedule(schedule) => {
+                schedule.amount_to_ui_amount(amount, self.decimals, unix_timestamp)
+            }
+            Conversion::ScaledUiAmount(config) => {
+                config.amount_to_ui_amount(amount, self.decimals, unix_timestamp)
+            }
+            Conversion::Decimals => Some(amount_to_ui_amount_string_trimmed(amount, self.decimals)),
+        }
+    }
+
+    /// Try to convert a UI representation of a token amount to its raw amount
+    /// at the given timestamp
+    pub fn try_ui_amount_into_amount(
+        &self,
+        ui_amount: &str,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        match &self.conversion {
+            Conversion::InterestBearing {
+                config,
+                compounding_period,
+            } => config.try_ui_amount_into_amount_with_compounding(
+                ui_amount,
+                self.decimals,
+                unix_timestamp,
+                *compounding_period,
+            ),
+            Conversion::ScaledUiAmountRational(config) => {
+                config.try_ui_amount_into_amount(ui_amount, self.decimals, unix_timestamp)
+            }
+            Conversion::ScaledUiAmountSchedule(schedule) => {
+                schedule.try_ui_amount_into_amount(ui_amount, self.decimals, unix_timestamp)
+            }
+            Conversion::ScaledUiAmount(config) => {
+                config.try_ui_amount_into_amount(ui_amount, self.decimals, unix_timestamp)
+            }
+            Conversion::Decimals => try_ui_amount_into_amount(ui_amount, self.decimals),
+        }
+    }
+
+    /// Convert raw amounts to their UI representations at the given timestamp,
+    /// failing if any of them cannot be converted
+    pub fn amounts_to_ui_amounts(
+        &self,
+        amounts: &[u64],
+        unix_timestamp: i64,
+    ) -> Option<Vec<String>> {
+        amounts
+            .iter()
+            .map(|amount| self.amount_to_ui_amount(*amount, unix_timestamp))
+            .collect()
+    }
+
+    /// Try to convert UI representations of token amounts to their raw amounts
+    /// at the given timestamp, failing if any of them cannot be converted
+    pub fn try_ui_amounts_into_amounts(
+        &self,
+        ui_amounts: &[&str],
+        unix_timestamp: i64,
+    ) -> Result<Vec<u64>, ProgramError> {
+        ui_amounts
+            .iter()
+            .map(|ui_amount| self.try_ui_amount_into_amount(ui_amount, unix_timestamp))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            extension::{
+                scaled_ui_amount::RationalMultiplier, BaseStateWithExtensionsMut, ExtensionType,
+                PodStateWithExtensionsMut,
+            },
+            pod::{test::TEST_POD_MINT, PodMint},
+            state::Mint,
+        },
+        alloc::vec,
+    };
+
+    const ONE_YEAR: i64 = 31_556_736;
+
+    fn mint_buffer(extension_types: &[ExtensionType]) -> Vec<u8> {
+        let mint_size = ExtensionType::try_calculate_account_len::<Mint>(extension_types).unwrap();
+        let mut buffer = vec![0; mint_size];
+        let mut state =
+            PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut buffer).unwrap();
+        *state.base = TEST_POD_MINT;
+        state.init_account_type().unwrap();
+        buffer
+    }
+
+    #[test]
+    fn plain_decimals() {
+        let mut buffer = mint_buffer(&[]);
+        let state = PodStateWithExtensionsMut::<PodMint>::unpack(&mut buffer).unwrap();
+        let converter = UiAmountConverter::new(state.base.decimals, &state).unwrap();
+        assert!(!converter.is_time_dependent());
+        assert_eq!(converter.decimals(), 7);
+        assert_eq!(
+            converter.amounts_to_ui_amounts(&[0, 1, 10_000_000, 12_345_678], 0),
+            Some(vec![
+                "0".to_string(),
+                "0.0000001".to_string(),
+                "1".to_string(),
+                "1.2345678".to_string()
+            ])
+        );
+        assert_eq!(
+            converter.try_ui_amounts_into_amounts(&["0", ".0000001", "1.", "1.2345678"], 0),
+            Ok(vec![0, 1, 10_000_000, 12_345_678])
+        );
+        for ui_amount in ["", ".", "0.00000001", "1.2.3", "-1"] {
+            assert_eq!(
+                converter.try_ui_amount_into_amount(ui_amount, 0),
+                Err(ProgramError::InvalidArgument)
+            );
+        }
+        assert_eq!(
+            converter.try_ui_amounts_into_amounts(&["1", "x"], 0),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn interest_bearing_with_terms() {
+        let mut buffer = mint_buffer(&[
+            ExtensionType::InterestBearingConfig,
+            ExtensionType::InterestBearingTerms,
+        ]);
+        let mut state = PodStateWithExtensionsMut::<PodMint>::unpack(&mut buffer).unwrap();
+        let config = state.init_extension::<InterestBearingConfig>(true).unwrap();
+        config.pre_update_average_rate = 500.into();
+        config.current_rate = 500.into();
+        let converter = UiAmountConverter::new(state.base.decimals, &state).unwrap();
+        assert!(converter.is_time_dependent());
+        assert_eq!(
+            converter.amount_to_ui_amount(10_000_000, ONE_YEAR),
+            Some("1.0512711".to_string())
+        );
+
+        let terms = state.init_extension::<InterestBearingTerms>(true).unwrap();
+        *terms = InterestBearingTerms::new(CompoundingPeriod::Monthly, None, None);
+        let converter = UiAmountConverter::new(state.base.decimals, &state).unwrap();
+        assert_eq!(
+            converter.amounts_to_ui_amounts(&[10_000_000], ONE_YEAR),
+            Some(vec!["1.0511619".to_string()])
+        );
+        assert_eq!(
+            converter.try_ui_amount_into_amount("1.0511619", ONE_YEAR),
+            Ok(10_000_000)
+        );
+        // historical amounts
+        assert_eq!(
+            converter.amount_to_ui_amount(10_000_000, 0),
+            Some("1".to_string())
+        );
+    }
+
+    #[test]
+    fn scaled_ui_amount_precedence() {
+        let mut buffer = mint_buffer(&[
+            ExtensionType::ScaledUiAmount,
+            ExtensionType::ScaledUiAmountRational,
+        ]);
+        let mut state = PodStateWithExtensionsMut::<PodMint>::unpack(&mut buffer).unwrap();
+        let config = state.init_extension::<ScaledUiAmountConfig>(true).unwrap();
+        config.multiplier = 2.0.into();
+        config.new_multiplier_effective_timestamp = 10.into();
+        config.new_multiplier = 3.0.into();
+        let converter = UiAmountConverter::new(state.base.decimals, &state).unwrap();
+        assert_eq!(
+            converter.amounts_to_ui_amounts(&[10_000_000, 10_000_000], 9),
+            Some(vec!["2".to_string(), "2".to_string()])
+        );
+        assert_eq!(
+            converter.amount_to_ui_amount(10_000_000, 10),
+            Some("3".to_string())
+        );
+
+        // the rational multiplier wins over the `f64` one
+        let rational = state
+            .init_extension::<ScaledUiAmountRationalConfig>(true)
+            .unwrap();
+        rational.multiplier = RationalMultiplier::new(1, 3);
+        rational.new_multiplier = RationalMultiplier::new(1, 3);
+        let converter = UiAmountConverter::new(state.base.decimals, &state).unwrap();
+        assert_eq!(
+            converter.amount_to_ui_amount(10_000_000, 10),
+            Some("0.3333333".to_string())
+        );
+        assert_eq!(
+            converter.try_ui_amounts_into_amounts(&["0.3333333", "1"], 10),
+            Ok(vec![9_999_999, 30_000_000])
+        );
+    }
+}
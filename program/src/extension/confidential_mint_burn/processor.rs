@@ -2,14 +2,21 @@
 use {
     crate::{
         check_auditor_ciphertext,
-        extension::confidential_mint_burn::verify_proof::{verify_burn_proof, verify_mint_proof},
+        extension::{
+            confidential_mint_burn::verify_proof::{verify_burn_proof, verify_mint_proof},
+            pausable::processor::check_not_paused,
+        },
         processor::BurnInstructionVariant,
     },
-    spl_token_2022_interface::extension::permissioned_burn::PermissionedBurnConfig,
+    spl_token_2022_interface::extension::{
+        partial_freeze::{incoming_frozen, outgoing_frozen},
+        pausable::PausedOperations,
+        permissioned_burn::PermissionedBurnConfig,
+    },
     spl_token_confidential_transfer_ciphertext_arithmetic as ciphertext_arithmetic,
 };
 use {
-    crate::{extension::cpi_guard::in_cpi, processor::Processor},
+    crate::{extension::cpi_guard::cpi_guard_blocks, processor::Processor},
     solana_account_info::{next_account_info, AccountInfo},
     solana_address::Address,
     solana_msg::msg,
@@ -34,10 +41,9 @@ use {
                 ConfidentialMintBurn,
             },
             confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
-            cpi_guard::CpiGuard,
+            cpi_guard::CpiGuardPolicy,
             immutable_owner::ImmutableOwner,
             non_transferable::{NonTransferable, NonTransferableAccount},
-            pausable::PausableConfig,
             BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
@@ -173,11 +179,7 @@ fn process_confidential_mint(
     let auditor_elgamal_pubkey = mint
         .get_extension::<ConfidentialTransferMint>()?
         .auditor_elgamal_pubkey;
-    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-        if extension.paused.into() {
-            return Err(TokenError::MintPaused.into());
-        }
-    }
+    check_not_paused(&mint, PausedOperations::MINT)?;
     check_program_account(token_account_info.owner)?;
     let token_account_data = &mut token_account_info.data.borrow_mut();
     let mut token_account = PodStateWithExtensionsMut::<PodAccount>::unpack(token_account_data)?;
@@ -215,6 +217,9 @@ fn process_confidential_mint(
     if token_account.base.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
     }
+    if incoming_frozen(&token_account) {
+        return Err(TokenError::AccountIncomingFrozen.into());
+    }
 
     if token_account.base.mint != *mint_info.key {
         return Err(TokenError::MintMismatch.into());
@@ -319,11 +324,7 @@ pub(crate) fn process_confidential_burn(
     let auditor_elgamal_pubkey = mint
         .get_extension::<ConfidentialTransferMint>()?
         .auditor_elgamal_pubkey;
-    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-        if extension.paused.into() {
-            return Err(TokenError::MintPaused.into());
-        }
-    }
+    check_not_paused(&mint, PausedOperations::BURN)?;
 
     let proof_context = verify_burn_proof(
         account_info_iter,
@@ -392,20 +393,22 @@ pub(crate) fn process_confidential_burn(
         account_info_iter.as_slice(),
     )?;
 
-    if let Ok(cpi_guard) = token_account.get_extension::<CpiGuard>() {
-        // Blocks all cases where the authority has signed if CPI Guard is
-        // enabled, including:
-        // * the account is delegated to the owner
-        // * the account owner is the permanent delegate
-        if *authority_info.key == token_account.base.owner && cpi_guard.lock_cpi.into() && in_cpi()
-        {
-            return Err(TokenError::CpiGuardBurnBlocked.into());
-        }
+    // Blocks all cases where the authority has signed if CPI Guard is
+    // enabled, including:
+    // * the account is delegated to the owner
+    // * the account owner is the permanent delegate
+    if *authority_info.key == token_account.base.owner
+        && cpi_guard_blocks(&token_account, CpiGuardPolicy::BURN, accounts)
+    {
+        return Err(TokenError::CpiGuardBurnBlocked.into());
     }
 
     if token_account.base.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
     }
+    if outgoing_frozen(&token_account) {
+        return Err(TokenError::AccountOutgoingFrozen.into());
+    }
 
     if token_account.base.mint != *mint_info.key {
         return Err(TokenError::MintMismatch.into());
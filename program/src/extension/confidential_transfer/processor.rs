@@ -1,9 +1,12 @@
 // Remove feature once zk ops syscalls are enabled on all networks
 #[cfg(feature = "zk-ops")]
 use {
-    crate::check_auditor_ciphertext,
+    crate::{check_auditor_ciphertext, extension::pausable::processor::check_not_paused},
     spl_token_2022_interface::extension::{
-        confidential_mint_burn::ConfidentialMintBurn, non_transferable::NonTransferableAccount,
+        confidential_mint_burn::ConfidentialMintBurn,
+        non_transferable::NonTransferableAccount,
+        partial_freeze::{incoming_frozen, outgoing_frozen},
+        pausable::PausedOperations,
     },
     spl_token_confidential_transfer_ciphertext_arithmetic as ciphertext_arithmetic,
 };
@@ -11,9 +14,14 @@ use {
     crate::{
         check_elgamal_registry_program_account,
         extension::{
-            confidential_transfer::verify_proof::*, cpi_guard::in_cpi,
-            memo_transfer::check_previous_sibling_instruction_is_memo,
+            confidential_transfer::verify_proof::*,
+            cpi_guard::cpi_guard_blocks,
+            memo_transfer::{
+                check_previous_sibling_instruction_is_memo,
+                check_previous_sibling_instruction_is_valid_memo, get_previous_sibling_memo,
+            },
         },
+        onchain,
         processor::Processor,
     },
     bytemuck::Zeroable,
@@ -45,9 +53,8 @@ use {
                 ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
                 EncryptedWithheldAmount,
             },
-            cpi_guard::CpiGuard,
-            memo_transfer::memo_required,
-            pausable::PausableConfig,
+            cpi_guard::CpiGuardPolicy,
+            memo_transfer::{memo_required, outgoing_memo_required},
             set_account_type,
             transfer_fee::TransferFeeConfig,
             transfer_hook, BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
@@ -119,13 +126,17 @@ fn process_update_mint(
 
 enum ElGamalPubkeySource<'a> {
     ProofInstructionOffset(i64),
-    ElGamalRegistry(&'a ElGamalRegistry),
+    ElGamalRegistry {
+        registry: &'a ElGamalRegistry,
+        elgamal_pubkey: &'a PodElGamalPubkey,
+    },
 }
 
 /// Processes a [`ConfigureAccountWithRegistry`] instruction.
 fn process_configure_account_with_registry(
     program_id: &Address,
     accounts: &[AccountInfo],
+    elgamal_key_label: Option<&ElGamalKeyLabel>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let token_account_info = next_account_info(account_info_iter)?;
@@ -145,9 +156,11 @@ fn process_configure_account_with_registry(
     }
 
     let elgamal_registry_account_data = &elgamal_registry_account.data.borrow();
-    let elgamal_registry_account =
-        bytemuck::try_from_bytes::<ElGamalRegistry>(elgamal_registry_account_data)
-            .map_err(|_| ProgramError::InvalidArgument)?;
+    let (elgamal_registry_account, labelled_keys) =
+        ElGamalRegistry::unpack(elgamal_registry_account_data)?;
+    let elgamal_pubkey = elgamal_registry_account
+        .get_elgamal_pubkey(labelled_keys, elgamal_key_label)
+        .ok_or(ProgramError::InvalidArgument)?;
 
     let decryptable_zero_balance = PodAeCiphertext::default();
     let maximum_pending_balance_credit_counter =
@@ -158,7 +171,10 @@ fn process_configure_account_with_registry(
         accounts,
         &decryptable_zero_balance,
         &maximum_pending_balance_credit_counter,
-        ElGamalPubkeySource::ElGamalRegistry(elgamal_registry_account),
+        ElGamalPubkeySource::ElGamalRegistry {
+            registry: elgamal_registry_account,
+            elgamal_pubkey,
+        },
     )
 }
 
@@ -238,9 +254,9 @@ fn process_configure_account(
             >(account_info_iter, offset, None)?;
             proof_context.pubkey
         }
-        ElGamalPubkeySource::ElGamalRegistry(elgamal_registry_account) => {
+        ElGamalPubkeySource::ElGamalRegistry { elgamal_pubkey, .. } => {
             let _elgamal_registry_account = next_account_info(account_info_iter)?;
-            elgamal_registry_account.elgamal_pubkey
+            *elgamal_pubkey
         }
     };
 
@@ -265,7 +281,10 @@ fn process_configure_account(
                 account_info_iter.as_slice(),
             )?;
         }
-        ElGamalPubkeySource::ElGamalRegistry(elgamal_registry_account) => {
+        ElGamalPubkeySource::ElGamalRegistry {
+            registry: elgamal_registry_account,
+            ..
+        } => {
             // if ElGamal registry was provided, then just verify that the owners of the
             // registry and token accounts match, then skip the signature
             // verification check
@@ -419,11 +438,7 @@ fn process_deposit(
     let mint_data = &mint_info.data.borrow_mut();
     let mint = PodStateWithExtensions::<PodMint>::unpack(mint_data)?;
 
-    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-        if extension.paused.into() {
-            return Err(TokenError::MintPaused.into());
-        }
-    }
+    check_not_paused(&mint, PausedOperations::TRANSFER)?;
 
     if expected_decimals != mint.base.decimals {
         return Err(TokenError::MintDecimalsMismatch.into());
@@ -548,11 +563,7 @@ fn process_withdraw(
         return Err(TokenError::IllegalMintBurnConversion.into());
     }
 
-    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-        if extension.paused.into() {
-            return Err(TokenError::MintPaused.into());
-        }
-    }
+    check_not_paused(&mint, PausedOperations::TRANSFER)?;
 
     check_program_account(token_account_info.owner)?;
     let token_account_data = &mut token_account_info.data.borrow_mut();
@@ -646,11 +657,7 @@ fn process_transfer(
     let mint_data = mint_info.data.borrow_mut();
     let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
 
-    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-        if extension.paused.into() {
-            return Err(TokenError::MintPaused.into());
-        }
-    }
+    check_not_paused(&mint, PausedOperations::TRANSFER)?;
 
     let confidential_transfer_mint = mint.get_extension::<ConfidentialTransferMint>()?;
 
@@ -710,6 +717,7 @@ fn process_transfer(
             mint_info,
             authority_info,
             account_info_iter.as_slice(),
+            accounts,
             &proof_context,
             new_source_decryptable_available_balance,
         )?;
@@ -790,6 +798,7 @@ fn process_transfer(
             mint_info,
             authority_info,
             account_info_iter.as_slice(),
+            accounts,
             &proof_context,
             new_source_decryptable_available_balance,
         )?;
@@ -807,25 +816,34 @@ fn process_transfer(
 
     if let Some(program_id) = transfer_hook::get_program_id(&mint) {
         // set transferring flags, scope the borrow to avoid double-borrow during CPI
+        let mut forward_memo = false;
         {
             let mut source_account_data = source_account_info.data.borrow_mut();
             let mut source_account =
                 PodStateWithExtensionsMut::<PodAccount>::unpack(&mut source_account_data)?;
+            forward_memo |= outgoing_memo_required(&source_account);
             transfer_hook::set_transferring(&mut source_account)?;
         }
         {
             let mut destination_account_data = destination_account_info.data.borrow_mut();
             let mut destination_account =
                 PodStateWithExtensionsMut::<PodAccount>::unpack(&mut destination_account_data)?;
+            forward_memo |= memo_required(&destination_account);
             transfer_hook::set_transferring(&mut destination_account)?;
         }
+        // the memo was already checked while processing the accounts
+        let memo = if forward_memo {
+            Some(get_previous_sibling_memo()?)
+        } else {
+            None
+        };
 
         // can't doubly-borrow the mint data either
         drop(mint_data);
 
         // Since the amount is unknown during a confidential transfer, pass in
         // u64::MAX as a convention.
-        spl_transfer_hook_interface::onchain::invoke_execute(
+        onchain::invoke_execute_with_memo(
             &program_id,
             source_account_info.clone(),
             mint_info.clone(),
@@ -833,6 +851,7 @@ fn process_transfer(
             authority_info.clone(),
             account_info_iter.as_slice(),
             u64::MAX,
+            memo.as_deref(),
         )?;
 
         // unset transferring flag
@@ -844,6 +863,7 @@ fn process_transfer(
 }
 
 /// Processes the changes for the sending party of a confidential transfer
+#[allow(clippy::too_many_arguments)]
 #[cfg(feature = "zk-ops")]
 fn process_source_for_transfer(
     program_id: &Address,
@@ -851,6 +871,7 @@ fn process_source_for_transfer(
     mint_info: &AccountInfo,
     authority_info: &AccountInfo,
     signers: &[AccountInfo],
+    account_infos: &[AccountInfo],
     proof_context: &TransferProofContext,
     new_source_decryptable_available_balance: DecryptableBalance,
 ) -> ProgramResult {
@@ -873,20 +894,26 @@ fn process_source_for_transfer(
         signers,
     )?;
 
-    if let Ok(cpi_guard) = token_account.get_extension::<CpiGuard>() {
-        // Blocks all cases where the authority has signed if CPI Guard is
-        // enabled, including:
-        // * the account is delegated to the owner
-        // * the account owner is the permanent delegate
-        if *authority_info.key == token_account.base.owner && cpi_guard.lock_cpi.into() && in_cpi()
-        {
-            return Err(TokenError::CpiGuardTransferBlocked.into());
-        }
+    // Blocks all cases where the authority has signed if CPI Guard is
+    // enabled, including:
+    // * the account is delegated to the owner
+    // * the account owner is the permanent delegate
+    if *authority_info.key == token_account.base.owner
+        && cpi_guard_blocks(&token_account, CpiGuardPolicy::TRANSFER, account_infos)
+    {
+        return Err(TokenError::CpiGuardTransferBlocked.into());
     }
 
     if token_account.base.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
     }
+    if outgoing_frozen(&token_account) {
+        return Err(TokenError::AccountOutgoingFrozen.into());
+    }
+
+    if outgoing_memo_required(&token_account) {
+        check_previous_sibling_instruction_is_memo()?;
+    }
 
     if token_account.base.mint != *mint_info.key {
         return Err(TokenError::MintMismatch.into());
@@ -945,13 +972,16 @@ fn process_destination_for_transfer(
     if destination_token_account.base.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
     }
+    if incoming_frozen(&destination_token_account) {
+        return Err(TokenError::AccountIncomingFrozen.into());
+    }
 
     if destination_token_account.base.mint != *mint_info.key {
         return Err(TokenError::MintMismatch.into());
     }
 
     if memo_required(&destination_token_account) {
-        check_previous_sibling_instruction_is_memo()?;
+        check_previous_sibling_instruction_is_valid_memo(&destination_token_account)?;
     }
 
     let destination_confidential_transfer_account =
@@ -998,6 +1028,7 @@ fn process_source_for_transfer_with_fee(
     mint_info: &AccountInfo,
     authority_info: &AccountInfo,
     signers: &[AccountInfo],
+    account_infos: &[AccountInfo],
     proof_context: &TransferWithFeeProofContext,
     new_source_decryptable_available_balance: DecryptableBalance,
 ) -> ProgramResult {
@@ -1020,20 +1051,26 @@ fn process_source_for_transfer_with_fee(
         signers,
     )?;
 
-    if let Ok(cpi_guard) = token_account.get_extension::<CpiGuard>() {
-        // Blocks all cases where the authority has signed if CPI Guard is
-        // enabled, including:
-        // * the account is delegated to the owner
-        // * the account owner is the permanent delegate
-        if *authority_info.key == token_account.base.owner && cpi_guard.lock_cpi.into() && in_cpi()
-        {
-            return Err(TokenError::CpiGuardTransferBlocked.into());
-        }
+    // Blocks all cases where the authority has signed if CPI Guard is
+    // enabled, including:
+    // * the account is delegated to the owner
+    // * the account owner is the permanent delegate
+    if *authority_info.key == token_account.base.owner
+        && cpi_guard_blocks(&token_account, CpiGuardPolicy::TRANSFER, account_infos)
+    {
+        return Err(TokenError::CpiGuardTransferBlocked.into());
     }
 
     if token_account.base.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
     }
+    if outgoing_frozen(&token_account) {
+        return Err(TokenError::AccountOutgoingFrozen.into());
+    }
+
+    if outgoing_memo_required(&token_account) {
+        check_previous_sibling_instruction_is_memo()?;
+    }
 
     if token_account.base.mint != *mint_info.key {
         return Err(TokenError::MintMismatch.into());
@@ -1095,13 +1132,16 @@ fn process_destination_for_transfer_with_fee(
     if destination_token_account.base.is_frozen() {
         return Err(TokenError::AccountFrozen.into());
     }
+    if incoming_frozen(&destination_token_account) {
+        return Err(TokenError::AccountIncomingFrozen.into());
+    }
 
     if destination_token_account.base.mint != *mint_info.key {
         return Err(TokenError::MintMismatch.into());
     }
 
     if memo_required(&destination_token_account) {
-        check_previous_sibling_instruction_is_memo()?;
+        check_previous_sibling_instruction_is_valid_memo(&destination_token_account)?;
     }
 
     let destination_confidential_transfer_account =
@@ -1459,7 +1499,15 @@ pub(crate) fn process_instruction(
         }
         ConfidentialTransferInstruction::ConfigureAccountWithRegistry => {
             msg!("ConfidentialTransferInstruction::ConfigureAccountWithRegistry");
-            process_configure_account_with_registry(program_id, accounts)
+            // a labelled key of the registry is used if its label is provided
+            let elgamal_key_label = if input.len() > 1 {
+                let data =
+                    decode_instruction_data::<ConfigureAccountWithRegistryInstructionData>(input)?;
+                Some(&data.elgamal_key_label)
+            } else {
+                None
+            };
+            process_configure_account_with_registry(program_id, accounts, elgamal_key_label)
         }
     }
 }
@@ -1,3 +1,12 @@
+use {
+    solana_account_info::AccountInfo,
+    solana_address::Address,
+    solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked},
+    spl_token_2022_interface::extension::{
+        cpi_guard::CpiGuardPolicy, BaseState, BaseStateWithExtensions,
+    },
+};
+
 /// CPI Guard extension instructions
 pub mod instruction;
 
@@ -23,3 +32,56 @@ pub fn in_cpi() -> bool {
         false
     }
 }
+
+/// Determine if we are in a CPI made directly by a top-level instruction
+fn in_direct_cpi() -> bool {
+    #[cfg(target_os = "solana")]
+    #[allow(unsafe_code)]
+    unsafe {
+        use solana_instruction::{syscalls::sol_get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+        sol_get_stack_height() as usize == TRANSACTION_LEVEL_STACK_HEIGHT + 1
+    }
+    #[cfg(not(target_os = "solana"))]
+    {
+        false
+    }
+}
+
+/// Determine the program that invoked this instruction through CPI.
+///
+/// The runtime doesn't expose the caller directly, so it is only known for
+/// CPIs made by a top-level instruction which pass the Instructions sysvar
+/// among `account_infos`. The caller is then the program of the top-level
+/// instruction currently executing.
+pub fn calling_program(account_infos: &[AccountInfo]) -> Option<Address> {
+    if !in_direct_cpi() {
+        return None;
+    }
+    let instructions_sysvar_info = account_infos
+        .iter()
+        .find(|info| solana_sdk_ids::sysvar::instructions::check_id(info.key))?;
+    let current_index = load_current_index_checked(instructions_sysvar_info).ok()?;
+    load_instruction_at_checked(current_index as usize, instructions_sysvar_info)
+        .ok()
+        .map(|instruction| instruction.program_id)
+}
+
+/// Determine if the CPI Guard on this account blocks the operation, one of
+/// the `CpiGuardPolicy` flags, taking into account the account's
+/// `CpiGuardPolicy`
+pub fn cpi_guard_blocks<BSE: BaseStateWithExtensions<S>, S: BaseState>(
+    account_state: &BSE,
+    operation: u8,
+    account_infos: &[AccountInfo],
+) -> bool {
+    let Ok(cpi_guard) = account_state.get_extension::<CpiGuard>() else {
+        return false;
+    };
+    if !bool::from(cpi_guard.lock_cpi) || !in_cpi() {
+        return false;
+    }
+    match account_state.get_extension::<CpiGuardPolicy>() {
+        Ok(policy) => !policy.allows(operation, calling_program(account_infos).as_ref()),
+        Err(_) => true,
+    }
+}
@@ -3,15 +3,15 @@ use {
     solana_account_info::{next_account_info, AccountInfo},
     solana_address::Address,
     solana_msg::msg,
-    solana_program_error::ProgramResult,
+    solana_program_error::{ProgramError, ProgramResult},
     spl_token_2022_interface::{
         check_program_account,
         error::TokenError,
         extension::{
-            cpi_guard::{instruction::CpiGuardInstruction, CpiGuard},
+            cpi_guard::{instruction::CpiGuardInstruction, CpiGuard, CpiGuardPolicy},
             BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
         },
-        instruction::decode_instruction_type,
+        instruction::{decode_instruction_data, decode_instruction_type},
         pod::PodAccount,
     },
 };
@@ -53,6 +53,47 @@ fn process_toggle_cpi_guard(
     Ok(())
 }
 
+/// Set the CPI Guard policy, initializing the `CpiGuardPolicy` extension if
+/// not already present.
+fn process_set_policy(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    policy: &CpiGuardPolicy,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+    check_program_account(token_account_info.owner)?;
+
+    if !policy.is_valid() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut account_data = token_account_info.data.borrow_mut();
+    let mut account = PodStateWithExtensionsMut::<PodAccount>::unpack(&mut account_data)?;
+
+    Processor::validate_owner(
+        program_id,
+        &account.base.owner,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    if in_cpi() {
+        return Err(TokenError::CpiGuardSettingsLocked.into());
+    }
+
+    let extension = if let Ok(extension) = account.get_extension_mut::<CpiGuardPolicy>() {
+        extension
+    } else {
+        account.init_extension::<CpiGuardPolicy>(true)?
+    };
+    *extension = *policy;
+    Ok(())
+}
+
 pub(crate) fn process_instruction(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -69,5 +110,10 @@ pub(crate) fn process_instruction(
             msg!("CpiGuardInstruction::Disable");
             process_toggle_cpi_guard(program_id, accounts, false /* disable */)
         }
+        CpiGuardInstruction::SetPolicy => {
+            msg!("CpiGuardInstruction::SetPolicy");
+            let policy = decode_instruction_data::<CpiGuardPolicy>(input)?;
+            process_set_policy(program_id, accounts, policy)
+        }
     }
 }
@@ -3,17 +3,23 @@ use {
     solana_account_info::{next_account_info, AccountInfo},
     solana_address::Address,
     solana_msg::msg,
+    solana_nullable::MaybeNull,
     solana_program_error::ProgramResult,
     spl_token_2022_interface::{
         check_program_account,
         error::TokenError,
         extension::{
+            alloc_and_serialize,
             default_account_state::{
-                instruction::{decode_instruction, DefaultAccountStateInstruction},
-                DefaultAccountState,
+                instruction::{
+                    decode_instruction, DefaultAccountStateInstruction, SetAttestorInstructionData,
+                },
+                DefaultAccountState, DefaultAccountStateAttestor,
             },
-            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
+            PodStateWithExtensionsMut,
         },
+        instruction::{decode_instruction_data, decode_instruction_type},
         pod::{PodCOption, PodMint},
         state::AccountState,
     },
@@ -76,6 +82,44 @@ fn process_update_default_account_state(
     Ok(())
 }
 
+fn process_set_attestor(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    attestor: MaybeNull<Address>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let freeze_authority_info = next_account_info(account_info_iter)?;
+    let freeze_authority_info_data_len = freeze_authority_info.data_len();
+    check_program_account(mint_account_info.owner)?;
+
+    // The mint data must not be borrowed when reallocating
+    {
+        let mint_data = mint_account_info.try_borrow_data()?;
+        let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+
+        match &mint.base.freeze_authority {
+            PodCOption {
+                option: PodCOption::<Address>::SOME,
+                value: freeze_authority,
+            } => Processor::validate_owner(
+                program_id,
+                freeze_authority,
+                freeze_authority_info,
+                freeze_authority_info_data_len,
+                account_info_iter.as_slice(),
+            ),
+            _ => Err(TokenError::NoAuthorityExists.into()),
+        }?;
+
+        mint.get_extension::<DefaultAccountState>()?;
+    }
+
+    let extension = DefaultAccountStateAttestor { attestor };
+    // Assumes that there's enough SOL for the new rent-exemption
+    alloc_and_serialize::<PodMint, DefaultAccountStateAttestor>(mint_account_info, &extension, true)
+}
+
 pub(crate) fn process_instruction(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -83,15 +127,21 @@ pub(crate) fn process_instruction(
 ) -> ProgramResult {
     check_program_account(program_id)?;
 
-    let (instruction, state) = decode_instruction(input)?;
-    match instruction {
+    match decode_instruction_type(input)? {
         DefaultAccountStateInstruction::Initialize => {
             msg!("DefaultAccountStateInstruction::Initialize");
+            let (_, state) = decode_instruction(input)?;
             process_initialize_default_account_state(accounts, state)
         }
         DefaultAccountStateInstruction::Update => {
             msg!("DefaultAccountStateInstruction::Update");
+            let (_, state) = decode_instruction(input)?;
             process_update_default_account_state(program_id, accounts, state)
         }
+        DefaultAccountStateInstruction::SetAttestor => {
+            msg!("DefaultAccountStateInstruction::SetAttestor");
+            let SetAttestorInstructionData { attestor } = decode_instruction_data(input)?;
+            process_set_attestor(program_id, accounts, *attestor)
+        }
     }
 }
@@ -5,17 +5,20 @@ use {
     solana_clock::Clock,
     solana_msg::msg,
     solana_nullable::MaybeNull,
-    solana_program_error::ProgramResult,
+    solana_program_error::{ProgramError, ProgramResult},
     solana_sysvar::Sysvar,
     spl_token_2022_interface::{
         check_program_account,
         error::TokenError,
         extension::{
             interest_bearing_mint::{
-                instruction::{InitializeInstructionData, InterestBearingMintInstruction},
-                BasisPoints, InterestBearingConfig,
+                instruction::{
+                    InitializeInstructionData, InterestBearingMintInstruction,
+                    UpdateTermsInstructionData,
+                },
+                BasisPoints, CompoundingPeriod, InterestBearingConfig, InterestBearingTerms,
             },
-            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
         pod::PodMint,
@@ -60,6 +63,7 @@ fn process_update_rate(
 
     let mut mint_data = mint_account_info.data.borrow_mut();
     let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let terms = mint.get_extension::<InterestBearingTerms>().ok().copied();
     let extension = mint.get_extension_mut::<InterestBearingConfig>()?;
     let rate_authority =
         Option::<Address>::from(extension.rate_authority).ok_or(TokenError::NoAuthorityExists)?;
@@ -72,18 +76,108 @@ fn process_update_rate(
         account_info_iter.as_slice(),
     )?;
 
+    let compounding_period = match terms {
+        Some(terms) => {
+            if !terms.contains_rate((*new_rate).into()) {
+                msg!("Interest rate must be within the mint's bounds");
+                return Err(TokenError::InterestRateOutOfBounds.into());
+            }
+            terms.compounding_period()?
+        }
+        None => CompoundingPeriod::Continuous,
+    };
+
     let clock = Clock::get()?;
     let new_average_rate = extension
-        .time_weighted_average_rate(clock.unix_timestamp)
+        .time_weighted_average_rate_with_compounding(clock.unix_timestamp, compounding_period)
         .ok_or(TokenError::Overflow)?;
     extension.pre_update_average_rate = new_average_rate.into();
     extension.last_update_timestamp = clock.unix_timestamp.into();
-    // There is no validation on the rate, since ridiculous values are *technically*
-    // possible!
+    // Without bounds, there is no validation on the rate, since ridiculous values
+    // are *technically* possible!
     extension.current_rate = *new_rate;
     Ok(())
 }
 
+fn process_initialize_terms(
+    _program_id: &Address,
+    accounts: &[AccountInfo],
+    terms: &InterestBearingTerms,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+
+    if !terms.is_valid() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let config = mint.get_extension::<InterestBearingConfig>()?;
+    if !terms.contains_rate(config.current_rate.into()) {
+        msg!("Interest rate must be within the mint's bounds");
+        return Err(TokenError::InterestRateOutOfBounds.into());
+    }
+
+    let extension = mint.init_extension::<InterestBearingTerms>(true)?;
+    *extension = *terms;
+    Ok(())
+}
+
+fn process_update_terms(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    new_terms: &InterestBearingTerms,
+    new_rate: &BasisPoints,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let compounding_period = mint
+        .get_extension::<InterestBearingTerms>()?
+        .compounding_period()?;
+    let extension = mint.get_extension_mut::<InterestBearingConfig>()?;
+    let rate_authority =
+        Option::<Address>::from(extension.rate_authority).ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &rate_authority,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    if !new_terms.is_valid() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !new_terms.contains_rate((*new_rate).into()) {
+        msg!("Interest rate must be within the new bounds");
+        return Err(TokenError::InterestRateOutOfBounds.into());
+    }
+    let new_compounding_period = new_terms.compounding_period()?;
+
+    // settle interest accrued so far, then express it over the new period
+    let clock = Clock::get()?;
+    let new_average_rate = extension
+        .time_weighted_average_rate_with_compounding(clock.unix_timestamp, compounding_period)
+        .and_then(|rate| compounding_period.equivalent_rate(rate, new_compounding_period))
+        .ok_or(TokenError::Overflow)?;
+    extension.pre_update_average_rate = new_average_rate.into();
+    extension.last_update_timestamp = clock.unix_timestamp.into();
+    extension.current_rate = *new_rate;
+
+    let terms = mint.get_extension_mut::<InterestBearingTerms>()?;
+    *terms = *new_terms;
+    Ok(())
+}
+
 pub(crate) fn process_instruction(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -104,5 +198,15 @@ pub(crate) fn process_instruction(
             let new_rate = decode_instruction_data(input)?;
             process_update_rate(program_id, accounts, new_rate)
         }
+        InterestBearingMintInstruction::InitializeTerms => {
+            msg!("InterestBearingMintInstruction::InitializeTerms");
+            let terms = decode_instruction_data(input)?;
+            process_initialize_terms(program_id, accounts, terms)
+        }
+        InterestBearingMintInstruction::UpdateTerms => {
+            msg!("InterestBearingMintInstruction::UpdateTerms");
+            let UpdateTermsInstructionData { terms, rate } = decode_instruction_data(input)?;
+            process_update_terms(program_id, accounts, terms, rate)
+        }
     }
 }
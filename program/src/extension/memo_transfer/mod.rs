@@ -1,6 +1,11 @@
 use {
-    solana_address::Address, solana_instruction::Instruction, solana_program_error::ProgramError,
-    spl_token_2022_interface::error::TokenError,
+    solana_address::Address,
+    solana_instruction::Instruction,
+    solana_program_error::ProgramError,
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{memo_transfer::MemoTransferPolicy, BaseState, BaseStateWithExtensions},
+    },
 };
 
 /// Memo Transfer extension instructions
@@ -17,6 +22,27 @@ pub use spl_token_2022_interface::extension::memo_transfer::{memo_required, Memo
 
 /// Check if the previous sibling instruction is a memo
 pub fn check_previous_sibling_instruction_is_memo() -> Result<(), ProgramError> {
+    get_previous_sibling_memo().map(|_| ())
+}
+
+/// Check if the previous sibling instruction is a memo satisfying the memo
+/// policy of the receiving account, if it has one, returning the memo
+pub fn check_previous_sibling_instruction_is_valid_memo<
+    BSE: BaseStateWithExtensions<S>,
+    S: BaseState,
+>(
+    account_state: &BSE,
+) -> Result<Vec<u8>, ProgramError> {
+    let memo = get_previous_sibling_memo()?;
+    if let Ok(policy) = account_state.get_extension::<MemoTransferPolicy>() {
+        policy.check(&memo)?;
+    }
+    Ok(memo)
+}
+
+/// Get the data of the previous sibling instruction, failing if it isn't a
+/// memo
+pub fn get_previous_sibling_memo() -> Result<Vec<u8>, ProgramError> {
     let is_memo_program = |program_id: &Address| -> bool {
         program_id == &spl_memo_interface::v4::id()
             || program_id == &spl_memo_interface::v3::id()
@@ -24,12 +50,9 @@ pub fn check_previous_sibling_instruction_is_memo() -> Result<(), ProgramError>
     };
     let previous_instruction = get_processed_sibling_instruction();
     match previous_instruction {
-        Some(instruction) if is_memo_program(&instruction.program_id) => {}
-        _ => {
-            return Err(TokenError::NoMemo.into());
-        }
+        Some(instruction) if is_memo_program(&instruction.program_id) => Ok(instruction.data),
+        _ => Err(TokenError::NoMemo.into()),
     }
-    Ok(())
 }
 
 /// Re-implementation of `get_processed_sibling_instruction` from `solana_program`
@@ -3,14 +3,17 @@ use {
     solana_account_info::{next_account_info, AccountInfo},
     solana_address::Address,
     solana_msg::msg,
-    solana_program_error::ProgramResult,
+    solana_program_error::{ProgramError, ProgramResult},
     spl_token_2022_interface::{
         check_program_account,
         extension::{
-            memo_transfer::{instruction::RequiredMemoTransfersInstruction, MemoTransfer},
+            memo_transfer::{
+                instruction::RequiredMemoTransfersInstruction, MemoTransfer, MemoTransferPolicy,
+                OutgoingMemoTransfer,
+            },
             BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
         },
-        instruction::decode_instruction_type,
+        instruction::{decode_instruction_data, decode_instruction_type},
         pod::PodAccount,
     },
 };
@@ -48,6 +51,76 @@ fn process_toggle_required_memo_transfers(
     Ok(())
 }
 
+/// Toggle the `OutgoingMemoTransfer` extension, initializing the extension if
+/// not already present.
+fn process_toggle_required_outgoing_memo_transfers(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    enable: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+    check_program_account(token_account_info.owner)?;
+
+    let mut account_data = token_account_info.data.borrow_mut();
+    let mut account = PodStateWithExtensionsMut::<PodAccount>::unpack(&mut account_data)?;
+
+    Processor::validate_owner(
+        program_id,
+        &account.base.owner,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = if let Ok(extension) = account.get_extension_mut::<OutgoingMemoTransfer>() {
+        extension
+    } else {
+        account.init_extension::<OutgoingMemoTransfer>(true)?
+    };
+    extension.require_outgoing_transfer_memos = enable.into();
+    Ok(())
+}
+
+/// Set the memo policy, initializing the `MemoTransferPolicy` extension if
+/// not already present.
+fn process_set_policy(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    policy: &MemoTransferPolicy,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+    check_program_account(token_account_info.owner)?;
+
+    policy
+        .prefix()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let mut account_data = token_account_info.data.borrow_mut();
+    let mut account = PodStateWithExtensionsMut::<PodAccount>::unpack(&mut account_data)?;
+
+    Processor::validate_owner(
+        program_id,
+        &account.base.owner,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = if let Ok(extension) = account.get_extension_mut::<MemoTransferPolicy>() {
+        extension
+    } else {
+        account.init_extension::<MemoTransferPolicy>(true)?
+    };
+    *extension = *policy;
+    Ok(())
+}
+
 pub(crate) fn process_instruction(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -64,5 +137,18 @@ pub(crate) fn process_instruction(
             msg!("RequiredMemoTransfersInstruction::Disable");
             process_toggle_required_memo_transfers(program_id, accounts, false /* disable */)
         }
+        RequiredMemoTransfersInstruction::SetPolicy => {
+            msg!("RequiredMemoTransfersInstruction::SetPolicy");
+            let policy = decode_instruction_data::<MemoTransferPolicy>(input)?;
+            process_set_policy(program_id, accounts, policy)
+        }
+        RequiredMemoTransfersInstruction::EnableOutgoing => {
+            msg!("RequiredMemoTransfersInstruction::EnableOutgoing");
+            process_toggle_required_outgoing_memo_transfers(program_id, accounts, true)
+        }
+        RequiredMemoTransfersInstruction::DisableOutgoing => {
+            msg!("RequiredMemoTransfersInstruction::DisableOutgoing");
+            process_toggle_required_outgoing_memo_transfers(program_id, accounts, false)
+        }
     }
 }
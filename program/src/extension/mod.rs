@@ -24,6 +24,8 @@ pub mod metadata_pointer;
 pub mod mint_close_authority;
 /// Non Transferable extension
 pub mod non_transferable;
+/// Partial freeze extension
+pub mod partial_freeze;
 /// Pausable extension
 pub mod pausable;
 /// Permanent Delegate extension
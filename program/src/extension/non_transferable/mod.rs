@@ -1,4 +1,7 @@
-#![deprecated(
+/// Instruction processor for the non-transferable extension
+pub mod processor;
+
+#[deprecated(
     since = "9.1.0",
     note = "Use spl_token_2022_interface instead and remove spl_token_2022 as a dependency"
 )]
@@ -0,0 +1,71 @@
+use {
+    crate::processor::{Processor, TransferInstruction},
+    solana_account_info::{next_account_info, AccountInfo},
+    solana_address::Address,
+    solana_msg::msg,
+    solana_program_error::{ProgramError, ProgramResult},
+    spl_token_2022_interface::{
+        check_program_account,
+        extension::{
+            non_transferable::{
+                instruction::{
+                    InitializeRecoveryAuthorityInstructionData, NonTransferableInstruction,
+                    RecoveryTransferInstructionData,
+                },
+                NonTransferableRecovery,
+            },
+            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        pod::PodMint,
+    },
+};
+
+fn process_initialize_recovery_authority(
+    _program_id: &Address,
+    accounts: &[AccountInfo],
+    authority: &Address,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+
+    let extension = mint.init_extension::<NonTransferableRecovery>(true)?;
+    extension.authority = Some(*authority)
+        .try_into()
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    Ok(())
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match decode_instruction_type(input)? {
+        NonTransferableInstruction::InitializeRecoveryAuthority => {
+            msg!("NonTransferableInstruction::InitializeRecoveryAuthority");
+            let InitializeRecoveryAuthorityInstructionData { authority } =
+                decode_instruction_data(input)?;
+            process_initialize_recovery_authority(program_id, accounts, authority)
+        }
+        NonTransferableInstruction::RecoveryTransfer => {
+            msg!("NonTransferableInstruction::RecoveryTransfer");
+            let data = decode_instruction_data::<RecoveryTransferInstructionData>(input)?;
+            Processor::process_transfer(
+                program_id,
+                accounts,
+                data.amount.into(),
+                TransferInstruction::Recovery {
+                    decimals: data.decimals,
+                },
+            )
+        }
+    }
+}
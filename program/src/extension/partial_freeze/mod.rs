@@ -0,0 +1,2 @@
+/// Instruction processor for the partial freeze extension
+pub mod processor;
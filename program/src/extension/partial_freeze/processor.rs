@@ -0,0 +1,158 @@
+use {
+    crate::processor::Processor,
+    solana_account_info::{next_account_info, AccountInfo},
+    solana_address::Address,
+    solana_msg::msg,
+    solana_program_error::{ProgramError, ProgramResult},
+    spl_token_2022_interface::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            partial_freeze::{
+                instruction::{PartialFreezeInstruction, RestrictionsInstructionData},
+                valid_restrictions, PartialFreezeAccount, PartialFreezeConfig,
+            },
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
+            PodStateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        pod::{PodAccount, PodCOption, PodMint},
+    },
+};
+
+fn check_valid_restrictions(restrictions: u8) -> ProgramResult {
+    if valid_restrictions(restrictions) {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
+
+fn validate_freeze_authority(
+    program_id: &Address,
+    mint: &PodMint,
+    freeze_authority_info: &AccountInfo,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    match &mint.freeze_authority {
+        PodCOption {
+            option: PodCOption::<Address>::SOME,
+            value: freeze_authority,
+        } => Processor::validate_owner(
+            program_id,
+            freeze_authority,
+            freeze_authority_info,
+            freeze_authority_info.data_len(),
+            signers,
+        ),
+        _ => Err(TokenError::MintCannotFreeze.into()),
+    }
+}
+
+fn process_initialize(
+    accounts: &[AccountInfo],
+    data: &RestrictionsInstructionData,
+) -> ProgramResult {
+    check_valid_restrictions(data.restrictions)?;
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+    let extension = mint.init_extension::<PartialFreezeConfig>(true)?;
+    extension.default_restrictions = data.restrictions;
+    extension.default_reason = data.reason;
+    Ok(())
+}
+
+fn process_update_default_restrictions(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    data: &RestrictionsInstructionData,
+) -> ProgramResult {
+    check_valid_restrictions(data.restrictions)?;
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let freeze_authority_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    validate_freeze_authority(
+        program_id,
+        mint.base,
+        freeze_authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = mint.get_extension_mut::<PartialFreezeConfig>()?;
+    extension.default_restrictions = data.restrictions;
+    extension.default_reason = data.reason;
+    Ok(())
+}
+
+fn process_set_account_restrictions(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    data: &RestrictionsInstructionData,
+) -> ProgramResult {
+    check_valid_restrictions(data.restrictions)?;
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let freeze_authority_info = next_account_info(account_info_iter)?;
+    check_program_account(token_account_info.owner)?;
+    check_program_account(mint_info.owner)?;
+
+    let mut token_account_data = token_account_info.data.borrow_mut();
+    let mut token_account =
+        PodStateWithExtensionsMut::<PodAccount>::unpack(&mut token_account_data)?;
+    if token_account.base.is_native() {
+        return Err(TokenError::NativeNotSupported.into());
+    }
+    if mint_info.key != &token_account.base.mint {
+        return Err(TokenError::MintMismatch.into());
+    }
+
+    let mint_data = mint_info.data.borrow();
+    let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+    mint.get_extension::<PartialFreezeConfig>()?;
+    validate_freeze_authority(
+        program_id,
+        mint.base,
+        freeze_authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = token_account.get_extension_mut::<PartialFreezeAccount>()?;
+    extension.restrictions = data.restrictions;
+    extension.reason = data.reason;
+    Ok(())
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match decode_instruction_type(input)? {
+        PartialFreezeInstruction::Initialize => {
+            msg!("PartialFreezeInstruction::Initialize");
+            let data = decode_instruction_data::<RestrictionsInstructionData>(input)?;
+            process_initialize(accounts, data)
+        }
+        PartialFreezeInstruction::UpdateDefaultRestrictions => {
+            msg!("PartialFreezeInstruction::UpdateDefaultRestrictions");
+            let data = decode_instruction_data::<RestrictionsInstructionData>(input)?;
+            process_update_default_restrictions(program_id, accounts, data)
+        }
+        PartialFreezeInstruction::SetAccountRestrictions => {
+            msg!("PartialFreezeInstruction::SetAccountRestrictions");
+            let data = decode_instruction_data::<RestrictionsInstructionData>(input)?;
+            process_set_account_restrictions(program_id, accounts, data)
+        }
+    }
+}
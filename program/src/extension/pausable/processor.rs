@@ -2,17 +2,24 @@ use {
     crate::processor::Processor,
     solana_account_info::{next_account_info, AccountInfo},
     solana_address::Address,
+    solana_clock::Clock,
     solana_msg::msg,
     solana_program_error::{ProgramError, ProgramResult},
+    solana_sysvar::Sysvar,
     spl_token_2022_interface::{
         check_program_account,
         error::TokenError,
         extension::{
+            alloc_and_serialize,
             pausable::{
-                instruction::{InitializeInstructionData, PausableInstruction},
-                PausableConfig,
+                instruction::{
+                    InitializeInstructionData, OperationsInstructionData, PausableInstruction,
+                    SchedulePauseInstructionData,
+                },
+                PausableConfig, PausableSchedule, PausedOperations,
             },
-            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
+            PodStateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
         pod::PodMint,
@@ -66,6 +73,158 @@ fn process_toggle_pause(
     )?;
 
     extension.paused = pause.into();
+    if !pause {
+        if let Ok(schedule) = mint.get_extension_mut::<PausableSchedule>() {
+            *schedule = PausableSchedule::default();
+        }
+        if let Ok(paused_operations) = mint.get_extension_mut::<PausedOperations>() {
+            *paused_operations = PausedOperations::default();
+        }
+    }
+    Ok(())
+}
+
+/// Check the pause authority on a mint whose data is not yet borrowed, so
+/// that the mint can be reallocated afterwards
+fn validate_pause_authority(
+    program_id: &Address,
+    mint_account_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    let mint_data = mint_account_info.try_borrow_data()?;
+    let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+    let extension = mint.get_extension::<PausableConfig>()?;
+    let maybe_authority: Option<Address> = extension.authority.into();
+    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        authority_info,
+        authority_info.data_len(),
+        signers,
+    )
+}
+
+/// Schedule a window during which minting / burning / transferring is paused
+fn process_schedule_pause(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    data: &SchedulePauseInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let schedule = PausableSchedule {
+        pause_timestamp: data.pause_timestamp,
+        resume_timestamp: data.resume_timestamp,
+    };
+    if !schedule.is_valid() {
+        return Err(TokenError::InvalidPauseSchedule.into());
+    }
+
+    validate_pause_authority(
+        program_id,
+        mint_account_info,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    // Assumes that there's enough SOL for the new rent-exemption
+    alloc_and_serialize::<PodMint, PausableSchedule>(mint_account_info, &schedule, true)
+}
+
+/// Pause individual operations on the mint
+fn process_pause_operations(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    operations: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    if !PausedOperations::is_valid(operations) {
+        return Err(TokenError::InvalidPausedOperations.into());
+    }
+
+    validate_pause_authority(
+        program_id,
+        mint_account_info,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let previous_operations = {
+        let mint_data = mint_account_info.try_borrow_data()?;
+        let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+        mint.get_extension::<PausedOperations>()
+            .map(|paused_operations| paused_operations.operations)
+            .unwrap_or_default()
+    };
+    let paused_operations = PausedOperations {
+        operations: previous_operations | operations,
+    };
+
+    // Assumes that there's enough SOL for the new rent-exemption
+    alloc_and_serialize::<PodMint, PausedOperations>(mint_account_info, &paused_operations, true)
+}
+
+/// Resume individual operations on the mint
+fn process_resume_operations(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    operations: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    if !PausedOperations::is_valid(operations) {
+        return Err(TokenError::InvalidPausedOperations.into());
+    }
+
+    validate_pause_authority(
+        program_id,
+        mint_account_info,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    if let Ok(paused_operations) = mint.get_extension_mut::<PausedOperations>() {
+        paused_operations.operations &= !operations;
+    }
+    Ok(())
+}
+
+/// Check that the given operation is not paused on the mint, either by the
+/// pause authority or by a scheduled pause window
+pub(crate) fn check_not_paused<BSE: BaseStateWithExtensions<PodMint>>(
+    mint: &BSE,
+    operation: u8,
+) -> ProgramResult {
+    if let Ok(extension) = mint.get_extension::<PausableConfig>() {
+        if extension.paused.into() {
+            return Err(TokenError::MintPaused.into());
+        }
+    }
+    if let Ok(paused_operations) = mint.get_extension::<PausedOperations>() {
+        if paused_operations.is_paused(operation) {
+            return Err(TokenError::MintPaused.into());
+        }
+    }
+    if let Ok(schedule) = mint.get_extension::<PausableSchedule>() {
+        if schedule.is_paused(Clock::get()?.unix_timestamp) {
+            return Err(TokenError::MintPaused.into());
+        }
+    }
     Ok(())
 }
 
@@ -90,5 +249,20 @@ pub(crate) fn process_instruction(
             msg!("PausableInstruction::Resume");
             process_toggle_pause(program_id, accounts, false /* resume */)
         }
+        PausableInstruction::SchedulePause => {
+            msg!("PausableInstruction::SchedulePause");
+            let data = decode_instruction_data::<SchedulePauseInstructionData>(input)?;
+            process_schedule_pause(program_id, accounts, data)
+        }
+        PausableInstruction::PauseOperations => {
+            msg!("PausableInstruction::PauseOperations");
+            let OperationsInstructionData { operations } = decode_instruction_data(input)?;
+            process_pause_operations(program_id, accounts, *operations)
+        }
+        PausableInstruction::ResumeOperations => {
+            msg!("PausableInstruction::ResumeOperations");
+            let OperationsInstructionData { operations } = decode_instruction_data(input)?;
+            process_resume_operations(program_id, accounts, *operations)
+        }
     }
 }
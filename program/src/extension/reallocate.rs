@@ -68,6 +68,24 @@ pub fn process_reallocate(
         return Ok(());
     }
 
+    resize_token_account(
+        token_account_info,
+        payer_info,
+        system_program_info,
+        needed_account_len,
+        native_token_amount,
+    )
+}
+
+/// Grow a token account to `needed_account_len`, with the payer funding any
+/// additional rent
+pub(crate) fn resize_token_account<'a>(
+    token_account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    needed_account_len: usize,
+    native_token_amount: Option<u64>,
+) -> ProgramResult {
     // reallocate
     msg!(
         "account needs resize, +{:?} bytes",
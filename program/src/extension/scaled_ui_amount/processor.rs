@@ -5,20 +5,24 @@ use {
     solana_clock::Clock,
     solana_msg::msg,
     solana_nullable::MaybeNull,
-    solana_program_error::ProgramResult,
+    solana_program_error::{ProgramError, ProgramResult},
     solana_sysvar::Sysvar,
     spl_token_2022_interface::{
         check_program_account,
         error::TokenError,
         extension::{
+            alloc_and_serialize_variable_len_extension,
             scaled_ui_amount::{
                 instruction::{
-                    InitializeInstructionData, ScaledUiAmountMintInstruction,
-                    UpdateMultiplierInstructionData,
+                    CancelScheduledMultiplierInstructionData, InitializeInstructionData,
+                    ScaledUiAmountMintInstruction, UpdateMultiplierInstructionData,
+                    UpdateRationalMultiplierInstructionData,
                 },
-                PodF64, ScaledUiAmountConfig, UnixTimestamp,
+                PodF64, RationalMultiplier, ScaledUiAmountConfig, ScaledUiAmountRationalConfig,
+                ScaledUiAmountSchedule, ScheduledMultiplier, UnixTimestamp,
             },
-            BaseStateWithExtensionsMut, PodStateWithExtensionsMut,
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
+            PodStateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
         pod::PodMint,
@@ -34,6 +38,14 @@ fn try_validate_multiplier(multiplier: &PodF64) -> ProgramResult {
     }
 }
 
+fn try_validate_rational_multiplier(multiplier: &RationalMultiplier) -> ProgramResult {
+    if multiplier.is_valid() {
+        Ok(())
+    } else {
+        Err(TokenError::InvalidScale.into())
+    }
+}
+
 fn process_initialize(
     _program_id: &Address,
     accounts: &[AccountInfo],
@@ -70,6 +82,12 @@ fn process_update_multiplier(
 
     let mut mint_data = mint_account_info.data.borrow_mut();
     let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    if mint.get_extension::<ScaledUiAmountRationalConfig>().is_ok() {
+        return Err(TokenError::RationalMultiplierRequired.into());
+    }
+    if mint.get_extension_bytes::<ScaledUiAmountSchedule>().is_ok() {
+        return Err(TokenError::MultiplierScheduleRequired.into());
+    }
     let extension = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
     let authority =
         Option::<Address>::from(extension.authority).ok_or(TokenError::NoAuthorityExists)?;
@@ -107,6 +125,208 @@ fn process_update_multiplier(
     Ok(())
 }
 
+fn process_initialize_rational(
+    _program_id: &Address,
+    accounts: &[AccountInfo],
+    multiplier: &RationalMultiplier,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+
+    try_validate_rational_multiplier(multiplier)?;
+    let float_multiplier = PodF64::from(multiplier.to_f64());
+    let config = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+    config.multiplier = float_multiplier;
+    config.new_multiplier_effective_timestamp = 0.into();
+    config.new_multiplier = float_multiplier;
+
+    let extension = mint.init_extension::<ScaledUiAmountRationalConfig>(true)?;
+    extension.multiplier = *multiplier;
+    extension.new_multiplier_effective_timestamp = 0.into();
+    extension.new_multiplier = *multiplier;
+    Ok(())
+}
+
+fn process_update_rational_multiplier(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    new_multiplier: &RationalMultiplier,
+    effective_timestamp: &UnixTimestamp,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let config = mint.get_extension::<ScaledUiAmountConfig>()?;
+    let authority =
+        Option::<Address>::from(config.authority).ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    try_validate_rational_multiplier(new_multiplier)?;
+    let clock = Clock::get()?;
+    let int_effective_timestamp = i64::from(*effective_timestamp);
+    // just floor it to 0
+    let effective_timestamp = if int_effective_timestamp < 0 {
+        0.into()
+    } else {
+        *effective_timestamp
+    };
+
+    let extension = mint.get_extension_mut::<ScaledUiAmountRationalConfig>()?;
+    // if the current new multiplier's timestamp has passed, set it as the old
+    if clock.unix_timestamp >= i64::from(extension.new_multiplier_effective_timestamp) {
+        extension.multiplier = extension.new_multiplier;
+    }
+    extension.new_multiplier = *new_multiplier;
+    extension.new_multiplier_effective_timestamp = effective_timestamp;
+    // if the new effective timestamp has already passed, also set the old
+    // multiplier, just to be clear
+    if clock.unix_timestamp >= int_effective_timestamp {
+        extension.multiplier = *new_multiplier;
+    }
+    let rational_config = *extension;
+
+    // keep the `f64` multipliers in sync for readers that only know about them
+    let config = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+    config.multiplier = rational_config.multiplier.to_f64().into();
+    config.new_multiplier = rational_config.new_multiplier.to_f64().into();
+    config.new_multiplier_effective_timestamp = effective_timestamp;
+
+    Ok(())
+}
+
+/// Checks the multiplier authority, and returns the mint's multiplier
+/// schedule, or a new one created from the current multipliers if `create` is
+/// set
+fn get_schedule_checked(
+    program_id: &Address,
+    mint_account_info: &AccountInfo,
+    owner_info: &AccountInfo,
+    signers: &[AccountInfo],
+    create: bool,
+) -> Result<ScaledUiAmountSchedule, ProgramError> {
+    let mint_data = mint_account_info.data.borrow();
+    let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+    if mint.get_extension::<ScaledUiAmountRationalConfig>().is_ok() {
+        return Err(TokenError::RationalMultiplierRequired.into());
+    }
+    let extension = mint.get_extension::<ScaledUiAmountConfig>()?;
+    let authority =
+        Option::<Address>::from(extension.authority).ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        owner_info,
+        owner_info.data_len(),
+        signers,
+    )?;
+
+    let has_schedule = mint.get_extension_bytes::<ScaledUiAmountSchedule>().is_ok();
+    if create && !has_schedule {
+        Ok(ScaledUiAmountSchedule::from_config(extension))
+    } else {
+        mint.get_variable_len_extension::<ScaledUiAmountSchedule>()
+    }
+}
+
+/// Writes the schedule, reallocating the mint as needed, and brings the
+/// multipliers of the config in line with it
+fn update_schedule(
+    mint_account_info: &AccountInfo,
+    schedule: &ScaledUiAmountSchedule,
+    unix_timestamp: i64,
+) -> ProgramResult {
+    // assumes that there's enough SOL for the new rent-exemption
+    alloc_and_serialize_variable_len_extension::<PodMint, _>(mint_account_info, schedule, true)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let extension = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+    schedule.update_config(extension, unix_timestamp);
+    Ok(())
+}
+
+fn process_schedule_multiplier(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    new_multiplier: &PodF64,
+    effective_timestamp: &UnixTimestamp,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut schedule = get_schedule_checked(
+        program_id,
+        mint_account_info,
+        owner_info,
+        account_info_iter.as_slice(),
+        true,
+    )?;
+
+    try_validate_multiplier(new_multiplier)?;
+    let clock = Clock::get()?;
+
+    // history can't be rewritten, so past changes take effect now
+    let effective_timestamp = if clock.unix_timestamp > i64::from(*effective_timestamp) {
+        clock.unix_timestamp.into()
+    } else {
+        *effective_timestamp
+    };
+    schedule.schedule(ScheduledMultiplier {
+        effective_timestamp,
+        multiplier: *new_multiplier,
+    });
+
+    update_schedule(mint_account_info, &schedule, clock.unix_timestamp)
+}
+
+fn process_cancel_scheduled_multiplier(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    effective_timestamp: &UnixTimestamp,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut schedule = get_schedule_checked(
+        program_id,
+        mint_account_info,
+        owner_info,
+        account_info_iter.as_slice(),
+        false,
+    )?;
+
+    let clock = Clock::get()?;
+    let effective_timestamp = i64::from(*effective_timestamp);
+    // only queued changes can be cancelled, past ones are history
+    if clock.unix_timestamp >= effective_timestamp || !schedule.cancel(effective_timestamp) {
+        msg!("No queued multiplier change at {}", effective_timestamp);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    update_schedule(mint_account_info, &schedule, clock.unix_timestamp)
+}
+
 pub(crate) fn process_instruction(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -130,5 +350,38 @@ pub(crate) fn process_instruction(
             } = decode_instruction_data(input)?;
             process_update_multiplier(program_id, accounts, multiplier, effective_timestamp)
         }
+        ScaledUiAmountMintInstruction::InitializeRational => {
+            msg!("ScaledUiAmountMintInstruction::InitializeRational");
+            let multiplier = decode_instruction_data(input)?;
+            process_initialize_rational(program_id, accounts, multiplier)
+        }
+        ScaledUiAmountMintInstruction::UpdateRationalMultiplier => {
+            msg!("ScaledUiAmountMintInstruction::UpdateRationalMultiplier");
+            let UpdateRationalMultiplierInstructionData {
+                effective_timestamp,
+                multiplier,
+            } = decode_instruction_data(input)?;
+            process_update_rational_multiplier(
+                program_id,
+                accounts,
+                multiplier,
+                effective_timestamp,
+            )
+        }
+        ScaledUiAmountMintInstruction::ScheduleMultiplier => {
+            msg!("ScaledUiAmountMintInstruction::ScheduleMultiplier");
+            let UpdateMultiplierInstructionData {
+                effective_timestamp,
+                multiplier,
+            } = decode_instruction_data(input)?;
+            process_schedule_multiplier(program_id, accounts, multiplier, effective_timestamp)
+        }
+        ScaledUiAmountMintInstruction::CancelScheduledMultiplier => {
+            msg!("ScaledUiAmountMintInstruction::CancelScheduledMultiplier");
+            let CancelScheduledMultiplierInstructionData {
+                effective_timestamp,
+            } = decode_instruction_data(input)?;
+            process_cancel_scheduled_multiplier(program_id, accounts, effective_timestamp)
+        }
     }
 }
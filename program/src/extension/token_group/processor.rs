@@ -10,10 +10,17 @@ use {
         check_program_account,
         error::TokenError,
         extension::{
-            alloc_and_serialize, group_member_pointer::GroupMemberPointer,
-            group_pointer::GroupPointer, BaseStateWithExtensions, BaseStateWithExtensionsMut,
-            PodStateWithExtensions, PodStateWithExtensionsMut,
+            alloc_and_serialize,
+            group_member_pointer::GroupMemberPointer,
+            group_pointer::GroupPointer,
+            token_group::{
+                instruction::TokenGroupExtensionInstruction, is_group_member,
+                TokenGroupMemberCounter, TokenGroupMembershipFreeze,
+            },
+            BaseState, BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
+            PodStateWithExtensionsMut,
         },
+        instruction::decode_instruction_type,
         pod::{PodCOption, PodMint},
     },
     spl_token_group_interface::{
@@ -40,6 +47,82 @@ fn check_update_authority(
     Ok(())
 }
 
+fn check_membership_not_frozen<S: BaseState>(
+    group_mint: &impl BaseStateWithExtensions<S>,
+) -> ProgramResult {
+    if group_mint
+        .get_extension::<TokenGroupMembershipFreeze>()
+        .is_ok_and(|membership| bool::from(membership.frozen))
+    {
+        return Err(TokenError::GroupMembershipFrozen.into());
+    }
+    Ok(())
+}
+
+/// Adds a member to the group, returning its member number. Once a member has
+/// left, numbers come from the member counter rather than the group size, so
+/// they're never handed out twice.
+fn add_member(group_mint: &mut PodStateWithExtensionsMut<PodMint>) -> Result<u64, ProgramError> {
+    let size = group_mint
+        .get_extension_mut::<TokenGroup>()?
+        .increment_size()?;
+    match group_mint.get_extension_mut::<TokenGroupMemberCounter>() {
+        Ok(counter) => {
+            let member_number = u64::from(counter.last_member_number)
+                .checked_add(1)
+                .ok_or(TokenError::Overflow)?;
+            counter.last_member_number = member_number.into();
+            Ok(member_number)
+        }
+        Err(_) => Ok(size),
+    }
+}
+
+/// Starts the member counter of a group that's about to lose a member, if it
+/// doesn't have one yet. Until then, the group size is the last member number
+/// handed out.
+fn init_member_counter(group_info: &AccountInfo) -> ProgramResult {
+    let size = {
+        let group_data = group_info.try_borrow_data()?;
+        let group_mint = PodStateWithExtensions::<PodMint>::unpack(&group_data)?;
+        if group_mint
+            .get_extension::<TokenGroupMemberCounter>()
+            .is_ok()
+        {
+            return Ok(());
+        }
+        u64::from(group_mint.get_extension::<TokenGroup>()?.size)
+    };
+
+    // Assumes that there's enough SOL for the new rent-exemption
+    let counter = TokenGroupMemberCounter {
+        last_member_number: size.into(),
+    };
+    alloc_and_serialize::<PodMint, TokenGroupMemberCounter>(group_info, &counter, false)
+}
+
+fn decrement_size(group: &mut TokenGroup) -> ProgramResult {
+    let size = u64::from(group.size)
+        .checked_sub(1)
+        .ok_or(TokenError::Overflow)?;
+    group.size = size.into();
+    Ok(())
+}
+
+/// Removes a member mint that's being closed from its group. A group mint
+/// that has itself been closed has no size left to update.
+pub(crate) fn remove_closed_member(group_info: &AccountInfo) -> ProgramResult {
+    if check_program_account(group_info.owner).is_err() || group_info.data_is_empty() {
+        return Ok(());
+    }
+
+    init_member_counter(group_info)?;
+    let mut group_data = group_info.try_borrow_mut_data()?;
+    let mut group_mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut group_data)?;
+    let group = group_mint.get_extension_mut::<TokenGroup>()?;
+    decrement_size(group)
+}
+
 /// Processes a [`InitializeGroup`](enum.TokenGroupInstruction.html)
 /// instruction.
 pub fn process_initialize_group(
@@ -160,7 +243,7 @@ pub fn process_initialize_member(_program_id: &Address, accounts: &[AccountInfo]
     }
 
     // scope the mint authority check, since the mint is in the same account!
-    {
+    let rejoining = {
         check_program_account(member_mint_info.owner)?;
         let member_mint_data = member_mint_info.try_borrow_data()?;
         let member_mint = PodStateWithExtensions::<PodMint>::unpack(&member_mint_data)?;
@@ -179,7 +262,12 @@ pub fn process_initialize_member(_program_id: &Address, accounts: &[AccountInfo]
             );
             return Err(TokenError::InvalidExtensionCombination.into());
         }
-    }
+
+        // a member removed from its group may join a new one
+        member_mint
+            .get_extension::<TokenGroupMember>()
+            .is_ok_and(|member| !is_group_member(member))
+    };
 
     // Make sure the member mint is not the same as the group mint
     if member_info.key == group_info.key {
@@ -189,18 +277,214 @@ pub fn process_initialize_member(_program_id: &Address, accounts: &[AccountInfo]
     // Increment the size of the group
     let mut buffer = group_info.try_borrow_mut_data()?;
     let mut state = PodStateWithExtensionsMut::<PodMint>::unpack(&mut buffer)?;
+    check_membership_not_frozen(&state)?;
     let group = state.get_extension_mut::<TokenGroup>()?;
 
     check_update_authority(group_update_authority_info, &group.update_authority)?;
-    let member_number = group.increment_size()?;
+    let member_number = add_member(&mut state)?;
 
     // Allocate a TLV entry for the space and write it in
     let member = TokenGroupMember::new(member_mint_info.key, group_info.key, member_number);
-    alloc_and_serialize::<PodMint, TokenGroupMember>(member_info, &member, false)?;
+    alloc_and_serialize::<PodMint, TokenGroupMember>(member_info, &member, rejoining)?;
 
     Ok(())
 }
 
+/// Processes a [`RemoveMember`](enum.TokenGroupExtensionInstruction.html)
+/// instruction
+pub fn process_remove_member(_program_id: &Address, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let member_info = next_account_info(account_info_iter)?;
+    let group_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    check_program_account(member_info.owner)?;
+    check_program_account(group_info.owner)?;
+
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // scope the member check, since we'll write to the member mint later
+    let member_mint_authority = {
+        let member_mint_data = member_info.try_borrow_data()?;
+        let member_mint = PodStateWithExtensions::<PodMint>::unpack(&member_mint_data)?;
+        let member = member_mint.get_extension::<TokenGroupMember>()?;
+        if member.group != *group_info.key {
+            msg!("The mint is not a member of the given group");
+            return Err(TokenError::MintMismatch.into());
+        }
+        member_mint.base.mint_authority
+    };
+
+    // Decrement the size of the group
+    init_member_counter(group_info)?;
+    let mut group_data = group_info.try_borrow_mut_data()?;
+    let mut group_mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut group_data)?;
+    check_membership_not_frozen(&group_mint)?;
+    let group = group_mint.get_extension_mut::<TokenGroup>()?;
+
+    // either the member mint authority or the group update authority may
+    // remove the member
+    if member_mint_authority != PodCOption::some(*authority_info.key) {
+        check_update_authority(authority_info, &group.update_authority)?;
+    }
+    decrement_size(group)?;
+
+    let mut member_mint_data = member_info.try_borrow_mut_data()?;
+    let mut member_mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut member_mint_data)?;
+    let member = member_mint.get_extension_mut::<TokenGroupMember>()?;
+    member.group = Address::default();
+    member.member_number = 0_u64.into();
+
+    Ok(())
+}
+
+/// Processes a [`MoveMember`](enum.TokenGroupExtensionInstruction.html)
+/// instruction
+pub fn process_move_member(_program_id: &Address, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let member_info = next_account_info(account_info_iter)?;
+    let member_mint_authority_info = next_account_info(account_info_iter)?;
+    let group_info = next_account_info(account_info_iter)?;
+    let new_group_info = next_account_info(account_info_iter)?;
+    let new_group_update_authority_info = next_account_info(account_info_iter)?;
+    check_program_account(member_info.owner)?;
+    check_program_account(group_info.owner)?;
+    check_program_account(new_group_info.owner)?;
+
+    if member_info.key == new_group_info.key {
+        return Err(TokenGroupError::MemberAccountIsGroupAccount.into());
+    }
+    if group_info.key == new_group_info.key {
+        msg!("The member is already in the new group");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // scope the member checks, since we'll write to the member mint later
+    {
+        let member_mint_data = member_info.try_borrow_data()?;
+        let member_mint = PodStateWithExtensions::<PodMint>::unpack(&member_mint_data)?;
+
+        if !member_mint_authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if member_mint.base.mint_authority != PodCOption::some(*member_mint_authority_info.key) {
+            return Err(TokenGroupError::IncorrectMintAuthority.into());
+        }
+
+        let member = member_mint.get_extension::<TokenGroupMember>()?;
+        if member.group != *group_info.key {
+            msg!("The mint is not a member of the given group");
+            return Err(TokenError::MintMismatch.into());
+        }
+    }
+
+    // Decrement the size of the current group
+    init_member_counter(group_info)?;
+    {
+        let mut group_data = group_info.try_borrow_mut_data()?;
+        let mut group_mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut group_data)?;
+        check_membership_not_frozen(&group_mint)?;
+        let group = group_mint.get_extension_mut::<TokenGroup>()?;
+        decrement_size(group)?;
+    }
+
+    // Increment the size of the new group
+    let mut new_group_data = new_group_info.try_borrow_mut_data()?;
+    let mut new_group_mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut new_group_data)?;
+    check_membership_not_frozen(&new_group_mint)?;
+    let new_group = new_group_mint.get_extension_mut::<TokenGroup>()?;
+    check_update_authority(new_group_update_authority_info, &new_group.update_authority)?;
+    let member_number = add_member(&mut new_group_mint)?;
+
+    let mut member_mint_data = member_info.try_borrow_mut_data()?;
+    let mut member_mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut member_mint_data)?;
+    let member = member_mint.get_extension_mut::<TokenGroupMember>()?;
+    member.group = *new_group_info.key;
+    member.member_number = member_number.into();
+
+    Ok(())
+}
+
+/// Processes a [`FreezeMembership`](enum.TokenGroupExtensionInstruction.html)
+/// instruction
+pub fn process_freeze_membership(_program_id: &Address, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let group_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    check_program_account(group_info.owner)?;
+
+    // scope the authority check, since we'll probably realloc the account
+    {
+        let buffer = group_info.try_borrow_data()?;
+        let state = PodStateWithExtensions::<PodMint>::unpack(&buffer)?;
+        let group = state.get_extension::<TokenGroup>()?;
+        check_update_authority(update_authority_info, &group.update_authority)?;
+    }
+
+    // Allocate the membership freeze if needed, assumes that there's enough
+    // SOL for the new rent-exemption
+    let membership = TokenGroupMembershipFreeze {
+        frozen: true.into(),
+    };
+    alloc_and_serialize::<PodMint, TokenGroupMembershipFreeze>(group_info, &membership, true)?;
+
+    Ok(())
+}
+
+/// Processes a [`ThawMembership`](enum.TokenGroupExtensionInstruction.html)
+/// instruction
+pub fn process_thaw_membership(_program_id: &Address, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let group_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    check_program_account(group_info.owner)?;
+
+    let mut buffer = group_info.try_borrow_mut_data()?;
+    let mut state = PodStateWithExtensionsMut::<PodMint>::unpack(&mut buffer)?;
+    let group = state.get_extension::<TokenGroup>()?;
+    check_update_authority(update_authority_info, &group.update_authority)?;
+
+    // a group without the extension has never been frozen
+    if let Ok(membership) = state.get_extension_mut::<TokenGroupMembershipFreeze>() {
+        membership.frozen = false.into();
+    }
+
+    Ok(())
+}
+
+/// Processes a [`TokenGroupExtensionInstruction`](enum.TokenGroupExtensionInstruction.html).
+pub(crate) fn process_extension_instruction(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match decode_instruction_type(input)? {
+        TokenGroupExtensionInstruction::RemoveMember => {
+            msg!("TokenGroupExtensionInstruction::RemoveMember");
+            process_remove_member(program_id, accounts)
+        }
+        TokenGroupExtensionInstruction::MoveMember => {
+            msg!("TokenGroupExtensionInstruction::MoveMember");
+            process_move_member(program_id, accounts)
+        }
+        TokenGroupExtensionInstruction::FreezeMembership => {
+            msg!("TokenGroupExtensionInstruction::FreezeMembership");
+            process_freeze_membership(program_id, accounts)
+        }
+        TokenGroupExtensionInstruction::ThawMembership => {
+            msg!("TokenGroupExtensionInstruction::ThawMembership");
+            process_thaw_membership(program_id, accounts)
+        }
+    }
+}
+
 /// Processes an [`Instruction`](enum.Instruction.html).
 pub fn process_instruction(
     program_id: &Address,
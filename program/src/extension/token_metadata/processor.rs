@@ -11,8 +11,14 @@ use {
         check_program_account,
         error::TokenError,
         extension::{
-            alloc_and_serialize_variable_len_extension, metadata_pointer::MetadataPointer,
-            BaseStateWithExtensions, PodStateWithExtensions,
+            alloc_and_serialize_variable_len_extension,
+            metadata_pointer::MetadataPointer,
+            token_metadata::{
+                instruction::{FieldUpdate, TokenMetadataExtensionInstruction},
+                TokenMetadataSizeLimit,
+            },
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
+            PodStateWithExtensionsMut,
         },
         pod::{PodCOption, PodMint},
     },
@@ -40,6 +46,16 @@ fn check_update_authority(
     Ok(())
 }
 
+fn check_size_limit(
+    size_limit: Option<&TokenMetadataSizeLimit>,
+    token_metadata: &TokenMetadata,
+) -> ProgramResult {
+    if let Some(size_limit) = size_limit {
+        size_limit.check(token_metadata)?;
+    }
+    Ok(())
+}
+
 /// Processes a [`Initialize`](enum.TokenMetadataInstruction.html) instruction.
 pub fn process_initialize(
     _program_id: &Address,
@@ -61,7 +77,7 @@ pub fn process_initialize(
     }
 
     // scope the mint authority check, since the mint is in the same account!
-    {
+    let size_limit = {
         check_program_account(mint_info.owner)?;
         let mint_data = mint_info.try_borrow_data()?;
         let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
@@ -77,7 +93,9 @@ pub fn process_initialize(
             msg!("A mint with metadata must have the metadata-pointer extension initialized");
             return Err(TokenError::InvalidExtensionCombination.into());
         }
-    }
+
+        mint.get_extension::<TokenMetadataSizeLimit>().ok().copied()
+    };
 
     // Create the token metadata
     let update_authority = Some(*update_authority_info.key)
@@ -91,6 +109,7 @@ pub fn process_initialize(
         mint: *mint_info.key,
         ..Default::default()
     };
+    check_size_limit(size_limit.as_ref(), &token_metadata)?;
 
     // allocate a TLV entry for the space and write it in, assumes that there's
     // enough SOL for the new rent-exemption
@@ -117,16 +136,20 @@ pub fn process_update_field(
 
     // deserialize the metadata, but scope the data borrow since we'll probably
     // realloc the account
-    let mut token_metadata = {
+    let (mut token_metadata, size_limit) = {
         let buffer = metadata_info.try_borrow_data()?;
         let mint = PodStateWithExtensions::<PodMint>::unpack(&buffer)?;
-        mint.get_variable_len_extension::<TokenMetadata>()?
+        (
+            mint.get_variable_len_extension::<TokenMetadata>()?,
+            mint.get_extension::<TokenMetadataSizeLimit>().ok().copied(),
+        )
     };
 
     check_update_authority(update_authority_info, &token_metadata.update_authority)?;
 
     // Update the field
     token_metadata.update(data.field, data.value);
+    check_size_limit(size_limit.as_ref(), &token_metadata)?;
 
     // Update / realloc the account
     alloc_and_serialize_variable_len_extension::<PodMint, _>(metadata_info, &token_metadata, true)?;
@@ -205,6 +228,82 @@ pub fn process_emit(_program_id: &Address, accounts: &[AccountInfo], data: Emit)
     Ok(())
 }
 
+/// Processes an [`InitializeSizeLimit`](enum.TokenMetadataExtensionInstruction.html)
+/// instruction.
+pub fn process_initialize_size_limit(accounts: &[AccountInfo], max_len: u32) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+    let size_limit = mint.init_extension::<TokenMetadataSizeLimit>(true)?;
+    size_limit.max_len = max_len.into();
+    Ok(())
+}
+
+/// Processes an [`UpdateFields`](enum.TokenMetadataExtensionInstruction.html)
+/// instruction.
+pub fn process_update_fields(
+    _program_id: &Address,
+    accounts: &[AccountInfo],
+    updates: Vec<FieldUpdate>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    check_program_account(metadata_info.owner)?;
+
+    // deserialize the metadata, but scope the data borrow since we'll probably
+    // realloc the account
+    let (mut token_metadata, size_limit) = {
+        let buffer = metadata_info.try_borrow_data()?;
+        let mint = PodStateWithExtensions::<PodMint>::unpack(&buffer)?;
+        (
+            mint.get_variable_len_extension::<TokenMetadata>()?,
+            mint.get_extension::<TokenMetadataSizeLimit>().ok().copied(),
+        )
+    };
+
+    check_update_authority(update_authority_info, &token_metadata.update_authority)?;
+
+    // Apply every update in memory, so the account is only reallocated once
+    for update in updates {
+        match update {
+            FieldUpdate::Set { field, value } => token_metadata.update(field, value),
+            FieldUpdate::Remove { key } => {
+                if !token_metadata.remove_key(&key) {
+                    return Err(TokenMetadataError::KeyNotFound.into());
+                }
+            }
+        }
+    }
+    check_size_limit(size_limit.as_ref(), &token_metadata)?;
+
+    alloc_and_serialize_variable_len_extension::<PodMint, _>(metadata_info, &token_metadata, true)?;
+    Ok(())
+}
+
+/// Processes a [`TokenMetadataExtensionInstruction`](enum.TokenMetadataExtensionInstruction.html).
+pub(crate) fn process_extension_instruction(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+
+    match TokenMetadataExtensionInstruction::unpack(input)? {
+        TokenMetadataExtensionInstruction::InitializeSizeLimit { max_len } => {
+            msg!("TokenMetadataExtensionInstruction::InitializeSizeLimit");
+            process_initialize_size_limit(accounts, max_len)
+        }
+        TokenMetadataExtensionInstruction::UpdateFields { updates } => {
+            msg!("TokenMetadataExtensionInstruction::UpdateFields");
+            process_update_fields(program_id, accounts, updates)
+        }
+    }
+}
+
 /// Processes an [`Instruction`](enum.Instruction.html).
 pub fn process_instruction(
     program_id: &Address,
@@ -1,5 +1,8 @@
 use {
-    crate::processor::{Processor, TransferInstruction},
+    crate::{
+        extension::reallocate::resize_token_account,
+        processor::{Processor, TransferInstruction},
+    },
     solana_account_info::{next_account_info, AccountInfo},
     solana_address::Address,
     solana_clock::Clock,
@@ -12,13 +15,16 @@ use {
         error::TokenError,
         extension::{
             transfer_fee::{
-                instruction::TransferFeeInstruction, TransferFee, TransferFeeAmount,
-                TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                instruction::TransferFeeInstruction, TieredTransferFee, TieredTransferFeeConfig,
+                TransferFee, TransferFeeAmount, TransferFeeCollector, TransferFeeConfig,
+                TransferFeeExemption, TransferFeeTier, MAX_FEE_BASIS_POINTS,
+                MAX_TRANSFER_FEE_TIERS,
             },
-            BaseStateWithExtensions, BaseStateWithExtensionsMut, PodStateWithExtensions,
-            PodStateWithExtensionsMut,
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
+            PodStateWithExtensions, PodStateWithExtensionsMut,
         },
         pod::{PodAccount, PodMint},
+        state::Account,
     },
 };
 
@@ -114,6 +120,113 @@ fn process_set_transfer_fee(
     Ok(())
 }
 
+/// Build a tiered schedule starting at `epoch`, checking that the tiers start
+/// at 0, are sorted by strictly increasing minimum amount and have valid rates
+fn tiered_transfer_fee(
+    epoch: u64,
+    maximum_fee: u64,
+    minimum_fee: u64,
+    tier_count: u8,
+    tiers: [TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+) -> Result<TieredTransferFee, ProgramError> {
+    let transfer_fee = TieredTransferFee {
+        epoch: epoch.into(),
+        maximum_fee: maximum_fee.into(),
+        minimum_fee: minimum_fee.into(),
+        tier_count,
+        tiers,
+    };
+    let tiers = transfer_fee.tiers();
+    if tiers.is_empty()
+        || tiers.len() != tier_count as usize
+        || u64::from(tiers[0].minimum_amount) != 0
+        || tiers
+            .windows(2)
+            .any(|pair| u64::from(pair[0].minimum_amount) >= u64::from(pair[1].minimum_amount))
+        || minimum_fee > maximum_fee
+    {
+        return Err(TokenError::InvalidTransferFeeTiers.into());
+    }
+    if tiers
+        .iter()
+        .any(|tier| u16::from(tier.transfer_fee_basis_points) > MAX_FEE_BASIS_POINTS)
+    {
+        return Err(TokenError::TransferFeeExceedsMaximum.into());
+    }
+    Ok(transfer_fee)
+}
+
+fn process_initialize_tiered_transfer_fee_config(
+    accounts: &[AccountInfo],
+    maximum_fee: u64,
+    minimum_fee: u64,
+    tier_count: u8,
+    tiers: [TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+    let extension = mint.init_extension::<TieredTransferFeeConfig>(true)?;
+
+    // As with the flat fee, only the newer schedule is used after init
+    let epoch = Clock::get()?.epoch;
+    let transfer_fee = tiered_transfer_fee(epoch, maximum_fee, minimum_fee, tier_count, tiers)?;
+    extension.older_transfer_fee = transfer_fee;
+    extension.newer_transfer_fee = transfer_fee;
+
+    Ok(())
+}
+
+fn process_set_tiered_transfer_fee(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    maximum_fee: u64,
+    minimum_fee: u64,
+    tier_count: u8,
+    tiers: [TransferFeeTier; MAX_TRANSFER_FEE_TIERS],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let transfer_fee_config = mint.get_extension::<TransferFeeConfig>()?;
+
+    let transfer_fee_config_authority =
+        Option::<Address>::from(transfer_fee_config.transfer_fee_config_authority)
+            .ok_or(TokenError::NoAuthorityExists)?;
+    Processor::validate_owner(
+        program_id,
+        &transfer_fee_config_authority,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    // Same rules as `SetTransferFee`, the new schedule starts two epochs ahead
+    let epoch = Clock::get()?.epoch;
+    let transfer_fee = tiered_transfer_fee(
+        epoch.saturating_add(2),
+        maximum_fee,
+        minimum_fee,
+        tier_count,
+        tiers,
+    )?;
+    let extension = mint.get_extension_mut::<TieredTransferFeeConfig>()?;
+    if u64::from(extension.newer_transfer_fee.epoch) <= epoch {
+        extension.older_transfer_fee = extension.newer_transfer_fee;
+    }
+    extension.newer_transfer_fee = transfer_fee;
+
+    Ok(())
+}
+
 fn process_withdraw_withheld_tokens_from_mint(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -282,6 +395,129 @@ fn process_withdraw_withheld_tokens_from_accounts(
     Ok(())
 }
 
+fn process_set_transfer_fee_exemption(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    exempt: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+    check_program_account(token_account_info.owner)?;
+    check_program_account(mint_account_info.owner)?;
+
+    {
+        let mint_data = mint_account_info.data.borrow();
+        let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+        let extension = mint.get_extension::<TransferFeeConfig>()?;
+        let transfer_fee_config_authority =
+            Option::<Address>::from(extension.transfer_fee_config_authority)
+                .ok_or(TokenError::NoAuthorityExists)?;
+        Processor::validate_owner(
+            program_id,
+            &transfer_fee_config_authority,
+            authority_info,
+            authority_info_data_len,
+            account_info_iter.as_slice(),
+        )?;
+    }
+
+    // make room for the extension on the first exemption
+    let needed_account_len = {
+        let token_account_data = token_account_info.data.borrow();
+        let token_account = PodStateWithExtensions::<PodAccount>::unpack(&token_account_data)?;
+        if &token_account.base.mint != mint_account_info.key {
+            return Err(TokenError::MintMismatch.into());
+        }
+        if exempt
+            && token_account
+                .get_extension::<TransferFeeExemption>()
+                .is_err()
+        {
+            let mut extension_types = token_account.get_extension_types()?;
+            extension_types.push(ExtensionType::TransferFeeExemption);
+            ExtensionType::try_calculate_account_len::<Account>(&extension_types)?
+        } else {
+            0
+        }
+    };
+    if token_account_info.data_len() < needed_account_len {
+        // fee-bearing mints are never native, so there is no reserve to sync
+        resize_token_account(
+            token_account_info,
+            payer_info,
+            system_program_info,
+            needed_account_len,
+            None,
+        )?;
+    }
+
+    let mut token_account_data = token_account_info.data.borrow_mut();
+    let mut token_account =
+        PodStateWithExtensionsMut::<PodAccount>::unpack(&mut token_account_data)?;
+    if let Ok(extension) = token_account.get_extension_mut::<TransferFeeExemption>() {
+        extension.exempt = exempt.into();
+    } else if exempt {
+        let extension = token_account.init_extension::<TransferFeeExemption>(true)?;
+        extension.exempt = true.into();
+    }
+    Ok(())
+}
+
+fn process_initialize_transfer_fee_collector(
+    accounts: &[AccountInfo],
+    collector: COption<Address>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack_uninitialized(&mut mint_data)?;
+    let extension = mint.init_extension::<TransferFeeCollector>(true)?;
+    extension.collector = collector
+        .try_into()
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    Ok(())
+}
+
+fn process_set_transfer_fee_collector(
+    program_id: &Address,
+    accounts: &[AccountInfo],
+    collector: COption<Address>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+    check_program_account(mint_account_info.owner)?;
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = PodStateWithExtensionsMut::<PodMint>::unpack(&mut mint_data)?;
+    let transfer_fee_config = mint.get_extension::<TransferFeeConfig>()?;
+
+    let transfer_fee_config_authority =
+        Option::<Address>::from(transfer_fee_config.transfer_fee_config_authority)
+            .ok_or(TokenError::NoAuthorityExists)?;
+    Processor::validate_owner(
+        program_id,
+        &transfer_fee_config_authority,
+        authority_info,
+        authority_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    let extension = mint.get_extension_mut::<TransferFeeCollector>()?;
+    extension.collector = collector
+        .try_into()
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    Ok(())
+}
+
 pub(crate) fn process_instruction(
     program_id: &Address,
     accounts: &[AccountInfo],
@@ -335,5 +571,48 @@ pub(crate) fn process_instruction(
             msg!("TransferFeeInstruction: SetTransferFee");
             process_set_transfer_fee(program_id, accounts, transfer_fee_basis_points, maximum_fee)
         }
+        TransferFeeInstruction::InitializeTieredTransferFeeConfig {
+            maximum_fee,
+            minimum_fee,
+            tier_count,
+            tiers,
+        } => {
+            msg!("TransferFeeInstruction: InitializeTieredTransferFeeConfig");
+            process_initialize_tiered_transfer_fee_config(
+                accounts,
+                maximum_fee,
+                minimum_fee,
+                tier_count,
+                tiers,
+            )
+        }
+        TransferFeeInstruction::SetTieredTransferFee {
+            maximum_fee,
+            minimum_fee,
+            tier_count,
+            tiers,
+        } => {
+            msg!("TransferFeeInstruction: SetTieredTransferFee");
+            process_set_tiered_transfer_fee(
+                program_id,
+                accounts,
+                maximum_fee,
+                minimum_fee,
+                tier_count,
+                tiers,
+            )
+        }
+        TransferFeeInstruction::SetTransferFeeExemption { exempt } => {
+            msg!("TransferFeeInstruction: SetTransferFeeExemption");
+            process_set_transfer_fee_exemption(program_id, accounts, exempt)
+        }
+        TransferFeeInstruction::InitializeTransferFeeCollector { collector } => {
+            msg!("TransferFeeInstruction: InitializeTransferFeeCollector");
+            process_initialize_transfer_fee_collector(accounts, collector)
+        }
+        TransferFeeInstruction::SetTransferFeeCollector { collector } => {
+            msg!("TransferFeeInstruction: SetTransferFeeCollector");
+            process_set_transfer_fee_collector(program_id, accounts, collector)
+        }
     }
 }
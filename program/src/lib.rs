@@ -61,43 +61,13 @@ pub fn amount_to_ui_amount_string(amount: u64, decimals: u8) -> String {
 /// Convert a raw amount to its UI representation using the given decimals field
 /// Excess zeroes or unneeded decimal point are trimmed.
 pub fn amount_to_ui_amount_string_trimmed(amount: u64, decimals: u8) -> String {
-    let s = amount_to_ui_amount_string(amount, decimals);
-    trim_ui_amount_string(s, decimals)
-}
-
-/// Trims a string number by removing excess zeroes or unneeded decimal point
-fn trim_ui_amount_string(mut ui_amount: String, decimals: u8) -> String {
-    if decimals > 0 {
-        let zeros_trimmed = ui_amount.trim_end_matches('0');
-        ui_amount = zeros_trimmed.trim_end_matches('.').to_string();
-    }
-    ui_amount
+    spl_token_2022_interface::ui_amount::amount_to_ui_amount_string_trimmed(amount, decimals)
 }
 
 /// Try to convert a UI representation of a token amount to its raw amount using
 /// the given decimals field
 pub fn try_ui_amount_into_amount(ui_amount: String, decimals: u8) -> Result<u64, ProgramError> {
-    let decimals = decimals as usize;
-    let mut parts = ui_amount.split('.');
-    // splitting a string, even an empty one, will always yield an iterator of at
-    // least length == 1
-    let mut amount_str = parts.next().unwrap().to_string();
-    let after_decimal = parts.next().unwrap_or("");
-    let after_decimal = after_decimal.trim_end_matches('0');
-    if (amount_str.is_empty() && after_decimal.is_empty())
-        || parts.next().is_some()
-        || after_decimal.len() > decimals
-    {
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    amount_str.push_str(after_decimal);
-    for _ in 0..decimals.saturating_sub(after_decimal.len()) {
-        amount_str.push('0');
-    }
-    amount_str
-        .parse::<u64>()
-        .map_err(|_| ProgramError::InvalidArgument)
+    spl_token_2022_interface::ui_amount::try_ui_amount_into_amount(&ui_amount, decimals)
 }
 
 /// Checks that the supplied program ID is correct for the ZK ElGamal proof
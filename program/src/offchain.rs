@@ -3,16 +3,79 @@
 pub use spl_transfer_hook_interface::offchain::{AccountDataResult, AccountFetchError};
 use {
     solana_address::Address,
-    solana_instruction::Instruction,
+    solana_instruction::{AccountMeta, Instruction},
     solana_program_error::ProgramError,
     spl_token_2022_interface::{
-        extension::{transfer_fee, transfer_hook, StateWithExtensions},
+        extension::{
+            transfer_fee::{self, instruction::TransferFeeInstruction},
+            transfer_hook, StateWithExtensions,
+        },
+        instruction::{batch, TokenInstruction},
         state::Mint,
     },
     spl_transfer_hook_interface::offchain::add_extra_account_metas_for_execute,
-    std::future::Future,
+    std::{
+        cell::RefCell,
+        collections::HashMap,
+        fmt,
+        future::{ready, Future, Ready},
+        pin::pin,
+        task::{Context, Poll, Waker},
+    },
 };
 
+/// Account data captured ahead of time, keyed by address, used to resolve
+/// required accounts without network access
+pub type AccountDataSnapshot = HashMap<Address, Vec<u8>>;
+
+/// Errors that can occur while resolving required accounts from an
+/// `AccountDataSnapshot`
+#[derive(Debug)]
+pub enum OfflineResolutionError {
+    /// Resolution failed, and the snapshot did not contain the data for some
+    /// of the requested accounts. Resolution probes every account it adds, so
+    /// not all of them may be needed, and the failure may be unrelated to
+    /// them, see `error`.
+    MissingAccounts {
+        /// Addresses absent from the snapshot, in the order they were
+        /// requested. Capture them and try again.
+        addresses: Vec<Address>,
+        /// The error that stopped resolution
+        error: AccountFetchError,
+    },
+    /// The snapshot contained all requested accounts, but resolution failed
+    Resolution(AccountFetchError),
+}
+
+impl fmt::Display for OfflineResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAccounts { addresses, error } => {
+                write!(f, "{error}, missing account data for:")?;
+                for address in addresses {
+                    write!(f, " {address}")?;
+                }
+                Ok(())
+            }
+            Self::Resolution(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for OfflineResolutionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingAccounts { error, .. } | Self::Resolution(error) => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<ProgramError> for OfflineResolutionError {
+    fn from(err: ProgramError) -> Self {
+        Self::Resolution(err.into())
+    }
+}
+
 /// Offchain helper to create a `TransferChecked` instruction with all
 /// additional required account metas for a transfer, including the ones
 /// required by the transfer hook.
@@ -143,7 +206,7 @@ where
 }
 
 /// Offchain helper to add required account metas to an instruction, including
-/// the ones required by the transfer hook.
+/// the ones required by the transfer hook and the mint's fee collector.
 ///
 /// To be client-agnostic and to avoid pulling in the full solana-sdk, this
 /// simply takes a function that will return its data as `Future<Vec<u8>>` for
@@ -202,9 +265,275 @@ where
         .await?;
     }
 
+    if let Some(fee_collector) = transfer_fee::get_fee_collector(&mint) {
+        match instruction
+            .accounts
+            .iter_mut()
+            .find(|meta| meta.pubkey == fee_collector)
+        {
+            Some(meta) => meta.is_writable = true,
+            None => instruction
+                .accounts
+                .push(AccountMeta::new(fee_collector, false)),
+        }
+    }
+
     Ok(())
 }
 
+/// Offline variant of `create_transfer_checked_instruction_with_extra_metas`,
+/// resolving the transfer hook accounts from `snapshot` rather than fetching
+/// them.
+///
+/// If resolution fails while accounts are absent from the snapshot, returns
+/// `OfflineResolutionError::MissingAccounts` with the addresses to capture.
+#[allow(clippy::too_many_arguments)]
+pub fn create_transfer_checked_instruction_with_extra_metas_offline(
+    token_program_id: &Address,
+    source_pubkey: &Address,
+    mint_pubkey: &Address,
+    destination_pubkey: &Address,
+    authority_pubkey: &Address,
+    signer_pubkeys: &[&Address],
+    amount: u64,
+    decimals: u8,
+    snapshot: &AccountDataSnapshot,
+) -> Result<Instruction, OfflineResolutionError> {
+    let mut transfer_instruction = crate::instruction::transfer_checked(
+        token_program_id,
+        source_pubkey,
+        mint_pubkey,
+        destination_pubkey,
+        authority_pubkey,
+        signer_pubkeys,
+        amount,
+        decimals,
+    )?;
+
+    add_extra_account_metas_offline(
+        &mut transfer_instruction,
+        source_pubkey,
+        mint_pubkey,
+        destination_pubkey,
+        authority_pubkey,
+        amount,
+        snapshot,
+    )?;
+
+    Ok(transfer_instruction)
+}
+
+/// Offline variant of
+/// `create_transfer_checked_with_fee_instruction_with_extra_metas`, resolving
+/// the transfer hook accounts from `snapshot` rather than fetching them.
+///
+/// If resolution fails while accounts are absent from the snapshot, returns
+/// `OfflineResolutionError::MissingAccounts` with the addresses to capture.
+#[allow(clippy::too_many_arguments)]
+pub fn create_transfer_checked_with_fee_instruction_with_extra_metas_offline(
+    token_program_id: &Address,
+    source_pubkey: &Address,
+    mint_pubkey: &Address,
+    destination_pubkey: &Address,
+    authority_pubkey: &Address,
+    signer_pubkeys: &[&Address],
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+    snapshot: &AccountDataSnapshot,
+) -> Result<Instruction, OfflineResolutionError> {
+    let mut transfer_instruction = transfer_fee::instruction::transfer_checked_with_fee(
+        token_program_id,
+        source_pubkey,
+        mint_pubkey,
+        destination_pubkey,
+        authority_pubkey,
+        signer_pubkeys,
+        amount,
+        decimals,
+        fee,
+    )?;
+
+    add_extra_account_metas_offline(
+        &mut transfer_instruction,
+        source_pubkey,
+        mint_pubkey,
+        destination_pubkey,
+        authority_pubkey,
+        amount,
+        snapshot,
+    )?;
+
+    Ok(transfer_instruction)
+}
+
+/// Offline variant of `add_extra_account_metas`, resolving the transfer hook
+/// accounts from `snapshot` rather than fetching them.
+///
+/// The mint, the transfer hook validation account, and any account whose data
+/// is used as a seed must be present in the snapshot; other extra accounts may
+/// be absent. If resolution fails, the missing accounts reported are the
+/// absent ones looked up before it stopped. Since later accounts may depend
+/// on earlier ones, more may be reported after capturing them.
+pub fn add_extra_account_metas_offline(
+    instruction: &mut Instruction,
+    source_pubkey: &Address,
+    mint_pubkey: &Address,
+    destination_pubkey: &Address,
+    authority_pubkey: &Address,
+    amount: u64,
+    snapshot: &AccountDataSnapshot,
+) -> Result<(), OfflineResolutionError> {
+    let missing = RefCell::new(Vec::new());
+    let result = poll_ready(add_extra_account_metas(
+        instruction,
+        source_pubkey,
+        mint_pubkey,
+        destination_pubkey,
+        authority_pubkey,
+        amount,
+        snapshot_fetch_fn(snapshot, &missing),
+    ));
+    check_missing_accounts(missing, result)
+}
+
+/// Offchain helper to create a `Batch` instruction from the given token
+/// instructions, adding the accounts required by the transfer hook to every
+/// `TransferChecked` and `TransferCheckedWithFee` instruction in the batch.
+///
+/// The inner instructions must not already contain the transfer hook
+/// accounts. The account count written for each inner instruction includes
+/// the accounts added for it, as expected by the program.
+///
+/// Uses the same `fetch_account_data_fn` as
+/// `create_transfer_checked_instruction_with_extra_metas`:
+///
+/// ```rust,ignore
+/// let instruction = create_batch_instruction_with_extra_metas(
+///     &spl_token_2022::id(),
+///     &[transfer_instruction_1, transfer_instruction_2, burn_instruction],
+///     |address| self.client.get_account(&address).map_ok(|opt| opt.map(|acc| acc.data)),
+/// )
+/// .await?
+/// ```
+pub async fn create_batch_instruction_with_extra_metas<F, Fut>(
+    token_program_id: &Address,
+    instructions: &[Instruction],
+    fetch_account_data_fn: F,
+) -> Result<Instruction, AccountFetchError>
+where
+    F: Fn(Address) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    let mut resolved_instructions = Vec::with_capacity(instructions.len());
+    for instruction in instructions {
+        let mut instruction = instruction.clone();
+        if instruction.program_id == *token_program_id {
+            if let Some(amount) = checked_transfer_amount(&instruction.data)? {
+                let [source, mint, destination, authority] = match instruction.accounts.get(..4) {
+                    Some([source, mint, destination, authority]) => [
+                        source.pubkey,
+                        mint.pubkey,
+                        destination.pubkey,
+                        authority.pubkey,
+                    ],
+                    _ => return Err(ProgramError::NotEnoughAccountKeys.into()),
+                };
+                add_extra_account_metas(
+                    &mut instruction,
+                    &source,
+                    &mint,
+                    &destination,
+                    &authority,
+                    amount,
+                    &fetch_account_data_fn,
+                )
+                .await?;
+            }
+        }
+        resolved_instructions.push(instruction);
+    }
+
+    Ok(batch(token_program_id, &resolved_instructions)?)
+}
+
+/// Offline variant of `create_batch_instruction_with_extra_metas`, resolving
+/// the transfer hook accounts from `snapshot` rather than fetching them.
+///
+/// If resolution fails while accounts are absent from the snapshot, returns
+/// `OfflineResolutionError::MissingAccounts` with the addresses to capture.
+pub fn create_batch_instruction_with_extra_metas_offline(
+    token_program_id: &Address,
+    instructions: &[Instruction],
+    snapshot: &AccountDataSnapshot,
+) -> Result<Instruction, OfflineResolutionError> {
+    let missing = RefCell::new(Vec::new());
+    let result = poll_ready(create_batch_instruction_with_extra_metas(
+        token_program_id,
+        instructions,
+        snapshot_fetch_fn(snapshot, &missing),
+    ));
+    check_missing_accounts(missing, result)
+}
+
+/// Returns the amount of a `TransferChecked` or `TransferCheckedWithFee`
+/// instruction, the only instructions that invoke the transfer hook
+fn checked_transfer_amount(data: &[u8]) -> Result<Option<u64>, ProgramError> {
+    Ok(match TokenInstruction::unpack(data)? {
+        TokenInstruction::TransferChecked { amount, .. } => Some(amount),
+        TokenInstruction::TransferFeeExtension => {
+            match TransferFeeInstruction::unpack(&data[1..])? {
+                TransferFeeInstruction::TransferCheckedWithFee { amount, .. } => Some(amount),
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
+
+/// Fetch function reading from `snapshot`, recording every requested address
+/// that it does not contain. Resolution probes every account it adds, so most
+/// of these are only needed if resolution then fails.
+fn snapshot_fetch_fn<'a>(
+    snapshot: &'a AccountDataSnapshot,
+    missing: &'a RefCell<Vec<Address>>,
+) -> impl Fn(Address) -> Ready<AccountDataResult> + 'a {
+    move |address| {
+        let data = snapshot.get(&address).cloned();
+        if data.is_none() && !missing.borrow().contains(&address) {
+            missing.borrow_mut().push(address);
+        }
+        ready(Ok(data))
+    }
+}
+
+/// Reports the missing accounts along with the error if resolution failed,
+/// since an absent account only matters when resolution could not go on
+/// without its data
+fn check_missing_accounts<T>(
+    missing: RefCell<Vec<Address>>,
+    result: Result<T, AccountFetchError>,
+) -> Result<T, OfflineResolutionError> {
+    result.map_err(|error| {
+        let addresses = missing.into_inner();
+        if addresses.is_empty() {
+            OfflineResolutionError::Resolution(error)
+        } else {
+            OfflineResolutionError::MissingAccounts { addresses, error }
+        }
+    })
+}
+
+/// Drives a future whose only await points are already-resolved lookups
+fn poll_ready<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("snapshot lookups never suspend"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -218,6 +547,9 @@ mod tests {
         spl_tlv_account_resolution::{
             account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
         },
+        spl_token_2022_interface::extension::transfer_fee::{
+            TransferFeeCollector, TransferFeeConfig,
+        },
         spl_transfer_hook_interface::{
             get_extra_account_metas_address, instruction::ExecuteInstruction,
         },
@@ -483,4 +815,347 @@ mod tests {
 
         assert_eq!(instruction.accounts, check_metas);
     }
+
+    async fn mock_snapshot() -> AccountDataSnapshot {
+        let validate_state_pubkey =
+            get_extra_account_metas_address(&MINT_PUBKEY, &TRANSFER_HOOK_PROGRAM_ID);
+        let mut snapshot = AccountDataSnapshot::new();
+        for address in [MINT_PUBKEY, validate_state_pubkey] {
+            let data = mock_fetch_account_data_fn(address).await.unwrap().unwrap();
+            snapshot.insert(address, data);
+        }
+        snapshot
+    }
+
+    #[tokio::test]
+    async fn test_offline_resolution_matches_fetched() {
+        let source = Address::new_unique();
+        let destination = Address::new_unique();
+        let authority = Address::new_unique();
+        let amount = 100u64;
+        let fee = 1u64;
+        let snapshot = mock_snapshot().await;
+
+        let fetched = create_transfer_checked_instruction_with_extra_metas(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            mock_fetch_account_data_fn,
+        )
+        .await
+        .unwrap();
+        let offline = create_transfer_checked_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            &snapshot,
+        )
+        .unwrap();
+        assert_eq!(offline, fetched);
+
+        let fetched = create_transfer_checked_with_fee_instruction_with_extra_metas(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            fee,
+            mock_fetch_account_data_fn,
+        )
+        .await
+        .unwrap();
+        let offline = create_transfer_checked_with_fee_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            fee,
+            &snapshot,
+        )
+        .unwrap();
+        assert_eq!(offline, fetched);
+    }
+
+    #[tokio::test]
+    async fn test_offline_resolution_reports_missing_accounts() {
+        let source = Address::new_unique();
+        let destination = Address::new_unique();
+        let authority = Address::new_unique();
+        let amount = 100u64;
+        let validate_state_pubkey =
+            get_extra_account_metas_address(&MINT_PUBKEY, &TRANSFER_HOOK_PROGRAM_ID);
+
+        // Nothing captured, the mint is needed first
+        let err = create_transfer_checked_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            &AccountDataSnapshot::new(),
+        )
+        .unwrap_err();
+        let OfflineResolutionError::MissingAccounts { addresses, .. } = err else {
+            panic!("expected missing accounts, got {err}");
+        };
+        assert_eq!(addresses, vec![MINT_PUBKEY]);
+
+        // Mint captured, the validation state account is still missing
+        let mut snapshot = mock_snapshot().await;
+        snapshot.remove(&validate_state_pubkey);
+        let err = create_transfer_checked_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            &snapshot,
+        )
+        .unwrap_err();
+        assert!(std::error::Error::source(&err).is_some());
+        let OfflineResolutionError::MissingAccounts { addresses, .. } = err else {
+            panic!("expected missing accounts, got {err}");
+        };
+        assert_eq!(addresses, vec![validate_state_pubkey]);
+
+        // Everything captured, but resolution fails for another reason
+        let mut snapshot = mock_snapshot().await;
+        snapshot.insert(validate_state_pubkey, vec![0; 3]);
+        let err = create_transfer_checked_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            &snapshot,
+        )
+        .unwrap_err();
+        assert!(matches!(err, OfflineResolutionError::Resolution(_)));
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_instruction_with_extra_metas() {
+        let source = Address::new_unique();
+        let destination = Address::new_unique();
+        let authority = Address::new_unique();
+        let amount = 100u64;
+        let fee = 1u64;
+
+        let transfer_instruction = crate::instruction::transfer_checked(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+        )
+        .unwrap();
+        let mint_to_instruction = crate::instruction::mint_to(
+            &crate::id(),
+            &MINT_PUBKEY,
+            &source,
+            &authority,
+            &[],
+            amount,
+        )
+        .unwrap();
+        let transfer_with_fee_instruction = transfer_fee::instruction::transfer_checked_with_fee(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            fee,
+        )
+        .unwrap();
+
+        let batch_instruction = create_batch_instruction_with_extra_metas(
+            &crate::id(),
+            &[
+                transfer_instruction,
+                mint_to_instruction.clone(),
+                transfer_with_fee_instruction,
+            ],
+            mock_fetch_account_data_fn,
+        )
+        .await
+        .unwrap();
+
+        let resolved_transfer = create_transfer_checked_instruction_with_extra_metas(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            DECIMALS,
+            mock_fetch_account_data_fn,
+        )
+        .await
+        .unwrap();
+        let resolved_transfer_with_fee =
+            create_transfer_checked_with_fee_instruction_with_extra_metas(
+                &crate::id(),
+                &source,
+                &MINT_PUBKEY,
+                &destination,
+                &authority,
+                &[],
+                amount,
+                DECIMALS,
+                fee,
+                mock_fetch_account_data_fn,
+            )
+            .await
+            .unwrap();
+
+        // only the transfers gain the transfer hook accounts
+        let expected = batch(
+            &crate::id(),
+            &[
+                resolved_transfer.clone(),
+                mint_to_instruction,
+                resolved_transfer_with_fee,
+            ],
+        )
+        .unwrap();
+        assert_eq!(batch_instruction, expected);
+        assert_eq!(resolved_transfer.accounts.len(), 10);
+        assert_eq!(batch_instruction.data[1], 10);
+        assert_eq!(batch_instruction.accounts.len(), 23);
+
+        // same result from a snapshot
+        let offline_batch_instruction = create_batch_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &[crate::instruction::transfer_checked(
+                &crate::id(),
+                &source,
+                &MINT_PUBKEY,
+                &destination,
+                &authority,
+                &[],
+                amount,
+                DECIMALS,
+            )
+            .unwrap()],
+            &mock_snapshot().await,
+        )
+        .unwrap();
+        assert_eq!(
+            offline_batch_instruction,
+            batch(&crate::id(), &[resolved_transfer]).unwrap()
+        );
+
+        // the mint is reported when it is absent from the snapshot
+        let err = create_batch_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &[crate::instruction::transfer_checked(
+                &crate::id(),
+                &source,
+                &MINT_PUBKEY,
+                &destination,
+                &authority,
+                &[],
+                amount,
+                DECIMALS,
+            )
+            .unwrap()],
+            &AccountDataSnapshot::new(),
+        )
+        .unwrap_err();
+        let OfflineResolutionError::MissingAccounts { addresses, .. } = err else {
+            panic!("expected missing accounts, got {err}");
+        };
+        assert_eq!(addresses, vec![MINT_PUBKEY]);
+    }
+
+    #[test]
+    fn test_add_extra_account_metas_with_fee_collector() {
+        let source = Address::new_unique();
+        let destination = Address::new_unique();
+        let authority = Address::new_unique();
+        let fee_collector = Address::new_unique();
+
+        let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::TransferFeeCollector,
+        ])
+        .unwrap();
+        let mut data = vec![0u8; mint_len];
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        mint.init_extension::<TransferFeeConfig>(true).unwrap();
+        let extension = mint.init_extension::<TransferFeeCollector>(true).unwrap();
+        extension.collector = Some(fee_collector).try_into().unwrap();
+        mint.base.decimals = DECIMALS;
+        mint.base.is_initialized = true;
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        let snapshot = AccountDataSnapshot::from([(MINT_PUBKEY, data)]);
+
+        let instruction = create_transfer_checked_with_fee_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &destination,
+            &authority,
+            &[],
+            100,
+            DECIMALS,
+            1,
+            &snapshot,
+        )
+        .unwrap();
+        assert_eq!(
+            instruction.accounts.last(),
+            Some(&AccountMeta::new(fee_collector, false))
+        );
+        assert_eq!(instruction.accounts.len(), 5);
+
+        // not added twice when the collector is the destination
+        let instruction = create_transfer_checked_instruction_with_extra_metas_offline(
+            &crate::id(),
+            &source,
+            &MINT_PUBKEY,
+            &fee_collector,
+            &authority,
+            &[],
+            100,
+            DECIMALS,
+            &snapshot,
+        )
+        .unwrap();
+        assert_eq!(instruction.accounts.len(), 4);
+    }
 }
@@ -4,9 +4,10 @@
 use {
     solana_account_info::AccountInfo,
     solana_address::Address,
-    solana_cpi::invoke_signed,
+    solana_cpi::{invoke, invoke_signed},
     solana_instruction::{AccountMeta, Instruction},
     solana_program_error::{ProgramError, ProgramResult},
+    spl_tlv_account_resolution::state::ExtraAccountMetaList,
     spl_token_2022_interface::inline_spl_token,
     spl_token_2022_interface::{
         extension::{transfer_fee, transfer_hook, StateWithExtensions},
@@ -14,7 +15,11 @@ use {
         pod::PodMultisig,
         state::{Mint, PackedSizeOf},
     },
-    spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi,
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address,
+        instruction::{execute, ExecuteInstruction},
+        onchain::{add_extra_accounts_for_execute_cpi, invoke_execute},
+    },
 };
 
 fn is_multisig_account(account: &AccountInfo) -> bool {
@@ -223,6 +228,95 @@ pub fn invoke_transfer_checked_with_fee<'a>(
     )
 }
 
+/// Internal function to create a transfer hook `Execute` instruction with the
+/// memo appended to its data, along with the proper account infos
+#[allow(clippy::too_many_arguments)]
+fn execute_instruction_and_account_infos_with_memo<'a>(
+    program_id: &Address,
+    source_info: AccountInfo<'a>,
+    mint_info: AccountInfo<'a>,
+    destination_info: AccountInfo<'a>,
+    authority_info: AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+    amount: u64,
+    memo: &[u8],
+) -> Result<(Instruction, Vec<AccountInfo<'a>>), ProgramError> {
+    let validation_address = get_extra_account_metas_address(mint_info.key, program_id);
+    let mut cpi_instruction = execute(
+        program_id,
+        source_info.key,
+        mint_info.key,
+        destination_info.key,
+        authority_info.key,
+        amount,
+    );
+    cpi_instruction.data.extend_from_slice(memo);
+    let mut cpi_account_infos = vec![source_info, mint_info, destination_info, authority_info];
+
+    if let Some(validation_info) = additional_accounts
+        .iter()
+        .find(|info| *info.key == validation_address)
+    {
+        cpi_instruction
+            .accounts
+            .push(AccountMeta::new_readonly(validation_address, false));
+        cpi_account_infos.push(validation_info.clone());
+        ExtraAccountMetaList::add_to_cpi_instruction::<ExecuteInstruction>(
+            &mut cpi_instruction,
+            &mut cpi_account_infos,
+            &validation_info.try_borrow_data()?,
+            additional_accounts,
+        )?;
+    }
+    Ok((cpi_instruction, cpi_account_infos))
+}
+
+/// Helper to CPI into a transfer hook program's `Execute` instruction,
+/// forwarding the memo that accompanied the transfer, if any.
+///
+/// The memo bytes are appended to the instruction data after the amount, so
+/// hook programs that only read the amount are unaffected. Without a memo,
+/// this is the same as `spl_transfer_hook_interface::onchain::invoke_execute`.
+///
+/// Callers only pass a memo if one of the accounts requires memos, so that
+/// hooks on mints without memo requirements get the same `Execute` data as
+/// before.
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_execute_with_memo<'a>(
+    program_id: &Address,
+    source_info: AccountInfo<'a>,
+    mint_info: AccountInfo<'a>,
+    destination_info: AccountInfo<'a>,
+    authority_info: AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+    amount: u64,
+    memo: Option<&[u8]>,
+) -> ProgramResult {
+    let Some(memo) = memo else {
+        return invoke_execute(
+            program_id,
+            source_info,
+            mint_info,
+            destination_info,
+            authority_info,
+            additional_accounts,
+            amount,
+        );
+    };
+    let (cpi_instruction, cpi_account_infos) = execute_instruction_and_account_infos_with_memo(
+        program_id,
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
+        additional_accounts,
+        amount,
+        memo,
+    )?;
+
+    invoke(&cpi_instruction, &cpi_account_infos)
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -670,6 +764,134 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_execute_instruction_with_memo() {
+        let hook_program_id = Address::new_unique();
+        let source_key = Address::new_unique();
+        let mint_key = Address::new_unique();
+        let destination_key = Address::new_unique();
+        let authority_key = Address::new_unique();
+        let extra_account_key = Address::new_unique();
+        let extra_account_metas = vec![AccountMeta::new_readonly(extra_account_key, false)];
+        let (validation_key, mut validation_data) =
+            setup_validation_state(&mint_key, &hook_program_id, &extra_account_metas);
+
+        let owner = Address::new_unique();
+        let mut source_lamports = 100;
+        let mut mint_lamports = 100;
+        let mut destination_lamports = 100;
+        let mut authority_lamports = 100;
+        let mut validation_lamports = 100;
+        let mut extra_account_lamports = 100;
+        let source_info = AccountInfo::new(
+            &source_key,
+            false,
+            true,
+            &mut source_lamports,
+            &mut [],
+            &owner,
+            false,
+        );
+        let mint_info = AccountInfo::new(
+            &mint_key,
+            false,
+            false,
+            &mut mint_lamports,
+            &mut [],
+            &owner,
+            false,
+        );
+        let destination_info = AccountInfo::new(
+            &destination_key,
+            false,
+            true,
+            &mut destination_lamports,
+            &mut [],
+            &owner,
+            false,
+        );
+        let authority_info = AccountInfo::new(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut [],
+            &owner,
+            false,
+        );
+        let additional_accounts = vec![
+            AccountInfo::new(
+                &extra_account_key,
+                false,
+                false,
+                &mut extra_account_lamports,
+                &mut [],
+                &owner,
+                false,
+            ),
+            AccountInfo::new(
+                &validation_key,
+                false,
+                false,
+                &mut validation_lamports,
+                &mut validation_data,
+                &hook_program_id,
+                false,
+            ),
+        ];
+
+        let memo = b"DEP-000042";
+        let (instruction, account_infos) = execute_instruction_and_account_infos_with_memo(
+            &hook_program_id,
+            source_info,
+            mint_info,
+            destination_info,
+            authority_info,
+            &additional_accounts,
+            200,
+            memo,
+        )
+        .unwrap();
+
+        // the memo follows the usual `Execute` data
+        let mut expected_data = execute(
+            &hook_program_id,
+            &source_key,
+            &mint_key,
+            &destination_key,
+            &authority_key,
+            200,
+        )
+        .data;
+        expected_data.extend_from_slice(memo);
+        assert_eq!(instruction.program_id, hook_program_id);
+        assert_eq!(instruction.data, expected_data);
+
+        let account_keys = account_infos
+            .into_iter()
+            .map(|info| *info.key)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            account_keys,
+            [
+                source_key,
+                mint_key,
+                destination_key,
+                authority_key,
+                validation_key,
+                extra_account_key
+            ]
+        );
+        assert_eq!(
+            instruction
+                .accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>(),
+            account_keys
+        );
+    }
+
     #[test]
     fn test_extract_multisig_when_account_not_owned_by_token_program() {
         let key = Address::new_unique();
@@ -116,6 +116,11 @@ pub(crate) enum PodTokenInstruction {
     // 45
     UnwrapLamports,
     PermissionedBurnExtension,
+    PartialFreezeExtension,
+    TokenMetadataExtension,
+    TokenGroupExtension,
+    // 50
+    NonTransferableExtension,
     // 255
     Batch = 255,
 }
@@ -4,12 +4,18 @@ use {
     crate::{
         extension::{
             confidential_mint_burn, confidential_transfer, confidential_transfer_fee,
-            cpi_guard::{self, in_cpi},
+            cpi_guard::{self, cpi_guard_blocks, in_cpi},
             default_account_state, group_member_pointer, group_pointer, interest_bearing_mint,
-            memo_transfer::{self, check_previous_sibling_instruction_is_memo},
-            metadata_pointer, pausable, permissioned_burn, reallocate, scaled_ui_amount,
-            token_group, token_metadata, transfer_fee, transfer_hook,
+            memo_transfer::{
+                self, check_previous_sibling_instruction_is_memo,
+                check_previous_sibling_instruction_is_valid_memo, get_previous_sibling_memo,
+            },
+            metadata_pointer, non_transferable, partial_freeze,
+            pausable::{self, processor::check_not_paused},
+            permissioned_burn, reallocate, scaled_ui_amount, token_group, token_metadata,
+            transfer_fee, transfer_hook,
         },
+        onchain,
         pod_instruction::{
             decode_instruction_data_with_coption_pubkey, decode_instruction_data_with_coption_u64,
             AmountCheckedData, AmountData, InitializeMintData, InitializeMultisigData,
@@ -41,21 +47,31 @@ use {
             confidential_transfer_fee::{
                 ConfidentialTransferFeeAmount, ConfidentialTransferFeeConfig,
             },
-            cpi_guard::CpiGuard,
-            default_account_state::DefaultAccountState,
+            cpi_guard::{CpiGuard, CpiGuardPolicy},
+            default_account_state::{DefaultAccountState, DefaultAccountStateAttestor},
             group_member_pointer::GroupMemberPointer,
             group_pointer::GroupPointer,
             immutable_owner::ImmutableOwner,
             interest_bearing_mint::InterestBearingConfig,
-            memo_transfer::memo_required,
+            memo_transfer::{memo_required, outgoing_memo_required},
             metadata_pointer::MetadataPointer,
             mint_close_authority::MintCloseAuthority,
-            non_transferable::{NonTransferable, NonTransferableAccount},
-            pausable::{PausableAccount, PausableConfig},
+            non_transferable::{
+                get_recovery_authority, NonTransferable, NonTransferableAccount,
+                NonTransferableRecovery,
+            },
+            partial_freeze::{
+                incoming_frozen, outgoing_frozen, PartialFreezeAccount, PartialFreezeConfig,
+            },
+            pausable::{PausableAccount, PausableConfig, PausedOperations},
             permanent_delegate::{get_permanent_delegate, PermanentDelegate},
             permissioned_burn::PermissionedBurnConfig,
             scaled_ui_amount::ScaledUiAmountConfig,
-            transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            token_group::is_group_member,
+            transfer_fee::{
+                calculate_mint_epoch_fee, get_fee_collector, transfer_fee_exempt,
+                TransferFeeAmount, TransferFeeConfig,
+            },
             transfer_hook::{TransferHook, TransferHookAccount},
             BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
             PodStateWithExtensions, PodStateWithExtensionsMut,
@@ -68,8 +84,9 @@ use {
         native_mint,
         pod::{PodAccount, PodCOption, PodMint, PodMultisig},
         state::{AccountState, Mint, PackedSizeOf},
+        ui_amount::UiAmountConverter,
     },
-    spl_token_group_interface::instruction::TokenGroupInstruction,
+    spl_token_group_interface::{instruction::TokenGroupInstruction, state::TokenGroupMember},
     spl_token_metadata_interface::instruction::TokenMetadataInstruction,
     std::convert::{TryFrom, TryInto},
 };
@@ -78,6 +95,7 @@ pub(crate) enum TransferInstruction {
     Unchecked,
     Checked { decimals: u8 },
     CheckedWithFee { decimals: u8, fee: u64 },
+    Recovery { decimals: u8 },
 }
 
 pub(crate) enum InstructionVariant {
@@ -136,6 +154,12 @@ impl Processor {
             }
         }
 
+        if let Ok(partial_freeze_config) = mint.get_extension::<PartialFreezeConfig>() {
+            if partial_freeze_config.default_restrictions != 0 && freeze_authority.is_none() {
+                return Err(TokenError::MintCannotFreeze.into());
+            }
+        }
+
         mint.base.mint_authority = PodCOption::some(*mint_authority);
         mint.base.decimals = decimals;
         mint.base.is_initialized = true.into();
@@ -189,6 +213,8 @@ impl Processor {
         } else {
             Rent::get()?
         };
+        // Trailing accounts that don't sign are ignored, as before attestors
+        let attestor_info = account_info_iter.next().filter(|info| info.is_signer);
 
         let mut account_data = new_account_info.data.borrow_mut();
         // unpack_uninitialized checks account.base.is_initialized() under the hood
@@ -231,6 +257,21 @@ impl Processor {
             } else {
                 AccountState::Initialized
             };
+        // The registered attestor can approve skipping the default state
+        let starting_state = match (
+            mint.get_extension::<DefaultAccountStateAttestor>()
+                .ok()
+                .and_then(|extension| Option::<Address>::from(extension.attestor)),
+            attestor_info,
+        ) {
+            (Some(attestor), Some(attestor_info)) => {
+                if *attestor_info.key != attestor {
+                    return Err(TokenError::InvalidAccountStateAttestor.into());
+                }
+                AccountState::Initialized
+            }
+            _ => starting_state,
+        };
 
         account.base.mint = *mint_info.key;
         account.base.owner = *owner;
@@ -238,6 +279,11 @@ impl Processor {
         account.base.delegate = PodCOption::none();
         account.base.delegated_amount = 0.into();
         account.base.state = starting_state.into();
+        if let Ok(partial_freeze_config) = mint.get_extension::<PartialFreezeConfig>() {
+            let partial_freeze_account = account.get_extension_mut::<PartialFreezeAccount>()?;
+            partial_freeze_account.restrictions = partial_freeze_config.default_restrictions;
+            partial_freeze_account.reason = partial_freeze_config.default_reason;
+        }
         if mint_info.key == &native_mint::id() {
             let rent_exempt_reserve = rent.minimum_balance(new_account_info_data_len);
             account.base.is_native = PodCOption::some(rent_exempt_reserve.into());
@@ -331,6 +377,49 @@ impl Processor {
         Self::_process_initialize_multisig(accounts, m, false)
     }
 
+    /// Check if the destination of a transfer is exempt from transfer fees.
+    /// Self-transfers are never charged through the destination, and invalid
+    /// destinations are rejected later in the transfer.
+    fn destination_fee_exempt(
+        source_account_info: &AccountInfo,
+        destination_account_info: &AccountInfo,
+    ) -> Result<bool, ProgramError> {
+        if source_account_info.key == destination_account_info.key {
+            return Ok(false);
+        }
+        let destination_account_data = destination_account_info.try_borrow_data()?;
+        Ok(
+            PodStateWithExtensions::<PodAccount>::unpack(&destination_account_data)
+                .is_ok_and(|destination_account| transfer_fee_exempt(&destination_account)),
+        )
+    }
+
+    /// Credit a transfer fee to the fee collector token account of the mint
+    fn credit_fee_collector(
+        fee_collector_info: &AccountInfo,
+        mint: &Address,
+        fee: u64,
+    ) -> ProgramResult {
+        check_program_account(fee_collector_info.owner)?;
+        let mut fee_collector_data = fee_collector_info.data.borrow_mut();
+        let fee_collector =
+            PodStateWithExtensionsMut::<PodAccount>::unpack(&mut fee_collector_data)?;
+        if &fee_collector.base.mint != mint {
+            return Err(TokenError::MintMismatch.into());
+        }
+        if fee_collector.base.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        if incoming_frozen(&fee_collector) {
+            return Err(TokenError::AccountIncomingFrozen.into());
+        }
+        fee_collector.base.amount = u64::from(fee_collector.base.amount)
+            .checked_add(fee)
+            .ok_or(TokenError::Overflow)?
+            .into();
+        Ok(())
+    }
+
     /// Processes a [`Transfer`](enum.TokenInstruction.html) instruction.
     pub(crate) fn process_transfer(
         program_id: &Address,
@@ -345,10 +434,12 @@ impl Processor {
         let expected_mint_info = match transfer_instruction {
             TransferInstruction::Unchecked => None,
             TransferInstruction::Checked { decimals }
-            | TransferInstruction::CheckedWithFee { decimals, .. } => {
+            | TransferInstruction::CheckedWithFee { decimals, .. }
+            | TransferInstruction::Recovery { decimals } => {
                 Some((next_account_info(account_info_iter)?, decimals))
             }
         };
+        let recovery = matches!(transfer_instruction, TransferInstruction::Recovery { .. });
 
         let destination_account_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
@@ -363,82 +454,99 @@ impl Processor {
         if source_account.base.is_frozen() {
             return Err(TokenError::AccountFrozen.into());
         }
+        if outgoing_frozen(&source_account) {
+            return Err(TokenError::AccountOutgoingFrozen.into());
+        }
         let source_amount = u64::from(source_account.base.amount);
         if source_amount < amount {
             return Err(TokenError::InsufficientFunds.into());
         }
-        if source_account
-            .get_extension::<NonTransferableAccount>()
-            .is_ok()
+        if !recovery
+            && source_account
+                .get_extension::<NonTransferableAccount>()
+                .is_ok()
         {
             return Err(TokenError::NonTransferable.into());
         }
 
-        let (calculated_fee, maybe_permanent_delegate, maybe_transfer_hook_program_id) =
-            if let Some((mint_info, expected_decimals)) = expected_mint_info {
-                check_program_account(mint_info.owner)?;
-
-                if &source_account.base.mint != mint_info.key {
-                    return Err(TokenError::MintMismatch.into());
-                }
+        let (
+            calculated_fee,
+            maybe_fee_collector,
+            maybe_permanent_delegate,
+            maybe_recovery_authority,
+            maybe_transfer_hook_program_id,
+        ) = if let Some((mint_info, expected_decimals)) = expected_mint_info {
+            check_program_account(mint_info.owner)?;
 
-                let mint_data = mint_info.try_borrow_data()?;
-                let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+            if &source_account.base.mint != mint_info.key {
+                return Err(TokenError::MintMismatch.into());
+            }
 
-                if expected_decimals != mint.base.decimals {
-                    return Err(TokenError::MintDecimalsMismatch.into());
-                }
+            let mint_data = mint_info.try_borrow_data()?;
+            let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
 
-                let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>()
-                {
-                    transfer_fee_config
-                        .calculate_epoch_fee(Clock::get()?.epoch, amount)
-                        .ok_or(TokenError::Overflow)?
-                } else {
-                    0
-                };
+            if expected_decimals != mint.base.decimals {
+                return Err(TokenError::MintDecimalsMismatch.into());
+            }
 
-                if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-                    if extension.paused.into() {
-                        return Err(TokenError::MintPaused.into());
-                    }
-                }
+            // Recovery transfers only move balances back to their holder,
+            // so they are not charged a fee
+            let fee = if !recovery
+                && mint.get_extension::<TransferFeeConfig>().is_ok()
+                && !transfer_fee_exempt(&source_account)
+                && !Self::destination_fee_exempt(source_account_info, destination_account_info)?
+            {
+                calculate_mint_epoch_fee(&mint, Clock::get()?.epoch, amount)
+                    .ok_or(TokenError::Overflow)?
+            } else {
+                0
+            };
 
-                let maybe_permanent_delegate = get_permanent_delegate(&mint);
-                let maybe_transfer_hook_program_id = transfer_hook::get_program_id(&mint);
+            check_not_paused(&mint, PausedOperations::TRANSFER)?;
 
-                (
-                    fee,
-                    maybe_permanent_delegate,
-                    maybe_transfer_hook_program_id,
-                )
+            let maybe_fee_collector = get_fee_collector(&mint);
+            let maybe_permanent_delegate = get_permanent_delegate(&mint);
+            let maybe_recovery_authority = if recovery {
+                Some(get_recovery_authority(&mint).ok_or(TokenError::NoAuthorityExists)?)
             } else {
-                // Transfer hook extension exists on the account, but no mint
-                // was provided to figure out required accounts, abort
-                if source_account
-                    .get_extension::<TransferHookAccount>()
-                    .is_ok()
-                {
-                    return Err(TokenError::MintRequiredForTransfer.into());
-                }
+                None
+            };
+            let maybe_transfer_hook_program_id = transfer_hook::get_program_id(&mint);
 
-                // Transfer fee amount extension exists on the account, but no mint
-                // was provided to calculate the fee, abort
-                if source_account
-                    .get_extension_mut::<TransferFeeAmount>()
-                    .is_ok()
-                {
-                    return Err(TokenError::MintRequiredForTransfer.into());
-                }
+            (
+                fee,
+                maybe_fee_collector,
+                maybe_permanent_delegate,
+                maybe_recovery_authority,
+                maybe_transfer_hook_program_id,
+            )
+        } else {
+            // Transfer hook extension exists on the account, but no mint
+            // was provided to figure out required accounts, abort
+            if source_account
+                .get_extension::<TransferHookAccount>()
+                .is_ok()
+            {
+                return Err(TokenError::MintRequiredForTransfer.into());
+            }
 
-                // Pausable extension exists on the account, but no mint
-                // was provided to see if it's paused, abort
-                if source_account.get_extension::<PausableAccount>().is_ok() {
-                    return Err(TokenError::MintRequiredForTransfer.into());
-                }
+            // Transfer fee amount extension exists on the account, but no mint
+            // was provided to calculate the fee, abort
+            if source_account
+                .get_extension_mut::<TransferFeeAmount>()
+                .is_ok()
+            {
+                return Err(TokenError::MintRequiredForTransfer.into());
+            }
 
-                (0, None, None)
-            };
+            // Pausable extension exists on the account, but no mint
+            // was provided to see if it's paused, abort
+            if source_account.get_extension::<PausableAccount>().is_ok() {
+                return Err(TokenError::MintRequiredForTransfer.into());
+            }
+
+            (0, None, None, None, None)
+        };
         if let TransferInstruction::CheckedWithFee { fee, .. } = transfer_instruction {
             if calculated_fee != fee {
                 msg!("Calculated fee {calculated_fee}, received {fee}");
@@ -447,20 +555,31 @@ impl Processor {
         }
 
         let self_transfer = source_account_info.key == destination_account_info.key;
-        if let Ok(cpi_guard) = source_account.get_extension::<CpiGuard>() {
-            // Blocks all cases where the authority has signed if CPI Guard is
-            // enabled, including:
-            // * the account is delegated to the owner
-            // * the account owner is the permanent delegate
-            if *authority_info.key == source_account.base.owner
-                && cpi_guard.lock_cpi.into()
-                && in_cpi()
-            {
-                return Err(TokenError::CpiGuardTransferBlocked.into());
-            }
+        // Blocks all cases where the authority has signed if CPI Guard is
+        // enabled, including:
+        // * the account is delegated to the owner
+        // * the account owner is the permanent delegate
+        // Recovery transfers are authorized by the mint, not the owner, so
+        // they are never blocked.
+        if maybe_recovery_authority.is_none()
+            && *authority_info.key == source_account.base.owner
+            && cpi_guard_blocks(&source_account, CpiGuardPolicy::TRANSFER, accounts)
+        {
+            return Err(TokenError::CpiGuardTransferBlocked.into());
         }
-        match (source_account.base.delegate, maybe_permanent_delegate) {
-            (_, Some(ref delegate)) if authority_info.key == delegate => Self::validate_owner(
+        match (
+            source_account.base.delegate,
+            maybe_permanent_delegate,
+            maybe_recovery_authority,
+        ) {
+            (_, _, Some(ref recovery_authority)) => Self::validate_owner(
+                program_id,
+                recovery_authority,
+                authority_info,
+                authority_info_data_len,
+                account_info_iter.as_slice(),
+            )?,
+            (_, Some(ref delegate), _) if authority_info.key == delegate => Self::validate_owner(
                 program_id,
                 delegate,
                 authority_info,
@@ -473,6 +592,7 @@ impl Processor {
                     value: delegate,
                 },
                 _,
+                _,
             ) if authority_info.key == &delegate => {
                 Self::validate_owner(
                     program_id,
@@ -510,6 +630,8 @@ impl Processor {
         // to ensure self-transfers are fully validated
         if self_transfer {
             if memo_required(&source_account) {
+                check_previous_sibling_instruction_is_valid_memo(&source_account)?;
+            } else if outgoing_memo_required(&source_account) {
                 check_previous_sibling_instruction_is_memo()?;
             }
             return Ok(());
@@ -523,13 +645,25 @@ impl Processor {
         if destination_account.base.is_frozen() {
             return Err(TokenError::AccountFrozen.into());
         }
+        if incoming_frozen(&destination_account) {
+            return Err(TokenError::AccountIncomingFrozen.into());
+        }
         if source_account.base.mint != destination_account.base.mint {
             return Err(TokenError::MintMismatch.into());
         }
 
-        if memo_required(&destination_account) {
-            check_previous_sibling_instruction_is_memo()?;
-        }
+        // keep a required memo to forward it to the transfer hook program,
+        // other memos aren't forwarded so that the hook's `Execute` data only
+        // changes for accounts that opted into memos
+        let memo = if memo_required(&destination_account) {
+            Some(check_previous_sibling_instruction_is_valid_memo(
+                &destination_account,
+            )?)
+        } else if outgoing_memo_required(&source_account) {
+            Some(get_previous_sibling_memo()?)
+        } else {
+            None
+        };
 
         if let Ok(confidential_transfer_state) =
             destination_account.get_extension::<ConfidentialTransferAccount>()
@@ -549,7 +683,37 @@ impl Processor {
             .ok_or(TokenError::Overflow)?
             .into();
         if calculated_fee > 0 {
-            if let Ok(extension) = destination_account.get_extension_mut::<TransferFeeAmount>() {
+            if let Some(fee_collector) = maybe_fee_collector {
+                // The fee goes straight to the collector rather than being
+                // withheld
+                if &fee_collector == destination_account_info.key {
+                    destination_account.base.amount = u64::from(destination_account.base.amount)
+                        .checked_add(calculated_fee)
+                        .ok_or(TokenError::Overflow)?
+                        .into();
+                } else if &fee_collector == source_account_info.key {
+                    if incoming_frozen(&source_account) {
+                        return Err(TokenError::AccountIncomingFrozen.into());
+                    }
+                    source_account.base.amount = u64::from(source_account.base.amount)
+                        .checked_add(calculated_fee)
+                        .ok_or(TokenError::Overflow)?
+                        .into();
+                } else {
+                    let fee_collector_info = account_info_iter
+                        .as_slice()
+                        .iter()
+                        .find(|account_info| account_info.key == &fee_collector)
+                        .ok_or(TokenError::MissingFeeCollector)?;
+                    Self::credit_fee_collector(
+                        fee_collector_info,
+                        &source_account.base.mint,
+                        calculated_fee,
+                    )?;
+                }
+            } else if let Ok(extension) =
+                destination_account.get_extension_mut::<TransferFeeAmount>()
+            {
                 let new_withheld_amount = u64::from(extension.withheld_amount)
                     .checked_add(calculated_fee)
                     .ok_or(TokenError::Overflow)?;
@@ -583,7 +747,7 @@ impl Processor {
                 // must drop these to avoid the double-borrow during CPI
                 drop(source_account_data);
                 drop(destination_account_data);
-                spl_transfer_hook_interface::onchain::invoke_execute(
+                onchain::invoke_execute_with_memo(
                     &program_id,
                     source_account_info.clone(),
                     mint_info.clone(),
@@ -591,6 +755,7 @@ impl Processor {
                     authority_info.clone(),
                     account_info_iter.as_slice(),
                     amount,
+                    memo.as_deref(),
                 )?;
 
                 // unset transferring flag
@@ -657,10 +822,8 @@ impl Processor {
             account_info_iter.as_slice(),
         )?;
 
-        if let Ok(cpi_guard) = source_account.get_extension::<CpiGuard>() {
-            if cpi_guard.lock_cpi.into() && in_cpi() {
-                return Err(TokenError::CpiGuardApproveBlocked.into());
-            }
+        if cpi_guard_blocks(&source_account, CpiGuardPolicy::APPROVE, accounts) {
+            return Err(TokenError::CpiGuardApproveBlocked.into());
         }
 
         source_account.base.delegate = PodCOption::some(*delegate_info.key);
@@ -775,10 +938,10 @@ impl Processor {
                         account_info_iter.as_slice(),
                     )?;
 
-                    if let Ok(cpi_guard) = account.get_extension::<CpiGuard>() {
-                        if cpi_guard.lock_cpi.into() && in_cpi() && new_authority.is_some() {
-                            return Err(TokenError::CpiGuardSetAuthorityBlocked.into());
-                        }
+                    if new_authority.is_some()
+                        && cpi_guard_blocks(&account, CpiGuardPolicy::SET_AUTHORITY, accounts)
+                    {
+                        return Err(TokenError::CpiGuardSetAuthorityBlocked.into());
                     }
 
                     account.base.close_authority = new_authority;
@@ -1013,6 +1176,19 @@ impl Processor {
                     )?;
                     extension.authority = new_authority.try_into()?;
                 }
+                AuthorityType::NonTransferableRecovery => {
+                    let extension = mint.get_extension_mut::<NonTransferableRecovery>()?;
+                    let maybe_authority: Option<Address> = extension.authority.into();
+                    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &authority,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.authority = new_authority.try_into()?;
+                }
                 _ => {
                     return Err(TokenError::AuthorityTypeNotSupported.into());
                 }
@@ -1046,6 +1222,9 @@ impl Processor {
         if destination_account.base.is_frozen() {
             return Err(TokenError::AccountFrozen.into());
         }
+        if incoming_frozen(&destination_account) {
+            return Err(TokenError::AccountIncomingFrozen.into());
+        }
 
         if destination_account.base.is_native() {
             return Err(TokenError::NativeNotSupported.into());
@@ -1074,11 +1253,7 @@ impl Processor {
             return Err(TokenError::NonTransferableNeedsImmutableOwnership.into());
         }
 
-        if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-            if extension.paused.into() {
-                return Err(TokenError::MintPaused.into());
-            }
-        }
+        check_not_paused(&mint, PausedOperations::MINT)?;
 
         if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
             return Err(TokenError::IllegalMintBurnConversion.into());
@@ -1191,6 +1366,9 @@ impl Processor {
         if source_account.base.is_frozen() {
             return Err(TokenError::AccountFrozen.into());
         }
+        if outgoing_frozen(&source_account) {
+            return Err(TokenError::AccountOutgoingFrozen.into());
+        }
         if source_account.base.is_native() {
             return Err(TokenError::NativeNotSupported.into());
         }
@@ -1206,11 +1384,7 @@ impl Processor {
                 return Err(TokenError::MintDecimalsMismatch.into());
             }
         }
-        if let Ok(extension) = mint.get_extension::<PausableConfig>() {
-            if extension.paused.into() {
-                return Err(TokenError::MintPaused.into());
-            }
-        }
+        check_not_paused(&mint, PausedOperations::BURN)?;
 
         if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
             return Err(TokenError::IllegalMintBurnConversion.into());
@@ -1218,17 +1392,14 @@ impl Processor {
 
         let maybe_permanent_delegate = get_permanent_delegate(&mint);
 
-        if let Ok(cpi_guard) = source_account.get_extension::<CpiGuard>() {
-            // Blocks all cases where the authority has signed if CPI Guard is
-            // enabled, including:
-            // * the account is delegated to the owner
-            // * the account owner is the permanent delegate
-            if *authority_info.key == source_account.base.owner
-                && cpi_guard.lock_cpi.into()
-                && in_cpi()
-            {
-                return Err(TokenError::CpiGuardBurnBlocked.into());
-            }
+        // Blocks all cases where the authority has signed if CPI Guard is
+        // enabled, including:
+        // * the account is delegated to the owner
+        // * the account owner is the permanent delegate
+        if *authority_info.key == source_account.base.owner
+            && cpi_guard_blocks(&source_account, CpiGuardPolicy::BURN, accounts)
+        {
+            return Err(TokenError::CpiGuardBurnBlocked.into());
         }
 
         if !source_account
@@ -1325,13 +1496,10 @@ impl Processor {
                 .base
                 .is_owned_by_system_program_or_incinerator()
             {
-                if let Ok(cpi_guard) = source_account.get_extension::<CpiGuard>() {
-                    if cpi_guard.lock_cpi.into()
-                        && in_cpi()
-                        && destination_account_info.key != &source_account.base.owner
-                    {
-                        return Err(TokenError::CpiGuardCloseAccountBlocked.into());
-                    }
+                if destination_account_info.key != &source_account.base.owner
+                    && cpi_guard_blocks(&source_account, CpiGuardPolicy::CLOSE_ACCOUNT, accounts)
+                {
+                    return Err(TokenError::CpiGuardCloseAccountBlocked.into());
                 }
 
                 Self::validate_owner(
@@ -1379,6 +1547,21 @@ impl Processor {
             if let Ok(confidential_mint_burn) = mint.get_extension::<ConfidentialMintBurn>() {
                 confidential_mint_burn.closable()?;
             }
+
+            // a member mint leaves its group, so that the group size stays
+            // accurate, which needs the group mint
+            if let Some(member) = mint
+                .get_extension::<TokenGroupMember>()
+                .ok()
+                .filter(|member| is_group_member(member))
+            {
+                let group_info = account_info_iter
+                    .as_slice()
+                    .iter()
+                    .find(|account_info| account_info.key == &member.group)
+                    .ok_or(TokenError::MintIsGroupMember)?;
+                token_group::processor::remove_closed_member(group_info)?;
+            }
         } else {
             return Err(ProgramError::UninitializedAccount);
         }
@@ -1552,19 +1735,15 @@ impl Processor {
         let mint_data = mint_info.data.borrow();
         let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)
             .map_err(|_| Into::<ProgramError>::into(TokenError::InvalidMint))?;
-        let ui_amount = if let Ok(extension) = mint.get_extension::<InterestBearingConfig>() {
-            let unix_timestamp = Clock::get()?.unix_timestamp;
-            extension
-                .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
-                .ok_or(ProgramError::InvalidArgument)?
-        } else if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
-            let unix_timestamp = Clock::get()?.unix_timestamp;
-            extension
-                .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
-                .ok_or(ProgramError::InvalidArgument)?
+        let converter = UiAmountConverter::new(mint.base.decimals, &mint)?;
+        let unix_timestamp = if converter.is_time_dependent() {
+            Clock::get()?.unix_timestamp
         } else {
-            crate::amount_to_ui_amount_string_trimmed(amount, mint.base.decimals)
+            0
         };
+        let ui_amount = converter
+            .amount_to_ui_amount(amount, unix_timestamp)
+            .ok_or(ProgramError::InvalidArgument)?;
 
         set_return_data(&ui_amount.into_bytes());
         Ok(())
@@ -1580,15 +1759,13 @@ impl Processor {
         let mint_data = mint_info.data.borrow();
         let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data)
             .map_err(|_| Into::<ProgramError>::into(TokenError::InvalidMint))?;
-        let amount = if let Ok(extension) = mint.get_extension::<InterestBearingConfig>() {
-            let unix_timestamp = Clock::get()?.unix_timestamp;
-            extension.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
-        } else if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
-            let unix_timestamp = Clock::get()?.unix_timestamp;
-            extension.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
+        let converter = UiAmountConverter::new(mint.base.decimals, &mint)?;
+        let unix_timestamp = if converter.is_time_dependent() {
+            Clock::get()?.unix_timestamp
         } else {
-            crate::try_ui_amount_into_amount(ui_amount.to_string(), mint.base.decimals)?
+            0
         };
+        let amount = converter.try_ui_amount_into_amount(ui_amount, unix_timestamp)?;
 
         set_return_data(&amount.to_le_bytes());
         Ok(())
@@ -1703,10 +1880,8 @@ impl Processor {
                 account_info_iter.as_slice(),
             )?;
 
-            if let Ok(cpi_guard) = account.get_extension::<CpiGuard>() {
-                if cpi_guard.lock_cpi.into() && in_cpi() {
-                    return Err(TokenError::CpiGuardTransferBlocked.into());
-                }
+            if cpi_guard_blocks(&account, CpiGuardPolicy::TRANSFER, accounts) {
+                return Err(TokenError::CpiGuardTransferBlocked.into());
             }
         } else if let Ok(mint) = PodStateWithExtensions::<PodMint>::unpack(&source_data) {
             match &mint.base.mint_authority {
@@ -1842,10 +2017,8 @@ impl Processor {
             }
         }
 
-        if let Ok(cpi_guard) = source_account.get_extension::<CpiGuard>() {
-            if cpi_guard.lock_cpi.into() && in_cpi() {
-                return Err(TokenError::CpiGuardTransferBlocked.into());
-            }
+        if cpi_guard_blocks(&source_account, CpiGuardPolicy::TRANSFER, accounts) {
+            return Err(TokenError::CpiGuardTransferBlocked.into());
         }
 
         if amount != 0 {
@@ -2233,6 +2406,38 @@ impl Processor {
                         &input[1..],
                     )
                 }
+                PodTokenInstruction::PartialFreezeExtension => {
+                    msg!("Instruction: PartialFreezeExtension");
+                    partial_freeze::processor::process_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
+                PodTokenInstruction::TokenMetadataExtension => {
+                    msg!("Instruction: TokenMetadataExtension");
+                    token_metadata::processor::process_extension_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
+                PodTokenInstruction::TokenGroupExtension => {
+                    msg!("Instruction: TokenGroupExtension");
+                    token_group::processor::process_extension_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
+                PodTokenInstruction::NonTransferableExtension => {
+                    msg!("Instruction: NonTransferableExtension");
+                    non_transferable::processor::process_instruction(
+                        program_id,
+                        accounts,
+                        &input[1..],
+                    )
+                }
                 PodTokenInstruction::UnwrapLamports => {
                     msg!("Instruction: UnwrapLamports");
                     let (_, amount) = decode_instruction_data_with_coption_u64::<()>(input)?;